@@ -0,0 +1,92 @@
+//! Times derived parsers on representative inputs.
+//!
+//! Run with `cargo bench --features testing`. Set `DARLING_BENCH_ITERATIONS` to change
+//! how many times each corpus is run.
+
+use std::env;
+
+use darling::testing::{bench_expansion, bench_parse};
+use darling::util::Flag;
+use darling::FromMeta;
+
+#[derive(Debug, Default, FromMeta)]
+#[allow(dead_code)]
+struct Nested {
+    #[darling(default)]
+    enabled: bool,
+    #[darling(default)]
+    level: u32,
+}
+
+#[derive(Debug, FromMeta)]
+#[allow(dead_code)]
+struct Settings {
+    name: String,
+    #[darling(default)]
+    rename: Option<String>,
+    skip: Flag,
+    #[darling(default)]
+    nested: Nested,
+    #[darling(multiple)]
+    tag: Vec<String>,
+    #[darling(default)]
+    with: Option<syn::Path>,
+}
+
+const PARSE_CORPUS: &[&str] = &[
+    r#"attr(name = "a")"#,
+    r#"attr(name = "b", skip)"#,
+    r#"attr(name = "c", rename = "d", with = "path::to::f")"#,
+    r#"attr(name = "e", nested(enabled, level = 3))"#,
+    r#"attr(name = "f", tag = "x", tag = "y", tag = "z")"#,
+    r#"attr(name = "g", rename = "h", skip, nested(level = 9), tag = "t", with = "f")"#,
+    // Rejected inputs exercise the error paths.
+    r#"attr(nmae = "typo")"#,
+    r#"attr(name = 1, skip = "yes")"#,
+    r#"attr(name = "a", name = "b")"#,
+];
+
+const EXPANSION_CORPUS: &[&str] = &[
+    r#"
+    #[derive(FromMeta)]
+    struct Small {
+        name: String,
+    }
+    "#,
+    r#"
+    #[derive(FromDeriveInput)]
+    #[darling(attributes(demo), forward_attrs(doc))]
+    struct Container {
+        ident: syn::Ident,
+        attrs: Vec<syn::Attribute>,
+        #[darling(default)]
+        rename: Option<String>,
+        #[darling(multiple)]
+        tag: Vec<String>,
+    }
+    "#,
+    r#"
+    #[derive(FromMeta)]
+    enum Mode {
+        Fast,
+        Careful { retries: u8 },
+        Custom(String),
+    }
+    "#,
+];
+
+fn main() {
+    let iterations = env::var("DARLING_BENCH_ITERATIONS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(10_000);
+
+    println!(
+        "{}",
+        bench_parse::<Settings>("parse", PARSE_CORPUS, iterations)
+    );
+    println!(
+        "{}",
+        bench_expansion("expansion", EXPANSION_CORPUS, iterations / 10)
+    );
+}
@@ -4,18 +4,21 @@ use proc_macro2::{Span, TokenStream};
 use quote::ToTokens;
 use syn::spanned::Spanned;
 
+use crate::ast::Inherit;
 use crate::usage::{
     self, IdentRefSet, IdentSet, LifetimeRefSet, LifetimeSet, UsesLifetimes, UsesTypeParams,
 };
 use crate::{Error, FromField, FromVariant, Result};
 
-/// A struct or enum body.
+/// A struct, enum or union body.
 ///
-/// `V` is the type which receives any encountered variants, and `F` receives struct fields.
+/// `V` is the type which receives any encountered variants, and `F` receives struct and union fields.
+/// The fields of a union always have `Style::Struct`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Data<V, F> {
     Enum(Vec<V>),
     Struct(Fields<F>),
+    Union(Fields<F>),
 }
 
 impl<V, F> Data<V, F> {
@@ -33,7 +36,8 @@ impl<V, F> Data<V, F> {
 
     /// Creates an empty body of the same shape as the passed-in body.
     ///
-    /// `darling` does not support unions; calling this function with a union body will return an error.
+    /// Calling this function with a union body will return an error; unions are only read by
+    /// [`Data::try_from_with_unions`].
     pub fn try_empty_from(src: &syn::Data) -> Result<Self> {
         match *src {
             syn::Data::Enum(_) => Ok(Data::Enum(vec![])),
@@ -50,6 +54,7 @@ impl<V, F> Data<V, F> {
         match *self {
             Data::Enum(ref variants) => Data::Enum(variants.iter().collect()),
             Data::Struct(ref data) => Data::Struct(data.as_ref()),
+            Data::Union(ref data) => Data::Union(data.as_ref()),
         }
     }
 
@@ -61,10 +66,11 @@ impl<V, F> Data<V, F> {
         match self {
             Data::Enum(v) => Data::Enum(v.into_iter().map(map).collect()),
             Data::Struct(f) => Data::Struct(f),
+            Data::Union(f) => Data::Union(f),
         }
     }
 
-    /// Applies a function `F -> U` on struct fields, if this is a struct.
+    /// Applies a function `F -> U` on struct or union fields, if this is a struct or union.
     pub fn map_struct_fields<T, U>(self, map: T) -> Data<V, U>
     where
         T: FnMut(F) -> U,
@@ -72,10 +78,11 @@ impl<V, F> Data<V, F> {
         match self {
             Data::Enum(v) => Data::Enum(v),
             Data::Struct(f) => Data::Struct(f.map(map)),
+            Data::Union(f) => Data::Union(f.map(map)),
         }
     }
 
-    /// Applies a function to the `Fields` if this is a struct.
+    /// Applies a function to the `Fields` if this is a struct or union.
     pub fn map_struct<T, U>(self, mut map: T) -> Data<V, U>
     where
         T: FnMut(Fields<F>) -> Fields<U>,
@@ -83,14 +90,23 @@ impl<V, F> Data<V, F> {
         match self {
             Data::Enum(v) => Data::Enum(v),
             Data::Struct(f) => Data::Struct(map(f)),
+            Data::Union(f) => Data::Union(map(f)),
         }
     }
 
     /// Consumes the `Data`, returning `Fields<F>` if it was a struct.
     pub fn take_struct(self) -> Option<Fields<F>> {
         match self {
-            Data::Enum(_) => None,
             Data::Struct(f) => Some(f),
+            Data::Enum(_) | Data::Union(_) => None,
+        }
+    }
+
+    /// Consumes the `Data`, returning `Fields<F>` if it was a union.
+    pub fn take_union(self) -> Option<Fields<F>> {
+        match self {
+            Data::Union(f) => Some(f),
+            Data::Enum(_) | Data::Struct(_) => None,
         }
     }
 
@@ -98,7 +114,7 @@ impl<V, F> Data<V, F> {
     pub fn take_enum(self) -> Option<Vec<V>> {
         match self {
             Data::Enum(v) => Some(v),
-            Data::Struct(_) => None,
+            Data::Struct(_) | Data::Union(_) => None,
         }
     }
 
@@ -106,19 +122,63 @@ impl<V, F> Data<V, F> {
     pub fn is_enum(&self) -> bool {
         match *self {
             Data::Enum(_) => true,
-            Data::Struct(_) => false,
+            Data::Struct(_) | Data::Union(_) => false,
         }
     }
 
     /// Returns `true` if this instance is `Data::Struct`.
     pub fn is_struct(&self) -> bool {
-        !self.is_enum()
+        match *self {
+            Data::Struct(_) => true,
+            Data::Enum(_) | Data::Union(_) => false,
+        }
+    }
+
+    /// Returns `true` if this instance is `Data::Union`.
+    pub fn is_union(&self) -> bool {
+        match *self {
+            Data::Union(_) => true,
+            Data::Enum(_) | Data::Struct(_) => false,
+        }
+    }
+}
+
+impl<V, F> Data<V, F> {
+    /// Let every variant or field take settings from an already-parsed `parent`.
+    pub fn inherit<P: ?Sized>(&mut self, parent: &P)
+    where
+        V: Inherit<P>,
+        F: Inherit<P>,
+    {
+        match *self {
+            Data::Enum(ref mut variants) => {
+                for variant in variants {
+                    variant.inherit(parent);
+                }
+            }
+            Data::Struct(ref mut fields) | Data::Union(ref mut fields) => fields.inherit(parent),
+        }
     }
 }
 
 impl<V: FromVariant, F: FromField> Data<V, F> {
     /// Attempt to convert from a `syn::Data` instance.
+    ///
+    /// Calling this function with a union body will return an error; use
+    /// [`Data::try_from_with_unions`] to read it.
     pub fn try_from(body: &syn::Data) -> Result<Self> {
+        match *body {
+            // This deliberately doesn't set a span on the error message, as the error is most useful if
+            // applied to the call site of the offending macro. Given that the message is very generic,
+            // putting it on the union keyword ends up being confusing.
+            syn::Data::Union(_) => Err(Error::custom("Unions are not supported")),
+            _ => Self::try_from_with_unions(body),
+        }
+    }
+
+    /// Like [`Data::try_from`], but reads the fields of a union into `Data::Union`. This is
+    /// what derives which declare `supports(union)` use.
+    pub fn try_from_with_unions(body: &syn::Data) -> Result<Self> {
         match *body {
             syn::Data::Enum(ref data) => {
                 let mut errors = Error::accumulator();
@@ -131,10 +191,9 @@ impl<V: FromVariant, F: FromField> Data<V, F> {
                 errors.finish_with(Data::Enum(items))
             }
             syn::Data::Struct(ref data) => Ok(Data::Struct(Fields::try_from(&data.fields)?)),
-            // This deliberately doesn't set a span on the error message, as the error is most useful if
-            // applied to the call site of the offending macro. Given that the message is very generic,
-            // putting it on the union keyword ends up being confusing.
-            syn::Data::Union(_) => Err(Error::custom("Unions are not supported")),
+            syn::Data::Union(ref data) => Ok(Data::Union(Fields::try_from(&syn::Fields::Named(
+                data.fields.clone(),
+            ))?)),
         }
     }
 }
@@ -146,7 +205,7 @@ impl<V: UsesTypeParams, F: UsesTypeParams> UsesTypeParams for Data<V, F> {
         type_set: &'a IdentSet,
     ) -> IdentRefSet<'a> {
         match *self {
-            Data::Struct(ref v) => v.uses_type_params(options, type_set),
+            Data::Struct(ref v) | Data::Union(ref v) => v.uses_type_params(options, type_set),
             Data::Enum(ref v) => v.uses_type_params(options, type_set),
         }
     }
@@ -159,7 +218,7 @@ impl<V: UsesLifetimes, F: UsesLifetimes> UsesLifetimes for Data<V, F> {
         lifetimes: &'a LifetimeSet,
     ) -> LifetimeRefSet<'a> {
         match *self {
-            Data::Struct(ref v) => v.uses_lifetimes(options, lifetimes),
+            Data::Struct(ref v) | Data::Union(ref v) => v.uses_lifetimes(options, lifetimes),
             Data::Enum(ref v) => v.uses_lifetimes(options, lifetimes),
         }
     }
@@ -241,7 +300,7 @@ impl<T> Fields<T> {
         }
     }
 
-    pub fn iter(&self) -> slice::Iter<T> {
+    pub fn iter(&self) -> slice::Iter<'_, T> {
         self.fields.iter()
     }
 
@@ -256,6 +315,18 @@ impl<T> Fields<T> {
     }
 }
 
+impl<T> Fields<T> {
+    /// Let every field take settings from an already-parsed `parent`.
+    pub fn inherit<P: ?Sized>(&mut self, parent: &P)
+    where
+        T: Inherit<P>,
+    {
+        for field in &mut self.fields {
+            field.inherit(parent);
+        }
+    }
+}
+
 impl<F: FromField> Fields<F> {
     pub fn try_from(fields: &syn::Fields) -> Result<Self> {
         let mut errors = Error::accumulator();
@@ -264,8 +335,9 @@ impl<F: FromField> Fields<F> {
                 syn::Fields::Named(fields) => fields
                     .named
                     .iter()
-                    .filter_map(|field| {
-                        errors.handle(FromField::from_field(field).map_err(|err| {
+                    .enumerate()
+                    .filter_map(|(index, field)| {
+                        errors.handle(FromField::from_field_at(field, Some(index)).map_err(|err| {
                             // There should always be an ident here, since this is a collection
                             // of named fields, but `syn` doesn't prevent someone from manually
                             // constructing an invalid collection so a guard is still warranted.
@@ -280,7 +352,10 @@ impl<F: FromField> Fields<F> {
                 syn::Fields::Unnamed(fields) => fields
                     .unnamed
                     .iter()
-                    .filter_map(|field| errors.handle(FromField::from_field(field)))
+                    .enumerate()
+                    .filter_map(|(index, field)| {
+                        errors.handle(FromField::from_field_at(field, Some(index)))
+                    })
                     .collect(),
                 syn::Fields::Unit => vec![],
             }
@@ -400,7 +475,7 @@ impl From<syn::Fields> for Style {
     }
 }
 
-impl<'a> From<&'a syn::Fields> for Style {
+impl From<&syn::Fields> for Style {
     fn from(vd: &syn::Fields) -> Self {
         match *vd {
             syn::Fields::Named(_) => Style::Struct,
@@ -3,7 +3,10 @@
 use std::iter::Iterator;
 use std::slice::Iter;
 
-use crate::{FromGenericParam, FromGenerics, FromTypeParam, Result};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+
+use crate::{FromConstParam, FromGenericParam, FromGenerics, FromTypeParam, Result};
 
 /// Extension trait for `GenericParam` to support getting values by variant.
 ///
@@ -74,6 +77,10 @@ impl GenericParamExt for syn::TypeParam {
 }
 
 /// A mirror of `syn::GenericParam` which is generic over all its contents.
+///
+/// Const params are read with `FromConstParam`, so `C` can be a custom type which reads
+/// attributes on the param. When every part implements `ToTokens`, the param can be re-emitted
+/// the way it was declared, attributes included.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum GenericParam<T = syn::TypeParam, L = syn::LifetimeDef, C = syn::ConstParam> {
     Type(T),
@@ -89,18 +96,32 @@ impl<T: FromTypeParam> FromTypeParam for GenericParam<T> {
     }
 }
 
-impl<T: FromTypeParam> FromGenericParam for GenericParam<T> {
+impl<T: FromTypeParam, C: FromConstParam> FromGenericParam
+    for GenericParam<T, syn::LifetimeDef, C>
+{
     fn from_generic_param(param: &syn::GenericParam) -> Result<Self> {
         Ok(match *param {
             syn::GenericParam::Type(ref ty) => {
                 GenericParam::Type(FromTypeParam::from_type_param(ty)?)
             }
             syn::GenericParam::Lifetime(ref val) => GenericParam::Lifetime(val.clone()),
-            syn::GenericParam::Const(ref val) => GenericParam::Const(val.clone()),
+            syn::GenericParam::Const(ref val) => {
+                GenericParam::Const(FromConstParam::from_const_param(val)?)
+            }
         })
     }
 }
 
+impl<T: ToTokens, L: ToTokens, C: ToTokens> ToTokens for GenericParam<T, L, C> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match *self {
+            GenericParam::Type(ref val) => val.to_tokens(tokens),
+            GenericParam::Lifetime(ref val) => val.to_tokens(tokens),
+            GenericParam::Const(ref val) => val.to_tokens(tokens),
+        }
+    }
+}
+
 impl<T, L, C> GenericParamExt for GenericParam<T, L, C> {
     type TypeParam = T;
     type LifetimeDef = L;
@@ -143,6 +164,11 @@ impl<P, W> Generics<P, W> {
     pub fn type_params(&self) -> TypeParams<'_, P> {
         TypeParams(self.params.iter())
     }
+
+    /// Iterate over the const params, in declaration order.
+    pub fn const_params(&self) -> ConstParams<'_, P> {
+        ConstParams(self.params.iter())
+    }
 }
 
 impl<P: FromGenericParam> FromGenerics for Generics<P> {
@@ -175,6 +201,16 @@ impl<'a, P: GenericParamExt> Iterator for TypeParams<'a, P> {
     }
 }
 
+pub struct ConstParams<'a, P: 'a>(Iter<'a, P>);
+
+impl<'a, P: GenericParamExt> Iterator for ConstParams<'a, P> {
+    type Item = &'a <P as GenericParamExt>::ConstParam;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.by_ref().find_map(GenericParamExt::as_const_param)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{GenericParam, Generics};
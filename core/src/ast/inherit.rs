@@ -0,0 +1,43 @@
+/// Implemented by field and variant receivers which copy settings from their already-parsed
+/// parent, such as a rename rule or default declared on the container.
+///
+/// `darling` parses each field and variant before the container is finished, so inherited
+/// settings are applied afterward with [`Data::inherit`](super::Data::inherit) or
+/// [`Fields::inherit`](super::Fields::inherit), usually from a container-level
+/// `#[darling(map = "...")]` function.
+///
+/// # Example
+/// A receiver's container-level `map` function would normally make the `inherit` call, on
+/// the `data` read by the derived impl. Here the data is built by hand.
+///
+/// ```rust
+/// use darling_core::ast::{Data, Fields, Inherit, Style};
+///
+/// #[derive(Debug, Default)]
+/// struct FieldReceiver {
+///     skip: bool,
+/// }
+///
+/// impl Inherit<bool> for FieldReceiver {
+///     fn inherit(&mut self, skip_all: &bool) {
+///         self.skip = self.skip || *skip_all;
+///     }
+/// }
+///
+/// let fields = vec![FieldReceiver::default(), FieldReceiver { skip: true }];
+/// let mut data: Data<(), FieldReceiver> = Data::Struct(Fields::new(Style::Struct, fields));
+///
+/// // The container's `skip_all` setting is passed down to every field.
+/// let skip_all = true;
+/// data.inherit(&skip_all);
+/// assert!(data.take_struct().unwrap().iter().all(|field| field.skip));
+/// ```
+pub trait Inherit<P: ?Sized> {
+    /// Update `self` with any settings it should take from `parent`.
+    fn inherit(&mut self, parent: &P);
+}
+
+/// Placeholder receivers such as the `()` in `ast::Data<(), MyField>` have nothing to inherit.
+impl<P: ?Sized> Inherit<P> for () {
+    fn inherit(&mut self, _parent: &P) {}
+}
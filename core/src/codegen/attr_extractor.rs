@@ -1,8 +1,41 @@
 use proc_macro2::TokenStream;
+use syn::Ident;
 
+use crate::codegen::variant_data;
 use crate::options::ForwardAttrs;
 use crate::util::PathList;
 
+/// Attributes whose names start with `prefix`, which are collected into `field` keyed by the
+/// rest of their name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrefixedAttrs<'a> {
+    pub prefix: &'a str,
+    pub field: &'a Ident,
+}
+
+impl<'a> PrefixedAttrs<'a> {
+    /// The initializer for the receiving field, written like the other struct initializers.
+    pub(in crate::codegen) fn initializer(&self) -> TokenStream {
+        let field = self.field;
+        quote!(#field: ::darling::export::FromIterator::from_iter(__prefixed_attrs),)
+    }
+}
+
+/// The initializer for the field which receives the span of the first attribute read by the
+/// extractor. This is the call site when no attribute was read.
+pub(in crate::codegen) fn attr_span_initializer(field: Option<&Ident>) -> Option<TokenStream> {
+    field.map(
+        |field| quote!(#field: __attr_span.unwrap_or_else(::darling::export::Span::call_site),),
+    )
+}
+
+/// The initializer for the field which receives the warnings found while parsing.
+pub(in crate::codegen) fn warnings_initializer(field: Option<&Ident>) -> Option<TokenStream> {
+    field.map(|field| {
+        quote!(#field: ::darling::export::mem::replace(__warnings, ::darling::export::Vec::new()),)
+    })
+}
+
 /// Infrastructure for generating an attribute extractor.
 pub trait ExtractAttribute {
     /// A set of mutable declarations for all members of the implementing type.
@@ -16,8 +49,33 @@ pub trait ExtractAttribute {
     /// Gets the list of attribute names that should be parsed by the extractor.
     fn attr_names(&self) -> &PathList;
 
+    /// Gets the attribute names searched for keys which none of `attr_names` set.
+    fn fallback_attr_names(&self) -> &PathList;
+
     fn forwarded_attrs(&self) -> Option<&ForwardAttrs>;
 
+    /// Gets the attribute name prefix whose attributes should be collected, if any.
+    fn prefixed_attrs(&self) -> Option<&PrefixedAttrs<'_>>;
+
+    /// The field which receives the span of the first attribute matching `attr_names`, if any.
+    fn attr_span(&self) -> Option<&Ident>;
+
+    /// An expression for the name of the field which receives the value of an attribute
+    /// written as `#[my_attr = value]`. Without one, that form is an error.
+    fn name_value_key(&self) -> Option<TokenStream>;
+
+    /// Whether a matching attribute which sets none of the fields is an error.
+    fn deny_empty(&self) -> bool;
+
+    /// Whether each attribute read is noted in the active parse report.
+    fn report(&self) -> bool;
+
+    /// Whether attributes wrapped in `cfg_attr` should be unwrapped before extraction.
+    fn unwrap_cfg_attr(&self) -> bool;
+
+    /// Whether attribute values can be expressions rather than only literals.
+    fn expr_values(&self) -> bool;
+
     /// Gets the name used by the generated impl to return to the `syn` item passed as input.
     fn param_name(&self) -> TokenStream;
 
@@ -32,6 +90,10 @@ pub trait ExtractAttribute {
     /// Gets the core from-meta-item loop that should be used on matching attributes.
     fn core_loop(&self) -> TokenStream;
 
+    /// Gets the declaration of the `__canonical_key` closure, which maps every key of a field
+    /// to the field's name.
+    fn canonical_key(&self) -> TokenStream;
+
     fn declarations(&self) -> TokenStream {
         if !self.attr_names().is_empty() {
             self.local_declarations()
@@ -50,28 +112,166 @@ pub trait ExtractAttribute {
             .map(|fa| !fa.is_empty())
             .unwrap_or_default();
 
-        if !(will_parse_any || will_fwd_any) {
+        let prefixed = self.prefixed_attrs();
+
+        // The collected values are declared even if no attributes can be searched, so the
+        // receiving field can always be initialized.
+        let declare_prefixed = prefixed.map(|_| {
+            quote!(
+                let mut __prefixed_attrs = ::darling::export::Vec::new();
+            )
+        });
+
+        let record_span = self.attr_span().is_some();
+        let declare_attr_span = if record_span {
+            quote!(
+                let mut __attr_span: ::darling::export::Option<::darling::export::Span> = ::darling::export::None;
+            )
+        } else {
+            quote!()
+        };
+
+        // Nested options push their warnings through `__warnings`, which the receiving
+        // field takes once parsing is done.
+        let declare_warnings = quote!(
+            let mut __warnings_vec: ::darling::export::Vec<::darling::export::Warning> = ::darling::export::Vec::new();
+            let __warnings = &mut __warnings_vec;
+        );
+
+        if !(will_parse_any || will_fwd_any || prefixed.is_some()) {
             return quote! {
                 #declarations
+                #declare_attr_span
+                #declare_warnings
             };
         }
 
-        let attrs_accessor = self.attrs_accessor();
+        let unwrap_cfg_attr = self.unwrap_cfg_attr();
+
+        let parse = match (self.name_value_key(), self.expr_values()) {
+            (Some(key), false) => {
+                quote!(::darling::export::parse_attribute_to_meta_list_with_value(__attr, #key))
+            }
+            (Some(key), true) => quote!(
+                ::darling::export::parse_attribute_to_meta_list_with_value_and_exprs(__attr, #key)
+            ),
+            (None, false) => quote!(::darling::export::parse_attribute_to_meta_list(__attr)),
+            (None, true) => {
+                quote!(::darling::export::parse_attribute_to_meta_list_with_exprs(
+                    __attr
+                ))
+            }
+        };
+
+        let record_attr = if self.report() {
+            quote!(::darling::export::report::attribute(__attr);)
+        } else {
+            quote!()
+        };
+
+        // Fallback attributes are searched once all attributes have been seen, so only keys
+        // which no claimed attribute set are read from them. Positional items aren't keys,
+        // so they're never taken from a fallback.
+        let fallback_names = self.fallback_attr_names().to_strings();
+        let uses_fallback = will_parse_any && !fallback_names.is_empty();
+        // Keys are compared by the field they set, so a key set through an alias in a claimed
+        // attribute isn't read again from a fallback.
+        let (declare_fallback, record_keys, parse_fallback, read_fallback) = if uses_fallback {
+            let core_loop = self.core_loop();
+            let canonical_key = self.canonical_key();
+            (
+                quote!(
+                    #canonical_key
+                    let mut __claimed_keys: ::darling::export::Vec<::darling::export::String> = ::darling::export::Vec::new();
+                    let mut __fallback_items: ::darling::export::Vec<::darling::export::syn::NestedMeta> = ::darling::export::Vec::new();
+                ),
+                quote!(for __item in __items {
+                    if let ::darling::export::syn::NestedMeta::Meta(ref __meta) = *__item {
+                        __claimed_keys.push(__canonical_key(&::darling::export::path_to_string(
+                            __meta.path(),
+                        )));
+                    }
+                }),
+                quote!(
+                    #(#fallback_names)|* => {
+                        #record_attr
+                        match #parse {
+                            ::darling::export::Ok(__data) => {
+                                __fallback_items.extend(__data.nested);
+                            }
+                            ::darling::export::Err(__err) => {
+                                __errors.push(__err);
+                            }
+                        }
+                    }
+                ),
+                quote!(
+                    let __fallback_items: ::darling::export::Vec<::darling::export::syn::NestedMeta> = __fallback_items
+                        .into_iter()
+                        .filter(|__item| match *__item {
+                            ::darling::export::syn::NestedMeta::Meta(ref __meta) => {
+                                !__claimed_keys.contains(&__canonical_key(&::darling::export::path_to_string(__meta.path())))
+                            }
+                            ::darling::export::syn::NestedMeta::Lit(_) => false,
+                        })
+                        .collect();
+                    if !__fallback_items.is_empty() {
+                        let __items = &__fallback_items;
+                        #core_loop
+                    }
+                ),
+            )
+        } else {
+            (quote!(), quote!(), quote!(), quote!())
+        };
+
+        // With `deny_empty`, the span of the first matching attribute is kept so that the
+        // error points at it, since an attribute with empty parens isn't read at all.
+        let deny_empty = will_parse_any && self.deny_empty();
+        let (declare_seen_attr, set_seen_attr, check_any_key) = if deny_empty {
+            (
+                quote!(
+                    let mut __seen_attr: ::darling::export::Option<::darling::export::Span> = ::darling::export::None;
+                ),
+                quote!(if __seen_attr.is_none() {
+                    __seen_attr = ::darling::export::Some(
+                        ::darling::export::syn::spanned::Spanned::span(__attr),
+                    );
+                }),
+                variant_data::check_any_key(Some(quote!(__seen_attr))),
+            )
+        } else {
+            (quote!(), quote!(), quote!())
+        };
 
         // The block for parsing attributes whose names have been claimed by the target
         // struct. If no attributes were claimed, this is a pass-through.
         let parse_handled = if will_parse_any {
             let attr_names = self.attr_names().to_strings();
             let core_loop = self.core_loop();
+            let set_attr_span = if record_span {
+                quote!(if __attr_span.is_none() {
+                    __attr_span = ::darling::export::Some(
+                        ::darling::export::syn::spanned::Spanned::span(__attr),
+                    );
+                })
+            } else {
+                quote!()
+            };
             quote!(
                 #(#attr_names)|* => {
-                    match ::darling::util::parse_attribute_to_meta_list(__attr) {
+                    #set_attr_span
+                    #set_seen_attr
+                    #record_attr
+
+                    match #parse {
                         ::darling::export::Ok(__data) => {
                             if __data.nested.is_empty() {
                                 continue;
                             }
 
                             let __items = &__data.nested;
+                            #record_keys
 
                             #core_loop
                         }
@@ -89,32 +289,114 @@ pub trait ExtractAttribute {
             quote!()
         };
 
+        // Attributes claimed by name take precedence over the prefix. Each collected attribute
+        // is read with the `FromMeta` impl of the receiving collection's value type.
+        let parse_prefixed = prefixed.map(|prefixed| {
+            let prefix = prefixed.prefix;
+            quote!(
+                __name if __name.len() > #prefix.len() && __name.starts_with(#prefix) => {
+                    let __suffix = &__name[#prefix.len()..];
+                    if __prefixed_attrs.iter().any(|__seen: &(::darling::export::String, _)| __seen.0 == __suffix) {
+                        __errors.push(::darling::export::Error::duplicate_field(__name).with_span(__attr));
+                        continue;
+                    }
+
+                    match __attr.parse_meta() {
+                        ::darling::export::Ok(__meta) => {
+                            match ::darling::export::FromMeta::from_meta(&__meta) {
+                                ::darling::export::Ok(__value) => {
+                                    __prefixed_attrs.push((::darling::export::ToString::to_string(__suffix), __value));
+                                }
+                                ::darling::export::Err(__err) => {
+                                    __errors.push(__err.with_span(&__meta).at(__name));
+                                }
+                            }
+                        }
+                        ::darling::export::Err(__err) => {
+                            __errors.push(::darling::export::From::from(__err));
+                        }
+                    }
+                }
+            )
+        });
+
         // Specifies the behavior for unhandled attributes. They will either be silently ignored or
         // forwarded to the inner struct for later analysis.
         let forward_unhandled = if will_fwd_any {
-            forwards_to_local(self.forwarded_attrs().unwrap())
+            forwards_to_local(self.forwarded_attrs().unwrap(), unwrap_cfg_attr)
         } else {
             quote!(_ => continue)
         };
 
+        // Filter attributes based on name. This uses the same rendering as the names being
+        // matched, so multi-segment paths such as `my_crate::opts` compare equal.
+        let dispatch = quote!(
+            match ::darling::export::path_to_string(&__attr.path).as_str() {
+                #parse_handled
+                #parse_fallback
+                #parse_prefixed
+                #forward_unhandled
+            }
+        );
+
+        // When requested, search the attributes inside each `cfg_attr` instead of the
+        // `cfg_attr` itself. It's unwrapped when the walk reaches it, so the attributes are
+        // still walked once. Each is forwarded as its source, which keeps its condition. A
+        // malformed `cfg_attr` is reported, and its siblings are still searched.
+        let attrs_accessor = self.attrs_accessor();
+        let walk = if unwrap_cfg_attr {
+            quote!(
+                for __outer_attr in #attrs_accessor {
+                    let (__plain, __unwrapped) = match ::darling::export::unwrap_cfg_attr(__outer_attr) {
+                        ::darling::export::Ok(::darling::export::None) => {
+                            (::darling::export::Some((__outer_attr, __outer_attr)), ::darling::export::Vec::new())
+                        }
+                        ::darling::export::Ok(::darling::export::Some(__attrs)) => (::darling::export::None, __attrs),
+                        ::darling::export::Err(__err) => {
+                            __errors.push(__err);
+                            continue;
+                        }
+                    };
+                    let __inner = __unwrapped.iter().map(|&(ref __attr, ref __source)| (__attr, __source));
+                    for (__attr, __source) in __plain.into_iter().chain(__inner) {
+                        #dispatch
+                    }
+                }
+            )
+        } else {
+            quote!(
+                for __attr in #attrs_accessor {
+                    #dispatch
+                }
+            )
+        };
+
         quote!(
             #declarations
-            use ::darling::ToTokens;
-            let mut __fwd_attrs: ::darling::export::Vec<::syn::Attribute> = vec![];
-
-            for __attr in #attrs_accessor {
-                // Filter attributes based on name
-                match  ::darling::export::ToString::to_string(&__attr.path.clone().into_token_stream()).as_str() {
-                    #parse_handled
-                    #forward_unhandled
-                }
-            }
+            #declare_prefixed
+            #declare_attr_span
+            #declare_warnings
+            #declare_fallback
+            #declare_seen_attr
+            let mut __fwd_attrs: ::darling::export::Vec<::darling::export::syn::Attribute> = ::darling::export::Vec::new();
+
+            #walk
+
+            #read_fallback
+
+            #check_any_key
         )
     }
 }
 
-fn forwards_to_local(behavior: &ForwardAttrs) -> TokenStream {
-    let push_command = quote!(__fwd_attrs.push(__attr.clone()));
+/// The arms which forward unhandled attributes. Attributes taken out of a `cfg_attr` are
+/// forwarded as their source, so they keep their condition.
+fn forwards_to_local(behavior: &ForwardAttrs, unwrapped: bool) -> TokenStream {
+    let push_command = if unwrapped {
+        quote!(__fwd_attrs.push(__source.clone()))
+    } else {
+        quote!(__fwd_attrs.push(__attr.clone()))
+    };
     match *behavior {
         ForwardAttrs::All => quote!(_ => #push_command),
         ForwardAttrs::Only(ref idents) => {
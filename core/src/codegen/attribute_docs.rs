@@ -0,0 +1,115 @@
+use std::fmt::Write;
+
+use syn::Path;
+
+use crate::ast::{Data, Style};
+use crate::codegen::{DefaultExpression, TraitImpl};
+use crate::util::render_tokens as render;
+
+/// Render the Markdown list of accepted options which is stored in `FromMeta::ATTRIBUTE_DOCS`.
+///
+/// Structs list one item per field with its type, default and whether it may be repeated.
+/// With `rename_all_with`, the names are listed before renaming, followed by a note naming the
+/// function.
+/// Enums list one item per variant, written the way it appears in an attribute. Unit and
+/// newtype structs don't have options of their own, so they produce an empty string.
+pub(crate) fn attribute_docs(base: &TraitImpl<'_>) -> String {
+    let mut docs = String::new();
+
+    match base.data {
+        Data::Struct(ref fields) | Data::Union(ref fields) => {
+            if fields.style != Style::Struct {
+                return docs;
+            }
+
+            for field in fields.iter().filter(|field| !field.skip) {
+                write!(docs, "* `{}`: `{}`", field.name_in_attr, render(field.ty)).unwrap();
+                match field.default_expression {
+                    Some(DefaultExpression::Trait) => {
+                        docs.push_str(", default `Default::default()`")
+                    }
+                    Some(DefaultExpression::Explicit(path)) => {
+                        write!(docs, ", default `{}()`", render(path)).unwrap()
+                    }
+                    Some(DefaultExpression::Inherit(_)) => {
+                        docs.push_str(", default from the container's default")
+                    }
+                    None => {}
+                }
+                match field.aliases.len() {
+                    0 => {}
+                    1 => write!(docs, ", alias `{}`", field.aliases[0]).unwrap(),
+                    _ => {
+                        docs.push_str(", aliases ");
+                        for (i, alias) in field.aliases.iter().enumerate() {
+                            if i > 0 {
+                                docs.push_str(", ");
+                            }
+                            write!(docs, "`{}`", alias).unwrap();
+                        }
+                    }
+                }
+                if field.multiple {
+                    docs.push_str(", may be repeated");
+                }
+                if field.deprecated.is_some() {
+                    docs.push_str(", deprecated");
+                }
+                docs.push('\n');
+            }
+
+            note_rename(
+                &mut docs,
+                fields.iter().filter_map(|field| field.rename_with).next(),
+            );
+        }
+        Data::Enum(ref variants) => {
+            for variant in variants.iter().filter(|variant| !variant.skip) {
+                if variant.data.is_unit() {
+                    writeln!(docs, "* `{}`", variant.name_in_attr).unwrap();
+                } else {
+                    writeln!(docs, "* `{}(...)`", variant.name_in_attr).unwrap();
+                }
+            }
+
+            note_rename(
+                &mut docs,
+                variants
+                    .iter()
+                    .filter_map(|variant| variant.rename_with)
+                    .next(),
+            );
+        }
+    }
+
+    if base.allow_unknown_fields && !docs.is_empty() {
+        docs.push_str("\nUnknown options are ignored.\n");
+    }
+
+    docs
+}
+
+/// Name the `rename_all_with` function, if there is one. The renamed names are only known
+/// when the generated code runs, so they can't be listed.
+fn note_rename(docs: &mut String, rename: Option<&Path>) {
+    if let Some(rename) = rename {
+        write!(
+            docs,
+            "\nThe names above are passed through `{}` before they are matched.\n",
+            render(rename)
+        )
+        .unwrap();
+    }
+}
+
+/// Render the expression which provides the value of an omitted field.
+pub(crate) fn describe_default(default: &DefaultExpression<'_>, base: &TraitImpl<'_>) -> String {
+    match *default {
+        DefaultExpression::Trait => "Default::default()".to_string(),
+        DefaultExpression::Explicit(path) => format!("{}()", render(path)),
+        DefaultExpression::Inherit(ident) => match base.default {
+            Some(ref container) => format!("{}.{}", describe_default(container, base), ident),
+            None => format!("Default::default().{}", ident),
+        },
+    }
+}
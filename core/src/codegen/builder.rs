@@ -0,0 +1,248 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{Generics, Ident, Path, Type};
+
+use crate::ast::{Data, Fields, Style};
+use crate::codegen::{DefaultExpression, Field, FromMetaImpl, PostfixTransform, TraitImpl};
+
+/// Declares a `FromMeta` schema for a named-field struct without deriving it.
+///
+/// This is for macros which generate the options struct themselves, and so can't put
+/// `#[darling(...)]` attributes on it. The builder emits the same impl the derive would
+/// produce for a struct with the equivalent attributes.
+///
+/// ```rust
+/// # extern crate darling_core;
+/// # extern crate syn;
+/// # extern crate quote;
+/// use darling_core::codegen::{FieldBuilder, FromMetaBuilder};
+/// use quote::ToTokens;
+/// use syn::parse_quote;
+///
+/// let tokens = FromMetaBuilder::new(parse_quote!(Opts))
+///     .field(FieldBuilder::new(parse_quote!(name), parse_quote!(String)).rename("label"))
+///     .field(FieldBuilder::new(parse_quote!(retries), parse_quote!(u8)).default())
+///     .into_token_stream();
+///
+/// let item: syn::ItemImpl = syn::parse2(tokens).unwrap();
+/// assert_eq!(item.self_ty, parse_quote!(Opts));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FromMetaBuilder {
+    ident: Ident,
+    generics: Generics,
+    fields: Vec<FieldBuilder>,
+    default: Option<FieldDefault>,
+    allow_unknown_fields: bool,
+}
+
+impl FromMetaBuilder {
+    /// Start a schema for the struct named `ident`, which has no generics and no fields.
+    pub fn new(ident: Ident) -> Self {
+        FromMetaBuilder {
+            ident,
+            generics: Generics::default(),
+            fields: vec![],
+            default: None,
+            allow_unknown_fields: false,
+        }
+    }
+
+    /// Set the generics of the struct. This is equivalent to declaring them on the struct.
+    pub fn generics(mut self, generics: Generics) -> Self {
+        self.generics = generics;
+        self
+    }
+
+    /// Add a field. Fields are initialized in the order they are added.
+    pub fn field(mut self, field: FieldBuilder) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Fill missing fields from the struct's `Default` impl, like `#[darling(default)]`.
+    pub fn default(mut self) -> Self {
+        self.default = Some(FieldDefault::Trait);
+        self
+    }
+
+    /// Fill missing fields from the value returned by `path`, like `#[darling(default = "...")]`.
+    pub fn default_with(mut self, path: Path) -> Self {
+        self.default = Some(FieldDefault::Explicit(path));
+        self
+    }
+
+    /// Ignore unknown fields instead of reporting them, like `#[darling(allow_unknown_fields)]`.
+    pub fn allow_unknown_fields(mut self, allow: bool) -> Self {
+        self.allow_unknown_fields = allow;
+        self
+    }
+}
+
+impl ToTokens for FromMetaBuilder {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let has_container_default = self.default.is_some();
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| field.as_codegen_field(has_container_default))
+            .collect();
+
+        let mut base = TraitImpl::new(
+            &self.ident,
+            &self.generics,
+            Data::Struct(Fields::new(Style::Struct, fields)),
+        );
+        base.default = self.default.as_ref().map(FieldDefault::as_codegen_default);
+        base.allow_unknown_fields = self.allow_unknown_fields;
+
+        FromMetaImpl::new(base).to_tokens(tokens);
+    }
+}
+
+/// A field in a [`FromMetaBuilder`] schema.
+#[derive(Debug, Clone)]
+pub struct FieldBuilder {
+    ident: Ident,
+    ty: Type,
+    name: Option<String>,
+    default: Option<FieldDefault>,
+    with: Option<Path>,
+    map: Option<PostfixTransform>,
+    multiple: bool,
+}
+
+impl FieldBuilder {
+    /// Declare a field named `ident` of type `ty`, read with the type's `FromMeta` impl.
+    pub fn new(ident: Ident, ty: Type) -> Self {
+        FieldBuilder {
+            ident,
+            ty,
+            name: None,
+            default: None,
+            with: None,
+            map: None,
+            multiple: false,
+        }
+    }
+
+    /// Read the field from `name` rather than from its identifier, like `#[darling(rename = "...")]`.
+    pub fn rename(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Use the field type's `Default` impl when the field is missing, like `#[darling(default)]`.
+    pub fn default(mut self) -> Self {
+        self.default = Some(FieldDefault::Trait);
+        self
+    }
+
+    /// Use the value returned by `path` when the field is missing, like `#[darling(default = "...")]`.
+    pub fn default_with(mut self, path: Path) -> Self {
+        self.default = Some(FieldDefault::Explicit(path));
+        self
+    }
+
+    /// Parse the field with `path` instead of `FromMeta::from_meta`, like `#[darling(with = "...")]`.
+    pub fn with(mut self, path: Path) -> Self {
+        self.with = Some(path);
+        self
+    }
+
+    /// Pass the parsed value through `path` before storing it, like `#[darling(map = "...")]`.
+    pub fn map(mut self, path: Path) -> Self {
+        self.map = Some(PostfixTransform::new(parse_quote!(map), path));
+        self
+    }
+
+    /// Accept the field more than once, like `#[darling(multiple)]`.
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    fn as_codegen_field(&self, has_container_default: bool) -> Field<'_> {
+        let mut field = Field::new(&self.ident, &self.ty);
+        if let Some(ref name) = self.name {
+            field.name_in_attr = Cow::Borrowed(name.as_str());
+        }
+
+        field.default_expression = match self.default {
+            Some(ref default) => Some(default.as_codegen_default()),
+            None if has_container_default => Some(DefaultExpression::Inherit(&self.ident)),
+            None => None,
+        };
+
+        if let Some(ref with) = self.with {
+            field.with_path = Cow::Borrowed(with);
+            field.default_with = false;
+        }
+
+        // Custom parsers and transforms change the stored type, so the field type's
+        // `from_none` can't be used as the fallback.
+        if self.with.is_some() || self.map.is_some() {
+            field.from_none = None;
+        }
+
+        field.post_transform = self.map.as_ref();
+        field.multiple = self.multiple;
+        field
+    }
+}
+
+#[derive(Debug, Clone)]
+enum FieldDefault {
+    Trait,
+    Explicit(Path),
+}
+
+impl FieldDefault {
+    fn as_codegen_default(&self) -> DefaultExpression<'_> {
+        match *self {
+            FieldDefault::Trait => DefaultExpression::Trait,
+            FieldDefault::Explicit(ref path) => DefaultExpression::Explicit(path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FieldBuilder, FromMetaBuilder};
+    use quote::ToTokens;
+    use syn::parse_quote;
+
+    /// The builder should produce exactly what the derive produces for the same schema.
+    #[test]
+    fn matches_derive() {
+        let built = FromMetaBuilder::new(parse_quote!(Opts))
+            .default()
+            .field(FieldBuilder::new(parse_quote!(name), parse_quote!(String)).rename("label"))
+            .field(
+                FieldBuilder::new(parse_quote!(retries), parse_quote!(u8))
+                    .default_with(parse_quote!(three)),
+            )
+            .field(
+                FieldBuilder::new(parse_quote!(tags), parse_quote!(Vec<String>))
+                    .multiple(true)
+                    .map(parse_quote!(sorted)),
+            )
+            .into_token_stream();
+
+        let derived = crate::derive::from_meta(&parse_quote! {
+            #[darling(default)]
+            struct Opts {
+                #[darling(rename = "label")]
+                name: String,
+                #[darling(default = "three")]
+                retries: u8,
+                #[darling(multiple, map = "sorted")]
+                tags: Vec<String>,
+            }
+        });
+
+        assert_eq!(built.to_string(), derived.to_string());
+    }
+}
@@ -0,0 +1,219 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+use syn::{parse_quote, Ident, Type};
+
+use crate::ast::Data;
+use crate::codegen::{compute_impl_bounds, DefaultExpression, Field, TraitImpl};
+
+/// Generates the `XxxBuilder` type requested by `#[darling(builder)]`.
+///
+/// The builder has a setter for every field, named after the field's key in the attribute.
+/// `build` fills unset fields the way parsing fills absent ones, and reports the fields which
+/// have no fallback as missing. The magic fields in `forwarded` have no fallback.
+pub struct BuilderImpl<'a> {
+    pub base: &'a TraitImpl<'a>,
+    pub forwarded: Vec<&'a Ident>,
+}
+
+impl<'a> BuilderImpl<'a> {
+    pub fn new<I: IntoIterator<Item = Option<&'a Ident>>>(
+        base: &'a TraitImpl<'a>,
+        forwarded: I,
+    ) -> Self {
+        BuilderImpl {
+            base,
+            forwarded: forwarded.into_iter().flatten().collect(),
+        }
+    }
+}
+
+/// A field of the builder, with the expression producing an `Option` of its value when unset.
+struct Member<'a> {
+    ident: &'a Ident,
+    ty: &'a Type,
+    setter: Ident,
+    doc: String,
+    fallback: TokenStream,
+}
+
+impl<'a> Member<'a> {
+    fn of_field(field: &'a Field<'a>) -> Self {
+        let name = &field.name_in_attr;
+        let fallback = match field.default_expression {
+            Some(DefaultExpression::Inherit(ident)) => {
+                quote!(::darling::export::Some(__default.#ident))
+            }
+            Some(ref expr) => quote!(::darling::export::Some(#expr)),
+            None if field.multiple => {
+                quote!(::darling::export::Some(
+                    ::darling::export::Default::default()
+                ))
+            }
+            None => {
+                let from_none = match field.from_none {
+                    Some(ref from_none) => quote!(#from_none),
+                    None => quote!(::darling::export::None),
+                };
+                missing_or(field.name_call(), from_none)
+            }
+        };
+
+        Member {
+            ident: field.ident,
+            ty: field.ty,
+            setter: syn::parse_str(name).unwrap_or_else(|_| field.ident.clone()),
+            doc: if field.skip {
+                format!(" Sets the skipped field `{}`.", field.ident)
+            } else {
+                format!(" Sets `{}`, as if it were written in the attribute.", name)
+            },
+            fallback,
+        }
+    }
+
+    fn forwarded(ident: &'a Ident, ty: &'a Type) -> Self {
+        let name = ident.to_string();
+        Member {
+            ident,
+            ty,
+            setter: ident.clone(),
+            doc: format!(" Sets the forwarded field `{}`.", name),
+            fallback: missing_or(quote!(#name), quote!(::darling::export::None)),
+        }
+    }
+}
+
+/// Use `value` if it's `Some`, or record a missing field error for `name`.
+fn missing_or(name: TokenStream, value: TokenStream) -> TokenStream {
+    quote!(match #value {
+        ::darling::export::Some(__value) => ::darling::export::Some(__value),
+        ::darling::export::None => {
+            __errors.push(::darling::export::Error::missing_field(#name));
+            ::darling::export::None
+        }
+    })
+}
+
+impl<'a> ToTokens for BuilderImpl<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let base = self.base;
+        let (input, fields) = match (base.builder, &base.data) {
+            (Some(input), Data::Struct(fields)) => (input, fields),
+            _ => return,
+        };
+        let input_fields = match input.data {
+            syn::Data::Struct(ref data) => &data.fields,
+            _ => return,
+        };
+
+        let mut members: Vec<Member> = fields.iter().map(Member::of_field).collect();
+        for ident in &self.forwarded {
+            if let Some(field) = input_fields
+                .iter()
+                .find(|field| field.ident.as_ref() == Some(*ident))
+            {
+                members.push(Member::forwarded(ident, &field.ty));
+            }
+        }
+
+        let inherits = fields.iter().any(|field| match field.default_expression {
+            Some(DefaultExpression::Inherit(_)) => true,
+            _ => false,
+        });
+        let vis = &input.vis;
+        let ty_ident = base.ident;
+        let generics = base.generics;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        // `Self` is the builder here, so the fallback instance is declared with the built type.
+        let fallback = match base.default {
+            Some(ref expr) if inherits => {
+                Some(quote!(let __default: #ty_ident #ty_generics = #expr;))
+            }
+            _ => None,
+        };
+
+        let builder_ident = Ident::new(&format!("{}Builder", ty_ident), ty_ident.span());
+        let struct_doc = format!(
+            " Builds a [`{}`] without parsing an attribute. Unset fields get the value they would have if they were absent from the attribute.",
+            ty_ident
+        );
+        let post_transform = base.post_transform_call();
+        // Unset fields fall back to `FromMeta::from_none`, like they do when parsing.
+        let build_generics = compute_impl_bounds(
+            parse_quote!(::darling::export::FromMeta),
+            generics.clone(),
+            &base.used_type_params(),
+        );
+        let (build_impl_generics, _, build_where_clause) = build_generics.split_for_impl();
+
+        let idents: Vec<&Ident> = members.iter().map(|member| member.ident).collect();
+        let idents = &idents;
+        let decls = members.iter().map(|member| {
+            let ident = member.ident;
+            let ty = member.ty;
+            quote!(#ident: ::darling::export::Option<#ty>)
+        });
+        let setters = members.iter().map(|member| {
+            let ident = member.ident;
+            let ty = member.ty;
+            let setter = &member.setter;
+            let doc = &member.doc;
+            quote!(
+                #[doc = #doc]
+                #vis fn #setter(mut self, value: #ty) -> Self {
+                    self.#ident = ::darling::export::Some(value);
+                    self
+                }
+            )
+        });
+        let fallbacks = members.iter().map(|member| {
+            let ident = member.ident;
+            let fallback = &member.fallback;
+            quote!(
+                let #ident = match self.#ident {
+                    ::darling::export::Some(__value) => ::darling::export::Some(__value),
+                    ::darling::export::None => #fallback,
+                };
+            )
+        });
+
+        tokens.append_all(quote!(
+            #[doc = #struct_doc]
+            #vis struct #builder_ident #generics #where_clause {
+                #(#decls,)*
+            }
+
+            impl #impl_generics ::darling::export::Default for #builder_ident #ty_generics #where_clause {
+                fn default() -> Self {
+                    #builder_ident {
+                        #(#idents: ::darling::export::None,)*
+                    }
+                }
+            }
+
+            impl #build_impl_generics #builder_ident #ty_generics #build_where_clause {
+                #(#setters)*
+
+                /// Creates the value, or returns an error naming the fields which were not set
+                /// and have no fallback.
+                #vis fn build(self) -> ::darling::export::Result<#ty_ident #ty_generics, ::darling::export::Error> {
+                    let mut __errors = ::darling::export::Error::accumulator();
+                    #fallback
+                    #(#fallbacks)*
+                    __errors.finish()?;
+
+                    ::darling::export::Ok(#ty_ident {
+                        #(#idents: #idents.unwrap(),)*
+                    }) #post_transform
+                }
+            }
+
+            impl #impl_generics #ty_ident #ty_generics #where_clause {
+                /// Starts a builder with no fields set.
+                #vis fn builder() -> #builder_ident #ty_generics {
+                    ::darling::export::Default::default()
+                }
+            }
+        ));
+    }
+}
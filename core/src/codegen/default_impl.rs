@@ -0,0 +1,89 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt};
+use syn::{parse_quote, Ident};
+
+use crate::ast::Data;
+use crate::codegen::{compute_impl_bounds, DefaultExpression, TraitImpl};
+
+/// Generates the `Default` impl requested by `#[darling(derive_default)]`.
+///
+/// Each field gets the value parsing would give it if it were absent from the input. Fields
+/// without a default, and the magic fields in `forwarded`, use their type's `Default` impl.
+pub struct DefaultImpl<'a> {
+    pub base: &'a TraitImpl<'a>,
+    pub forwarded: Vec<&'a Ident>,
+}
+
+impl<'a> DefaultImpl<'a> {
+    pub fn new<I: IntoIterator<Item = Option<&'a Ident>>>(
+        base: &'a TraitImpl<'a>,
+        forwarded: I,
+    ) -> Self {
+        DefaultImpl {
+            base,
+            forwarded: forwarded.into_iter().flatten().collect(),
+        }
+    }
+}
+
+impl<'a> ToTokens for DefaultImpl<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let base = self.base;
+        let fields = match base.data {
+            Data::Struct(ref fields) if base.derive_default => fields,
+            _ => return,
+        };
+
+        // A container default of `Default::default()` would call the impl being generated, so
+        // fields which inherit from it take their type's default instead.
+        let fallback = match base.default {
+            Some(DefaultExpression::Explicit(_)) => Some(base.fallback_decl()),
+            _ => None,
+        };
+
+        let inits = fields.iter().map(|field| {
+            let member = match field.index {
+                Some(index) => syn::Index::from(index).into_token_stream(),
+                None => field.ident.into_token_stream(),
+            };
+            let value = match field.default_expression {
+                Some(DefaultExpression::Inherit(_)) if fallback.is_some() => {
+                    quote!(__default.#member)
+                }
+                Some(DefaultExpression::Explicit(_)) => {
+                    let expr = field.default_expression.as_ref();
+                    quote!(#expr)
+                }
+                _ => quote!(::darling::export::Default::default()),
+            };
+            quote!(#member: #value)
+        });
+        let forwarded = self
+            .forwarded
+            .iter()
+            .map(|ident| quote!(#ident: ::darling::export::Default::default()));
+
+        let ty_ident = base.ident;
+        let generics = compute_impl_bounds(
+            parse_quote!(::darling::export::Default),
+            base.generics.clone(),
+            &base.declared_type_params(),
+        );
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        tokens.append_all(quote!(
+            impl #impl_generics ::darling::export::Default for #ty_ident #ty_generics
+                #where_clause
+            {
+                fn default() -> Self {
+                    #fallback
+
+                    #ty_ident {
+                        #(#inits,)*
+                        #(#forwarded,)*
+                    }
+                }
+            }
+        ));
+    }
+}
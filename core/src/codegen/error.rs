@@ -17,31 +17,47 @@ impl ToTokens for ErrorDeclaration {
 
 /// Returns early if attribute or body parsing has caused any errors.
 #[derive(Default)]
-pub struct ErrorCheck<'a> {
-    location: Option<&'a str>,
+pub struct ErrorCheck {
+    /// An expression for the `&str` the errors are located at, if any.
+    location: Option<TokenStream>,
+    /// An expression for the input item, which errors without a span of their own, such as
+    /// missing fields, point at.
+    item: Option<TokenStream>,
     __hidden: (),
 }
 
-impl<'a> ErrorCheck<'a> {
-    pub fn with_location(location: &'a str) -> Self {
+impl ErrorCheck {
+    pub fn with_location(location: TokenStream) -> Self {
         ErrorCheck {
             location: Some(location),
-            __hidden: (),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_item(item: TokenStream) -> Self {
+        ErrorCheck {
+            item: Some(item),
+            ..Default::default()
         }
     }
 }
 
-impl<'a> ToTokens for ErrorCheck<'a> {
+impl ToTokens for ErrorCheck {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let at_call = if let Some(ref s) = self.location {
             quote!(.at(#s))
         } else {
             quote!()
         };
+        let span_call = if let Some(ref item) = self.item {
+            quote!(.with_span(#item))
+        } else {
+            quote!()
+        };
 
         tokens.append_all(quote! {
             if !__errors.is_empty() {
-                return ::darling::export::Err(::darling::Error::multiple(__errors) #at_call);
+                return ::darling::export::Err(::darling::export::Error::multiple(__errors) #at_call #span_call);
             }
         })
     }
@@ -1,10 +1,13 @@
 use std::borrow::Cow;
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, TokenStreamExt};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
 use syn::{Ident, Path, Type};
 
 use crate::codegen::{DefaultExpression, PostfixTransform};
+use crate::codegen::{ErrorSpan, RequiredField, SkippedInput};
 use crate::usage::{self, IdentRefSet, IdentSet, UsesTypeParams};
 
 /// Properties needed to generate code for a field in all the contexts
@@ -13,7 +16,15 @@ use crate::usage::{self, IdentRefSet, IdentSet, UsesTypeParams};
 pub struct Field<'a> {
     /// The name presented to the user of the library. This will appear
     /// in error messages and will be looked when parsing names.
-    pub name_in_attr: Cow<'a, String>,
+    pub name_in_attr: Cow<'a, str>,
+
+    /// Other names the field can be read from. A field set under more than one of its names
+    /// is an error.
+    pub aliases: &'a [String],
+
+    /// A function from the container's `rename_all_with`, which computes the name presented
+    /// to the user from `name_in_attr` when the generated code runs.
+    pub rename_with: Option<&'a Path>,
 
     /// The name presented to the author of the library. This will appear
     /// in the setters or temporary variables which contain the values.
@@ -23,16 +34,167 @@ pub struct Field<'a> {
     pub ty: &'a Type,
     pub default_expression: Option<DefaultExpression<'a>>,
     pub with_path: Cow<'a, Path>,
+    /// If `true`, `with_path` is the field type's `FromMeta` impl rather than a custom
+    /// function. Such fields are read with `from_meta_with_warnings`, so the warnings from
+    /// their options reach the caller.
+    pub default_with: bool,
     pub post_transform: Option<&'a PostfixTransform>,
     pub skip: bool,
+    /// What the generated code does when the input sets a skipped field.
+    pub skipped_input: SkippedInput,
     pub multiple: bool,
+    /// An expression producing `Option<T>` which is used to fill the field if it
+    /// doesn't appear in the input and has no default.
+    pub from_none: Option<syn::Expr>,
+    /// Which part of the item parsing errors should point at. If `None`, errors keep the
+    /// most specific span available.
+    pub error_span: Option<ErrorSpan>,
+    /// The note to include in a deprecation warning when the field is used, if any.
+    pub deprecated: Option<&'a str>,
+    /// If `true`, the field's value is read through `darling::util::DynFieldSlot` rather than
+    /// by code generated for this field.
+    pub dyn_fields: bool,
+    /// Guidance for the error reported when the field is absent, if the author gave any.
+    pub required: Option<&'a RequiredField>,
+    /// If set, the field is read as a list from the keys which start with this prefix rather
+    /// than from a key of its own.
+    pub prefix: Option<&'a str>,
+    /// If `true`, a value written as `#[my_attr = value]` or `my_attr = value` is read as if it
+    /// were `my_attr(<this field> = value)`.
+    pub name_value: bool,
+    /// The position of an unnamed field. Unnamed fields are read from positional items, and
+    /// are initialized by position instead of by `ident`.
+    pub index: Option<usize>,
+    /// If `true`, the generated code which parses this field uses the call-site span instead
+    /// of the span of the field type. The field type's span reports a missing `FromMeta` impl
+    /// at the field rather than at the derive.
+    pub call_site_span: bool,
+    /// If `true`, the generated code notes in the active parse report which meta item
+    /// supplied the field, or that it fell back to a default.
+    pub report: bool,
+    /// If `true`, the field is the magic field which receives the config files that were
+    /// read, rather than an option.
+    pub config_files: bool,
 }
 
 impl<'a> Field<'a> {
+    /// Create a field which is read using its own name and its type's `FromMeta` impl.
+    pub fn new(ident: &'a Ident, ty: &'a Type) -> Self {
+        Field {
+            name_in_attr: Cow::Owned(ident.to_string()),
+            aliases: &[],
+            rename_with: None,
+            ident,
+            ty,
+            default_expression: None,
+            with_path: Cow::Owned(default_with_path(ty.span())),
+            default_with: true,
+            post_transform: None,
+            skip: false,
+            skipped_input: SkippedInput::default(),
+            multiple: false,
+            from_none: Some(default_from_none(ty, ty.span())),
+            error_span: None,
+            deprecated: None,
+            dyn_fields: false,
+            required: None,
+            prefix: None,
+            name_value: false,
+            index: None,
+            call_site_span: false,
+            report: false,
+            config_files: false,
+        }
+    }
+
+    /// The span given to the generated code which parses this field.
+    pub fn span(&self) -> Span {
+        field_span(self.ty, self.call_site_span)
+    }
+
+    /// The expression for the field's default. `Default::default()` is spanned like the rest
+    /// of the field's parsing code, so a type without a `Default` impl is reported at the field.
+    fn default_tokens(&self, expr: &DefaultExpression<'_>) -> TokenStream {
+        match *expr {
+            DefaultExpression::Trait => {
+                quote_spanned!(self.span()=> ::darling::export::Default::default())
+            }
+            ref expr => quote!(#expr),
+        }
+    }
+
     pub fn as_name(&'a self) -> &'a str {
         &self.name_in_attr
     }
 
+    /// Returns `true` if the field can be left out of the input, as far as can be told from
+    /// its options and how its type is written.
+    pub(in crate::codegen) fn has_default(&self) -> bool {
+        self.default_expression.is_some() || (self.from_none.is_some() && is_optional(self.ty))
+    }
+
+    /// An expression for the name presented to the user, as a `&str`.
+    ///
+    /// A renamed field reads the local from its declaration, so the rename function runs
+    /// once per parse rather than once per item.
+    pub(in crate::codegen) fn name_tokens(&self) -> TokenStream {
+        match self.rename_with {
+            Some(_) => {
+                let renamed = self.renamed_name();
+                quote!(::darling::export::String::as_str(&#renamed))
+            }
+            None => {
+                let name = &self.name_in_attr;
+                quote!(#name)
+            }
+        }
+    }
+
+    /// Like `name_tokens`, but calls the rename function itself, for generated code which
+    /// doesn't declare the field's locals.
+    pub(in crate::codegen) fn name_call(&self) -> TokenStream {
+        let name = &self.name_in_attr;
+        match self.rename_with {
+            Some(rename) => quote!(::darling::export::String::as_str(&#rename(#name))),
+            None => quote!(#name),
+        }
+    }
+
+    /// The pattern matching the keys of the field in a `match` on a key's length and text,
+    /// as written by [`key_scrutinee`].
+    pub(in crate::codegen) fn name_pattern(&self) -> TokenStream {
+        let aliases = self.aliases;
+        match self.rename_with {
+            Some(_) => {
+                let name = self.name_tokens();
+                quote!((_, __key) if __key == #name #(|| __key == #aliases)*)
+            }
+            None => {
+                let keys = Some(&*self.name_in_attr)
+                    .into_iter()
+                    .chain(aliases.iter().map(String::as_str))
+                    .map(key_pattern);
+                quote!(#(#keys)|*)
+            }
+        }
+    }
+
+    /// The local variable which holds the name of a renamed field.
+    fn renamed_name(&self) -> Ident {
+        Ident::new(
+            &format!("__renamed_{}", self.ident.unraw()),
+            Span::call_site(),
+        )
+    }
+
+    /// The local variable which collects the items of a field with a `prefix`.
+    pub(in crate::codegen) fn prefixed_items(&self) -> Ident {
+        Ident::new(
+            &format!("__prefixed_{}", self.ident.unraw()),
+            Span::call_site(),
+        )
+    }
+
     pub fn as_declaration(&'a self) -> Declaration<'a> {
         Declaration(self, !self.skip)
     }
@@ -60,6 +222,58 @@ impl<'a> UsesTypeParams for Field<'a> {
     }
 }
 
+/// The value matched against field keys, where `name` is a `&str` expression.
+///
+/// Matching on the length before the text lets the compiler pick the few keys of the right
+/// length with one jump instead of comparing the key to every name in turn, which adds up
+/// for structs with many fields.
+pub(in crate::codegen) fn key_scrutinee(name: TokenStream) -> TokenStream {
+    quote!((#name.len(), #name))
+}
+
+/// The pattern matching exactly `key` in a `match` on [`key_scrutinee`].
+pub(in crate::codegen) fn key_pattern(key: &str) -> TokenStream {
+    let len = proc_macro2::Literal::usize_unsuffixed(key.len());
+    quote!((#len, #key))
+}
+
+/// The span given to the generated code which parses a field of type `ty`. This is the
+/// span of the type, or the call site if `call_site` is set.
+pub(crate) fn field_span(ty: &Type, call_site: bool) -> Span {
+    if call_site {
+        Span::call_site()
+    } else {
+        ty.span()
+    }
+}
+
+/// The types whose `from_none` fills in a value when they're absent.
+const OPTIONAL_TYPES: &[&str] = &["Option", "Flag"];
+
+/// Returns `true` if `ty` is written as one of the types whose `from_none` fills in a value.
+fn is_optional(ty: &Type) -> bool {
+    match *ty {
+        Type::Path(ref ty) if ty.qself.is_none() => {
+            ty.path.segments.last().map_or(false, |segment| {
+                OPTIONAL_TYPES.iter().any(|name| segment.ident == name)
+            })
+        }
+        _ => false,
+    }
+}
+
+/// The parser used for a field which doesn't set `with`, spanned at `span`.
+pub(crate) fn default_with_path(span: Span) -> Path {
+    let tokens = quote_spanned!(span=> ::darling::export::FromMeta::from_meta);
+    parse_quote!(#tokens)
+}
+
+/// The fallback used for an absent field of type `ty`, spanned at `span`.
+pub(crate) fn default_from_none(ty: &Type, span: Span) -> syn::Expr {
+    let tokens = quote_spanned!(span=> <#ty as ::darling::export::FromMeta>::from_none());
+    parse_quote!(#tokens)
+}
+
 /// An individual field during variable declaration in the generated parsing method.
 pub struct Declaration<'a>(&'a Field<'a>, bool);
 
@@ -76,13 +290,35 @@ impl<'a> ToTokens for Declaration<'a> {
         let ident = field.ident;
         let ty = field.ty;
 
+        // The config files are collected in a local of their own.
+        if field.config_files {
+            return;
+        }
+
         let mutable = if self.1 { quote!(mut) } else { quote!() };
 
+        if let Some(rename) = field.rename_with {
+            let name = &field.name_in_attr;
+            let renamed = field.renamed_name();
+            tokens.append_all(quote!(
+                let #renamed: ::darling::export::String = #rename(#name);
+            ));
+        }
+
+        if field.prefix.is_some() {
+            let items = field.prefixed_items();
+            tokens.append_all(quote!(
+                let #mutable #items: ::darling::export::Vec<::darling::export::syn::NestedMeta> =
+                    ::darling::export::Vec::new();
+            ));
+        }
+
         tokens.append_all(if field.multiple {
             // This is NOT mutable, as it will be declared mutable only temporarily.
-            quote!(let #mutable #ident: #ty = ::darling::export::Default::default();)
+            let empty = quote_spanned!(field.span()=> ::darling::export::Default::default());
+            quote!(let #mutable #ident: #ty = #empty;)
         } else {
-            quote!(let #mutable #ident: (bool, ::darling::export::Option<#ty>) = (false, None);)
+            quote!(let #mutable #ident: ::darling::export::FieldSlot<#ty> = ::darling::export::FieldSlot::new();)
         });
     }
 }
@@ -93,8 +329,37 @@ pub struct MatchArm<'a>(&'a Field<'a>);
 impl<'a> ToTokens for MatchArm<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let field: &Field = self.0;
-        if !field.skip {
-            let name_str = &field.name_in_attr;
+        let name_str = field.name_tokens();
+        let pattern = field.name_pattern();
+        if field.prefix.is_some() {
+            // The keys of a prefixed field are matched by `PrefixMatchArm`.
+        } else if field.skip {
+            let report_ignored = if field.report {
+                quote!(::darling::export::report::ignored(#name_str, __inner);)
+            } else {
+                quote!()
+            };
+            tokens.append_all(match field.skipped_input {
+                SkippedInput::Error => quote!(
+                    #pattern => {
+                        __errors.push(::darling::export::Error::skipped_field(#name_str).with_span(__inner));
+                    }
+                ),
+                SkippedInput::Warn => quote!(
+                    #pattern => {
+                        __warnings.push(
+                            ::darling::export::Warning::skipped_field(#name_str).with_span(__inner)
+                        );
+                        #report_ignored
+                    }
+                ),
+                SkippedInput::Ignore => quote!(
+                    #pattern => {
+                        #report_ignored
+                    }
+                ),
+            });
+        } else {
             let ident = field.ident;
             let with_path = &field.with_path;
             let post_transform = field.post_transform.as_ref();
@@ -106,7 +371,7 @@ impl<'a> ToTokens for MatchArm<'a> {
                 // we use the local variable `len` here because location is accessed via
                 // a closure, and the borrow checker gets very unhappy if we try to immutably
                 // borrow `#ident` in that closure when it was declared `mut` outside.
-                quote!(&format!("{}[{}]", #name_str, __len))
+                quote!(&::darling::export::format!("{}[{}]", #name_str, __len))
             } else {
                 quote!(#name_str)
             };
@@ -115,40 +380,64 @@ impl<'a> ToTokens for MatchArm<'a> {
             // The behavior of `with_span` makes this safe to do; if the child applied an
             // even-more-specific span, our attempt here will not overwrite that and will only cost
             // us one `if` check.
-            let extractor = quote!(#with_path(__inner)#post_transform.map_err(|e| e.with_span(&__inner).at(#location)));
+            //
+            // An explicit `error_span = "key"` instead replaces whatever span the child chose.
+            // With `error_span = "value"`, the errors of a single literal are moved onto it,
+            // but the errors inside a list keep their own spans and only fill in missing ones.
+            let apply_span = match field.error_span {
+                None => quote!(e.with_span(&__inner)),
+                Some(ErrorSpan::Key) => quote!(e.with_span_override(__inner.path())),
+                Some(ErrorSpan::Value) => quote!(match *__inner {
+                    ::darling::export::syn::Meta::NameValue(ref __nv) =>
+                        e.with_span_override(&__nv.lit),
+                    ::darling::export::syn::Meta::List(ref __list) => e.with_span(
+                        &::darling::export::SpannedValue::new((), __list.paren_token.span)
+                    ),
+                    ::darling::export::syn::Meta::Path(ref __path) => e.with_span_override(__path),
+                }),
+            };
+            let warn_deprecated = field.deprecated.map(|note| {
+                quote!(__warnings.push(
+                    ::darling::export::Warning::deprecated(#name_str, #note).with_span(__inner.path())
+                );)
+            });
+            let call = if field.default_with {
+                quote_spanned!(field.span()=> ::darling::export::FromMeta::from_meta_with_warnings(__inner, __warnings))
+            } else {
+                quote_spanned!(field.span()=> #with_path(__inner))
+            };
+            let parse = quote!(#call #post_transform.map_err(|e| #apply_span.at(#location)));
+            let extractor = if field.report {
+                quote!(::darling::export::report::scope(#name_str, __inner, || #parse))
+            } else {
+                parse
+            };
 
-            tokens.append_all(if field.multiple {
+            tokens.append_all(if field.dyn_fields {
+                let parse = quote_spanned!(field.span()=>
+                    ::darling::export::parse_dyn_field(&mut #ident, #name_str, &__name, __inner, &mut __errors, __warnings)
+                );
+                quote!(
+                    #pattern => {
+                        #warn_deprecated
+                        #parse;
+                    }
+                )
+            } else if field.multiple {
                 quote!(
-                    #name_str => {
+                    #pattern => {
+                        #warn_deprecated
                         // Store the index of the name we're assessing in case we need
                         // it for error reporting.
                         let __len = #ident.len();
-                        match #extractor {
-                            ::darling::export::Ok(__val) => {
-                                #ident.push(__val)
-                            }
-                            ::darling::export::Err(__err) => {
-                                __errors.push(__err)
-                            }
-                        }
+                        ::darling::export::push_multiple(&mut #ident, &mut __errors, #extractor);
                     }
                 )
             } else {
                 quote!(
-                    #name_str => {
-                        if !#ident.0 {
-                            match #extractor {
-                                ::darling::export::Ok(__val) => {
-                                    #ident = (true, ::darling::export::Some(__val));
-                                }
-                                ::darling::export::Err(__err) => {
-                                    #ident = (true, None);
-                                    __errors.push(__err);
-                                }
-                            }
-                        } else {
-                            __errors.push(::darling::Error::duplicate_field(#name_str).with_span(&__inner));
-                        }
+                    #pattern => {
+                        #warn_deprecated
+                        #ident.parse(#name_str, &__name, __inner, &mut __errors, || #extractor);
                     }
                 )
             });
@@ -156,6 +445,67 @@ impl<'a> ToTokens for MatchArm<'a> {
     }
 }
 
+/// Collects an item whose key starts with the prefix of a field with `prefix`.
+///
+/// This has a guard, so it must follow the arms which match exact names.
+pub struct PrefixMatchArm<'a>(&'a Field<'a>);
+
+impl<'a> PrefixMatchArm<'a> {
+    pub fn new(field: &'a Field<'a>) -> Option<Self> {
+        field.prefix.map(|_| PrefixMatchArm(field))
+    }
+}
+
+impl<'a> ToTokens for PrefixMatchArm<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let prefix = self.0.prefix;
+        let items = self.0.prefixed_items();
+        tokens.append_all(quote!(
+            (_, __other) if __other.starts_with(#prefix) => {
+                match ::darling::export::strip_key_prefix(__inner, #prefix) {
+                    ::darling::export::Ok(__stripped) => #items.push(__stripped),
+                    ::darling::export::Err(__err) => __errors.push(__err),
+                }
+            }
+        ));
+    }
+}
+
+/// Reads an unnamed field from the item at `position` in the input.
+///
+/// Skipped fields don't take up a position, so it can differ from the field's index.
+pub struct PositionalArm<'a>(&'a Field<'a>, usize);
+
+impl<'a> PositionalArm<'a> {
+    pub fn new(field: &'a Field<'a>, position: usize) -> Self {
+        PositionalArm(field, position)
+    }
+}
+
+impl<'a> ToTokens for PositionalArm<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let field: &Field = self.0;
+        let position = self.1;
+        let ident = field.ident;
+        let name_str = &field.name_in_attr;
+        let post_transform = field.post_transform.as_ref();
+        let call =
+            quote_spanned!(field.span()=> ::darling::export::FromMeta::from_nested_meta(__item));
+
+        tokens.append_all(quote!(
+            #position => {
+                if #ident.is_seen() {
+                    __errors.push(::darling::export::Error::duplicate_field(#name_str).with_span(__item));
+                } else {
+                    #ident.parse_items(&mut __errors, || {
+                        #call #post_transform.map_err(|e| e.with_span(__item).at(#name_str))
+                    });
+                }
+            }
+        ));
+    }
+}
+
 /// Wrapper to generate initialization code for a field.
 pub struct Initializer<'a>(&'a Field<'a>);
 
@@ -163,23 +513,60 @@ impl<'a> ToTokens for Initializer<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let field: &Field = self.0;
         let ident = field.ident;
+        if field.config_files {
+            tokens.append_all(quote!(#ident: __config_files));
+            return;
+        }
+
+        // Unnamed fields are initialized by position, as in `Self { 0: value }`, and inherit
+        // their default from the same position of the fallback instance.
+        let (member, default_expression) = match field.index {
+            Some(index) => {
+                let index = syn::Index::from(index);
+                let default_expression = match field.default_expression {
+                    Some(DefaultExpression::Inherit(_)) => Some(quote!(__default.#index)),
+                    Some(ref expr) => Some(field.default_tokens(expr)),
+                    None => None,
+                };
+                (quote!(#index), default_expression)
+            }
+            None => (
+                quote!(#ident),
+                field
+                    .default_expression
+                    .as_ref()
+                    .map(|expr| field.default_tokens(expr)),
+            ),
+        };
+
+        let report_defaulted = if field.report {
+            let name_in_attr = field.name_tokens();
+            quote!(::darling::export::report::defaulted(#name_in_attr);)
+        } else {
+            quote!()
+        };
+
         tokens.append_all(if field.multiple {
-            if let Some(ref expr) = field.default_expression {
-                quote!(#ident: if !#ident.is_empty() {
+            if let Some(expr) = default_expression {
+                quote!(#member: if !#ident.is_empty() {
                     #ident
                 } else {
+                    #report_defaulted
                     #expr
                 })
             } else {
-                quote!(#ident: #ident)
+                quote!(#member: #ident)
             }
-        } else if let Some(ref expr) = field.default_expression {
-            quote!(#ident: match #ident.1 {
+        } else if let Some(expr) = default_expression {
+            quote!(#member: match #ident.into_value() {
                 ::darling::export::Some(__val) => __val,
-                ::darling::export::None => #expr,
+                ::darling::export::None => {
+                    #report_defaulted
+                    #expr
+                }
             })
         } else {
-            quote!(#ident: #ident.1.expect("Uninitialized fields without defaults were already checked"))
+            quote!(#member: #ident.unwrap())
         });
     }
 }
@@ -189,15 +576,146 @@ pub struct CheckMissing<'a>(&'a Field<'a>);
 
 impl<'a> ToTokens for CheckMissing<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        if !self.0.multiple && self.0.default_expression.is_none() {
+        // A prefixed field is parsed once all of its items are collected. Without a default,
+        // it's parsed even if there are none, so the fields of the nested type can use theirs.
+        if self.0.prefix.is_some() {
             let ident = self.0.ident;
             let name_in_attr = &self.0.name_in_attr;
+            let items = self.0.prefixed_items();
+            let post_transform = self.0.post_transform;
+            let call = quote_spanned!(self.0.span()=> ::darling::export::FromMeta::from_list_with_warnings(&#items, __warnings));
+            let condition = if self.0.default_expression.is_some() {
+                quote!(!#items.is_empty())
+            } else {
+                quote!(true)
+            };
 
             tokens.append_all(quote! {
-                if !#ident.0 {
-                    __errors.push(::darling::Error::missing_field(#name_in_attr));
+                let mut #ident = #ident;
+                if #condition {
+                    #ident.parse_items(&mut __errors, || {
+                        #call #post_transform.map_err(|e| e.at(#name_in_attr))
+                    });
+                }
+            });
+        }
+
+        if !self.0.multiple && self.0.default_expression.is_none() {
+            let ident = self.0.ident;
+            let name_in_attr = self.0.name_tokens();
+
+            let from_none = match self.0.from_none {
+                Some(ref from_none) if self.0.report => quote!({
+                    let __value = #from_none;
+                    if __value.is_some() {
+                        ::darling::export::report::defaulted(#name_in_attr);
+                    }
+                    __value
+                }),
+                Some(ref from_none) => quote!(#from_none),
+                None => quote!(::darling::export::None),
+            };
+
+            // The slot is consumed and rebound, so this works on immutable declarations too.
+            tokens.append_all(match self.0.required {
+                Some(&RequiredField {
+                    message: None,
+                    help: None,
+                })
+                | None => quote! {
+                    let #ident = #ident.require(#name_in_attr, &mut __errors, || #from_none);
+                },
+                Some(required) => {
+                    let note = required
+                        .message
+                        .as_ref()
+                        .map(|message| quote!(.note(#message)));
+                    let help = required.help.as_ref().map(|help| quote!(.help(#help)));
+                    quote! {
+                        let #ident = #ident.require_or_else(&mut __errors, || #from_none, || {
+                            ::darling::export::Error::missing_field(#name_in_attr)#note#help
+                        });
+                    }
                 }
             })
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::{TokenStream, TokenTree};
+    use quote::ToTokens;
+
+    use super::Field;
+    use crate::codegen::DefaultExpression;
+
+    /// The lines of each ident named `name` in `tokens`.
+    fn lines_of(tokens: TokenStream, name: &str) -> Vec<usize> {
+        let mut lines = Vec::new();
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ref ident) if ident == name => {
+                    lines.push(ident.span().start().line)
+                }
+                TokenTree::Group(group) => lines.extend(lines_of(group.stream(), name)),
+                _ => {}
+            }
+        }
+        lines
+    }
+
+    #[test]
+    fn parsing_code_is_spanned_at_the_type() {
+        let ident: syn::Ident = syn::parse_str("count").unwrap();
+        // The type is on line 3 of its source.
+        let ty: syn::Type = syn::parse_str("\n\nVec<u8>").unwrap();
+        let mut field = Field::new(&ident, &ty);
+        field.default_expression = Some(DefaultExpression::Trait);
+
+        let parse = field.as_match().into_token_stream();
+        assert_eq!(lines_of(parse, "from_meta_with_warnings"), vec![3]);
+        let init = field.as_initializer().into_token_stream();
+        assert_eq!(lines_of(init, "Default"), vec![3]);
+
+        field.multiple = true;
+        let decl = field.as_declaration().into_token_stream();
+        assert_eq!(lines_of(decl, "Default"), vec![3]);
+    }
+
+    #[test]
+    fn missing_fallback_is_spanned_at_the_type() {
+        let ident: syn::Ident = syn::parse_str("count").unwrap();
+        let ty: syn::Type = syn::parse_str("\nu8").unwrap();
+        let field = Field::new(&ident, &ty);
+
+        let check = field.as_presence_check().into_token_stream();
+        assert_eq!(lines_of(check, "from_none"), vec![2]);
+    }
+
+    #[test]
+    fn dyn_fields_share_the_parsing_code() {
+        let ident: syn::Ident = syn::parse_str("count").unwrap();
+        let ty: syn::Type = syn::parse_str("\nu8").unwrap();
+        let mut field = Field::new(&ident, &ty);
+        field.dyn_fields = true;
+
+        let parse = field.as_match().into_token_stream();
+        assert_eq!(lines_of(parse.clone(), "parse_dyn_field"), vec![2]);
+        assert!(lines_of(parse, "from_meta_with_warnings").is_empty());
+    }
+
+    #[test]
+    fn keys_are_matched_with_their_lengths() {
+        let ident: syn::Ident = syn::parse_str("count").unwrap();
+        let ty: syn::Type = syn::parse_str("u8").unwrap();
+        let aliases = vec!["n".to_string()];
+        let mut field = Field::new(&ident, &ty);
+        field.aliases = &aliases;
+
+        assert_eq!(
+            field.name_pattern().to_string(),
+            quote!((5, "count") | (1, "n")).to_string()
+        );
+    }
+}
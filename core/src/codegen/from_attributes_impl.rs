@@ -1,9 +1,13 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
+use syn::Ident;
 
 use crate::{
     ast::Data,
-    codegen::{ExtractAttribute, OuterFromImpl, TraitImpl},
+    codegen::{
+        attr_span_initializer, warnings_initializer, ExtractAttribute, OuterFromImpl,
+        PrefixedAttrs, TraitImpl,
+    },
     options::ForwardAttrs,
     util::PathList,
 };
@@ -11,6 +15,13 @@ use crate::{
 pub struct FromAttributesImpl<'a> {
     pub base: TraitImpl<'a>,
     pub attr_names: &'a PathList,
+    pub fallback_attr_names: &'a PathList,
+    pub unwrap_cfg_attr: bool,
+    pub expr_values: bool,
+    pub prefixed_attrs: Option<PrefixedAttrs<'a>>,
+    pub attr_span: Option<&'a Ident>,
+    /// The field which receives the warnings found while parsing, if any.
+    pub warnings: Option<&'a Ident>,
 }
 
 impl ToTokens for FromAttributesImpl<'_> {
@@ -23,9 +34,9 @@ impl ToTokens for FromAttributesImpl<'_> {
             if data.is_newtype() {
                 self.wrap(
                     quote! {
-                        fn from_attributes(#input: &[::syn::Attribute]) -> ::darling::Result<Self> {
+                        fn from_attributes(#input: &[::darling::export::syn::Attribute]) -> ::darling::export::Result<Self, ::darling::export::Error> {
                             ::darling::export::Ok(
-                                #ty_ident(::darling::FromAttributes::from_attributes(#input)?)
+                                #ty_ident(::darling::export::FromAttributes::from_attributes(#input)?)
                             ) #post_transform
                         }
                     },
@@ -37,6 +48,9 @@ impl ToTokens for FromAttributesImpl<'_> {
         }
 
         let inits = self.base.initializers();
+        let passed_prefixed_attrs = self.prefixed_attrs.map(|prefixed| prefixed.initializer());
+        let passed_attr_span = attr_span_initializer(self.attr_span);
+        let passed_warnings = warnings_initializer(self.warnings);
         let default = self.base.fallback_decl();
 
         let grab_attrs = self.extractor();
@@ -47,7 +61,7 @@ impl ToTokens for FromAttributesImpl<'_> {
 
         self.wrap(
             quote! {
-                fn from_attributes(#input: &[::syn::Attribute]) -> ::darling::Result<Self> {
+                fn from_attributes(#input: &[::darling::export::syn::Attribute]) -> ::darling::export::Result<Self, ::darling::export::Error> {
                     #declare_errors
 
                     #grab_attrs
@@ -59,6 +73,9 @@ impl ToTokens for FromAttributesImpl<'_> {
                     #default
 
                     ::darling::export::Ok(#ty_ident {
+                        #passed_prefixed_attrs
+                        #passed_attr_span
+                        #passed_warnings
                         #inits
                     }) #post_transform
                 }
@@ -81,10 +98,42 @@ impl<'a> ExtractAttribute for FromAttributesImpl<'a> {
         self.attr_names
     }
 
+    fn fallback_attr_names(&self) -> &PathList {
+        self.fallback_attr_names
+    }
+
     fn forwarded_attrs(&self) -> Option<&ForwardAttrs> {
         None
     }
 
+    fn prefixed_attrs(&self) -> Option<&PrefixedAttrs<'_>> {
+        self.prefixed_attrs.as_ref()
+    }
+
+    fn attr_span(&self) -> Option<&Ident> {
+        self.attr_span
+    }
+
+    fn name_value_key(&self) -> Option<TokenStream> {
+        self.base.name_value_key()
+    }
+
+    fn deny_empty(&self) -> bool {
+        self.base.deny_empty
+    }
+
+    fn report(&self) -> bool {
+        self.base.report
+    }
+
+    fn unwrap_cfg_attr(&self) -> bool {
+        self.unwrap_cfg_attr
+    }
+
+    fn expr_values(&self) -> bool {
+        self.expr_values
+    }
+
     fn param_name(&self) -> TokenStream {
         quote!(__di)
     }
@@ -96,15 +145,19 @@ impl<'a> ExtractAttribute for FromAttributesImpl<'a> {
     fn core_loop(&self) -> TokenStream {
         self.base.core_loop()
     }
+
+    fn canonical_key(&self) -> TokenStream {
+        self.base.canonical_key()
+    }
 }
 
 impl<'a> OuterFromImpl<'a> for FromAttributesImpl<'a> {
     fn trait_path(&self) -> syn::Path {
-        path!(::darling::FromAttributes)
+        path!(::darling::export::FromAttributes)
     }
 
     fn trait_bound(&self) -> syn::Path {
-        path!(::darling::FromMeta)
+        path!(::darling::export::FromMeta)
     }
 
     fn base(&'a self) -> &'a TraitImpl<'a> {
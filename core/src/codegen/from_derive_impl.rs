@@ -4,7 +4,10 @@ use syn::Ident;
 
 use crate::{
     ast::Data,
-    codegen::{ExtractAttribute, OuterFromImpl, TraitImpl},
+    codegen::{
+        attr_span_initializer, warnings_initializer, ExtractAttribute, OuterFromImpl,
+        PrefixedAttrs, TraitImpl,
+    },
     options::{ForwardAttrs, Shape},
     util::PathList,
 };
@@ -15,10 +18,18 @@ pub struct FromDeriveInputImpl<'a> {
     pub vis: Option<&'a Ident>,
     pub attrs: Option<&'a Ident>,
     pub data: Option<&'a Ident>,
+    pub input: Option<&'a Ident>,
     pub base: TraitImpl<'a>,
     pub attr_names: &'a PathList,
+    pub fallback_attr_names: &'a PathList,
     pub forward_attrs: Option<&'a ForwardAttrs>,
     pub from_ident: bool,
+    pub unwrap_cfg_attr: bool,
+    pub expr_values: bool,
+    pub prefixed_attrs: Option<PrefixedAttrs<'a>>,
+    pub attr_span: Option<&'a Ident>,
+    /// The field which receives the warnings found while parsing, if any.
+    pub warnings: Option<&'a Ident>,
     pub supports: Option<&'a Shape>,
 }
 
@@ -26,15 +37,17 @@ impl<'a> ToTokens for FromDeriveInputImpl<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let ty_ident = self.base.ident;
         let input = self.param_name();
-        let post_transform = self.base.post_transform_call();
+        // Errors which don't come from a particular part of the input point at the item.
+        let item = quote!(&#input.ident);
+        let post_transform = self.base.post_transform_call_at(item.clone());
 
         if let Data::Struct(ref data) = self.base.data {
             if data.is_newtype() {
                 self.wrap(
                     quote!{
-                        fn from_derive_input(#input: &::syn::DeriveInput) -> ::darling::Result<Self> {
+                        fn from_derive_input(#input: &::darling::export::syn::DeriveInput) -> ::darling::export::Result<Self, ::darling::export::Error> {
                             ::darling::export::Ok(
-                                #ty_ident(::darling::FromDeriveInput::from_derive_input(#input)?)
+                                #ty_ident(::darling::export::FromDeriveInput::from_derive_input(#input)?)
                             ) #post_transform
                         }
                     },
@@ -50,22 +63,33 @@ impl<'a> ToTokens for FromDeriveInputImpl<'a> {
             .as_ref()
             .map(|i| quote!(#i: #input.ident.clone(),));
         let passed_vis = self.vis.as_ref().map(|i| quote!(#i: #input.vis.clone(),));
-        let passed_generics = self
-            .generics
-            .as_ref()
-            .map(|i| quote!(#i: ::darling::FromGenerics::from_generics(&#input.generics)?,));
+        let passed_generics = self.generics.as_ref().map(|i| {
+            quote!(#i: ::darling::export::FromGenerics::from_generics(&#input.generics)
+                .map_err(|e| e.with_span(&#input.generics))?,)
+        });
         let passed_attrs = self.attrs.as_ref().map(|i| quote!(#i: __fwd_attrs,));
-        let passed_body = self
-            .data
-            .as_ref()
-            .map(|i| quote!(#i: ::darling::ast::Data::try_from(&#input.data)?,));
+        let passed_input = self.input.as_ref().map(|i| quote!(#i: #input.clone(),));
+        let passed_prefixed_attrs = self.prefixed_attrs.map(|prefixed| prefixed.initializer());
+        let passed_attr_span = attr_span_initializer(self.attr_span);
+        let passed_warnings = warnings_initializer(self.warnings);
+        let passed_body = self.data.as_ref().map(|i| {
+            quote!(#i: ::darling::export::Data::try_from_with_unions(&#input.data)
+                .map_err(|e| e.with_span(#item))?,)
+        });
 
-        let supports = self.supports.map(|i| {
-            quote! {
+        let supports = match self.supports {
+            Some(i) => Some(quote! {
                 #i
-                __validate_body(&#input.data)?;
-            }
-        });
+                __validate_body(&#input.data).map_err(|e| e.with_span(#item))?;
+            }),
+            // Unions are only read into `data` when the deriving type opts in with `supports(union)`.
+            None if self.data.is_some() => Some(quote! {
+                if let ::darling::export::syn::Data::Union(_) = #input.data {
+                    return ::darling::export::Err(::darling::export::Error::custom("Unions are not supported").with_span(#item));
+                }
+            }),
+            None => None,
+        };
 
         let inits = self.base.initializers();
         let default = if self.from_ident {
@@ -78,11 +102,11 @@ impl<'a> ToTokens for FromDeriveInputImpl<'a> {
 
         let declare_errors = self.base.declare_errors();
         let require_fields = self.base.require_fields();
-        let check_errors = self.base.check_errors();
+        let check_errors = self.base.check_errors_at(item);
 
         self.wrap(
             quote! {
-                fn from_derive_input(#input: &::syn::DeriveInput) -> ::darling::Result<Self> {
+                fn from_derive_input(#input: &::darling::export::syn::DeriveInput) -> ::darling::export::Result<Self, ::darling::export::Error> {
                     #declare_errors
 
                     #grab_attrs
@@ -100,6 +124,10 @@ impl<'a> ToTokens for FromDeriveInputImpl<'a> {
                         #passed_generics
                         #passed_vis
                         #passed_attrs
+                        #passed_input
+                        #passed_prefixed_attrs
+                        #passed_attr_span
+                        #passed_warnings
                         #passed_body
                         #inits
                     }) #post_transform
@@ -115,10 +143,42 @@ impl<'a> ExtractAttribute for FromDeriveInputImpl<'a> {
         self.attr_names
     }
 
+    fn fallback_attr_names(&self) -> &PathList {
+        self.fallback_attr_names
+    }
+
     fn forwarded_attrs(&self) -> Option<&ForwardAttrs> {
         self.forward_attrs
     }
 
+    fn prefixed_attrs(&self) -> Option<&PrefixedAttrs<'_>> {
+        self.prefixed_attrs.as_ref()
+    }
+
+    fn attr_span(&self) -> Option<&Ident> {
+        self.attr_span
+    }
+
+    fn name_value_key(&self) -> Option<TokenStream> {
+        self.base.name_value_key()
+    }
+
+    fn deny_empty(&self) -> bool {
+        self.base.deny_empty
+    }
+
+    fn report(&self) -> bool {
+        self.base.report
+    }
+
+    fn unwrap_cfg_attr(&self) -> bool {
+        self.unwrap_cfg_attr
+    }
+
+    fn expr_values(&self) -> bool {
+        self.expr_values
+    }
+
     fn param_name(&self) -> TokenStream {
         quote!(__di)
     }
@@ -127,6 +187,10 @@ impl<'a> ExtractAttribute for FromDeriveInputImpl<'a> {
         self.base.core_loop()
     }
 
+    fn canonical_key(&self) -> TokenStream {
+        self.base.canonical_key()
+    }
+
     fn local_declarations(&self) -> TokenStream {
         self.base.local_declarations()
     }
@@ -138,11 +202,11 @@ impl<'a> ExtractAttribute for FromDeriveInputImpl<'a> {
 
 impl<'a> OuterFromImpl<'a> for FromDeriveInputImpl<'a> {
     fn trait_path(&self) -> syn::Path {
-        path!(::darling::FromDeriveInput)
+        path!(::darling::export::FromDeriveInput)
     }
 
     fn trait_bound(&self) -> syn::Path {
-        path!(::darling::FromMeta)
+        path!(::darling::export::FromMeta)
     }
 
     fn base(&'a self) -> &'a TraitImpl<'a> {
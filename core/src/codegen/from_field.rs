@@ -3,7 +3,10 @@ use quote::ToTokens;
 use syn::Ident;
 
 use crate::{
-    codegen::{ExtractAttribute, OuterFromImpl, TraitImpl},
+    codegen::{
+        attr_span_initializer, warnings_initializer, ExtractAttribute, OuterFromImpl,
+        PrefixedAttrs, TraitImpl,
+    },
     options::ForwardAttrs,
     util::PathList,
 };
@@ -14,20 +17,32 @@ pub struct FromFieldImpl<'a> {
     pub ident: Option<&'a Ident>,
     pub vis: Option<&'a Ident>,
     pub ty: Option<&'a Ident>,
+    /// If set, the field which receives the position of the input field in its parent.
+    pub index: Option<&'a Ident>,
     pub attrs: Option<&'a Ident>,
     pub base: TraitImpl<'a>,
     pub attr_names: &'a PathList,
+    pub fallback_attr_names: &'a PathList,
     pub forward_attrs: Option<&'a ForwardAttrs>,
     pub from_ident: bool,
+    pub unwrap_cfg_attr: bool,
+    pub expr_values: bool,
+    pub prefixed_attrs: Option<PrefixedAttrs<'a>>,
+    pub attr_span: Option<&'a Ident>,
+    /// The field which receives the warnings found while parsing, if any.
+    pub warnings: Option<&'a Ident>,
 }
 
 impl<'a> ToTokens for FromFieldImpl<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let input = self.param_name();
+        // Errors which don't come from a particular part of the input point at the field type,
+        // since unnamed fields have no ident.
+        let item = quote!(&#input.ty);
 
         let error_declaration = self.base.declare_errors();
         let require_fields = self.base.require_fields();
-        let error_check = self.base.check_errors();
+        let error_check = self.base.check_errors_at(item.clone());
 
         let initializers = self.base.initializers();
 
@@ -43,15 +58,36 @@ impl<'a> ToTokens for FromFieldImpl<'a> {
             .map(|i| quote!(#i: #input.ident.clone(),));
         let passed_vis = self.vis.as_ref().map(|i| quote!(#i: #input.vis.clone(),));
         let passed_ty = self.ty.as_ref().map(|i| quote!(#i: #input.ty.clone(),));
+        let passed_index = self.index.as_ref().map(|i| quote!(#i: __index,));
         let passed_attrs = self.attrs.as_ref().map(|i| quote!(#i: __fwd_attrs,));
+        let passed_prefixed_attrs = self.prefixed_attrs.map(|prefixed| prefixed.initializer());
+        let passed_attr_span = attr_span_initializer(self.attr_span);
+        let passed_warnings = warnings_initializer(self.warnings);
 
         // Determine which attributes to forward (if any).
         let grab_attrs = self.extractor();
-        let post_transform = self.base.post_transform_call();
+        let post_transform = self.base.post_transform_call_at(item);
+
+        // Only types which receive the index need it, so the others keep a plain `from_field`.
+        // Calling `from_field` directly on a type which does gets no index, as its position
+        // isn't known.
+        let signature = if self.index.is_some() {
+            quote! {
+                fn from_field(#input: &::darling::export::syn::Field) -> ::darling::export::Result<Self, ::darling::export::Error> {
+                    ::darling::export::FromField::from_field_at(#input, ::darling::export::None)
+                }
+
+                fn from_field_at(#input: &::darling::export::syn::Field, __index: ::darling::export::Option<usize>) -> ::darling::export::Result<Self, ::darling::export::Error>
+            }
+        } else {
+            quote! {
+                fn from_field(#input: &::darling::export::syn::Field) -> ::darling::export::Result<Self, ::darling::export::Error>
+            }
+        };
 
         self.wrap(
             quote! {
-                fn from_field(#input: &::syn::Field) -> ::darling::Result<Self> {
+                #signature {
                     #error_declaration
 
                     #grab_attrs
@@ -65,8 +101,12 @@ impl<'a> ToTokens for FromFieldImpl<'a> {
                     ::darling::export::Ok(Self {
                         #passed_ident
                         #passed_ty
+                        #passed_index
                         #passed_vis
                         #passed_attrs
+                        #passed_prefixed_attrs
+                        #passed_attr_span
+                        #passed_warnings
                         #initializers
                     }) #post_transform
 
@@ -82,10 +122,42 @@ impl<'a> ExtractAttribute for FromFieldImpl<'a> {
         self.attr_names
     }
 
+    fn fallback_attr_names(&self) -> &PathList {
+        self.fallback_attr_names
+    }
+
     fn forwarded_attrs(&self) -> Option<&ForwardAttrs> {
         self.forward_attrs
     }
 
+    fn prefixed_attrs(&self) -> Option<&PrefixedAttrs<'_>> {
+        self.prefixed_attrs.as_ref()
+    }
+
+    fn attr_span(&self) -> Option<&Ident> {
+        self.attr_span
+    }
+
+    fn name_value_key(&self) -> Option<TokenStream> {
+        self.base.name_value_key()
+    }
+
+    fn deny_empty(&self) -> bool {
+        self.base.deny_empty
+    }
+
+    fn report(&self) -> bool {
+        self.base.report
+    }
+
+    fn unwrap_cfg_attr(&self) -> bool {
+        self.unwrap_cfg_attr
+    }
+
+    fn expr_values(&self) -> bool {
+        self.expr_values
+    }
+
     fn param_name(&self) -> TokenStream {
         quote!(__field)
     }
@@ -94,6 +166,10 @@ impl<'a> ExtractAttribute for FromFieldImpl<'a> {
         self.base.core_loop()
     }
 
+    fn canonical_key(&self) -> TokenStream {
+        self.base.canonical_key()
+    }
+
     fn local_declarations(&self) -> TokenStream {
         self.base.local_declarations()
     }
@@ -105,11 +181,11 @@ impl<'a> ExtractAttribute for FromFieldImpl<'a> {
 
 impl<'a> OuterFromImpl<'a> for FromFieldImpl<'a> {
     fn trait_path(&self) -> syn::Path {
-        path!(::darling::FromField)
+        path!(::darling::export::FromField)
     }
 
     fn trait_bound(&self) -> syn::Path {
-        path!(::darling::FromMeta)
+        path!(::darling::export::FromMeta)
     }
 
     fn base(&'a self) -> &'a TraitImpl<'a> {
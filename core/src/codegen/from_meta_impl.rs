@@ -1,23 +1,35 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
+use syn::Path;
 
 use crate::ast::{Data, Fields, Style};
+use crate::codegen::attribute_docs::attribute_docs;
+use crate::codegen::variant::DeprecatedUnitArm;
 use crate::codegen::{Field, OuterFromImpl, TraitImpl, Variant};
+use crate::Error;
 
+/// Generator for a `FromMeta` impl.
 pub struct FromMetaImpl<'a> {
     pub base: TraitImpl<'a>,
+    /// The deepest nesting of meta lists to accept. When `None`, the library default is used.
+    pub max_depth: Option<usize>,
+    /// A function to call when the type appears as a bare word.
+    pub from_word: Option<&'a Path>,
+    /// A function to call when the type is absent from the input.
+    pub from_none: Option<&'a Path>,
 }
 
 impl<'a> ToTokens for FromMetaImpl<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let base = &self.base;
+        let enter_nesting = self.enter_nesting();
 
         let impl_block = match base.data {
             // Unit structs allow empty bodies only.
             Data::Struct(ref vd) if vd.style.is_unit() => {
                 let ty_ident = base.ident;
                 quote!(
-                    fn from_word() -> ::darling::Result<Self> {
+                    fn from_word() -> ::darling::export::Result<Self, ::darling::export::Error> {
                         ::darling::export::Ok(#ty_ident)
                     }
                 )
@@ -31,19 +43,22 @@ impl<'a> ToTokens for FromMetaImpl<'a> {
             }) if fields.len() == 1 => {
                 let ty_ident = base.ident;
                 quote!(
-                    fn from_meta(__item: &::syn::Meta) -> ::darling::Result<Self> {
-                        ::darling::FromMeta::from_meta(__item)
+                    fn from_meta(__item: &::darling::export::syn::Meta) -> ::darling::export::Result<Self, ::darling::export::Error> {
+                        ::darling::export::FromMeta::from_meta(__item)
+                            .map_err(|e| e.with_span(&__item))
+                            .map(#ty_ident)
+                    }
+
+                    fn from_meta_with_warnings(
+                        __item: &::darling::export::syn::Meta,
+                        __warnings: &mut ::darling::export::Vec<::darling::export::Warning>,
+                    ) -> ::darling::export::Result<Self, ::darling::export::Error> {
+                        ::darling::export::FromMeta::from_meta_with_warnings(__item, __warnings)
                             .map_err(|e| e.with_span(&__item))
                             .map(#ty_ident)
                     }
                 )
             }
-            Data::Struct(Fields {
-                style: Style::Tuple,
-                ..
-            }) => {
-                panic!("Multi-field tuples are not supported");
-            }
             Data::Struct(ref data) => {
                 let inits = data.fields.iter().map(Field::as_initializer);
                 let declare_errors = base.declare_errors();
@@ -51,11 +66,65 @@ impl<'a> ToTokens for FromMetaImpl<'a> {
                 let check_errors = base.check_errors();
                 let decls = base.local_declarations();
                 let core_loop = base.core_loop();
+                let check_any_key = base.check_any_key();
                 let default = base.fallback_decl();
                 let post_transform = base.post_transform_call();
+                let from_value = base.name_value_key().map(|key| {
+                    quote!(
+                        fn from_value(__value: &::darling::export::syn::Lit) -> ::darling::export::Result<Self, ::darling::export::Error> {
+                            ::darling::export::FromMeta::from_list(&[::darling::export::name_value_item(#key, __value)])
+                        }
+                    )
+                });
+                let name_value_arm = base.name_value_key().map(|key| {
+                    quote!(
+                        ::darling::export::syn::Meta::NameValue(ref __nv) => {
+                            <Self as ::darling::export::FromMeta>::from_list_with_warnings(
+                                &[::darling::export::name_value_item(#key, &__nv.lit)],
+                                __warnings,
+                            )
+                            .map_err(|e| e.with_span(__item))
+                        }
+                    )
+                });
+                let from_meta_with_warnings = list_with_warnings(
+                    name_value_arm,
+                    quote!(::darling::export::FromMeta::from_meta(__item)),
+                );
+
+                // A bare word is read as an empty list when every field has a default, so the
+                // defaults don't have to be written out. Otherwise the word is rejected as
+                // before, rather than reported as a list of missing fields. An explicit
+                // `from_word` hook replaces this.
+                let from_word = if self.from_word.is_none()
+                    && data.fields.iter().all(|field| field.has_default())
+                {
+                    Some(quote!(
+                        fn from_word() -> ::darling::export::Result<Self, ::darling::export::Error>
+                        {
+                            ::darling::export::FromMeta::from_list(&[])
+                        }
+                    ))
+                } else {
+                    None
+                };
 
                 quote!(
-                    fn from_list(__items: &[::syn::NestedMeta]) -> ::darling::Result<Self> {
+                    #from_value
+
+                    #from_word
+
+                    #from_meta_with_warnings
+
+                    fn from_list(__items: &[::darling::export::syn::NestedMeta]) -> ::darling::export::Result<Self, ::darling::export::Error> {
+                        <Self as ::darling::export::FromMeta>::from_list_with_warnings(__items, &mut ::darling::export::Vec::new())
+                    }
+
+                    fn from_list_with_warnings(
+                        __items: &[::darling::export::syn::NestedMeta],
+                        __warnings: &mut ::darling::export::Vec<::darling::export::Warning>,
+                    ) -> ::darling::export::Result<Self, ::darling::export::Error> {
+                        #enter_nesting
 
                         #decls
 
@@ -63,6 +132,8 @@ impl<'a> ToTokens for FromMetaImpl<'a> {
 
                         #core_loop
 
+                        #check_any_key
+
                         #require_fields
 
                         #check_errors
@@ -79,8 +150,22 @@ impl<'a> ToTokens for FromMetaImpl<'a> {
                 let unit_arms = variants.iter().map(Variant::as_unit_match_arm);
                 let struct_arms = variants.iter().map(Variant::as_data_match_arm);
 
-                let unknown_variant_err = if !variants.is_empty() {
-                    let names = variants.iter().map(Variant::as_name);
+                // Only unit variants can be written as a literal, so they're the only ones listed.
+                // The list is short and is the only way to see every accepted spelling, so it's
+                // embedded even without suggestions.
+                let unit_names: Vec<TokenStream> = variants
+                    .iter()
+                    .filter(|variant| !variant.skip && variant.data.is_unit())
+                    .map(Variant::name_tokens)
+                    .collect();
+                let unknown_value_err = if !unit_names.is_empty() {
+                    quote!(unknown_value_with_alts(__other, &[#(#unit_names),*]))
+                } else {
+                    quote!(unknown_value(__other))
+                };
+
+                let unknown_variant_err = if cfg!(feature = "suggestions") && !variants.is_empty() {
+                    let names = variants.iter().map(Variant::name_tokens);
                     quote! {
                         unknown_field_with_alts(__other, &[#(#names),*])
                     }
@@ -90,43 +175,164 @@ impl<'a> ToTokens for FromMetaImpl<'a> {
                     }
                 };
 
+                // The value of a unit variant is read without a warnings collector, so uses of
+                // deprecated ones are noted once the value is known.
+                let deprecated_units: Vec<DeprecatedUnitArm> =
+                    variants.iter().filter_map(DeprecatedUnitArm::new).collect();
+                let check_deprecated = if deprecated_units.is_empty() {
+                    quote!()
+                } else {
+                    let others = if deprecated_units.len() < variants.len() {
+                        quote!(_ => {})
+                    } else {
+                        quote!()
+                    };
+                    quote!(match __value {
+                        #(#deprecated_units)*
+                        #others
+                    })
+                };
+                // The names computed by `rename_all_with`, declared once in each method which
+                // matches on them.
+                let renamed: Vec<TokenStream> =
+                    variants.iter().map(Variant::rename_declaration).collect();
+                let from_meta_with_warnings = list_with_warnings(
+                    None,
+                    quote!({
+                        let __value = <Self as ::darling::export::FromMeta>::from_meta(__item)?;
+                        #check_deprecated
+                        ::darling::export::Ok(__value)
+                    }),
+                );
+
                 quote!(
-                    fn from_list(__outer: &[::syn::NestedMeta]) -> ::darling::Result<Self> {
+                    #from_meta_with_warnings
+
+                    fn from_list(__outer: &[::darling::export::syn::NestedMeta]) -> ::darling::export::Result<Self, ::darling::export::Error> {
+                        <Self as ::darling::export::FromMeta>::from_list_with_warnings(__outer, &mut ::darling::export::Vec::new())
+                    }
+
+                    fn from_list_with_warnings(
+                        __outer: &[::darling::export::syn::NestedMeta],
+                        __warnings: &mut ::darling::export::Vec<::darling::export::Warning>,
+                    ) -> ::darling::export::Result<Self, ::darling::export::Error> {
+                        #enter_nesting
+                        #(#renamed)*
+
                         // An enum must have exactly one value inside the parentheses if it's not a unit
                         // match arm
                         match __outer.len() {
-                            0 => ::darling::export::Err(::darling::Error::too_few_items(1)),
+                            0 => ::darling::export::Err(::darling::export::Error::too_few_items(1)),
                             1 => {
-                                if let ::syn::NestedMeta::Meta(ref __nested) = __outer[0] {
-                                    match ::darling::util::path_to_string(__nested.path()).as_ref() {
+                                if let ::darling::export::syn::NestedMeta::Meta(ref __nested) = __outer[0] {
+                                    match ::darling::export::path_to_string(__nested.path()).as_ref() {
                                         #(#struct_arms)*
-                                        __other => ::darling::export::Err(::darling::Error::#unknown_variant_err.with_span(__nested))
+                                        __other => ::darling::export::Err(::darling::export::Error::#unknown_variant_err.with_span(__nested))
                                     }
                                 } else {
-                                    ::darling::export::Err(::darling::Error::unsupported_format("literal"))
+                                    ::darling::export::Err(::darling::export::Error::unsupported_format("literal"))
                                 }
                             }
-                            _ => ::darling::export::Err(::darling::Error::too_many_items(1)),
+                            _ => ::darling::export::Err(::darling::export::Error::too_many_items(1)),
                         }
                     }
 
-                    fn from_string(lit: &str) -> ::darling::Result<Self> {
+                    fn from_string(lit: &str) -> ::darling::export::Result<Self, ::darling::export::Error> {
+                        #(#renamed)*
                         match lit {
                             #(#unit_arms)*
-                            __other => ::darling::export::Err(::darling::Error::unknown_value(__other))
+                            __other => ::darling::export::Err(::darling::export::Error::#unknown_value_err)
                         }
                     }
                 )
             }
+            Data::Union(_) => {
+                tokens.extend(Error::custom("FromMeta can't be derived for unions").write_errors());
+                return;
+            }
+        };
+
+        let from_word = self.from_word.map(|body| {
+            quote!(
+                fn from_word() -> ::darling::export::Result<Self, ::darling::export::Error> {
+                    #body()
+                }
+            )
+        });
+
+        let from_none = self.from_none.map(|body| {
+            quote!(
+                fn from_none() -> ::darling::export::Option<Self> {
+                    #body()
+                }
+            )
+        });
+
+        let docs = attribute_docs(base);
+
+        self.wrap(
+            quote!(
+                const ATTRIBUTE_DOCS: &'static str = #docs;
+
+                #from_word
+
+                #from_none
+
+                #impl_block
+            ),
+            tokens,
+        );
+    }
+}
+
+/// The `from_meta_with_warnings` of a derived impl, which passes the collector to
+/// `from_list_with_warnings` for lists. Other forms are read by `other_arms`, then `fallback`.
+fn list_with_warnings(other_arms: Option<TokenStream>, fallback: TokenStream) -> TokenStream {
+    quote!(
+        fn from_meta_with_warnings(
+            __item: &::darling::export::syn::Meta,
+            __warnings: &mut ::darling::export::Vec<::darling::export::Warning>,
+        ) -> ::darling::export::Result<Self, ::darling::export::Error> {
+            match *__item {
+                ::darling::export::syn::Meta::List(ref __list) => {
+                    let __items: ::darling::export::Vec<::darling::export::syn::NestedMeta> =
+                        __list.nested.iter().cloned().collect();
+                    <Self as ::darling::export::FromMeta>::from_list_with_warnings(&__items, __warnings)
+                        .map_err(|e| e.with_span(__item))
+                }
+                #other_arms
+                _ => #fallback,
+            }
+        }
+    )
+}
+
+impl<'a> FromMetaImpl<'a> {
+    /// Create a generator which uses the default nesting limit and no word or absence hooks.
+    pub fn new(base: TraitImpl<'a>) -> Self {
+        FromMetaImpl {
+            base,
+            max_depth: None,
+            from_word: None,
+            from_none: None,
+        }
+    }
+
+    /// Declares a guard which keeps recursive types from overflowing the stack on deeply
+    /// nested input. The guard must stay in scope until the list has been parsed.
+    fn enter_nesting(&self) -> TokenStream {
+        let max_depth = match self.max_depth {
+            Some(max_depth) => quote!(#max_depth),
+            None => quote!(::darling::export::DEFAULT_MAX_DEPTH),
         };
 
-        self.wrap(impl_block, tokens);
+        quote!(let __nesting = ::darling::export::NestingGuard::enter(#max_depth)?;)
     }
 }
 
 impl<'a> OuterFromImpl<'a> for FromMetaImpl<'a> {
     fn trait_path(&self) -> syn::Path {
-        path!(::darling::FromMeta)
+        path!(::darling::export::FromMeta)
     }
 
     fn base(&'a self) -> &'a TraitImpl<'a> {
@@ -2,7 +2,10 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::Ident;
 
-use crate::codegen::{ExtractAttribute, OuterFromImpl, TraitImpl};
+use crate::codegen::{
+    attr_span_initializer, warnings_initializer, ExtractAttribute, OuterFromImpl, PrefixedAttrs,
+    TraitImpl,
+};
 use crate::options::ForwardAttrs;
 use crate::util::PathList;
 
@@ -13,18 +16,27 @@ pub struct FromTypeParamImpl<'a> {
     pub bounds: Option<&'a Ident>,
     pub default: Option<&'a Ident>,
     pub attr_names: &'a PathList,
+    pub fallback_attr_names: &'a PathList,
     pub forward_attrs: Option<&'a ForwardAttrs>,
     pub from_ident: bool,
+    pub unwrap_cfg_attr: bool,
+    pub expr_values: bool,
+    pub prefixed_attrs: Option<PrefixedAttrs<'a>>,
+    pub attr_span: Option<&'a Ident>,
+    /// The field which receives the warnings found while parsing, if any.
+    pub warnings: Option<&'a Ident>,
 }
 
 impl<'a> ToTokens for FromTypeParamImpl<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let input = self.param_name();
+        // Errors which don't come from a particular part of the input point at the type param.
+        let item = quote!(&#input.ident);
 
         let error_declaration = self.base.declare_errors();
         let grab_attrs = self.extractor();
         let require_fields = self.base.require_fields();
-        let error_check = self.base.check_errors();
+        let error_check = self.base.check_errors_at(item.clone());
 
         let default = if self.from_ident {
             quote!(let __default: Self = ::darling::export::From::from(#input.ident.clone());)
@@ -37,6 +49,9 @@ impl<'a> ToTokens for FromTypeParamImpl<'a> {
             .as_ref()
             .map(|i| quote!(#i: #input.ident.clone(),));
         let passed_attrs = self.attrs.as_ref().map(|i| quote!(#i: __fwd_attrs,));
+        let passed_prefixed_attrs = self.prefixed_attrs.map(|prefixed| prefixed.initializer());
+        let passed_attr_span = attr_span_initializer(self.attr_span);
+        let passed_warnings = warnings_initializer(self.warnings);
         let passed_bounds = self
             .bounds
             .as_ref()
@@ -47,11 +62,11 @@ impl<'a> ToTokens for FromTypeParamImpl<'a> {
             .map(|i| quote!(#i: #input.default.clone(),));
         let initializers = self.base.initializers();
 
-        let post_transform = self.base.post_transform_call();
+        let post_transform = self.base.post_transform_call_at(item);
 
         self.wrap(
             quote! {
-                fn from_type_param(#input: &::syn::TypeParam) -> ::darling::Result<Self> {
+                fn from_type_param(#input: &::darling::export::syn::TypeParam) -> ::darling::export::Result<Self, ::darling::export::Error> {
                     #error_declaration
 
                     #grab_attrs
@@ -67,6 +82,9 @@ impl<'a> ToTokens for FromTypeParamImpl<'a> {
                         #passed_bounds
                         #passed_default
                         #passed_attrs
+                        #passed_prefixed_attrs
+                        #passed_attr_span
+                        #passed_warnings
                         #initializers
                     }) #post_transform
                 }
@@ -81,10 +99,42 @@ impl<'a> ExtractAttribute for FromTypeParamImpl<'a> {
         self.attr_names
     }
 
+    fn fallback_attr_names(&self) -> &PathList {
+        self.fallback_attr_names
+    }
+
     fn forwarded_attrs(&self) -> Option<&ForwardAttrs> {
         self.forward_attrs
     }
 
+    fn prefixed_attrs(&self) -> Option<&PrefixedAttrs<'_>> {
+        self.prefixed_attrs.as_ref()
+    }
+
+    fn attr_span(&self) -> Option<&Ident> {
+        self.attr_span
+    }
+
+    fn name_value_key(&self) -> Option<TokenStream> {
+        self.base.name_value_key()
+    }
+
+    fn deny_empty(&self) -> bool {
+        self.base.deny_empty
+    }
+
+    fn report(&self) -> bool {
+        self.base.report
+    }
+
+    fn unwrap_cfg_attr(&self) -> bool {
+        self.unwrap_cfg_attr
+    }
+
+    fn expr_values(&self) -> bool {
+        self.expr_values
+    }
+
     fn param_name(&self) -> TokenStream {
         quote!(__type_param)
     }
@@ -93,6 +143,10 @@ impl<'a> ExtractAttribute for FromTypeParamImpl<'a> {
         self.base.core_loop()
     }
 
+    fn canonical_key(&self) -> TokenStream {
+        self.base.canonical_key()
+    }
+
     fn local_declarations(&self) -> TokenStream {
         self.base.local_declarations()
     }
@@ -104,11 +158,11 @@ impl<'a> ExtractAttribute for FromTypeParamImpl<'a> {
 
 impl<'a> OuterFromImpl<'a> for FromTypeParamImpl<'a> {
     fn trait_path(&self) -> syn::Path {
-        path!(::darling::FromTypeParam)
+        path!(::darling::export::FromTypeParam)
     }
 
     fn trait_bound(&self) -> syn::Path {
-        path!(::darling::FromMeta)
+        path!(::darling::export::FromMeta)
     }
 
     fn base(&'a self) -> &'a TraitImpl<'a> {
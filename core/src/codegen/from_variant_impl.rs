@@ -2,7 +2,10 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::Ident;
 
-use crate::codegen::{ExtractAttribute, OuterFromImpl, TraitImpl};
+use crate::codegen::{
+    attr_span_initializer, warnings_initializer, ExtractAttribute, OuterFromImpl, PrefixedAttrs,
+    TraitImpl,
+};
 use crate::options::{DataShape, ForwardAttrs};
 use crate::util::PathList;
 
@@ -31,14 +34,23 @@ pub struct FromVariantImpl<'a> {
     /// This is one of `darling`'s "magic fields".
     pub discriminant: Option<&'a Ident>,
     pub attr_names: &'a PathList,
+    pub fallback_attr_names: &'a PathList,
     pub forward_attrs: Option<&'a ForwardAttrs>,
     pub from_ident: bool,
+    pub unwrap_cfg_attr: bool,
+    pub expr_values: bool,
+    pub prefixed_attrs: Option<PrefixedAttrs<'a>>,
+    pub attr_span: Option<&'a Ident>,
+    /// The field which receives the warnings found while parsing, if any.
+    pub warnings: Option<&'a Ident>,
     pub supports: Option<&'a DataShape>,
 }
 
 impl<'a> ToTokens for FromVariantImpl<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let input = self.param_name();
+        // Errors which don't come from a particular part of the input point at the variant.
+        let item = quote!(&#input.ident);
         let extractor = self.extractor();
         let passed_ident = self
             .ident
@@ -49,13 +61,16 @@ impl<'a> ToTokens for FromVariantImpl<'a> {
             .as_ref()
             .map(|i| quote!(#i: #input.discriminant.as_ref().map(|(_, expr)| expr.clone()),));
         let passed_attrs = self.attrs.as_ref().map(|i| quote!(#i: __fwd_attrs,));
-        let passed_fields = self
-            .fields
-            .as_ref()
-            .map(|i| quote!(#i: ::darling::ast::Fields::try_from(&#input.fields)?,));
+        let passed_prefixed_attrs = self.prefixed_attrs.map(|prefixed| prefixed.initializer());
+        let passed_attr_span = attr_span_initializer(self.attr_span);
+        let passed_warnings = warnings_initializer(self.warnings);
+        let passed_fields = self.fields.as_ref().map(|i| {
+            quote!(#i: ::darling::export::Fields::try_from(&#input.fields)
+                .map_err(|e| e.with_span(#item))?,)
+        });
 
         let inits = self.base.initializers();
-        let post_transform = self.base.post_transform_call();
+        let post_transform = self.base.post_transform_call_at(item.clone());
 
         let default = if self.from_ident {
             quote!(let __default: Self = ::darling::export::From::from(#input.ident.clone());)
@@ -66,17 +81,17 @@ impl<'a> ToTokens for FromVariantImpl<'a> {
         let supports = self.supports.map(|i| {
             quote! {
                 #i
-                __validate_data(&#input.fields)?;
+                __validate_data(&#input.fields).map_err(|e| e.with_span(#item))?;
             }
         });
 
         let error_declaration = self.base.declare_errors();
         let require_fields = self.base.require_fields();
-        let error_check = self.base.check_errors();
+        let error_check = self.base.check_errors_at(item);
 
         self.wrap(
             quote!(
-                fn from_variant(#input: &::syn::Variant) -> ::darling::Result<Self> {
+                fn from_variant(#input: &::darling::export::syn::Variant) -> ::darling::export::Result<Self, ::darling::export::Error> {
                     #error_declaration
 
                     #extractor
@@ -93,6 +108,9 @@ impl<'a> ToTokens for FromVariantImpl<'a> {
                         #passed_ident
                         #passed_discriminant
                         #passed_attrs
+                        #passed_prefixed_attrs
+                        #passed_attr_span
+                        #passed_warnings
                         #passed_fields
                         #inits
                     }) #post_transform
@@ -116,10 +134,42 @@ impl<'a> ExtractAttribute for FromVariantImpl<'a> {
         self.attr_names
     }
 
+    fn fallback_attr_names(&self) -> &PathList {
+        self.fallback_attr_names
+    }
+
     fn forwarded_attrs(&self) -> Option<&ForwardAttrs> {
         self.forward_attrs
     }
 
+    fn prefixed_attrs(&self) -> Option<&PrefixedAttrs<'_>> {
+        self.prefixed_attrs.as_ref()
+    }
+
+    fn attr_span(&self) -> Option<&Ident> {
+        self.attr_span
+    }
+
+    fn name_value_key(&self) -> Option<TokenStream> {
+        self.base.name_value_key()
+    }
+
+    fn deny_empty(&self) -> bool {
+        self.base.deny_empty
+    }
+
+    fn report(&self) -> bool {
+        self.base.report
+    }
+
+    fn unwrap_cfg_attr(&self) -> bool {
+        self.unwrap_cfg_attr
+    }
+
+    fn expr_values(&self) -> bool {
+        self.expr_values
+    }
+
     fn param_name(&self) -> TokenStream {
         quote!(__variant)
     }
@@ -127,15 +177,19 @@ impl<'a> ExtractAttribute for FromVariantImpl<'a> {
     fn core_loop(&self) -> TokenStream {
         self.base.core_loop()
     }
+
+    fn canonical_key(&self) -> TokenStream {
+        self.base.canonical_key()
+    }
 }
 
 impl<'a> OuterFromImpl<'a> for FromVariantImpl<'a> {
     fn trait_path(&self) -> syn::Path {
-        path!(::darling::FromVariant)
+        path!(::darling::export::FromVariant)
     }
 
     fn trait_bound(&self) -> syn::Path {
-        path!(::darling::FromMeta)
+        path!(::darling::export::FromMeta)
     }
 
     fn base(&'a self) -> &'a TraitImpl<'a> {
@@ -0,0 +1,146 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::Path;
+
+use crate::ast::{Data, Fields, Style};
+use crate::codegen::attribute_docs::describe_default;
+use crate::codegen::{Field, OuterFromImpl, TraitImpl};
+use crate::util::render_tokens as render;
+
+/// Generator for a `MetaSchema` impl, which describes what the type's `FromMeta` impl accepts.
+pub struct MetaSchemaImpl<'a> {
+    pub base: TraitImpl<'a>,
+}
+
+impl<'a> MetaSchemaImpl<'a> {
+    pub fn new(base: TraitImpl<'a>) -> Self {
+        MetaSchemaImpl { base }
+    }
+
+    fn body(&self, fields: &Fields<Field<'_>>) -> TokenStream {
+        match fields.style {
+            Style::Unit => quote!(::darling::export::SchemaBody::Unit),
+            Style::Tuple => {
+                let ty = fields
+                    .fields
+                    .first()
+                    .map(|field| render(field.ty))
+                    .unwrap_or_default();
+                quote!(::darling::export::SchemaBody::Newtype(#ty))
+            }
+            Style::Struct => {
+                let fields = fields
+                    .iter()
+                    .filter(|field| !field.skip)
+                    .map(|field| self.field(field));
+                quote!(::darling::export::SchemaBody::Fields(
+                    ::darling::export::vec![#(#fields),*]
+                ))
+            }
+        }
+    }
+
+    fn field(&self, field: &Field<'_>) -> TokenStream {
+        let name = &field.name_in_attr;
+        let name = match field.rename_with {
+            Some(rename) => quote!(::darling::export::Cow::Owned(#rename(#name))),
+            None => quote!(::darling::export::Cow::Borrowed(#name)),
+        };
+        let ty = render(field.ty);
+        let multiple = field.multiple;
+        // Whether the field type fills in an absent value is only known at run time.
+        let required = if field.default_expression.is_some() || field.multiple {
+            quote!(false)
+        } else if let Some(ref from_none) = field.from_none {
+            quote!(#from_none.is_none())
+        } else {
+            quote!(true)
+        };
+        let default = optional_str(
+            field
+                .default_expression
+                .as_ref()
+                .map(|default| describe_default(default, &self.base)),
+        );
+        let deprecated = optional_str(field.deprecated);
+        let aliases = field.aliases;
+
+        quote!(::darling::export::FieldSchema {
+            name: #name,
+            aliases: &[#(#aliases),*],
+            ty: #ty,
+            required: #required,
+            default: #default,
+            multiple: #multiple,
+            deprecated: #deprecated,
+        })
+    }
+}
+
+fn optional_str<T: ToTokens>(value: Option<T>) -> TokenStream {
+    match value {
+        Some(value) => quote!(::darling::export::Some(#value)),
+        None => quote!(::darling::export::None),
+    }
+}
+
+impl<'a> ToTokens for MetaSchemaImpl<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let base = &self.base;
+        let ident = base.ident.to_string();
+        let allow_unknown_fields = base.allow_unknown_fields;
+
+        let body = match base.data {
+            Data::Struct(ref fields) | Data::Union(ref fields) => self.body(fields),
+            Data::Enum(ref variants) => {
+                let variants = variants
+                    .iter()
+                    .filter(|variant| !variant.skip)
+                    .map(|variant| {
+                        let name = &variant.name_in_attr;
+                        let name = match variant.rename_with {
+                            Some(rename) => quote!(::darling::export::Cow::Owned(#rename(#name))),
+                            None => quote!(::darling::export::Cow::Borrowed(#name)),
+                        };
+                        let body = self.body(&variant.data);
+                        let deprecated = optional_str(variant.deprecated);
+                        quote!(::darling::export::VariantSchema {
+                            name: #name,
+                            body: #body,
+                            deprecated: #deprecated,
+                        })
+                    });
+                quote!(::darling::export::SchemaBody::Variants(
+                    ::darling::export::vec![#(#variants),*]
+                ))
+            }
+        };
+
+        self.wrap(
+            quote!(
+                fn meta_schema() -> ::darling::export::TypeSchema {
+                    ::darling::export::TypeSchema {
+                        ident: #ident,
+                        body: #body,
+                        allow_unknown_fields: #allow_unknown_fields,
+                    }
+                }
+            ),
+            tokens,
+        );
+    }
+}
+
+impl<'a> OuterFromImpl<'a> for MetaSchemaImpl<'a> {
+    fn trait_path(&self) -> Path {
+        path!(::darling::export::MetaSchema)
+    }
+
+    fn trait_bound(&self) -> Path {
+        path!(::darling::export::FromMeta)
+    }
+
+    fn base(&'a self) -> &'a TraitImpl<'a> {
+        &self.base
+    }
+}
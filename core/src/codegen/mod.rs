@@ -1,5 +1,43 @@
+//! Code generators for darling's derived traits.
+//!
+//! These are the building blocks used by `darling_macro`. Downstream proc-macro crates can
+//! assemble a [`TraitImpl`] from [`Field`] and [`Variant`] values, wrap it in one of the
+//! `*Impl` types, and emit the result with [`quote::ToTokens`] as part of larger generated code.
+//!
+//! The generated code refers to `::darling`, so the crate receiving it must depend on `darling`.
+//!
+//! The emitted tokens depend only on the input: fields, variants and generic parameters are
+//! visited in declaration order, and sets of identifiers are only used to test membership,
+//! never iterated. Expanding the same input therefore always produces the same output, which
+//! snapshot tests and build caches can rely on.
+//!
+//! ```rust
+//! # extern crate darling_core;
+//! # extern crate syn;
+//! # extern crate quote;
+//! use darling_core::ast::{Data, Fields, Style};
+//! use darling_core::codegen::{Field, FromMetaImpl, TraitImpl};
+//! use quote::ToTokens;
+//!
+//! let ident: syn::Ident = syn::parse_quote!(Opts);
+//! let generics = syn::Generics::default();
+//! let field_ident: syn::Ident = syn::parse_quote!(name);
+//! let field_ty: syn::Type = syn::parse_quote!(String);
+//!
+//! let fields = Fields::new(Style::Struct, vec![Field::new(&field_ident, &field_ty)]);
+//! let base = TraitImpl::new(&ident, &generics, Data::Struct(fields));
+//! let tokens = FromMetaImpl::new(base).into_token_stream();
+//!
+//! let item: syn::ItemImpl = syn::parse2(tokens).unwrap();
+//! assert!(item.trait_.is_some());
+//! ```
+
 mod attr_extractor;
+mod attribute_docs;
+mod builder;
+mod builder_impl;
 mod default_expr;
+mod default_impl;
 mod error;
 mod field;
 mod from_attributes_impl;
@@ -8,23 +46,38 @@ mod from_field;
 mod from_meta_impl;
 mod from_type_param;
 mod from_variant_impl;
+mod meta_schema_impl;
 mod outer_from_impl;
 mod postfix_transform;
+mod to_meta_impl;
 mod trait_impl;
 mod variant;
 mod variant_data;
 
-pub(in crate::codegen) use self::attr_extractor::ExtractAttribute;
+pub use self::attr_extractor::PrefixedAttrs;
+pub(in crate::codegen) use self::attr_extractor::{
+    attr_span_initializer, warnings_initializer, ExtractAttribute,
+};
+pub use self::builder::{FieldBuilder, FromMetaBuilder};
+pub use self::builder_impl::BuilderImpl;
 pub use self::default_expr::DefaultExpression;
+pub use self::default_impl::DefaultImpl;
 pub use self::field::Field;
+pub(crate) use self::field::{default_from_none, default_with_path, field_span};
 pub use self::from_attributes_impl::FromAttributesImpl;
 pub use self::from_derive_impl::FromDeriveInputImpl;
 pub use self::from_field::FromFieldImpl;
 pub use self::from_meta_impl::FromMetaImpl;
 pub use self::from_type_param::FromTypeParamImpl;
 pub use self::from_variant_impl::FromVariantImpl;
+pub use self::meta_schema_impl::MetaSchemaImpl;
+pub(in crate::codegen) use self::outer_from_impl::compute_impl_bounds;
 pub use self::outer_from_impl::OuterFromImpl;
 pub use self::postfix_transform::PostfixTransform;
+pub use self::to_meta_impl::ToMetaImpl;
 pub use self::trait_impl::TraitImpl;
 pub use self::variant::Variant;
 pub use self::variant_data::FieldsGen;
+pub use crate::options::{
+    DataShape, ErrorSpan, ForwardAttrs, RenamedField, RequiredField, Shape, SkippedInput,
+};
@@ -0,0 +1,147 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::Path;
+
+use crate::ast::{Data, Fields, Style};
+use crate::codegen::{Field, OuterFromImpl, TraitImpl};
+use crate::Error;
+
+/// Generator for a `ToMeta` impl, which writes the type back out in the syntax its derived
+/// `FromMeta` impl reads.
+pub struct ToMetaImpl<'a> {
+    pub base: TraitImpl<'a>,
+}
+
+impl<'a> ToMetaImpl<'a> {
+    pub fn new(base: TraitImpl<'a>) -> Self {
+        ToMetaImpl { base }
+    }
+}
+
+/// Statements which push each non-skipped field to `__items`. Each field must be bound to a
+/// reference named after its ident.
+fn push_fields(fields: &Fields<Field<'_>>) -> TokenStream {
+    let pushes = fields.iter().filter(|field| !field.skip).map(|field| {
+        let ident = field.ident;
+        let name = field.name_call();
+        if let Some(prefix) = field.prefix {
+            quote!(::darling::export::push_prefixed_meta(&mut __items, #prefix, #ident);)
+        } else if field.multiple {
+            quote!(
+                for __value in #ident {
+                    ::darling::export::push_meta(&mut __items, #name, __value);
+                }
+            )
+        } else {
+            quote!(::darling::export::push_meta(&mut __items, #name, #ident);)
+        }
+    });
+
+    quote!(
+        #[allow(unused_mut)]
+        let mut __items = ::darling::export::Vec::new();
+        #(#pushes)*
+    )
+}
+
+/// A pattern which binds each named field to a reference named after its ident.
+fn bind_fields(fields: &Fields<Field<'_>>) -> TokenStream {
+    let idents = fields
+        .iter()
+        .filter(|field| !field.skip)
+        .map(|field| field.ident);
+    quote!({ #(#idents: ref #idents,)* .. })
+}
+
+impl<'a> ToTokens for ToMetaImpl<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let base = &self.base;
+        let ty_ident = base.ident;
+
+        let body = match base.data {
+            Data::Struct(ref fields) => match fields.style {
+                Style::Unit => quote!(::darling::export::Some(::darling::export::syn::Meta::Path(
+                    __name.clone()
+                ))),
+                Style::Tuple => quote!(::darling::export::ToMeta::to_meta(&self.0, __name)),
+                Style::Struct => {
+                    let bind = bind_fields(fields);
+                    let push = push_fields(fields);
+                    quote!(
+                        let #ty_ident #bind = *self;
+                        #push
+                        ::darling::export::Some(::darling::export::meta_list(__name, __items))
+                    )
+                }
+            },
+            Data::Enum(ref variants) => {
+                let arms = variants.iter().map(|variant| {
+                    let variant_ident = variant.variant_ident;
+                    let name = variant.name_call();
+                    if variant.skip {
+                        return quote!(#ty_ident::#variant_ident { .. } => ::darling::export::None,);
+                    }
+
+                    match variant.data.style {
+                        Style::Unit => quote!(
+                            #ty_ident::#variant_ident => ::darling::export::ToMeta::to_meta(#name, __name),
+                        ),
+                        Style::Tuple => quote!(
+                            #ty_ident::#variant_ident(ref __value) => {
+                                let mut __items = ::darling::export::Vec::new();
+                                ::darling::export::push_meta(&mut __items, #name, __value);
+                                ::darling::export::Some(::darling::export::meta_list(__name, __items))
+                            }
+                        ),
+                        Style::Struct => {
+                            let bind = bind_fields(&variant.data);
+                            let push = push_fields(&variant.data);
+                            // `Meta`'s impl renames the list, so it's written under the variant name.
+                            quote!(
+                                #ty_ident::#variant_ident #bind => {
+                                    #push
+                                    let mut __outer = ::darling::export::Vec::new();
+                                    ::darling::export::push_meta(
+                                        &mut __outer,
+                                        #name,
+                                        &::darling::export::meta_list(__name, __items),
+                                    );
+                                    ::darling::export::Some(::darling::export::meta_list(__name, __outer))
+                                }
+                            )
+                        }
+                    }
+                });
+
+                quote!(
+                    match *self {
+                        #(#arms)*
+                    }
+                )
+            }
+            Data::Union(_) => {
+                tokens.extend(Error::custom("ToMeta can't be derived for unions").write_errors());
+                return;
+            }
+        };
+
+        self.wrap(
+            quote!(
+                fn to_meta(&self, __name: &::darling::export::syn::Path) -> ::darling::export::Option<::darling::export::syn::Meta> {
+                    #body
+                }
+            ),
+            tokens,
+        );
+    }
+}
+
+impl<'a> OuterFromImpl<'a> for ToMetaImpl<'a> {
+    fn trait_path(&self) -> Path {
+        path!(::darling::export::ToMeta)
+    }
+
+    fn base(&'a self) -> &'a TraitImpl<'a> {
+        &self.base
+    }
+}
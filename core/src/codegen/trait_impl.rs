@@ -4,10 +4,12 @@ use syn::{Generics, Ident, WherePredicate};
 use crate::ast::{Data, Fields};
 use crate::codegen::{
     error::{ErrorCheck, ErrorDeclaration},
-    field, DefaultExpression, Field, FieldsGen, PostfixTransform, Variant,
+    field, variant_data, DefaultExpression, Field, FieldsGen, PostfixTransform, Variant,
 };
+use crate::options::RenamedField;
 use crate::usage::{CollectTypeParams, IdentSet, Purpose};
 
+/// The parts of a derived impl shared by every trait darling generates.
 #[derive(Debug)]
 pub struct TraitImpl<'a> {
     pub ident: &'a Ident,
@@ -15,11 +17,47 @@ pub struct TraitImpl<'a> {
     pub data: Data<Variant<'a>, Field<'a>>,
     pub default: Option<DefaultExpression<'a>>,
     pub post_transform: Option<&'a PostfixTransform>,
+    // TODO: The parsed `bound` is not yet applied when generating impl generics.
+    #[allow(dead_code)]
     pub bound: Option<&'a [WherePredicate]>,
     pub allow_unknown_fields: bool,
+    pub renamed: &'a [RenamedField],
+    /// Whether a `config = "path"` item is replaced with the options in that file.
+    pub config_file: bool,
+    /// Whether a `Default` impl is generated alongside the trait impl.
+    pub derive_default: bool,
+    /// If set, a builder type is generated alongside the trait impl for this input.
+    pub builder: Option<&'a syn::DeriveInput>,
+    /// Whether input which sets none of the fields is an error.
+    pub deny_empty: bool,
+    /// Whether the generated code notes what it reads in the active parse report.
+    pub report: bool,
 }
 
 impl<'a> TraitImpl<'a> {
+    /// Create a base for a type with no container-level options set.
+    pub fn new(
+        ident: &'a Ident,
+        generics: &'a Generics,
+        data: Data<Variant<'a>, Field<'a>>,
+    ) -> Self {
+        TraitImpl {
+            ident,
+            generics,
+            data,
+            default: None,
+            post_transform: None,
+            bound: None,
+            allow_unknown_fields: false,
+            renamed: &[],
+            config_file: false,
+            derive_default: false,
+            builder: None,
+            deny_empty: false,
+            report: false,
+        }
+    }
+
     /// Get all declared type parameters.
     pub fn declared_type_params(&self) -> IdentSet {
         self.generics
@@ -42,7 +80,9 @@ impl<'a> TraitImpl<'a> {
     {
         let declared = self.declared_type_params();
         match self.data {
-            Data::Struct(ref v) => self.type_params_in_fields(v, &field_filter, &declared),
+            Data::Struct(ref v) | Data::Union(ref v) => {
+                self.type_params_in_fields(v, &field_filter, &declared)
+            }
             Data::Enum(ref v) => {
                 v.iter()
                     .filter(variant_filter)
@@ -86,12 +126,29 @@ impl<'a> TraitImpl<'a> {
         ErrorCheck::default()
     }
 
+    /// Like `check_errors`, but errors without a span of their own point at `item`, an
+    /// expression for the input item.
+    pub(in crate::codegen) fn check_errors_at(&self, item: TokenStream) -> ErrorCheck {
+        ErrorCheck::with_item(item)
+    }
+
     /// Generate local variable declarations for all fields.
     pub(in crate::codegen) fn local_declarations(&self) -> TokenStream {
         if let Data::Struct(ref vd) = self.data {
             let vdr = vd.as_ref().map(Field::as_declaration);
             let decls = vdr.fields.as_slice();
-            quote!(#(#decls)*)
+            // The flag the core loop sets once an item has set a field.
+            let any_key = if self.deny_empty {
+                quote!(let mut __any_key = false;)
+            } else {
+                quote!()
+            };
+            let config_files = if self.config_file {
+                quote!(let mut __config_files: ::darling::export::Vec<::darling::export::ConfigFile> = ::darling::export::Vec::new();)
+            } else {
+                quote!()
+            };
+            quote!(#(#decls)* #any_key #config_files)
         } else {
             quote!()
         }
@@ -102,16 +159,48 @@ impl<'a> TraitImpl<'a> {
         if let Data::Struct(ref vd) = self.data {
             let vdr = vd.as_ref().map(|f| field::Declaration::new(f, false));
             let decls = vdr.fields.as_slice();
-            quote!(#(#decls)*)
+            let config_files = if self.config_file {
+                quote!(let __config_files: ::darling::export::Vec<::darling::export::ConfigFile> = ::darling::export::Vec::new();)
+            } else {
+                quote!()
+            };
+            quote!(#(#decls)* #config_files)
         } else {
             quote!()
         }
     }
 
+    /// An expression for the name of the field which receives the name-value form of the
+    /// attribute, if any.
+    pub(in crate::codegen) fn name_value_key(&self) -> Option<TokenStream> {
+        match self.data {
+            Data::Struct(ref vd) | Data::Union(ref vd) => vd
+                .iter()
+                .find(|field| field.name_value)
+                .map(Field::name_call),
+            Data::Enum(_) => None,
+        }
+    }
+
     pub(in crate::codegen) fn post_transform_call(&self) -> Option<TokenStream> {
         self.post_transform.map(|pt| quote!(#pt))
     }
 
+    /// Like `post_transform_call`, but an error from an `and_then` function which doesn't have
+    /// a span of its own points at `item`, an expression for the input item.
+    pub(in crate::codegen) fn post_transform_call_at(
+        &self,
+        item: TokenStream,
+    ) -> Option<TokenStream> {
+        self.post_transform.map(|pt| {
+            if pt.transformer == "and_then" {
+                quote!(#pt.map_err(|e| e.with_span(#item)))
+            } else {
+                quote!(#pt)
+            }
+        })
+    }
+
     /// Generate local variable declaration and initialization for instance from which missing fields will be taken.
     pub(in crate::codegen) fn fallback_decl(&self) -> TokenStream {
         let default = self.default.as_ref().map(DefaultExpression::as_declaration);
@@ -128,19 +217,48 @@ impl<'a> TraitImpl<'a> {
         }
     }
 
+    /// Report a list which set none of the fields, for `deny_empty`.
+    pub(in crate::codegen) fn check_any_key(&self) -> TokenStream {
+        if self.deny_empty {
+            variant_data::check_any_key(None)
+        } else {
+            quote!()
+        }
+    }
+
     pub(in crate::codegen) fn initializers(&self) -> TokenStream {
         self.make_field_ctx().initializers()
     }
 
     /// Generate the loop which walks meta items looking for property matches.
     pub(in crate::codegen) fn core_loop(&self) -> TokenStream {
-        self.make_field_ctx().core_loop()
+        let core_loop = self.make_field_ctx().core_loop();
+        if self.config_file {
+            quote!(
+                let __config_items = ::darling::export::merge_config_file(__items, &mut __errors, &mut __config_files);
+                let __items = &__config_items;
+                #core_loop
+            )
+        } else {
+            core_loop
+        }
+    }
+
+    /// Generate the `__canonical_key` closure, which maps the keys of each field to its name.
+    pub(in crate::codegen) fn canonical_key(&self) -> TokenStream {
+        self.make_field_ctx().canonical_key()
     }
 
     fn make_field_ctx(&'a self) -> FieldsGen<'a> {
         match self.data {
             Data::Enum(_) => panic!("Core loop on enums isn't supported"),
-            Data::Struct(ref data) => FieldsGen::new(data, self.allow_unknown_fields),
+            Data::Struct(ref data) | Data::Union(ref data) => FieldsGen::new(
+                data,
+                self.allow_unknown_fields,
+                self.renamed,
+                self.deny_empty,
+                self.report,
+            ),
         }
     }
 }
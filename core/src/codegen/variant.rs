@@ -1,8 +1,8 @@
 use std::borrow::Cow;
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, TokenStreamExt};
-use syn::Ident;
+use syn::{Ident, Path};
 
 use crate::ast::Fields;
 use crate::codegen::error::{ErrorCheck, ErrorDeclaration};
@@ -13,7 +13,11 @@ use crate::usage::{self, IdentRefSet, IdentSet, UsesTypeParams};
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Variant<'a> {
     /// The name which will appear in code passed to the `FromMeta` input.
-    pub name_in_attr: Cow<'a, String>,
+    pub name_in_attr: Cow<'a, str>,
+
+    /// A function from the enum's `rename_all_with`, which computes the name presented to
+    /// the user from `name_in_attr` when the generated code runs.
+    pub rename_with: Option<&'a Path>,
 
     /// The name of the variant which will be returned for a given `name_in_attr`.
     pub variant_ident: &'a Ident,
@@ -27,6 +31,12 @@ pub struct Variant<'a> {
     pub skip: bool,
 
     pub allow_unknown_fields: bool,
+
+    /// Whether the variant's fields are noted in the active parse report.
+    pub report: bool,
+
+    /// The note to include in a deprecation warning when the variant is used, if any.
+    pub deprecated: Option<&'a str>,
 }
 
 impl<'a> Variant<'a> {
@@ -34,6 +44,65 @@ impl<'a> Variant<'a> {
         &self.name_in_attr
     }
 
+    /// An expression for the name presented to the user, as a `&str`. A renamed variant
+    /// reads the local from `rename_declaration`.
+    pub(in crate::codegen) fn name_tokens(&self) -> TokenStream {
+        match self.rename_with {
+            Some(_) => {
+                let renamed = self.renamed_name();
+                quote!(::darling::export::String::as_str(&#renamed))
+            }
+            None => {
+                let name = &self.name_in_attr;
+                quote!(#name)
+            }
+        }
+    }
+
+    /// Like `name_tokens`, but calls the rename function itself, for generated code which
+    /// doesn't declare the variant's local.
+    pub(in crate::codegen) fn name_call(&self) -> TokenStream {
+        let name = &self.name_in_attr;
+        match self.rename_with {
+            Some(rename) => quote!(::darling::export::String::as_str(&#rename(#name))),
+            None => quote!(#name),
+        }
+    }
+
+    /// The pattern matching the name of the variant in a `match` on a `&str`.
+    fn name_pattern(&self) -> TokenStream {
+        match self.rename_with {
+            Some(_) => {
+                let name = self.name_tokens();
+                quote!(__key if __key == #name)
+            }
+            None => {
+                let name = &self.name_in_attr;
+                quote!(#name)
+            }
+        }
+    }
+
+    /// The local variable which holds the name of a renamed variant.
+    fn renamed_name(&self) -> Ident {
+        Ident::new(
+            &format!("__renamed_variant_{}", self.variant_ident),
+            Span::call_site(),
+        )
+    }
+
+    /// Declares the local read by `name_tokens`, if the variant is renamed.
+    pub(in crate::codegen) fn rename_declaration(&self) -> TokenStream {
+        match self.rename_with {
+            Some(rename) if !self.skip => {
+                let name = &self.name_in_attr;
+                let renamed = self.renamed_name();
+                quote!(let #renamed: ::darling::export::String = #rename(#name);)
+            }
+            _ => quote!(),
+        }
+    }
+
     pub fn as_unit_match_arm(&'a self) -> UnitMatchArm<'a> {
         UnitMatchArm(self)
     }
@@ -56,6 +125,9 @@ impl<'a> UsesTypeParams for Variant<'a> {
 /// Code generator for an enum variant in a unit match position.
 /// This is placed in generated `from_string` calls for the parent enum.
 /// Value-carrying variants wrapped in this type will emit code to produce an "unsupported format" error.
+///
+/// `from_string` has no warnings collector, so uses of deprecated unit variants are noted
+/// by [`DeprecatedUnitArm`] instead.
 pub struct UnitMatchArm<'a>(&'a Variant<'a>);
 
 impl<'a> ToTokens for UnitMatchArm<'a> {
@@ -66,18 +138,18 @@ impl<'a> ToTokens for UnitMatchArm<'a> {
             return;
         }
 
-        let name_in_attr = &val.name_in_attr;
+        let pattern = val.name_pattern();
 
         if val.data.is_unit() {
             let variant_ident = val.variant_ident;
             let ty_ident = val.ty_ident;
 
             tokens.append_all(quote!(
-                #name_in_attr => ::darling::export::Ok(#ty_ident::#variant_ident),
+                #pattern => ::darling::export::Ok(#ty_ident::#variant_ident),
             ));
         } else {
             tokens.append_all(quote!(
-                #name_in_attr => ::darling::export::Err(::darling::Error::unsupported_format("literal")),
+                #pattern => ::darling::export::Err(::darling::export::Error::unsupported_format("literal")),
             ));
         }
     }
@@ -96,31 +168,40 @@ impl<'a> ToTokens for DataMatchArm<'a> {
             return;
         }
 
-        let name_in_attr = &val.name_in_attr;
+        let name_in_attr = val.name_tokens();
+        let pattern = val.name_pattern();
         let variant_ident = val.variant_ident;
         let ty_ident = val.ty_ident;
 
         if val.data.is_unit() {
             tokens.append_all(quote!(
-                #name_in_attr => ::darling::export::Err(::darling::Error::unsupported_format("list")),
+                #pattern => ::darling::export::Err(::darling::export::Error::unsupported_format("list")),
             ));
 
             return;
         }
 
-        let vdg = FieldsGen::new(&val.data, val.allow_unknown_fields);
+        let warn_deprecated = val.deprecated.map(|note| {
+            quote!(__warnings.push(
+                ::darling::export::Warning::deprecated(#name_in_attr, #note).with_span(__nested.path())
+            );)
+        });
 
-        if val.data.is_struct() {
+        let vdg = FieldsGen::new(&val.data, val.allow_unknown_fields, &[], false, val.report);
+
+        // Tuple variants with more than one field read them from positional items.
+        if !val.data.is_newtype() {
             let declare_errors = ErrorDeclaration::default();
-            let check_errors = ErrorCheck::with_location(name_in_attr);
+            let check_errors = ErrorCheck::with_location(name_in_attr.clone());
             let require_fields = vdg.require_fields();
             let decls = vdg.declarations();
             let core_loop = vdg.core_loop();
             let inits = vdg.initializers();
 
             tokens.append_all(quote!(
-                #name_in_attr => {
-                    if let ::syn::Meta::List(ref __data) = *__nested {
+                #pattern => {
+                    #warn_deprecated
+                    if let ::darling::export::syn::Meta::List(ref __data) = *__nested {
                         let __items = &__data.nested;
 
                         #declare_errors
@@ -137,22 +218,57 @@ impl<'a> ToTokens for DataMatchArm<'a> {
                             #inits
                         })
                     } else {
-                        ::darling::export::Err(::darling::Error::unsupported_format("non-list"))
+                        ::darling::export::Err(::darling::export::Error::unsupported_format("non-list"))
                     }
                 }
             ));
-        } else if val.data.is_newtype() {
+        } else {
             tokens.append_all(quote!(
-                #name_in_attr => {
+                #pattern => {
+                    #warn_deprecated
                     ::darling::export::Ok(
                         #ty_ident::#variant_ident(
-                            ::darling::FromMeta::from_meta(__nested)
+                            ::darling::export::FromMeta::from_meta_with_warnings(__nested, __warnings)
                                 .map_err(|e| e.at(#name_in_attr))?)
                     )
                 }
             ));
-        } else {
-            panic!("Match arms aren't supported for tuple variants yet");
         }
     }
 }
+
+/// Notes the use of a deprecated unit variant once the parent enum's value has been read.
+/// This is placed in a `match` on the parsed value, and generates nothing for other variants.
+pub struct DeprecatedUnitArm<'a>(&'a Variant<'a>);
+
+impl<'a> DeprecatedUnitArm<'a> {
+    pub fn new(variant: &'a Variant<'a>) -> Option<Self> {
+        if variant.skip || !variant.data.is_unit() {
+            return None;
+        }
+
+        variant.deprecated.map(|_| DeprecatedUnitArm(variant))
+    }
+}
+
+impl<'a> ToTokens for DeprecatedUnitArm<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let val = self.0;
+        let name_in_attr = val.name_call();
+        let note = val.deprecated;
+        let variant_ident = val.variant_ident;
+        let ty_ident = val.ty_ident;
+
+        // The warning points at the word which named the variant when there is one, such as
+        // the `"quick"` of `mode = "quick"`.
+        tokens.append_all(quote!(
+            #ty_ident::#variant_ident => {
+                let __warning = ::darling::export::Warning::deprecated(#name_in_attr, #note);
+                __warnings.push(match *__item {
+                    ::darling::export::syn::Meta::NameValue(ref __nv) => __warning.with_span(&__nv.lit),
+                    _ => __warning.with_span(__item),
+                });
+            }
+        ));
+    }
+}
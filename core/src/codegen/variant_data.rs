@@ -1,88 +1,196 @@
 use proc_macro2::TokenStream;
 
 use crate::ast::{Fields, Style};
+use crate::codegen::field::{key_pattern, key_scrutinee, PositionalArm, PrefixMatchArm};
 use crate::codegen::Field;
+use crate::options::RenamedField;
 
 pub struct FieldsGen<'a> {
     fields: &'a Fields<Field<'a>>,
     allow_unknown_fields: bool,
+    renamed: &'a [RenamedField],
+    deny_empty: bool,
+    report: bool,
 }
 
 impl<'a> FieldsGen<'a> {
-    pub fn new(fields: &'a Fields<Field<'a>>, allow_unknown_fields: bool) -> Self {
+    pub fn new(
+        fields: &'a Fields<Field<'a>>,
+        allow_unknown_fields: bool,
+        renamed: &'a [RenamedField],
+        deny_empty: bool,
+        report: bool,
+    ) -> Self {
         Self {
             fields,
             allow_unknown_fields,
+            renamed,
+            deny_empty,
+            report,
         }
     }
 
     /// Create declarations for all the fields in the struct.
     pub(in crate::codegen) fn declarations(&self) -> TokenStream {
-        match *self.fields {
-            Fields {
-                style: Style::Struct,
-                ref fields,
-                ..
-            } => {
-                let vdr = fields.iter().map(Field::as_declaration);
-                quote!(#(#vdr)*)
-            }
-            _ => panic!("FieldsGen doesn't support tuples yet"),
-        }
+        let vdr = self.fields.iter().map(Field::as_declaration);
+        quote!(#(#vdr)*)
     }
 
     /// Generate the loop which walks meta items looking for property matches.
     pub(in crate::codegen) fn core_loop(&self) -> TokenStream {
+        if self.fields.style == Style::Tuple {
+            return self.positional_loop();
+        }
+
         let arms = self.fields.as_ref().map(Field::as_match);
 
-        // If we're allowing unknown fields, then handling one is a no-op.
-        // Otherwise, we're going to push a new spanned error pointing at the field.
+        // If we're allowing unknown fields, then handling one at most notes it in the parse
+        // report. Otherwise, we're going to push a new spanned error pointing at the field.
         let handle_unknown = if self.allow_unknown_fields {
-            quote!()
+            if self.report {
+                quote!(::darling::export::report::ignored(__other, __inner);)
+            } else {
+                quote!()
+            }
         } else {
             // We can't call `unknown_field_with_alts` with an empty slice, or else it fails to
-            // infer the type of the slice item.
-            let err_fn = if arms.is_empty() {
+            // infer the type of the slice item. Without suggestions, the names aren't embedded.
+            // Skipped fields can't be set, so they aren't suggested.
+            let names: Vec<TokenStream> = self
+                .fields
+                .iter()
+                .filter(|field| !field.skip && field.prefix.is_none())
+                .map(Field::name_tokens)
+                .collect();
+            let err_fn = if names.is_empty() || !cfg!(feature = "suggestions") {
                 quote!(unknown_field(__other))
             } else {
-                let names = self.fields.as_ref().map(Field::as_name);
-                let names = names.iter();
                 quote!(unknown_field_with_alts(__other, &[#(#names),*]))
             };
 
             quote! {
-                __errors.push(::darling::Error::#err_fn.with_span(__inner));
+                __errors.push(::darling::export::Error::#err_fn.with_span(__inner));
             }
         };
         let arms = arms.iter();
 
+        // With `deny_empty`, every item is assumed to be a recognized key unless it reaches
+        // the arms for unknown or renamed keys.
+        let (track_key, unrecognized, record_key) = self.key_tracking();
+
+        // Renamed fields are reported even when unknown fields are allowed, since the author
+        // has explicitly said callers shouldn't use those names.
+        let renamed_arms = self.renamed.iter().map(|renamed| {
+            let old = &renamed.old;
+            let old_pattern = key_pattern(old);
+            let new = &renamed.new;
+            let since = match renamed.since {
+                Some(ref since) => quote!(::darling::export::Some(#since)),
+                None => quote!(::darling::export::None),
+            };
+
+            quote!(
+                #old_pattern => {
+                    #unrecognized
+                    __errors.push(::darling::export::Error::renamed_field(#old, #new, #since).with_span(__inner));
+                }
+            )
+        });
+
+        let scrutinee = key_scrutinee(quote!(__name.as_str()));
+        let prefix_arms = self.fields.iter().filter_map(PrefixMatchArm::new);
+
         quote!(
             for __item in __items {
-                if let ::syn::NestedMeta::Meta(ref __inner) = *__item {
-                    let __name = ::darling::util::path_to_string(__inner.path());
-                    match __name.as_str() {
+                if let ::darling::export::syn::NestedMeta::Meta(ref __inner) = *__item {
+                    let __name = ::darling::export::path_to_string(__inner.path());
+                    #track_key
+                    match #scrutinee {
                         #(#arms)*
-                        __other => { #handle_unknown }
+                        #(#renamed_arms)*
+                        #(#prefix_arms)*
+                        (_, __other) => {
+                            #unrecognized
+                            #handle_unknown
+                        }
                     }
+                    #record_key
                 }
             }
         )
     }
 
-    pub fn require_fields(&self) -> TokenStream {
-        match *self.fields {
-            Fields {
-                style: Style::Struct,
-                ref fields,
-                ..
-            } => {
-                let checks = fields.iter().map(Field::as_presence_check);
-                quote!(#(#checks)*)
+    /// Generate a closure named `__canonical_key` which maps every key of a field, such as
+    /// an alias, to the field's name. Other keys are returned unchanged.
+    pub(in crate::codegen) fn canonical_key(&self) -> TokenStream {
+        let arms = self
+            .fields
+            .iter()
+            .filter(|field| !field.skip && field.prefix.is_none())
+            .map(|field| {
+                let pattern = field.name_pattern();
+                let name = field.name_tokens();
+                quote!(#pattern => ::darling::export::ToString::to_string(#name),)
+            });
+        let scrutinee = key_scrutinee(quote!(__name));
+
+        quote!(
+            let __canonical_key = |__name: &str| -> ::darling::export::String {
+                match #scrutinee {
+                    #(#arms)*
+                    (_, __other) => ::darling::export::ToString::to_string(__other),
+                }
+            };
+        )
+    }
+
+    /// Generate the loop which reads the fields of a tuple struct from positional items.
+    /// Skipped fields don't take up a position.
+    fn positional_loop(&self) -> TokenStream {
+        let arms = self
+            .fields
+            .iter()
+            .filter(|field| !field.skip)
+            .enumerate()
+            .map(|(position, field)| PositionalArm::new(field, position));
+        let count = self.fields.iter().filter(|field| !field.skip).count();
+        let (track_key, unrecognized, record_key) = self.key_tracking();
+
+        quote!(
+            for (__position, __item) in __items.into_iter().enumerate() {
+                #track_key
+                match __position {
+                    #(#arms)*
+                    _ => {
+                        #unrecognized
+                        __errors.push(::darling::export::Error::too_many_items(#count).with_span(__item));
+                    }
+                }
+                #record_key
             }
-            _ => panic!("FieldsGen doesn't support tuples for requirement checks"),
+        )
+    }
+
+    /// The statements which note whether an item set a field, for `deny_empty`: one before
+    /// the match on the item, one for arms which don't set a field, and one after the match.
+    /// The generated code must declare `__any_key` before the loop.
+    fn key_tracking(&self) -> (TokenStream, TokenStream, TokenStream) {
+        if self.deny_empty {
+            (
+                quote!(let mut __known_key = true;),
+                quote!(__known_key = false;),
+                quote!(__any_key |= __known_key;),
+            )
+        } else {
+            (quote!(), quote!(), quote!())
         }
     }
 
+    pub fn require_fields(&self) -> TokenStream {
+        let checks = self.fields.iter().map(Field::as_presence_check);
+        quote!(#(#checks)*)
+    }
+
     pub(in crate::codegen) fn initializers(&self) -> TokenStream {
         let inits = self.fields.as_ref().map(Field::as_initializer);
         let inits = inits.iter();
@@ -90,3 +198,23 @@ impl<'a> FieldsGen<'a> {
         quote!(#(#inits),*)
     }
 }
+
+/// Report input which set none of the fields, for `deny_empty`. Given an `Option<Span>`
+/// expression, the check only happens once it is set, and the error points there.
+pub(in crate::codegen) fn check_any_key(present: Option<TokenStream>) -> TokenStream {
+    let error = quote!(::darling::export::Error::too_few_items(1));
+    match present {
+        Some(present) => quote!(
+            if let ::darling::export::Some(__span) = #present {
+                if !__any_key {
+                    __errors.push(#error.with_span(&::darling::export::SpannedValue::new((), __span)));
+                }
+            }
+        ),
+        None => quote!(
+            if !__any_key {
+                __errors.push(#error);
+            }
+        ),
+    }
+}
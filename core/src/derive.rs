@@ -1,61 +1,218 @@
 //! Functions to derive `darling`'s traits from well-formed input, without directly depending
 //! on `proc_macro`.
 
+use std::env;
+
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::DeriveInput;
 
-use crate::options;
+use crate::options::{self, ContainerOptions, Core};
+use crate::util::render_tokens;
+use crate::{Error, Result};
+
+/// The environment variable which names types whose generated impls are printed to stderr,
+/// as if they had `#[darling(debug)]`. It holds a comma-separated list of type names, or `*`
+/// for every type.
+pub const DEBUG_ENV_VAR: &str = "DARLING_DEBUG";
 
 /// Run an expression which returns a `darling::Result`, then either return the tokenized
 /// representation of the `Ok` value, or the tokens of the compiler errors in the `Err` case.
+/// The tokens are printed first if the deriving type asked for that.
 macro_rules! emit_impl_or_error {
-    ($e:expr) => {
+    ($derive:expr, $e:expr) => {
         match $e {
-            Ok(val) => val.into_token_stream(),
+            Ok(val) => {
+                let tokens = val.to_token_stream();
+                if debug_requested(val.container()) {
+                    eprintln!(
+                        "darling: `#[derive({})]` on `{}` generated:\n{}\n",
+                        $derive,
+                        val.container().ident,
+                        render_tokens(&tokens)
+                    );
+                }
+                tokens
+            }
             Err(err) => err.write_errors(),
         }
     };
 }
 
+/// Whether the impls generated for a type should be printed, because it has
+/// `#[darling(debug)]` or is named by [`DEBUG_ENV_VAR`].
+fn debug_requested(container: &Core<'_>) -> bool {
+    container.debug.is_present()
+        || env::var(DEBUG_ENV_VAR)
+            .map(|names| names_type(&names, &container.ident.to_string()))
+            .unwrap_or_default()
+}
+
+/// Whether a comma-separated list of type names, as found in [`DEBUG_ENV_VAR`], includes `ty`.
+fn names_type(names: &str, ty: &str) -> bool {
+    names
+        .split(',')
+        .map(str::trim)
+        .any(|name| name == "*" || name == ty)
+}
+
 /// Create tokens for a `darling::FromMeta` impl from a `DeriveInput`. If
 /// the input cannot produce a valid impl, the returned tokens will contain
 /// compile errors instead.
 pub fn from_meta(input: &DeriveInput) -> TokenStream {
-    emit_impl_or_error!(options::FromMetaOptions::new(input))
+    emit_impl_or_error!("FromMeta", options::FromMetaOptions::new(input))
 }
 
 /// Create tokens for a `darling::FromAttributes` impl from a `DeriveInput`. If
 /// the input cannot produce a valid impl, the returned tokens will contain
 /// compile errors instead.
 pub fn from_attributes(input: &DeriveInput) -> TokenStream {
-    emit_impl_or_error!(options::FromAttributesOptions::new(input))
+    emit_impl_or_error!("FromAttributes", options::FromAttributesOptions::new(input))
 }
 
 /// Create tokens for a `darling::FromDeriveInput` impl from a `DeriveInput`. If
 /// the input cannot produce a valid impl, the returned tokens will contain
 /// compile errors instead.
 pub fn from_derive_input(input: &DeriveInput) -> TokenStream {
-    emit_impl_or_error!(options::FdiOptions::new(input))
+    emit_impl_or_error!("FromDeriveInput", options::FdiOptions::new(input))
 }
 
 /// Create tokens for a `darling::FromField` impl from a `DeriveInput`. If
 /// the input cannot produce a valid impl, the returned tokens will contain
 /// compile errors instead.
 pub fn from_field(input: &DeriveInput) -> TokenStream {
-    emit_impl_or_error!(options::FromFieldOptions::new(input))
+    emit_impl_or_error!("FromField", options::FromFieldOptions::new(input))
 }
 
 /// Create tokens for a `darling::FromTypeParam` impl from a `DeriveInput`. If
 /// the input cannot produce a valid impl, the returned tokens will contain
 /// compile errors instead.
 pub fn from_type_param(input: &DeriveInput) -> TokenStream {
-    emit_impl_or_error!(options::FromTypeParamOptions::new(input))
+    emit_impl_or_error!("FromTypeParam", options::FromTypeParamOptions::new(input))
 }
 
 /// Create tokens for a `darling::FromVariant` impl from a `DeriveInput`. If
 /// the input cannot produce a valid impl, the returned tokens will contain
 /// compile errors instead.
 pub fn from_variant(input: &DeriveInput) -> TokenStream {
-    emit_impl_or_error!(options::FromVariantOptions::new(input))
+    emit_impl_or_error!("FromVariant", options::FromVariantOptions::new(input))
+}
+
+/// Create tokens for a `darling::MetaSchema` impl from a `DeriveInput`. If
+/// the input cannot produce a valid impl, the returned tokens will contain
+/// compile errors instead.
+pub fn meta_schema(input: &DeriveInput) -> TokenStream {
+    emit_impl_or_error!("MetaSchema", options::MetaSchemaOptions::new(input))
+}
+
+/// Create tokens for a `darling::ToMeta` impl from a `DeriveInput`. If
+/// the input cannot produce a valid impl, the returned tokens will contain
+/// compile errors instead.
+pub fn to_meta(input: &DeriveInput) -> TokenStream {
+    emit_impl_or_error!("ToMeta", options::ToMetaOptions::new(input))
+}
+
+/// The derives which [`expand_str`] knows how to expand.
+const EXPANDABLE_DERIVES: &[&str] = &[
+    "FromMeta",
+    "FromAttributes",
+    "FromDeriveInput",
+    "FromField",
+    "FromTypeParam",
+    "FromVariant",
+    "MetaSchema",
+    "ToMeta",
+];
+
+/// Expand the `darling` derives listed in the `#[derive(...)]` attributes of `source`, and
+/// return the generated impls as a string.
+///
+/// This is meant for snapshot tests of generated code, which otherwise need a full
+/// proc-macro harness. Derives which don't belong to `darling` are ignored, and the impls
+/// appear in the order their derives are listed. As with the derive macros themselves,
+/// problems with the options struct produce code containing `compile_error!` invocations
+/// rather than an `Err`; an error is only returned if `source` isn't a valid item or
+/// doesn't derive any of `darling`'s traits.
+///
+/// The output only depends on `source`, so it can be compared byte-for-byte across runs.
+///
+/// ```rust
+/// # extern crate darling_core;
+/// let expanded = darling_core::derive::expand_str(
+///     r#"
+///     #[derive(FromMeta)]
+///     struct Opts {
+///         name: String,
+///     }
+///     "#,
+/// )
+/// .unwrap();
+///
+/// assert!(expanded.starts_with("impl :: darling :: export :: FromMeta for Opts"));
+/// ```
+pub fn expand_str(source: &str) -> Result<String> {
+    let input: DeriveInput = syn::parse_str(source)?;
+    let mut tokens = TokenStream::new();
+    let mut expanded_any = false;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+    {
+        let paths = attr.parse_args_with(
+            syn::punctuated::Punctuated::<syn::Path, Token![,]>::parse_terminated,
+        )?;
+
+        for path in paths {
+            let expansion = match path.segments.last() {
+                Some(segment) => expand_derive(&segment.ident.to_string(), &input),
+                None => None,
+            };
+
+            if let Some(expansion) = expansion {
+                tokens.extend(expansion);
+                expanded_any = true;
+            }
+        }
+    }
+
+    if expanded_any {
+        Ok(tokens.to_string())
+    } else {
+        Err(Error::custom(format!(
+            "`{}` doesn't derive any of {}",
+            input.ident,
+            EXPANDABLE_DERIVES.join(", ")
+        ))
+        .with_span(&input.ident))
+    }
+}
+
+fn expand_derive(name: &str, input: &DeriveInput) -> Option<TokenStream> {
+    Some(match name {
+        "FromMeta" => from_meta(input),
+        "FromAttributes" => from_attributes(input),
+        "FromDeriveInput" => from_derive_input(input),
+        "FromField" => from_field(input),
+        "FromTypeParam" => from_type_param(input),
+        "FromVariant" => from_variant(input),
+        "MetaSchema" => meta_schema(input),
+        "ToMeta" => to_meta(input),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::names_type;
+
+    #[test]
+    fn debug_env_var_names() {
+        assert!(names_type("Opts", "Opts"));
+        assert!(names_type("Other, Opts", "Opts"));
+        assert!(names_type("*", "Opts"));
+        assert!(!names_type("Options", "Opts"));
+        assert!(!names_type("", "Opts"));
+    }
 }
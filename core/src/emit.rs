@@ -0,0 +1,135 @@
+//! Turning the outcome of a proc-macro into the tokens it returns.
+//!
+//! Proc-macro entry points usually end with the same `match` over a `darling::Result`.
+//! [`expand`] does that in one call, reporting errors in the way chosen by [`Strategy`]:
+//!
+//! ```rust,ignore
+//! #[proc_macro_derive(MyTrait, attributes(my_trait))]
+//! pub fn derive_my_trait(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+//!     darling::emit::expand(|| {
+//!         let input = syn::parse(input)?;
+//!         let opts = MyTraitOpts::from_derive_input(&input)?;
+//!         Ok(opts.into_token_stream())
+//!     })
+//! }
+//! ```
+
+use std::env;
+
+use proc_macro2::TokenStream;
+
+use crate::{Error, Result};
+
+/// The environment variable which overrides the [`Strategy`] chosen by [`Strategy::from_env`].
+///
+/// The accepted values are `compile_error`, `panic` and `diagnostics`.
+pub const STRATEGY_VAR: &str = "DARLING_EMIT";
+
+/// How errors are reported to the compiler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Return a `compile_error!` invocation for each error, pointing at the error's span.
+    /// This works on every toolchain.
+    CompileError,
+    /// Panic with the error's message. The compiler reports the panic at the macro's call
+    /// site, so spans are lost, but the message is shown in full.
+    Panic,
+    /// Emit each error and recorded warning as a compiler diagnostic. This needs the
+    /// `diagnostics` feature, which only builds on nightly; without it, errors are reported
+    /// as with [`Strategy::CompileError`].
+    Diagnostics,
+}
+
+impl Strategy {
+    /// The strategy named by the `DARLING_EMIT` environment variable, or the best one for
+    /// the toolchain if it isn't set: [`Strategy::Diagnostics`] when the `diagnostics`
+    /// feature is enabled, and [`Strategy::CompileError`] otherwise.
+    pub fn from_env() -> Self {
+        match env::var(STRATEGY_VAR).as_ref().map(String::as_str) {
+            Ok("compile_error") => Strategy::CompileError,
+            Ok("panic") => Strategy::Panic,
+            Ok("diagnostics") => Strategy::Diagnostics,
+            _ if cfg!(feature = "diagnostics") => Strategy::Diagnostics,
+            _ => Strategy::CompileError,
+        }
+    }
+
+    /// Report `error`, returning the tokens the macro should output in place of its expansion.
+    ///
+    /// # Panics
+    /// Panics with the error's message when the strategy is [`Strategy::Panic`].
+    pub fn emit(self, error: Error) -> TokenStream {
+        match self {
+            Strategy::CompileError => error.write_compile_errors(),
+            Strategy::Panic => panic!("{}", error),
+            Strategy::Diagnostics => {
+                #[cfg(feature = "diagnostics")]
+                {
+                    error.emit();
+                    TokenStream::new()
+                }
+
+                #[cfg(not(feature = "diagnostics"))]
+                {
+                    error.write_compile_errors()
+                }
+            }
+        }
+    }
+
+    /// Return the expansion in `result`, or report its error.
+    pub fn finish(self, result: Result<TokenStream>) -> TokenStream {
+        match result {
+            Ok(tokens) => tokens,
+            Err(error) => self.emit(error),
+        }
+    }
+}
+
+/// Run the body of a proc-macro, reporting any error with [`Strategy::from_env`].
+///
+/// The return type can be either `proc_macro::TokenStream` or `proc_macro2::TokenStream`,
+/// so the call can be the whole body of a macro's entry point.
+pub fn expand<T, F>(body: F) -> T
+where
+    T: From<TokenStream>,
+    F: FnOnce() -> Result<TokenStream>,
+{
+    Strategy::from_env().finish(body()).into()
+}
+
+/// Report `error` with [`Strategy::from_env`], returning the tokens the macro should output.
+pub fn errors(error: Error) -> TokenStream {
+    Strategy::from_env().emit(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Strategy;
+    use crate::Error;
+
+    #[test]
+    fn compile_error() {
+        let tokens = Strategy::CompileError.finish(Err(Error::multiple(vec![
+            Error::missing_field("a"),
+            Error::missing_field("b"),
+        ])));
+        let tokens = tokens.to_string();
+        assert_eq!(tokens.matches("compile_error").count(), 2);
+        assert!(tokens.contains("Missing field `a`"));
+    }
+
+    #[test]
+    fn passes_expansion_through() {
+        let tokens = Strategy::Panic.finish(Ok(quote!(
+            struct Expanded;
+        )));
+        assert_eq!(tokens.to_string(), "struct Expanded ;");
+    }
+
+    #[test]
+    #[should_panic(expected = "Missing field `a`")]
+    fn panics() {
+        Strategy::Panic.emit(Error::missing_field("a"));
+    }
+}
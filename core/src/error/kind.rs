@@ -6,22 +6,50 @@ type DeriveInputShape = String;
 type FieldName = String;
 type MetaFormat = String;
 
+/// The category of a [`darling::Error`](Error), obtained through `Error::kind`.
+///
+/// New variants may be added in any release, so matches on this type should include
+/// a wildcard arm.
 #[derive(Debug)]
 // Don't want to publicly commit to ErrorKind supporting equality yet, but
 // not having it makes testing very difficult.
 #[cfg_attr(test, derive(Clone, PartialEq, Eq))]
-pub(in crate::error) enum ErrorKind {
+pub enum ErrorKind {
     /// An arbitrary error message.
     Custom(String),
+    /// A field which can only appear once appeared more than once.
     DuplicateField(FieldName),
+    /// A required field did not appear in the input.
     MissingField(FieldName),
-    UnsupportedShape(DeriveInputShape),
-    UnknownField(ErrorUnknownField),
+    /// The input's body did not match any of the shapes allowed by `supports`.
+    UnsupportedShape {
+        observed: DeriveInputShape,
+        expected: Option<String>,
+    },
+    /// A field appeared which doesn't correspond to any known field.
+    /// This is boxed to keep `Error` small.
+    UnknownField(Box<ErrorUnknownField>),
+    /// A field which the receiver computes internally appeared in the input.
+    SkippedField(FieldName),
+    /// A field appeared under a name which the receiver has since renamed.
+    /// This is boxed to keep `Error` small.
+    RenamedField(Box<ErrorRenamedField>),
+    /// The input was written in a meta-item format the receiver does not accept,
+    /// such as a word where a list was expected.
     UnexpectedFormat(MetaFormat),
+    /// A literal of the wrong type was given.
     UnexpectedType(String),
-    UnknownValue(String),
+    /// A literal had the right type but did not match any accepted value.
+    UnknownValue(ErrorUnknownValue),
+    /// A number did not fit in the integer type it was read as.
+    /// This is boxed to keep `Error` small.
+    OutOfRange(Box<ErrorOutOfRange>),
+    /// A list had fewer than the required number of items.
     TooFewItems(usize),
+    /// A list had more than the maximum number of items.
     TooManyItems(usize),
+    /// Meta lists were nested more deeply than the receiver allows.
+    NestingLimitExceeded(usize),
     /// A set of errors.
     Multiple(Vec<Error>),
 
@@ -31,6 +59,7 @@ pub(in crate::error) enum ErrorKind {
 }
 
 impl ErrorKind {
+    /// A short, fixed description of this category of error.
     pub fn description(&self) -> &str {
         use self::ErrorKind::*;
 
@@ -39,19 +68,23 @@ impl ErrorKind {
             DuplicateField(_) => "Duplicate field",
             MissingField(_) => "Missing field",
             UnknownField(_) => "Unexpected field",
-            UnsupportedShape(_) => "Unsupported shape",
+            SkippedField(_) => "Skipped field",
+            RenamedField(_) => "Renamed field",
+            UnsupportedShape { .. } => "Unsupported shape",
             UnexpectedFormat(_) => "Unexpected meta-item format",
             UnexpectedType(_) => "Unexpected literal type",
             UnknownValue(_) => "Unknown literal value",
+            OutOfRange(_) => "Value out of range",
             TooFewItems(_) => "Too few items",
             TooManyItems(_) => "Too many items",
+            NestingLimitExceeded(_) => "Nesting limit exceeded",
             Multiple(_) => "Multiple errors",
             __NonExhaustive => unreachable!(),
         }
     }
 
     /// Deeply counts the number of errors this item represents.
-    pub fn len(&self) -> usize {
+    pub(in crate::error) fn len(&self) -> usize {
         if let ErrorKind::Multiple(ref items) = *self {
             items.iter().map(Error::len).sum()
         } else {
@@ -69,28 +102,39 @@ impl fmt::Display for ErrorKind {
             DuplicateField(ref field) => write!(f, "Duplicate field `{}`", field),
             MissingField(ref field) => write!(f, "Missing field `{}`", field),
             UnknownField(ref field) => field.fmt(f),
-            UnsupportedShape(ref shape) => write!(f, "Unsupported shape `{}`", shape),
+            SkippedField(ref field) => write!(
+                f,
+                "`{}` is ignored because it is computed internally",
+                field
+            ),
+            RenamedField(ref field) => field.fmt(f),
+            UnsupportedShape {
+                ref observed,
+                ref expected,
+            } => {
+                write!(f, "Unsupported shape `{}`", observed)?;
+
+                if let Some(expected) = &expected {
+                    write!(f, ". Expected {}.", expected)?;
+                }
+
+                Ok(())
+            }
             UnexpectedFormat(ref format) => write!(f, "Unexpected meta-item format `{}`", format),
             UnexpectedType(ref ty) => write!(f, "Unexpected literal type `{}`", ty),
-            UnknownValue(ref val) => write!(f, "Unknown literal value `{}`", val),
+            UnknownValue(ref val) => val.fmt(f),
+            OutOfRange(ref val) => val.fmt(f),
             TooFewItems(ref min) => write!(f, "Too few items: Expected at least {}", min),
             TooManyItems(ref max) => write!(f, "Too many items: Expected no more than {}", max),
-            Multiple(ref items) if items.len() == 1 => items[0].fmt(f),
-            Multiple(ref items) => {
-                write!(f, "Multiple errors: (")?;
-                let mut first = true;
-                for item in items {
-                    if !first {
-                        write!(f, ", ")?;
-                    } else {
-                        first = false;
-                    }
-
-                    item.fmt(f)?;
-                }
-
-                write!(f, ")")
+            NestingLimitExceeded(ref max) => {
+                write!(
+                    f,
+                    "Nesting limit exceeded: Expected no more than {} levels",
+                    max
+                )
             }
+            Multiple(ref items) if items.len() == 1 => items[0].fmt(f),
+            Multiple(ref items) => Error::fmt_numbered(items, &[], f),
             __NonExhaustive => unreachable!(),
         }
     }
@@ -98,7 +142,7 @@ impl fmt::Display for ErrorKind {
 
 impl From<ErrorUnknownField> for ErrorKind {
     fn from(err: ErrorUnknownField) -> Self {
-        ErrorKind::UnknownField(err)
+        ErrorKind::UnknownField(Box::new(err))
     }
 }
 
@@ -108,33 +152,85 @@ impl From<ErrorUnknownField> for ErrorKind {
 // Don't want to publicly commit to ErrorKind supporting equality yet, but
 // not having it makes testing very difficult.
 #[cfg_attr(test, derive(Clone, PartialEq, Eq))]
-pub(in crate::error) struct ErrorUnknownField {
+pub struct ErrorUnknownField {
     name: String,
     did_you_mean: Option<String>,
+    /// The names which would have been accepted, if known. This is boxed as a slice
+    /// to keep `Error` small.
+    alts: Box<[String]>,
 }
 
 impl ErrorUnknownField {
-    pub fn new<I: Into<String>>(name: I, did_you_mean: Option<String>) -> Self {
+    /// The unknown field name as it appeared in the input.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A similarly-named known field, if one was found.
+    pub fn did_you_mean(&self) -> Option<&str> {
+        self.did_you_mean.as_ref().map(String::as_str)
+    }
+
+    /// The names of the fields which would have been accepted, if known.
+    pub fn alternates(&self) -> &[String] {
+        &self.alts
+    }
+
+    pub(in crate::error) fn new<I: Into<String>>(name: I, did_you_mean: Option<String>) -> Self {
         ErrorUnknownField {
             name: name.into(),
             did_you_mean,
+            alts: Box::new([]),
         }
     }
 
-    pub fn with_alts<'a, T, I>(field: &str, alternates: I) -> Self
+    pub(in crate::error) fn with_alts<'a, T, I>(field: &str, alternates: I) -> Self
     where
         T: AsRef<str> + 'a,
         I: IntoIterator<Item = &'a T>,
     {
-        ErrorUnknownField::new(field, did_you_mean(field, alternates))
+        let alts: Vec<String> = alternates
+            .into_iter()
+            .map(|alt| alt.as_ref().to_string())
+            .collect();
+
+        ErrorUnknownField {
+            name: field.into(),
+            did_you_mean: did_you_mean(field, &alts),
+            alts: alts.into_boxed_slice(),
+        }
+    }
+
+    /// Renders the accepted names as `` `a`, `b` ``, or returns `None` if they aren't known.
+    #[cfg(feature = "diagnostics")]
+    fn expected(&self) -> Option<String> {
+        if self.alts.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.alts
+                .iter()
+                .map(|alt| format!("`{}`", alt))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
     }
 
     #[cfg(feature = "diagnostics")]
-    pub fn into_diagnostic(self, span: Option<::proc_macro2::Span>) -> ::proc_macro::Diagnostic {
+    pub(in crate::error) fn into_diagnostic(
+        self,
+        span: Option<::proc_macro2::Span>,
+    ) -> ::proc_macro::Diagnostic {
         let base = span
             .unwrap_or_else(::proc_macro2::Span::call_site)
             .unwrap()
             .error(self.top_line());
+        let base = match self.expected() {
+            Some(expected) => base.note(format!("expected one of {}", expected)),
+            None => base,
+        };
+
         match self.did_you_mean {
             Some(alt_name) => base.help(format!("did you mean `{}`?", alt_name)),
             None => base,
@@ -162,15 +258,94 @@ impl<'a> From<&'a str> for ErrorUnknownField {
 impl fmt::Display for ErrorUnknownField {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Unknown field: `{}`", self.name)?;
+        write_suggestions(f, self.did_you_mean(), &self.alts)
+    }
+}
+
+/// An error for a literal value which didn't match any accepted value, with a possible
+/// "did-you-mean" suggestion.
+#[derive(Debug)]
+#[cfg_attr(test, derive(Clone, PartialEq, Eq))]
+pub struct ErrorUnknownValue {
+    value: String,
+    /// The suggestion and the accepted values are boxed to keep `Error` small.
+    did_you_mean: Option<Box<str>>,
+    alts: Box<[String]>,
+}
+
+impl ErrorUnknownValue {
+    /// The unknown value as it appeared in the input.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// A similar accepted value, if one was found.
+    pub fn did_you_mean(&self) -> Option<&str> {
+        self.did_you_mean.as_ref().map(|s| &**s)
+    }
+
+    /// The values which would have been accepted, if known.
+    pub fn alternates(&self) -> &[String] {
+        &self.alts
+    }
 
-        if let Some(ref did_you_mean) = self.did_you_mean {
-            write!(f, ". Did you mean `{}`?", did_you_mean)?;
+    pub(in crate::error) fn new(value: &str) -> Self {
+        ErrorUnknownValue {
+            value: value.into(),
+            did_you_mean: None,
+            alts: Box::new([]),
         }
+    }
 
-        Ok(())
+    pub(in crate::error) fn with_alts<'a, T, I>(value: &str, alternates: I) -> Self
+    where
+        T: AsRef<str> + 'a,
+        I: IntoIterator<Item = &'a T>,
+    {
+        let alts: Vec<String> = alternates
+            .into_iter()
+            .map(|alt| alt.as_ref().to_string())
+            .collect();
+
+        ErrorUnknownValue {
+            value: value.into(),
+            did_you_mean: did_you_mean(value, &alts).map(String::into_boxed_str),
+            alts: alts.into_boxed_slice(),
+        }
+    }
+}
+
+impl fmt::Display for ErrorUnknownValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown literal value `{}`", self.value)?;
+        write_suggestions(f, self.did_you_mean(), &self.alts)
     }
 }
 
+/// Write the "did you mean" suggestion and list of accepted names which follow the first
+/// sentence of an unknown field or value error.
+fn write_suggestions(
+    f: &mut fmt::Formatter,
+    did_you_mean: Option<&str>,
+    alts: &[String],
+) -> fmt::Result {
+    if let Some(did_you_mean) = did_you_mean {
+        write!(f, ". Did you mean `{}`?", did_you_mean)?;
+    }
+
+    if !alts.is_empty() {
+        let separator = if did_you_mean.is_some() { " " } else { ". " };
+        let expected = alts
+            .iter()
+            .map(|alt| format!("`{}`", alt))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{}Expected one of {}", separator, expected)?;
+    }
+
+    Ok(())
+}
+
 #[cfg(feature = "suggestions")]
 fn did_you_mean<'a, T, I>(field: &str, alternates: I) -> Option<String>
 where
@@ -196,3 +371,100 @@ where
 {
     None
 }
+
+/// An error for a field which appeared under a name that has since been renamed.
+#[derive(Debug)]
+#[cfg_attr(test, derive(Clone, PartialEq, Eq))]
+pub struct ErrorRenamedField {
+    old: FieldName,
+    new: FieldName,
+    since: Option<String>,
+}
+
+impl ErrorRenamedField {
+    pub(in crate::error) fn new(old: &str, new: &str, since: Option<&str>) -> Self {
+        ErrorRenamedField {
+            old: old.into(),
+            new: new.into(),
+            since: since.map(Into::into),
+        }
+    }
+
+    /// The name which appeared in the input.
+    pub fn old(&self) -> &str {
+        &self.old
+    }
+
+    /// The name the field should now be written as.
+    pub fn new_name(&self) -> &str {
+        &self.new
+    }
+
+    /// The version in which the field was renamed, if known.
+    pub fn since(&self) -> Option<&str> {
+        self.since.as_ref().map(String::as_str)
+    }
+}
+
+impl fmt::Display for ErrorRenamedField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Field `{}` was renamed to `{}`", self.old, self.new)?;
+
+        if let Some(ref since) = self.since {
+            write!(f, " in version {}", since)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An error for a number which doesn't fit in the integer type it was read as.
+#[derive(Debug)]
+#[cfg_attr(test, derive(Clone, PartialEq, Eq))]
+pub struct ErrorOutOfRange {
+    value: String,
+    ty: String,
+    min: String,
+    max: String,
+}
+
+impl ErrorOutOfRange {
+    pub(in crate::error) fn new(value: &str, ty: &str, min: String, max: String) -> Self {
+        ErrorOutOfRange {
+            value: value.into(),
+            ty: ty.into(),
+            min,
+            max,
+        }
+    }
+
+    /// The number which appeared in the input.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// The name of the integer type the number was read as, such as `u8`.
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+
+    /// The smallest value of the integer type.
+    pub fn min(&self) -> &str {
+        &self.min
+    }
+
+    /// The largest value of the integer type.
+    pub fn max(&self) -> &str {
+        &self.max
+    }
+}
+
+impl fmt::Display for ErrorOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Value `{}` is out of range for `{}`: Expected {} to {}",
+            self.value, self.ty, self.min, self.max
+        )
+    }
+}
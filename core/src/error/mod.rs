@@ -1,4 +1,4 @@
-//! The `darling::Error` type, the multiple error `Accumulator`, and their internals.
+//! The `darling::Error` type, the multiple error `Accumulator`, non-fatal `Warning`s, and their internals.
 //!
 //! Error handling is one of the core values of `darling`; creating great errors is hard and
 //! never the reason that a proc-macro author started writing their crate. As a result, the
@@ -7,19 +7,29 @@
 //! them.
 
 use proc_macro2::{Span, TokenStream};
+use std::any::Any;
 use std::error::Error as StdError;
 use std::fmt;
 use std::iter::{self, Iterator};
+use std::rc::Rc;
 use std::string::ToString;
 use std::vec;
 use syn::spanned::Spanned;
 use syn::{Lit, LitStr, Path};
 
 mod kind;
+mod warning;
 
-use crate::util::path_to_string;
+use crate::util::{expr_value, path_to_string};
 
-use self::kind::{ErrorKind, ErrorUnknownField};
+pub use self::kind::{
+    ErrorKind, ErrorOutOfRange, ErrorRenamedField, ErrorUnknownField, ErrorUnknownValue,
+};
+pub use self::warning::Warning;
+
+/// The message attached to the earlier location referenced by an error.
+#[cfg(feature = "diagnostics")]
+const FIRST_SPAN_NOTE: &str = "first used here";
 
 /// An alias of `Result` specific to attribute parsing.
 pub type Result<T> = ::std::result::Result<T, Error>;
@@ -42,10 +52,11 @@ pub type Result<T> = ::std::result::Result<T, Error>;
 /// These characteristics make for great experiences when using darling-powered crates,
 /// provided crates using darling adhere to some best practices:
 ///
-/// 1. Do not attempt to simplify a `darling::Error` into some other error type, such as
-///    `syn::Error`. To surface compile errors, instead use `darling::Error::write_errors`.
-///    This preserves all span information, suggestions, etc. Wrapping a `darling::Error` in
-///    a custom error enum works as-expected and does not force any loss of fidelity.
+/// 1. Do not attempt to simplify a `darling::Error` into some other error type. To surface
+///    compile errors, instead use `darling::Error::write_errors`. This preserves all span
+///    information, suggestions, etc. Wrapping a `darling::Error` in a custom error enum works
+///    as-expected and does not force any loss of fidelity. Converting into a `syn::Error` keeps
+///    every error and its span, but can't carry the notes of the `diagnostics` feature.
 /// 2. Do not use early return (e.g. the `?` operator) for custom validations. Instead,
 ///    create an [`error::Accumulator`](Accumulator) to collect errors as they are encountered.  Then use
 ///    [`Accumulator::finish`] to return your validated result; it will give `Ok` if and only if
@@ -63,6 +74,22 @@ pub struct Error {
     locations: Vec<String>,
     /// The span to highlight in the emitted diagnostic.
     span: Option<Span>,
+    /// The span of an earlier part of the input which the error refers back to, such as
+    /// the first occurrence of a duplicated field.
+    first_span: Option<Span>,
+    /// Notes and caller-defined data, which most errors don't have.
+    /// This is boxed to keep `Error` small.
+    context: Option<Box<Context>>,
+}
+
+/// The parts of an [`Error`] which are only set on some errors.
+#[derive(Debug, Default)]
+#[cfg_attr(test, derive(Clone))]
+struct Context {
+    /// Lines shown after the message, each with its label such as `help`.
+    notes: Vec<(&'static str, String)>,
+    /// Caller-defined data attached with `with_payload`.
+    payload: Option<Rc<dyn Any>>,
 }
 
 /// Error creation functions
@@ -72,6 +99,8 @@ impl Error {
             kind,
             locations: Vec::new(),
             span: None,
+            first_span: None,
+            context: None,
         }
     }
 
@@ -85,6 +114,52 @@ impl Error {
         Error::new(ErrorKind::DuplicateField(name.into()))
     }
 
+    /// Creates a new error for a field that appears twice in the input, pointing at the `second`
+    /// occurrence. Emitted diagnostics will also point back at the `first` occurrence.
+    pub fn duplicate_field_with_spans<T: Spanned, U: Spanned>(
+        name: &str,
+        first: &T,
+        second: &U,
+    ) -> Self {
+        Error::duplicate_field(name)
+            .with_span(second)
+            .with_first_span(first.span())
+    }
+
+    /// Creates a new error for a field `name` which is set under two of its names, such as
+    /// an alias and the name it stands for. The error points at the `second` key, and emitted
+    /// diagnostics will also point back at the `first` one.
+    pub fn conflicting_keys_with_spans(name: &str, first: &Path, second: &Path) -> Self {
+        Error::conflicting_keys(name, &path_to_string(first), &path_to_string(second))
+            .with_span(second)
+            .with_first_span(first.span())
+    }
+
+    /// Creates a new error for a field `name` which is set by both the keys `first` and `second`.
+    pub(crate) fn conflicting_keys(name: &str, first: &str, second: &str) -> Self {
+        Error::custom(format!(
+            "Keys `{}` and `{}` both set the field `{}`; use only one of them",
+            first, second, name
+        ))
+    }
+
+    /// Note the span of an earlier occurrence of whatever this error reports, such as the first
+    /// use of a duplicated field.
+    pub(crate) fn with_first_span(self, span: Span) -> Self {
+        Error {
+            first_span: Some(span),
+            ..self
+        }
+    }
+
+    /// The span of the earlier occurrence noted by errors such as
+    /// [`duplicate_field_with_spans`](Error::duplicate_field_with_spans), if any. With the
+    /// `diagnostics` feature, it is emitted as a note on the error; otherwise, only the error's
+    /// own span is emitted.
+    pub fn first_span(&self) -> Option<Span> {
+        self.first_span
+    }
+
     /// Creates a new error for a field that appears twice in the input. Helper to avoid repeating
     /// the syn::Path to String conversion.
     pub fn duplicate_field_path(path: &Path) -> Self {
@@ -99,7 +174,7 @@ impl Error {
     /// Creates a new error for a field name that appears in the input but does not correspond
     /// to a known field.
     pub fn unknown_field(name: &str) -> Self {
-        Error::new(ErrorKind::UnknownField(name.into()))
+        Error::new(ErrorKind::UnknownField(Box::new(name.into())))
     }
 
     /// Creates a new error for a field name that appears in the input but does not correspond
@@ -109,8 +184,8 @@ impl Error {
     }
 
     /// Creates a new error for a field name that appears in the input but does not correspond to
-    /// a known attribute. The second argument is the list of known attributes; if a similar name
-    /// is found that will be shown in the emitted error message.
+    /// a known attribute. The second argument is the list of known attributes, which will be
+    /// listed in the emitted error message. If a similar name is found, it will also be suggested.
     pub fn unknown_field_with_alts<'a, T, I>(field: &str, alternates: I) -> Self
     where
         T: AsRef<str> + 'a,
@@ -119,9 +194,35 @@ impl Error {
         Error::new(ErrorUnknownField::with_alts(field, alternates).into())
     }
 
+    /// Creates a new error for a field that appears in the input but is marked `skip`, so its
+    /// value is computed internally rather than read.
+    pub fn skipped_field(name: &str) -> Self {
+        Error::new(ErrorKind::SkippedField(name.into()))
+    }
+
+    /// Creates a new error for a field that appears in the input under a name which has been
+    /// renamed to `new`. If known, `since` is the version in which the rename happened.
+    pub fn renamed_field(old: &str, new: &str, since: Option<&str>) -> Self {
+        Error::new(ErrorKind::RenamedField(Box::new(ErrorRenamedField::new(
+            old, new, since,
+        ))))
+    }
+
     /// Creates a new error for a struct or variant that does not adhere to the supported shape.
     pub fn unsupported_shape(shape: &str) -> Self {
-        Error::new(ErrorKind::UnsupportedShape(shape.into()))
+        Error::new(ErrorKind::UnsupportedShape {
+            observed: shape.into(),
+            expected: None,
+        })
+    }
+
+    /// Creates a new error for a struct or variant that does not adhere to the supported shape,
+    /// naming the shapes that would have been accepted.
+    pub fn unsupported_shape_with_expected<T: fmt::Display>(shape: &str, expected: &T) -> Self {
+        Error::new(ErrorKind::UnsupportedShape {
+            observed: shape.into(),
+            expected: Some(expected.to_string()),
+        })
     }
 
     pub fn unsupported_format(format: &str) -> Self {
@@ -171,6 +272,7 @@ impl Error {
             Lit::Int(_) => "int",
             Lit::Float(_) => "float",
             Lit::Bool(_) => "bool",
+            Lit::Verbatim(_) if expr_value(lit).is_some() => "expression",
             Lit::Verbatim(_) => "verbatim",
         })
         .with_span(lit)
@@ -178,7 +280,20 @@ impl Error {
 
     /// Creates a new error for a value which doesn't match a set of expected literals.
     pub fn unknown_value(value: &str) -> Self {
-        Error::new(ErrorKind::UnknownValue(value.into()))
+        Error::new(ErrorKind::UnknownValue(ErrorUnknownValue::new(value)))
+    }
+
+    /// Creates a new error for a value which doesn't match any of `alternates`. The accepted
+    /// values will be listed in the emitted error message, along with a similar value if one
+    /// is found.
+    pub fn unknown_value_with_alts<'a, T, I>(value: &str, alternates: I) -> Self
+    where
+        T: AsRef<str> + 'a,
+        I: IntoIterator<Item = &'a T>,
+    {
+        Error::new(ErrorKind::UnknownValue(ErrorUnknownValue::with_alts(
+            value, alternates,
+        )))
     }
 
     /// Creates a new error for a list which did not get enough items to proceed.
@@ -192,6 +307,23 @@ impl Error {
         Error::new(ErrorKind::TooManyItems(max))
     }
 
+    /// Creates a new error for a number which doesn't fit in the integer type `ty`, whose
+    /// values run from `min` to `max`.
+    pub fn out_of_range<T: fmt::Display>(value: &str, ty: &str, min: T, max: T) -> Self {
+        Error::new(ErrorKind::OutOfRange(Box::new(ErrorOutOfRange::new(
+            value,
+            ty,
+            min.to_string(),
+            max.to_string(),
+        ))))
+    }
+
+    /// Creates a new error when meta lists are nested more deeply than the receiver allows.
+    /// The `max` argument is the deepest nesting the receiver could accept.
+    pub fn nesting_limit_exceeded(max: usize) -> Self {
+        Error::new(ErrorKind::NestingLimitExceeded(max))
+    }
+
     /// Bundle a set of multiple errors into a single `Error` instance.
     ///
     /// Usually it will be more convenient to use an [`error::Accumulator`](Accumulator).
@@ -233,10 +365,76 @@ impl Error {
         self.span.is_some()
     }
 
+    /// Attach caller-defined data to this error, replacing any existing payload. Frameworks
+    /// built on darling can use this to carry a machine-readable code or other context
+    /// alongside the message, and read it back with [`Error::payload`].
+    ///
+    /// The payload belongs to this error only; it is not copied to the children of a
+    /// combined error when that error is flattened.
+    pub fn with_payload<P: Any>(mut self, payload: P) -> Self {
+        self.context_mut().payload = Some(Rc::new(payload));
+        self
+    }
+
+    /// Get the payload attached with [`Error::with_payload`], if there is one of type `P`.
+    pub fn payload<P: Any>(&self) -> Option<&P> {
+        self.context
+            .as_ref()
+            .and_then(|context| context.payload.as_ref())
+            .and_then(|payload| payload.downcast_ref::<P>())
+    }
+
+    /// Add a suggestion of how to fix the error, shown below the message. With the
+    /// `diagnostics` feature, it is emitted as a help message on the error.
+    pub fn help<T: fmt::Display>(self, help: T) -> Self {
+        self.with_note("help", help.to_string())
+    }
+
+    /// Add more context about the error, shown below the message. With the `diagnostics`
+    /// feature, it is emitted as a note on the error.
+    pub fn note<T: fmt::Display>(self, note: T) -> Self {
+        self.with_note("note", note.to_string())
+    }
+
+    fn with_note(mut self, label: &'static str, text: String) -> Self {
+        self.context_mut().notes.push((label, text));
+        self
+    }
+
+    fn context_mut(&mut self) -> &mut Context {
+        self.context.get_or_insert_with(Default::default)
+    }
+
+    /// The notes added with [`help`](Error::help) and [`note`](Error::note), in order.
+    fn notes(&self) -> &[(&'static str, String)] {
+        match self.context {
+            Some(ref context) => &context.notes,
+            None => &[],
+        }
+    }
+
+    /// Write each note on its own line after the message.
+    fn fmt_notes(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &(label, ref text) in self.notes() {
+            write!(f, "\n{}: {}", label, text)?;
+        }
+
+        Ok(())
+    }
+
+    /// The category of this error, for callers which need to handle some kinds of errors
+    /// differently. An error combining several others has the kind `ErrorKind::Multiple`.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
     /// Tie a span to the error if none is already present. This is used in `darling::FromMeta`
     /// and other traits to attach errors to the most specific possible location in the input
     /// source code.
     ///
+    /// An error combining several others passes the span on to each of them which doesn't
+    /// have one, so an error such as a missing field inside a nested list points at that list.
+    ///
     /// All `darling`-built impls, either from the crate or from the proc macro, will call this
     /// when appropriate during parsing, so it should not be necessary to call this unless you have
     /// overridden:
@@ -245,13 +443,38 @@ impl Error {
     /// * `FromMeta::from_nested_meta`
     /// * `FromMeta::from_value`
     pub fn with_span<T: Spanned>(mut self, node: &T) -> Self {
+        self.fill_span(node.span());
+        self
+    }
+
+    fn fill_span(&mut self, span: Span) {
         if !self.has_span() {
-            self.span = Some(node.span());
+            self.span = Some(span);
+        }
+
+        if let ErrorKind::Multiple(ref mut errors) = self.kind {
+            for error in errors {
+                error.fill_span(span);
+            }
         }
+    }
 
+    /// Point this error, and every error it contains, at `node`, replacing any spans
+    /// they already have.
+    pub fn with_span_override<T: Spanned>(mut self, node: &T) -> Self {
+        self.override_span(node.span());
         self
     }
 
+    fn override_span(&mut self, span: Span) {
+        self.span = Some(span);
+        if let ErrorKind::Multiple(ref mut errors) = self.kind {
+            for error in errors {
+                error.override_span(span);
+            }
+        }
+    }
+
     /// Recursively converts a tree of errors to a flattened list.
     pub fn flatten(self) -> Self {
         Error::multiple(self.into_vec())
@@ -328,25 +551,39 @@ impl Error {
     /// }
     /// ```
     pub fn write_errors(self) -> TokenStream {
+        // Diagnostics can only be emitted from inside a running proc-macro; elsewhere, such
+        // as in `expand_str` or unit tests, fall back to `compile_error!` invocations.
         #[cfg(feature = "diagnostics")]
         {
+            if !::proc_macro::is_available() {
+                return self.write_compile_errors();
+            }
+
             self.emit();
             quote!()
         }
 
         #[cfg(not(feature = "diagnostics"))]
         {
-            self.flatten()
-                .into_iter()
-                .map(|e| e.single_to_syn_error().to_compile_error())
-                .collect()
+            self.write_compile_errors()
         }
     }
 
-    #[cfg(not(feature = "diagnostics"))]
+    /// Transform this error and its children into `compile_error!` invocations, even when
+    /// the `diagnostics` feature is enabled.
+    pub(crate) fn write_compile_errors(self) -> TokenStream {
+        self.flatten()
+            .into_iter()
+            .map(|e| e.single_to_syn_error().to_compile_error())
+            .collect()
+    }
+
+    /// Convert a single error to a `syn::Error`. A `syn::Error` can't hold notes, so the first
+    /// span of a duplicate isn't included: adding it as a second message would make the
+    /// compiler report one mistake twice.
     fn single_to_syn_error(self) -> ::syn::Error {
         match self.span {
-            Some(span) => ::syn::Error::new(span, self.kind),
+            Some(span) => ::syn::Error::new(span, SpannedMessage(&self)),
             None => ::syn::Error::new(Span::call_site(), self),
         }
     }
@@ -359,12 +596,31 @@ impl Error {
         //
         // If span information is available, don't include the error property path
         // since it's redundant and not consistent with native compiler diagnostics.
-        match self.kind {
-            ErrorKind::UnknownField(euf) => euf.into_diagnostic(self.span),
+        let first_span = self.first_span;
+        let notes = self
+            .context
+            .as_ref()
+            .map(|context| context.notes.clone())
+            .unwrap_or_default();
+        let mut diagnostic = match self.kind {
+            ErrorKind::UnknownField(euf) => (*euf).into_diagnostic(self.span),
             _ => match self.span {
                 Some(span) => span.unwrap().error(self.kind.to_string()),
                 None => Diagnostic::new(Level::Error, self.to_string()),
             },
+        };
+
+        for (label, text) in notes {
+            diagnostic = if label == "help" {
+                diagnostic.help(text)
+            } else {
+                diagnostic.note(text)
+            };
+        }
+
+        match first_span {
+            Some(first_span) => diagnostic.span_note(first_span.unwrap(), FIRST_SPAN_NOTE),
+            None => diagnostic,
         }
     }
 
@@ -417,30 +673,111 @@ impl StdError for Error {
     }
 }
 
+impl Error {
+    /// Write `items` as a numbered list with one line per leaf error. Each line shows the
+    /// full location of its error, starting with `prefix`.
+    fn fmt_numbered(items: &[Error], prefix: &[&str], f: &mut fmt::Formatter) -> fmt::Result {
+        let mut leaves = Vec::new();
+        for item in items {
+            item.collect_leaves(prefix.to_vec(), &mut leaves);
+        }
+
+        write!(f, "Multiple errors:")?;
+        for (index, (locations, leaf)) in leaves.into_iter().enumerate() {
+            write!(f, "\n{}. {}", index + 1, leaf.kind)?;
+            if !locations.is_empty() {
+                write!(f, " at {}", locations.join("/"))?;
+            }
+            leaf.fmt_notes(f)?;
+        }
+
+        Ok(())
+    }
+
+    /// Gather every non-`Multiple` error in this tree, along with its full location.
+    fn collect_leaves<'a>(
+        &'a self,
+        mut locations: Vec<&'a str>,
+        leaves: &mut Vec<(Vec<&'a str>, &'a Error)>,
+    ) {
+        locations.extend(self.locations.iter().map(String::as_str));
+        match self.kind {
+            ErrorKind::Multiple(ref items) => {
+                for item in items {
+                    item.collect_leaves(locations.clone(), leaves);
+                }
+            }
+            _ => leaves.push((locations, self)),
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let ErrorKind::Multiple(ref items) = self.kind {
+            if self.len() > 1 {
+                let prefix: Vec<&str> = self.locations.iter().map(String::as_str).collect();
+                return Error::fmt_numbered(items, &prefix, f);
+            }
+        }
+
         write!(f, "{}", self.kind)?;
         if !self.locations.is_empty() {
             write!(f, " at {}", self.locations.join("/"))?;
         }
 
-        Ok(())
+        self.fmt_notes(f)
+    }
+}
+
+/// The message of an error whose span is known, which leaves out the locations that the
+/// span already shows.
+struct SpannedMessage<'a>(&'a Error);
+
+impl<'a> fmt::Display for SpannedMessage<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.kind)?;
+        self.0.fmt_notes(f)
     }
 }
 
 impl From<syn::Error> for Error {
     fn from(e: syn::Error) -> Self {
-        // This impl assumes there is nothing but the message and span that needs to be preserved
-        // from the passed-in error. If this changes at some point, a new ErrorKind should be made
-        // to hold the syn::Error, and this impl should preserve it unmodified while setting its own
-        // span to be a copy of the passed-in error.
-        Self {
-            span: Some(e.span()),
-            ..Self::custom(e)
+        // A `syn::Error` can hold several messages which have been combined, each with its own
+        // span, so each becomes its own error. This assumes there is nothing but the message and
+        // span that needs to be preserved from them. If this changes at some point, a new
+        // ErrorKind should be made to hold the syn::Error, and this impl should preserve it
+        // unmodified while setting its own span to be a copy of the passed-in error.
+        let mut errors: Vec<Error> = e
+            .into_iter()
+            .map(|e| Self {
+                span: Some(e.span()),
+                ..Self::custom(e)
+            })
+            .collect();
+
+        if errors.len() == 1 {
+            errors.remove(0)
+        } else {
+            Self::multiple(errors)
         }
     }
 }
 
+/// Every error is kept, combined into one `syn::Error` in the order `flatten` gives them, and
+/// rendered as `write_errors` would render it without the `diagnostics` feature.
+impl From<Error> for syn::Error {
+    fn from(e: Error) -> Self {
+        let mut errors = e.flatten().into_iter().map(Error::single_to_syn_error);
+        let mut combined = errors.next().expect("darling::Error can never be empty");
+        for error in errors {
+            combined.combine(error);
+        }
+
+        combined
+    }
+}
+
 // Don't want to publicly commit to Error supporting equality yet, but
 // not having it makes testing very difficult. Note that spans are not
 // considered for equality since that would break testing in most cases.
@@ -587,7 +924,7 @@ impl Accumulator {
     /// This function defuses the drop bomb.
     #[must_use = "Accumulated errors should be handled or propagated to the caller"]
     pub fn into_inner(mut self) -> Vec<Error> {
-        match std::mem::replace(&mut self.0, None) {
+        match self.0.take() {
             Some(errors) => errors,
             None => panic!("darling internal error: Accumulator accessed after defuse"),
         }
@@ -673,7 +1010,10 @@ impl Drop for Accumulator {
 
 #[cfg(test)]
 mod tests {
-    use super::Error;
+    use proc_macro2::Span;
+
+    use super::{Error, ErrorKind};
+    use crate::util::SpannedValue;
 
     #[test]
     fn flatten_noop() {
@@ -706,6 +1046,137 @@ mod tests {
         assert!(err_iter.next().is_none());
     }
 
+    #[test]
+    fn unknown_field_lists_alternates() {
+        let err = Error::unknown_field_with_alts("xyz", &["ipsum", "dolor"]);
+        assert_eq!(
+            err.to_string(),
+            "Unknown field: `xyz`. Expected one of `ipsum`, `dolor`"
+        );
+    }
+
+    #[test]
+    fn unknown_value_lists_alternates() {
+        assert_eq!(
+            Error::unknown_value_with_alts("exec", &["read", "write"]).to_string(),
+            "Unknown literal value `exec`. Expected one of `read`, `write`"
+        );
+        assert_eq!(
+            Error::unknown_value("exec").to_string(),
+            "Unknown literal value `exec`"
+        );
+    }
+
+    #[test]
+    fn renamed_field_display() {
+        assert_eq!(
+            Error::renamed_field("with_fn", "with", Some("0.2")).to_string(),
+            "Field `with_fn` was renamed to `with` in version 0.2"
+        );
+        assert_eq!(
+            Error::renamed_field("with_fn", "with", None).to_string(),
+            "Field `with_fn` was renamed to `with`"
+        );
+    }
+
+    #[test]
+    fn notes_follow_the_message() {
+        let err = Error::missing_field("format")
+            .note("An export needs a format")
+            .help("add `format = \"json\"`")
+            .at("export");
+        assert_eq!(err.kind(), &ErrorKind::MissingField("format".into()));
+        assert_eq!(
+            err.to_string(),
+            "Missing field `format` at export\nnote: An export needs a format\nhelp: add `format = \"json\"`"
+        );
+
+        let spanned = syn::Error::from(err.with_span(&Span::call_site()));
+        assert_eq!(
+            spanned.to_string(),
+            "Missing field `format`\nnote: An export needs a format\nhelp: add `format = \"json\"`"
+        );
+    }
+
+    #[cfg(feature = "suggestions")]
+    #[test]
+    fn unknown_field_lists_alternates_after_suggestion() {
+        let err = Error::unknown_field_with_alts("dolorr", &["ipsum", "dolor"]);
+        assert_eq!(
+            err.to_string(),
+            "Unknown field: `dolorr`. Did you mean `dolor`? Expected one of `ipsum`, `dolor`"
+        );
+    }
+
+    #[cfg(not(feature = "diagnostics"))]
+    #[test]
+    fn duplicate_field_is_one_error() {
+        let first: syn::Path = syn::parse_quote!(hello);
+        let second: syn::Path = syn::parse_quote!(hello);
+        let err = Error::duplicate_field_with_spans("hello", &first, &second);
+        assert_eq!(err, Error::duplicate_field("hello"));
+
+        assert!(err.first_span().is_some());
+
+        let tokens = err.write_errors().to_string();
+        assert_eq!(tokens.matches("compile_error").count(), 1);
+        assert!(!tokens.contains("first used here"));
+
+        let err = syn::Error::from(Error::duplicate_field_with_spans("hello", &first, &second));
+        assert_eq!(err.into_iter().count(), 1);
+    }
+
+    #[test]
+    fn payload_round_trip() {
+        #[derive(Debug, PartialEq)]
+        struct Code(u16);
+
+        let err = Error::custom("Bad").with_payload(Code(42));
+        assert_eq!(err.payload::<Code>(), Some(&Code(42)));
+        assert_eq!(err.payload::<String>(), None);
+        assert_eq!(Error::custom("Bad").payload::<Code>(), None);
+        assert_eq!(err.at("foo").payload::<Code>(), Some(&Code(42)));
+    }
+
+    #[test]
+    fn span_override_applies_to_children() {
+        use crate::util::SpannedValue;
+        use proc_macro2::Span;
+
+        let err = Error::multiple(vec![
+            Error::custom("Oops").with_span(&SpannedValue::new((), Span::call_site())),
+            Error::custom("Whoops"),
+        ])
+        .with_span_override(&SpannedValue::new((), Span::call_site()));
+
+        assert!(err.has_span());
+        assert!(err.into_iter().all(|e| e.has_span()));
+    }
+
+    #[test]
+    fn display_multiple_as_numbered_list() {
+        let err = Error::multiple(vec![
+            Error::unknown_field("hello").at("world"),
+            Error::multiple(vec![Error::missing_field("hell_no"), Error::custom("Oops")])
+                .at("nested"),
+        ])
+        .at("foo");
+
+        assert_eq!(
+            err.to_string(),
+            "Multiple errors:\n\
+             1. Unknown field: `hello` at foo/world\n\
+             2. Missing field `hell_no` at foo/nested\n\
+             3. Oops at foo/nested"
+        );
+    }
+
+    #[test]
+    fn display_multiple_with_one_error() {
+        let err = Error::multiple(vec![Error::custom("Oops").at("world")]).at("foo");
+        assert_eq!(err.to_string(), "Oops at foo/world");
+    }
+
     #[test]
     fn len_single() {
         let err = Error::duplicate_field("hello");
@@ -784,4 +1255,39 @@ mod tests {
         errs = errs.checkpoint().unwrap();
         let _ = errs;
     }
+
+    #[test]
+    fn from_combined_syn_error() {
+        let mut combined = syn::Error::new(Span::call_site(), "first");
+        combined.combine(syn::Error::new(Span::call_site(), "second"));
+
+        let err = Error::from(combined);
+        assert_eq!(err.len(), 2);
+        let errors: Vec<Error> = err.into_iter().collect();
+        assert!(errors.iter().all(Error::has_span));
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(messages, vec!["first", "second"]);
+    }
+
+    #[test]
+    fn into_syn_error() {
+        let err = Error::multiple(vec![
+            Error::custom("first").at("a"),
+            Error::unknown_field("b").with_span(&SpannedValue::new((), Span::call_site())),
+        ]);
+
+        let messages: Vec<String> = syn::Error::from(err)
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(messages, vec!["first at a", "Unknown field: `b`"]);
+    }
+
+    #[test]
+    fn syn_error_round_trip() {
+        let original = Error::custom("nope").with_span(&SpannedValue::new((), Span::call_site()));
+        let err = Error::from(syn::Error::from(original));
+        assert_eq!(err.to_string(), "nope");
+        assert!(err.has_span());
+    }
 }
@@ -0,0 +1,122 @@
+use std::fmt;
+
+use proc_macro2::{Span, TokenStream};
+use syn::spanned::Spanned;
+
+use crate::util::hidden_item_ident;
+
+/// A non-fatal problem found while parsing, such as the use of a deprecated option.
+///
+/// Unlike an [`Error`](super::Error), a warning doesn't stop parsing. Derived impls push
+/// warnings to the collector passed to `FromMeta::from_meta_with_warnings` or
+/// `FromMeta::from_list_with_warnings`, and the top-level traits store them in a field named
+/// `warnings` when the container sets `#[darling(forward_warnings)]`. The proc-macro then
+/// adds [`write_warning`](Warning::write_warning) to its output so the compiler shows them.
+#[derive(Debug, Clone)]
+pub struct Warning {
+    message: String,
+    span: Option<Span>,
+}
+
+impl Warning {
+    /// Creates a new warning with the given message.
+    pub fn new<T: fmt::Display>(message: T) -> Self {
+        Warning {
+            message: message.to_string(),
+            span: None,
+        }
+    }
+
+    /// Creates a new warning for the use of a deprecated field or variant. The `note`
+    /// typically tells the caller what to use instead.
+    pub fn deprecated(name: &str, note: &str) -> Self {
+        Warning::new(format!("`{}` is deprecated: {}", name, note))
+    }
+
+    /// Creates a new warning for a value given for a field marked `skip`, which is ignored.
+    pub fn skipped_field(name: &str) -> Self {
+        Warning::new(format!(
+            "`{}` is ignored because it is computed internally",
+            name
+        ))
+    }
+
+    /// Adds a span to the warning if it doesn't already have one.
+    pub fn with_span<T: Spanned>(mut self, node: &T) -> Self {
+        if self.span.is_none() {
+            self.span = Some(node.span());
+        }
+
+        self
+    }
+
+    /// The text of the warning.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The span the warning should point at, if one was attached.
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Write the warning as an item which makes the compiler emit a deprecation warning with
+    /// this message at the warning's span. Proc-macros add this to the code they generate,
+    /// which works on stable compilers.
+    pub fn write_warning(&self) -> TokenStream {
+        let message = &self.message;
+        let span = self.span.unwrap_or_else(Span::call_site);
+        let warning = quote_spanned!(span=> warning);
+        let name = hidden_item_ident("WARNING", &format!("{}{:?}", message, span));
+        quote! {
+            #[allow(dead_code)]
+            const #name: () = {
+                #[deprecated(note = #message)]
+                #[allow(non_upper_case_globals)]
+                const warning: () = ();
+                #warning
+            };
+        }
+    }
+
+    /// Emit the warning as a compiler diagnostic.
+    ///
+    /// # Stability
+    /// This is only available on `nightly` until the compiler `proc_macro_diagnostic`
+    /// feature stabilizes. Until then, it may break at any time.
+    #[cfg(feature = "diagnostics")]
+    pub fn emit(self) {
+        use proc_macro::{Diagnostic, Level};
+
+        match self.span {
+            Some(span) => span.unwrap().warning(self.message).emit(),
+            None => Diagnostic::new(Level::Warning, self.message).emit(),
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Warning;
+
+    #[test]
+    fn written_as_deprecated_item() {
+        let tokens = Warning::new("stop").write_warning().to_string();
+        assert!(tokens.contains("deprecated (note = \"stop\")"));
+        syn::parse2::<syn::Item>(Warning::new("stop").write_warning()).unwrap();
+    }
+
+    #[test]
+    fn deprecated_message() {
+        assert_eq!(
+            Warning::deprecated("with_fn", "use `with` instead").to_string(),
+            "`with_fn` is deprecated: use `with` instead"
+        );
+    }
+}
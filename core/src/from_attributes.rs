@@ -1,4 +1,5 @@
-use syn::Attribute;
+use syn::parse::Parser;
+use syn::{Attribute, ImplItem, Item, ItemFn, TraitItem};
 
 use crate::Result;
 
@@ -23,5 +24,82 @@ pub trait FromAttributes: Sized {
     /// #[serde(borrow, rename_all = "camel_case")]
     /// pub struct JoinedExample {}
     /// ```
+    ///
+    /// Inner attributes such as `#![my_attr(...)]` are read the same way as outer ones, so
+    /// the attributes of a module or crate can be passed here directly.
     fn from_attributes(attrs: &[Attribute]) -> Result<Self>;
+
+    /// Create an instance from the source text of a list of outer attributes, such as
+    /// `#[my_attr(a = 1)] #[my_attr(b)]`. This is meant for unit tests of option types.
+    fn from_attributes_str(source: &str) -> Result<Self> {
+        Self::from_attributes(&Attribute::parse_outer.parse_str(source)?)
+    }
+
+    /// Create an instance from the attributes of a free function.
+    fn from_item_fn(item: &ItemFn) -> Result<Self> {
+        Self::from_attributes(&item.attrs)
+    }
+
+    /// Create an instance from the attributes of an item in an `impl` block.
+    ///
+    /// Verbatim items carry no parsed attributes, so they are treated as having none.
+    fn from_impl_item(item: &ImplItem) -> Result<Self> {
+        Self::from_attributes(impl_item_attrs(item))
+    }
+
+    /// Create an instance from the attributes of an item in a trait definition.
+    ///
+    /// Verbatim items carry no parsed attributes, so they are treated as having none.
+    fn from_trait_item(item: &TraitItem) -> Result<Self> {
+        Self::from_attributes(trait_item_attrs(item))
+    }
+
+    /// Create an instance from the attributes of any module-level item.
+    ///
+    /// Verbatim items carry no parsed attributes, so they are treated as having none.
+    fn from_item(item: &Item) -> Result<Self> {
+        Self::from_attributes(item_attrs(item))
+    }
+}
+
+fn impl_item_attrs(item: &ImplItem) -> &[Attribute] {
+    match *item {
+        ImplItem::Const(ref item) => &item.attrs,
+        ImplItem::Method(ref item) => &item.attrs,
+        ImplItem::Type(ref item) => &item.attrs,
+        ImplItem::Macro(ref item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+fn trait_item_attrs(item: &TraitItem) -> &[Attribute] {
+    match *item {
+        TraitItem::Const(ref item) => &item.attrs,
+        TraitItem::Method(ref item) => &item.attrs,
+        TraitItem::Type(ref item) => &item.attrs,
+        TraitItem::Macro(ref item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+fn item_attrs(item: &Item) -> &[Attribute] {
+    match *item {
+        Item::Const(ref item) => &item.attrs,
+        Item::Enum(ref item) => &item.attrs,
+        Item::ExternCrate(ref item) => &item.attrs,
+        Item::Fn(ref item) => &item.attrs,
+        Item::ForeignMod(ref item) => &item.attrs,
+        Item::Impl(ref item) => &item.attrs,
+        Item::Macro(ref item) => &item.attrs,
+        Item::Macro2(ref item) => &item.attrs,
+        Item::Mod(ref item) => &item.attrs,
+        Item::Static(ref item) => &item.attrs,
+        Item::Struct(ref item) => &item.attrs,
+        Item::Trait(ref item) => &item.attrs,
+        Item::TraitAlias(ref item) => &item.attrs,
+        Item::Type(ref item) => &item.attrs,
+        Item::Union(ref item) => &item.attrs,
+        Item::Use(ref item) => &item.attrs,
+        _ => &[],
+    }
 }
@@ -0,0 +1,32 @@
+use syn::ConstParam;
+
+use crate::Result;
+
+/// Creates an instance by parsing an individual const param and its attributes.
+pub trait FromConstParam: Sized {
+    fn from_const_param(const_param: &ConstParam) -> Result<Self>;
+}
+
+impl FromConstParam for () {
+    fn from_const_param(_: &ConstParam) -> Result<Self> {
+        Ok(())
+    }
+}
+
+impl FromConstParam for ConstParam {
+    fn from_const_param(const_param: &ConstParam) -> Result<Self> {
+        Ok(const_param.clone())
+    }
+}
+
+impl FromConstParam for Vec<syn::Attribute> {
+    fn from_const_param(const_param: &ConstParam) -> Result<Self> {
+        Ok(const_param.attrs.clone())
+    }
+}
+
+impl FromConstParam for syn::Ident {
+    fn from_const_param(const_param: &ConstParam) -> Result<Self> {
+        Ok(const_param.ident.clone())
+    }
+}
@@ -11,6 +11,12 @@ use crate::Result;
 pub trait FromDeriveInput: Sized {
     /// Create an instance from `syn::DeriveInput`, or return an error.
     fn from_derive_input(input: &DeriveInput) -> Result<Self>;
+
+    /// Create an instance from the source text of a struct, enum or union, including its
+    /// attributes. This is meant for unit tests of option types.
+    fn from_derive_input_str(source: &str) -> Result<Self> {
+        Self::from_derive_input(&syn::parse_str(source)?)
+    }
 }
 
 impl FromDeriveInput for () {
@@ -5,6 +5,17 @@ use crate::Result;
 /// Creates an instance by parsing an individual field and its attributes.
 pub trait FromField: Sized {
     fn from_field(field: &Field) -> Result<Self>;
+
+    /// Create an instance from the field at position `index` of its parent, such as the `1`
+    /// of a tuple struct's `self.1`. Collections of fields like [`ast::Fields`](crate::ast::Fields)
+    /// read each field this way. The index is `None` if the position isn't known, which is
+    /// how derived impls read a field passed to `from_field` directly.
+    ///
+    /// The default ignores the position and calls [`FromField::from_field`].
+    fn from_field_at(field: &Field, index: Option<usize>) -> Result<Self> {
+        let _ = index;
+        Self::from_field(field)
+    }
 }
 
 impl FromField for () {
@@ -7,9 +7,13 @@ use std::rc::Rc;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
+use proc_macro2::TokenStream;
+use quote::ToTokens;
 use syn::{Expr, Lit, Meta, NestedMeta};
 
-use crate::{util::path_to_string, Error, Result};
+use crate::error::Warning;
+use crate::util::{expr_value, non_finite, parse_attribute_args, parse_macro_body, path_to_string};
+use crate::{Error, Result};
 
 /// Create an instance from an item in an attribute declaration.
 ///
@@ -34,7 +38,8 @@ use crate::{util::path_to_string, Error, Result};
 ///
 /// ## Number
 /// * As a string literal, e.g. `foo = "-25"`.
-/// * As an unquoted positive value, e.g. `foo = 404`. Negative numbers must be in quotation marks.
+/// * As an unquoted positive value, e.g. `foo = 404`.
+/// * As an unquoted negative value, e.g. `foo = -25`, if the attribute allows expressions.
 ///
 /// ## ()
 /// * Word with no value specified, e.g. `foo`. This is best used with `Option`.
@@ -47,6 +52,11 @@ use crate::{util::path_to_string, Error, Result};
 /// * Allows for fallible parsing; will populate the target field with the result of the
 ///   parse attempt.
 pub trait FromMeta: Sized {
+    /// A Markdown list of the options the type accepts, for use in the documentation of
+    /// macros which read it. Derived impls list each field's name, type and default, or
+    /// each variant's name for enums; other impls leave this empty.
+    const ATTRIBUTE_DOCS: &'static str = "";
+
     fn from_nested_meta(item: &NestedMeta) -> Result<Self> {
         (match *item {
             NestedMeta::Lit(ref lit) => Self::from_value(lit),
@@ -73,23 +83,83 @@ pub trait FromMeta: Sized {
                     .cloned()
                     .collect::<Vec<syn::NestedMeta>>()[..],
             ),
-            Meta::NameValue(ref value) => Self::from_value(&value.lit),
+            Meta::NameValue(ref value) => match expr_value(&value.lit) {
+                Some(expr) => expr.and_then(|expr| Self::from_expr(&expr)),
+                None => Self::from_value(&value.lit),
+            },
         })
         .map_err(|e| e.with_span(item))
     }
 
+    /// Like [`from_meta`](FromMeta::from_meta), but pushes the warnings found while parsing,
+    /// such as the use of a deprecated field, to `warnings`.
+    ///
+    /// Derived impls override this; `from_meta` on a derived type parses the same way but
+    /// discards the warnings. The default forwards to `from_meta`, as impls which don't read
+    /// other options have nothing to warn about. Wrappers such as `Option` and `Box` forward
+    /// to the wrapped type.
+    #[allow(unused_variables)]
+    fn from_meta_with_warnings(item: &Meta, warnings: &mut Vec<Warning>) -> Result<Self> {
+        Self::from_meta(item)
+    }
+
     /// Create an instance from the presence of the word in the attribute with no
     /// additional options specified.
     fn from_word() -> Result<Self> {
         Err(Error::unsupported_format("word"))
     }
 
+    /// Create an instance for a field which was not present in the attribute, and which
+    /// has no default. Returning `None` causes a "missing field" error.
+    fn from_none() -> Option<Self> {
+        None
+    }
+
     /// Create an instance from a list of nested meta items.
     #[allow(unused_variables)]
     fn from_list(items: &[NestedMeta]) -> Result<Self> {
         Err(Error::unsupported_format("list"))
     }
 
+    /// Like [`from_list`](FromMeta::from_list), but pushes the warnings found while parsing to
+    /// `warnings`. Attribute macros use this to read their arguments; see
+    /// [`from_meta_with_warnings`](FromMeta::from_meta_with_warnings).
+    #[allow(unused_variables)]
+    fn from_list_with_warnings(items: &[NestedMeta], warnings: &mut Vec<Warning>) -> Result<Self> {
+        Self::from_list(items)
+    }
+
+    /// Create an instance from the arguments of an attribute macro, such as the `hello = 1`
+    /// in `#[your_attr(hello = 1)]`. This parses `tokens` with
+    /// [`parse_attribute_args`](crate::util::parse_attribute_args) and passes the result to
+    /// `from_list`, and generally should not be overridden by implementers.
+    fn from_attribute_args(tokens: TokenStream) -> Result<Self> {
+        Self::from_list(&parse_attribute_args(tokens)?)
+    }
+
+    /// Create an instance from the body of a function-like macro, such as the
+    /// `key = "value", nested(x = 1)` in `my_macro! { key = "value", nested(x = 1) }`. This
+    /// parses `tokens` with [`parse_macro_body`](crate::util::parse_macro_body) and passes the
+    /// result to `from_list`, so the same options type can be read from an attribute or a
+    /// macro call. It generally should not be overridden by implementers.
+    fn from_macro_body(tokens: TokenStream) -> Result<Self> {
+        Self::from_list(&parse_macro_body(tokens)?)
+    }
+
+    /// Create an instance from the source text of a meta item, such as
+    /// `my_attr(a = 1, b = "x")`. This is meant for unit tests of option types, which can
+    /// then avoid building `syn` values by hand.
+    ///
+    /// ```rust
+    /// # extern crate darling_core;
+    /// use darling_core::FromMeta;
+    ///
+    /// assert_eq!(bool::from_meta_str("skip = false").unwrap(), false);
+    /// ```
+    fn from_meta_str(source: &str) -> Result<Self> {
+        Self::from_meta(&syn::parse_str(source)?)
+    }
+
     /// Create an instance from a literal value of either `foo = "bar"` or `foo("bar")`.
     /// This dispatches to the appropriate method based on the type of literal encountered,
     /// and generally should not be overridden by implementers.
@@ -107,6 +177,18 @@ pub trait FromMeta: Sized {
         .map_err(|e| e.with_span(value))
     }
 
+    /// Create an instance from a value which is an expression rather than a literal, such as
+    /// `foo = some::CONST`. These are only read from attributes which allow expressions; see
+    /// [`parse_attribute_to_meta_list_with_exprs`](crate::util::parse_attribute_to_meta_list_with_exprs).
+    ///
+    /// Types which parse tokens, such as `syn::Expr` and `syn::Path`, and the numeric types,
+    /// which accept negative numbers, override this. The default returns an error, so types
+    /// which read strings don't mistake an expression for text.
+    #[allow(unused_variables)]
+    fn from_expr(expr: &Expr) -> Result<Self> {
+        Err(Error::unsupported_format("expression").with_span(expr))
+    }
+
     /// Create an instance from a char literal in a value position.
     #[allow(unused_variables)]
     fn from_char(value: char) -> Result<Self> {
@@ -183,54 +265,123 @@ impl FromMeta for String {
 }
 
 /// Generate an impl of `FromMeta` that will accept strings which parse to numbers or
-/// integer literals.
+/// integer literals. A number which is too large or small for the type is reported with
+/// the type's range.
 macro_rules! from_meta_num {
     ($ty:ident) => {
         impl FromMeta for $ty {
             fn from_string(s: &str) -> Result<Self> {
-                s.parse().map_err(|_| Error::unknown_value(s))
+                s.parse().map_err(|_| {
+                    if is_integer(s) {
+                        Error::out_of_range(s, stringify!($ty), $ty::min_value(), $ty::max_value())
+                    } else {
+                        Error::unknown_value(s)
+                    }
+                })
             }
 
             fn from_value(value: &Lit) -> Result<Self> {
                 (match *value {
                     Lit::Str(ref s) => Self::from_string(&s.value()),
-                    Lit::Int(ref s) => Ok(s.base10_parse::<$ty>().unwrap()),
+                    Lit::Int(ref s) => s.base10_parse::<$ty>().map_err(|_| {
+                        Error::out_of_range(
+                            s.base10_digits(),
+                            stringify!($ty),
+                            $ty::min_value(),
+                            $ty::max_value(),
+                        )
+                    }),
                     _ => Err(Error::unexpected_lit_type(value)),
                 })
                 .map_err(|e| e.with_span(value))
             }
+
+            fn from_expr(expr: &Expr) -> Result<Self> {
+                (match negated_lit(expr) {
+                    Some(&Lit::Int(ref lit)) => {
+                        Self::from_string(&format!("-{}", lit.base10_digits()))
+                    }
+                    _ => Err(Error::unsupported_format("expression")),
+                })
+                .map_err(|e| e.with_span(expr))
+            }
         }
     };
 }
 
+/// Whether `s` is written as an integer, meaning an optional `-` followed by ASCII digits. Such
+/// a value which doesn't parse is out of range, however many digits it has.
+fn is_integer(s: &str) -> bool {
+    let digits = if s.starts_with('-') { &s[1..] } else { s };
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Get the literal of an expression such as `-1`, which is how negative numbers are written.
+fn negated_lit(expr: &Expr) -> Option<&Lit> {
+    match *expr {
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr: ref inner,
+            ..
+        }) => match **inner {
+            Expr::Lit(ref lit) => Some(&lit.lit),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 from_meta_num!(u8);
 from_meta_num!(u16);
 from_meta_num!(u32);
 from_meta_num!(u64);
+from_meta_num!(u128);
 from_meta_num!(usize);
 from_meta_num!(i8);
 from_meta_num!(i16);
 from_meta_num!(i32);
 from_meta_num!(i64);
+from_meta_num!(i128);
 from_meta_num!(isize);
 
 /// Generate an impl of `FromMeta` that will accept strings which parse to floats or
-/// float literals.
+/// float literals. Infinite and not-a-number values are only accepted through
+/// `util::special_float`.
 macro_rules! from_meta_float {
     ($ty:ident) => {
         impl FromMeta for $ty {
             fn from_string(s: &str) -> Result<Self> {
-                s.parse().map_err(|_| Error::unknown_value(s))
+                match s.parse::<$ty>() {
+                    Ok(v) if v.is_finite() => Ok(v),
+                    Ok(_) => Err(non_finite(s)),
+                    Err(_) => Err(Error::unknown_value(s)),
+                }
             }
 
             fn from_value(value: &Lit) -> Result<Self> {
                 (match *value {
                     Lit::Str(ref s) => Self::from_string(&s.value()),
-                    Lit::Float(ref s) => Ok(s.base10_parse::<$ty>().unwrap()),
+                    Lit::Float(ref s) => match s.base10_parse::<$ty>() {
+                        Ok(v) if v.is_finite() => Ok(v),
+                        _ => Err(non_finite(s.base10_digits())),
+                    },
                     _ => Err(Error::unexpected_lit_type(value)),
                 })
                 .map_err(|e| e.with_span(value))
             }
+
+            fn from_expr(expr: &Expr) -> Result<Self> {
+                (match negated_lit(expr) {
+                    Some(&Lit::Float(ref lit)) => {
+                        Self::from_string(&format!("-{}", lit.base10_digits()))
+                    }
+                    Some(&Lit::Int(ref lit)) => {
+                        Self::from_string(&format!("-{}", lit.base10_digits()))
+                    }
+                    _ => Err(Error::unsupported_format("expression")),
+                })
+                .map_err(|e| e.with_span(expr))
+            }
         }
     };
 }
@@ -272,6 +423,12 @@ macro_rules! from_syn_parse {
                     Err(Error::unexpected_lit_type(value))
                 }
             }
+
+            fn from_expr(expr: &Expr) -> Result<Self> {
+                let tokens = expr.to_token_stream();
+                syn::parse2(tokens.clone())
+                    .map_err(|_| Error::unknown_value(&tokens.to_string()).with_span(expr))
+            }
         }
     };
 }
@@ -331,6 +488,10 @@ from_numeric_array!(usize);
 
 impl FromMeta for syn::Lit {
     fn from_value(value: &Lit) -> Result<Self> {
+        if expr_value(value).is_some() {
+            return Err(Error::unexpected_lit_type(value));
+        }
+
         Ok(value.clone())
     }
 }
@@ -339,10 +500,9 @@ macro_rules! from_meta_lit {
     ($impl_ty:path, $lit_variant:path) => {
         impl FromMeta for $impl_ty {
             fn from_value(value: &Lit) -> Result<Self> {
-                if let $lit_variant(ref value) = *value {
-                    Ok(value.clone())
-                } else {
-                    Err(Error::unexpected_lit_type(value))
+                match *value {
+                    $lit_variant(ref inner) if expr_value(value).is_none() => Ok(inner.clone()),
+                    _ => Err(Error::unexpected_lit_type(value)),
                 }
             }
         }
@@ -389,22 +549,75 @@ impl FromMeta for ident_case::RenameRule {
     }
 }
 
+/// An absent `Option` field is `None`, so `Option` fields don't need `#[darling(default)]`.
 impl<T: FromMeta> FromMeta for Option<T> {
+    fn from_none() -> Option<Self> {
+        Some(None)
+    }
+
     fn from_meta(item: &Meta) -> Result<Self> {
         FromMeta::from_meta(item).map(Some)
     }
+
+    fn from_meta_with_warnings(item: &Meta, warnings: &mut Vec<Warning>) -> Result<Self> {
+        FromMeta::from_meta_with_warnings(item, warnings).map(Some)
+    }
+
+    fn from_list(items: &[NestedMeta]) -> Result<Self> {
+        FromMeta::from_list(items).map(Some)
+    }
+
+    fn from_list_with_warnings(items: &[NestedMeta], warnings: &mut Vec<Warning>) -> Result<Self> {
+        FromMeta::from_list_with_warnings(items, warnings).map(Some)
+    }
+
+    fn from_value(value: &Lit) -> Result<Self> {
+        FromMeta::from_value(value).map(Some)
+    }
+
+    fn from_expr(expr: &Expr) -> Result<Self> {
+        FromMeta::from_expr(expr).map(Some)
+    }
 }
 
 impl<T: FromMeta> FromMeta for Box<T> {
+    fn from_none() -> Option<Self> {
+        T::from_none().map(Box::new)
+    }
+
     fn from_meta(item: &Meta) -> Result<Self> {
         FromMeta::from_meta(item).map(Box::new)
     }
+
+    fn from_meta_with_warnings(item: &Meta, warnings: &mut Vec<Warning>) -> Result<Self> {
+        FromMeta::from_meta_with_warnings(item, warnings).map(Box::new)
+    }
+
+    fn from_list(items: &[NestedMeta]) -> Result<Self> {
+        FromMeta::from_list(items).map(Box::new)
+    }
+
+    fn from_list_with_warnings(items: &[NestedMeta], warnings: &mut Vec<Warning>) -> Result<Self> {
+        FromMeta::from_list_with_warnings(items, warnings).map(Box::new)
+    }
+
+    fn from_value(value: &Lit) -> Result<Self> {
+        FromMeta::from_value(value).map(Box::new)
+    }
+
+    fn from_expr(expr: &Expr) -> Result<Self> {
+        FromMeta::from_expr(expr).map(Box::new)
+    }
 }
 
 impl<T: FromMeta> FromMeta for Result<T> {
     fn from_meta(item: &Meta) -> Result<Self> {
         Ok(FromMeta::from_meta(item))
     }
+
+    fn from_meta_with_warnings(item: &Meta, warnings: &mut Vec<Warning>) -> Result<Self> {
+        Ok(FromMeta::from_meta_with_warnings(item, warnings))
+    }
 }
 
 /// Parses the meta-item, and in case of error preserves a copy of the input for
@@ -418,18 +631,38 @@ impl<T: FromMeta> FromMeta for ::std::result::Result<T, Meta> {
 }
 
 impl<T: FromMeta> FromMeta for Rc<T> {
+    fn from_none() -> Option<Self> {
+        T::from_none().map(Rc::new)
+    }
+
     fn from_meta(item: &Meta) -> Result<Self> {
         FromMeta::from_meta(item).map(Rc::new)
     }
+
+    fn from_meta_with_warnings(item: &Meta, warnings: &mut Vec<Warning>) -> Result<Self> {
+        FromMeta::from_meta_with_warnings(item, warnings).map(Rc::new)
+    }
 }
 
 impl<T: FromMeta> FromMeta for Arc<T> {
+    fn from_none() -> Option<Self> {
+        T::from_none().map(Arc::new)
+    }
+
     fn from_meta(item: &Meta) -> Result<Self> {
         FromMeta::from_meta(item).map(Arc::new)
     }
+
+    fn from_meta_with_warnings(item: &Meta, warnings: &mut Vec<Warning>) -> Result<Self> {
+        FromMeta::from_meta_with_warnings(item, warnings).map(Arc::new)
+    }
 }
 
 impl<T: FromMeta> FromMeta for RefCell<T> {
+    fn from_none() -> Option<Self> {
+        T::from_none().map(RefCell::new)
+    }
+
     fn from_meta(item: &Meta) -> Result<Self> {
         FromMeta::from_meta(item).map(RefCell::new)
     }
@@ -480,7 +713,10 @@ impl KeyFromPath for syn::Ident {
 
 macro_rules! hash_map {
     ($key:ty) => {
-        impl<V: FromMeta, S: BuildHasher + Default> FromMeta for HashMap<$key, V, S> {
+        hash_map!(HashMap, $key);
+    };
+    ($map:ident, $key:ty) => {
+        impl<V: FromMeta, S: BuildHasher + Default> FromMeta for $map<$key, V, S> {
             fn from_list(nested: &[syn::NestedMeta]) -> Result<Self> {
                 // Convert the nested meta items into a sequence of (path, value result) result tuples.
                 // An outer Err means no (key, value) structured could be found, while an Err in the
@@ -514,7 +750,7 @@ macro_rules! hash_map {
                 // The map to return in the Ok case. Its size will always be exactly nested.len(),
                 // since otherwise ≥1 field had a problem and the entire map is dropped immediately
                 // when the function returns `Err`.
-                let mut map = HashMap::with_capacity_and_hasher(nested.len(), Default::default());
+                let mut map = $map::with_capacity_and_hasher(nested.len(), Default::default());
 
                 for item in pairs {
                     if let Some((path, value)) = errors.handle(item) {
@@ -562,6 +798,17 @@ hash_map!(String);
 hash_map!(syn::Ident);
 hash_map!(syn::Path);
 
+// An `IndexMap` keeps its entries in the order the keys were written.
+#[cfg(feature = "indexmap")]
+use indexmap::IndexMap;
+
+#[cfg(feature = "indexmap")]
+hash_map!(IndexMap, String);
+#[cfg(feature = "indexmap")]
+hash_map!(IndexMap, syn::Ident);
+#[cfg(feature = "indexmap")]
+hash_map!(IndexMap, syn::Path);
+
 /// Tests for `FromMeta` implementations. Wherever the word `ignore` appears in test input,
 /// it should not be considered by the parsing.
 #[cfg(test)]
@@ -637,6 +884,74 @@ mod tests {
         assert_eq!(fm::<u32>(quote!(ignore = 5000i32)), 5000u32);
     }
 
+    /// Raw strings and escapes are processed the same way no matter which type reads them.
+    #[test]
+    fn string_forms_read_alike() {
+        assert_eq!(fm::<String>(quote!(ignore = "a\tb")), "a\tb");
+        assert_eq!(fm::<String>(quote!(ignore = "a\u{9}b")), "a\tb");
+        assert_eq!(fm::<String>(quote!(ignore = r"a	b")), "a\tb");
+
+        let path: syn::Path = parse_quote!(a::b);
+        assert_eq!(fm::<syn::Path>(quote!(ignore = r#"a::b"#)), path);
+        assert_eq!(fm::<syn::Path>(quote!(ignore = "\u{61}::b")), path);
+
+        let expr: syn::Expr = parse_quote!(x + "y");
+        assert_eq!(fm::<syn::Expr>(quote!(ignore = r#"x + "y""#)), expr);
+        assert_eq!(fm::<syn::Expr>(quote!(ignore = "x\u{20}+ \"y\"")), expr);
+    }
+
+    #[test]
+    fn float_rejects_non_finite() {
+        let err = f64::from_meta(&pm(quote!(ignore = "inf")).unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`inf` is not a finite number; use `with = \"darling::util::special_float\"` to allow it"
+        );
+        assert!(f32::from_meta(&pm(quote!(ignore = "NaN")).unwrap()).is_err());
+        assert!(f32::from_meta(&pm(quote!(ignore = 1e99)).unwrap()).is_err());
+    }
+
+    #[test]
+    fn int_out_of_range() {
+        let err = u8::from_meta(&pm(quote!(ignore = 300)).unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Value `300` is out of range for `u8`: Expected 0 to 255"
+        );
+
+        let err = i8::from_meta(&pm(quote!(ignore = "-129")).unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Value `-129` is out of range for `i8`: Expected -128 to 127"
+        );
+
+        let err = u128::from_meta(
+            &pm(quote!(ignore = "340282366920938463463374607431768211456")).unwrap(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Value `340282366920938463463374607431768211456` is out of range for `u128`: Expected 0 to 340282366920938463463374607431768211455"
+        );
+
+        let err = i128::from_meta(
+            &pm(quote!(ignore = "-170141183460469231731687303715884105729")).unwrap(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with(
+            "Value `-170141183460469231731687303715884105729` is out of range for `i128`"
+        ));
+
+        let err = u16::from_meta(&pm(quote!(ignore = "many")).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown literal value `many`");
+
+        // Only a sign followed by digits is a number.
+        let err = u16::from_meta(&pm(quote!(ignore = "-")).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown literal value `-`");
+        let err = u16::from_meta(&pm(quote!(ignore = "+1x")).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown literal value `+1x`");
+    }
+
     #[test]
     #[allow(clippy::float_cmp)] // we want exact equality
     fn float_without_quotes() {
@@ -673,6 +988,21 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn index_map_keeps_order() {
+        use indexmap::IndexMap;
+
+        let map = fm::<IndexMap<String, bool>>(quote!(ignore(zeta, alpha = false, mid)));
+        let keys: Vec<&str> = map.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["zeta", "alpha", "mid"]);
+        assert!(!map["alpha"]);
+
+        let err = IndexMap::<String, bool>::from_meta(&pm(quote!(ignore(a, a))).unwrap())
+            .expect_err("Duplicate keys in IndexMap should error");
+        assert_eq!(err.to_string(), "Duplicate field `a`");
+    }
+
     /// Check that a `HashMap` cannot have duplicate keys, and that the generated error
     /// is assigned a span to correctly target the diagnostic message.
     #[test]
@@ -0,0 +1,441 @@
+//! Random meta items for property tests of derived parsers.
+//!
+//! A [`MetaGenerator`] builds attributes from the [`TypeSchema`] of an options type. Most
+//! of what it generates is close to valid input, with the fields the type expects and values
+//! of roughly the right shape, so that parsing reaches deep into the derived impl. Mixed in
+//! are the mistakes users make: missing and repeated keys, misspelled and unknown keys,
+//! literals of the wrong type, out-of-range numbers and arbitrary nesting.
+//!
+//! [`check_parser`] runs a type's `FromMeta` impl over many generated inputs and panics if
+//! parsing panics or produces a malformed error.
+//!
+//! ```rust,ignore
+//! #[derive(FromMeta, MetaSchema)]
+//! struct Settings {
+//!     name: String,
+//!     #[darling(default)]
+//!     mode: Mode,
+//! }
+//!
+//! #[test]
+//! fn settings_never_panic() {
+//!     check_parser::<Settings>(MetaGenerator::new(7).with_schema::<Mode>(), 1000);
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+
+use quote::ToTokens;
+use syn::Meta;
+
+use crate::meta_schema::{FieldSchema, MetaSchema, SchemaBody, TypeSchema};
+use crate::FromMeta;
+
+/// The name of the attribute wrapping each generated input.
+const ATTR_NAME: &str = "attr";
+
+/// Numbers at the edges of the primitive integer types, where parsers tend to go wrong.
+const EDGE_INTS: &[&str] = &[
+    "0",
+    "1",
+    "-1",
+    "127",
+    "128",
+    "255",
+    "256",
+    "-129",
+    "65535",
+    "65536",
+    "2147483648",
+    "4294967296",
+    "9223372036854775807",
+    "18446744073709551616",
+    "340282366920938463463374607431768211456",
+];
+
+const EDGE_FLOATS: &[&str] = &["0.0", "-0.5", "1.5", "1e10", "1e999", "3"];
+
+/// Characters used in generated strings, including ones which need escaping.
+const STR_CHARS: &[char] = &['a', 'z', '_', ':', ' ', '"', '\\', '\n', 'é', '0', '-'];
+
+const INT_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// A generator of meta items which follow an options type's schema, with mistakes mixed in.
+///
+/// Generation is deterministic for a given seed, so a failing input can be reproduced.
+#[derive(Debug, Clone)]
+pub struct MetaGenerator {
+    state: u64,
+    schemas: HashMap<&'static str, TypeSchema>,
+    max_depth: usize,
+}
+
+impl MetaGenerator {
+    /// Create a generator which produces the same sequence of inputs for the same `seed`.
+    pub fn new(seed: u64) -> Self {
+        MetaGenerator {
+            // Mix the seed so that small seeds don't produce similar sequences.
+            state: seed ^ 0x9e37_79b9_7f4a_7c15,
+            schemas: HashMap::new(),
+            max_depth: 4,
+        }
+    }
+
+    /// Use the schema of `T` for fields whose type is named like `T`.
+    ///
+    /// Schemas only record the names of field types, so nested options types are filled
+    /// with arbitrary values unless their schema is registered here.
+    pub fn with_schema<T: MetaSchema>(mut self) -> Self {
+        let schema = T::meta_schema();
+        self.schemas.insert(schema.ident, schema);
+        self
+    }
+
+    /// Stop following schemas below `depth` levels of nesting. Deeper values are arbitrary.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Generate an attribute for a type with the given schema.
+    pub fn generate(&mut self, schema: &TypeSchema) -> Meta {
+        let source = if self.one_in(16) {
+            self.arbitrary(ATTR_NAME, 0)
+        } else {
+            self.body(ATTR_NAME, &schema.body, 0)
+        };
+
+        syn::parse_str(&source).expect("Generated input should be a valid meta item")
+    }
+
+    fn body(&mut self, key: &str, body: &SchemaBody, depth: usize) -> String {
+        match *body {
+            SchemaBody::Unit => key.to_string(),
+            SchemaBody::Newtype(ty) => self.value(key, ty, depth),
+            SchemaBody::Fields(ref fields) => {
+                let items = self.fields(fields, depth + 1);
+                format!("{}({})", key, items.join(", "))
+            }
+            SchemaBody::Variants(ref variants) => {
+                if variants.is_empty() {
+                    return self.arbitrary(key, depth);
+                }
+
+                let variant = &variants[self.below(variants.len())];
+                match variant.body {
+                    SchemaBody::Unit if self.one_in(2) => format!("{} = \"{}\"", key, variant.name),
+                    ref body => {
+                        let inner = self.body(&variant.name, body, depth + 1);
+                        format!("{}({})", key, inner)
+                    }
+                }
+            }
+        }
+    }
+
+    fn fields(&mut self, fields: &[FieldSchema], depth: usize) -> Vec<String> {
+        let mut items = vec![];
+        for field in fields {
+            let count = if field.multiple {
+                self.below(4)
+            } else if self.one_in(16) {
+                // A repeated key.
+                2
+            } else if field.required {
+                if self.one_in(8) {
+                    0
+                } else {
+                    1
+                }
+            } else {
+                self.below(2)
+            };
+
+            for _ in 0..count {
+                items.push(self.value(&field.name, field.ty, depth));
+            }
+        }
+
+        if !fields.is_empty() && self.one_in(8) {
+            let field = &fields[self.below(fields.len())];
+            let key = self.misspelled(&field.name);
+            items.push(self.arbitrary(&key, depth));
+        }
+
+        if self.one_in(16) {
+            items.push(self.lit());
+        }
+
+        // Swap a few items, so the order of keys varies.
+        for _ in 0..items.len() / 2 {
+            let (a, b) = (self.below(items.len()), self.below(items.len()));
+            items.swap(a, b);
+        }
+
+        items
+    }
+
+    /// Generate an item with `key` holding a value of the type written as `ty`.
+    fn value(&mut self, key: &str, ty: &str, depth: usize) -> String {
+        if depth > self.max_depth || self.one_in(8) {
+            return self.arbitrary(key, depth);
+        }
+
+        let ty: String = ty.chars().filter(|c| !c.is_whitespace()).collect();
+        if let Some(inner) = strip_wrapper(&ty, &["Option", "SpannedValue", "Box"]) {
+            return self.value(key, inner, depth);
+        }
+
+        let name = type_name(&ty);
+        if let Some(schema) = self.schemas.get(name).cloned() {
+            return self.body(key, &schema.body, depth);
+        }
+
+        match name {
+            "bool" => match self.below(3) {
+                0 => key.to_string(),
+                1 => format!("{} = {}", key, self.one_in(2)),
+                _ => format!("{} = \"{}\"", key, self.one_in(2)),
+            },
+            "Flag" => key.to_string(),
+            "String" => format!("{} = {}", key, self.string()),
+            "f32" | "f64" => {
+                let float = EDGE_FLOATS[self.below(EDGE_FLOATS.len())];
+                if self.one_in(4) {
+                    format!("{} = \"{}\"", key, float)
+                } else {
+                    format!("{} = {}", key, float)
+                }
+            }
+            "Path" | "Ident" => format!("{} = \"{}\"", key, self.path()),
+            name if INT_TYPES.contains(&name) => {
+                let int = EDGE_INTS[self.below(EDGE_INTS.len())];
+                if self.one_in(4) {
+                    format!("{} = \"{}\"", key, int)
+                } else {
+                    format!("{} = {}", key, int)
+                }
+            }
+            _ => self.arbitrary(key, depth),
+        }
+    }
+
+    /// Generate an item with `key` and a value of any form.
+    fn arbitrary(&mut self, key: &str, depth: usize) -> String {
+        let choice = if depth > self.max_depth {
+            self.below(2)
+        } else {
+            self.below(3)
+        };
+
+        match choice {
+            0 => key.to_string(),
+            1 => format!("{} = {}", key, self.lit()),
+            _ => {
+                let items: Vec<String> = (0..self.below(4))
+                    .map(|_| {
+                        if self.one_in(4) {
+                            self.lit()
+                        } else {
+                            let key = self.ident();
+                            self.arbitrary(&key, depth + 1)
+                        }
+                    })
+                    .collect();
+                format!("{}({})", key, items.join(", "))
+            }
+        }
+    }
+
+    fn lit(&mut self) -> String {
+        match self.below(6) {
+            0 => self.string(),
+            1 => EDGE_INTS[self.below(EDGE_INTS.len())]
+                .trim_start_matches('-')
+                .to_string(),
+            2 => EDGE_FLOATS[self.below(EDGE_FLOATS.len())]
+                .trim_start_matches('-')
+                .to_string(),
+            3 => self.one_in(2).to_string(),
+            4 => "'c'".to_string(),
+            _ => "b\"bytes\"".to_string(),
+        }
+    }
+
+    fn string(&mut self) -> String {
+        let value: String = (0..self.below(8))
+            .map(|_| STR_CHARS[self.below(STR_CHARS.len())])
+            .collect();
+        // `Debug` output for `str` is valid Rust string literal syntax.
+        format!("{:?}", value)
+    }
+
+    fn path(&mut self) -> String {
+        match self.below(4) {
+            0 => self.ident(),
+            1 => format!("{}::{}", self.ident(), self.ident()),
+            2 => format!("::{}", self.ident()),
+            _ => "not a path".to_string(),
+        }
+    }
+
+    fn ident(&mut self) -> String {
+        const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+        let mut ident = "k_".to_string();
+        for _ in 0..=self.below(5) {
+            ident.push(LETTERS[self.below(LETTERS.len())] as char);
+        }
+        ident
+    }
+
+    /// A key which is close to `name`, or an unrelated one.
+    fn misspelled(&mut self, name: &str) -> String {
+        if name.len() < 2
+            || self.one_in(3)
+            || name.contains(|c: char| !c.is_alphanumeric() && c != '_')
+        {
+            return self.ident();
+        }
+
+        let mut chars: Vec<char> = name.chars().collect();
+        chars.remove(self.below(chars.len()));
+        let misspelled: String = chars.into_iter().collect();
+        if misspelled.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            misspelled
+        } else {
+            self.ident()
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        // xorshift64*
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            0
+        } else {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+}
+
+/// Get the contents of `ty` if it is one of the generic `wrappers`, such as `Option<T>`.
+fn strip_wrapper<'a>(ty: &'a str, wrappers: &[&str]) -> Option<&'a str> {
+    let start = ty.find('<')?;
+    if !ty.ends_with('>') || !wrappers.contains(&type_name(&ty[..start])) {
+        return None;
+    }
+
+    Some(&ty[start + 1..ty.len() - 1])
+}
+
+/// The last path segment of `ty`, without generic arguments.
+fn type_name(ty: &str) -> &str {
+    let ty = match ty.find('<') {
+        Some(start) => &ty[..start],
+        None => ty,
+    };
+    ty.rsplit("::").next().unwrap_or(ty)
+}
+
+/// How many generated inputs [`check_parser`] accepted and rejected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CheckSummary {
+    pub parsed: usize,
+    pub rejected: usize,
+}
+
+/// Parse `iterations` inputs from `generator` with `T`, checking that each one produces
+/// either a value or a well-formed error.
+///
+/// An error is well-formed if it contains at least one error and every error has a
+/// message.
+///
+/// # Panics
+/// Panics with the offending input if parsing panics or returns a malformed error.
+pub fn check_parser<T>(mut generator: MetaGenerator, iterations: usize) -> CheckSummary
+where
+    T: FromMeta + MetaSchema,
+{
+    let schema = T::meta_schema();
+    let mut summary = CheckSummary::default();
+
+    for iteration in 0..iterations {
+        let meta = generator.generate(&schema);
+        let input = meta.to_token_stream().to_string();
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| T::from_meta(&meta)));
+        match result {
+            Ok(Ok(_)) => summary.parsed += 1,
+            Ok(Err(err)) => {
+                if err.len() == 0 || err.into_iter().any(|e| e.to_string().is_empty()) {
+                    panic!(
+                        "`{}` returned a malformed error on input {}: {}",
+                        schema.ident, iteration, input
+                    );
+                }
+                summary.rejected += 1;
+            }
+            Err(_) => panic!(
+                "`{}` panicked on input {}: {}",
+                schema.ident, iteration, input
+            ),
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{strip_wrapper, type_name, MetaGenerator};
+    use crate::meta_schema::{FieldSchema, SchemaBody, TypeSchema};
+
+    fn schema() -> TypeSchema {
+        TypeSchema {
+            ident: "Settings",
+            body: SchemaBody::Fields(vec![FieldSchema {
+                name: "name".into(),
+                aliases: &[],
+                ty: "String",
+                required: true,
+                default: None,
+                multiple: false,
+                deprecated: None,
+            }]),
+            allow_unknown_fields: false,
+        }
+    }
+
+    #[test]
+    fn same_seed_same_inputs() {
+        let mut a = MetaGenerator::new(3);
+        let mut b = MetaGenerator::new(3);
+        for _ in 0..50 {
+            assert_eq!(a.generate(&schema()), b.generate(&schema()));
+        }
+    }
+
+    #[test]
+    fn types() {
+        assert_eq!(type_name("::std::string::String"), "String");
+        assert_eq!(type_name("Vec<String>"), "Vec");
+        assert_eq!(
+            strip_wrapper("Option<Vec<u8>>", &["Option"]),
+            Some("Vec<u8>")
+        );
+        assert_eq!(strip_wrapper("Vec<u8>", &["Option"]), None);
+    }
+}
@@ -19,10 +19,12 @@ mod macros_private;
 mod macros_public;
 
 pub mod ast;
-pub(crate) mod codegen;
+pub mod codegen;
 pub mod derive;
+pub mod emit;
 pub mod error;
 mod from_attributes;
+mod from_const_param;
 mod from_derive_input;
 mod from_field;
 mod from_generic_param;
@@ -30,12 +32,20 @@ mod from_generics;
 mod from_meta;
 mod from_type_param;
 mod from_variant;
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
+pub mod meta_schema;
 pub(crate) mod options;
+pub mod report;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod to_meta;
 pub mod usage;
 pub mod util;
 
 pub use self::error::{Error, Result};
 pub use self::from_attributes::FromAttributes;
+pub use self::from_const_param::FromConstParam;
 pub use self::from_derive_input::FromDeriveInput;
 pub use self::from_field::FromField;
 pub use self::from_generic_param::FromGenericParam;
@@ -43,6 +53,8 @@ pub use self::from_generics::FromGenerics;
 pub use self::from_meta::FromMeta;
 pub use self::from_type_param::FromTypeParam;
 pub use self::from_variant::FromVariant;
+pub use self::meta_schema::MetaSchema;
+pub use self::to_meta::ToMeta;
 
 // Re-export tokenizer
 #[doc(hidden)]
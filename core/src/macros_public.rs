@@ -94,3 +94,89 @@ macro_rules! uses_lifetimes {
         }
     };
 }
+
+/// Assert that the source text of a meta item parses into an options type.
+///
+/// # Usage
+/// The macro takes the options type, which must implement `FromMeta`, then the source text of
+/// the meta item. It evaluates to the parsed value, or panics with the rendered errors if
+/// parsing failed. An optional third argument is compared with the parsed value using
+/// `assert_eq!`.
+///
+/// ```rust
+/// # extern crate darling_core;
+/// # use darling_core::assert_parses;
+/// #
+/// let enabled = assert_parses!(bool, "enabled = true");
+/// assert!(enabled);
+///
+/// assert_parses!(String, r#"name = "x""#, "x".to_string());
+/// ```
+#[macro_export]
+macro_rules! assert_parses {
+    ($ty:ty, $source:expr) => {
+        match <$ty as $crate::FromMeta>::from_meta_str($source) {
+            ::std::result::Result::Ok(value) => value,
+            ::std::result::Result::Err(err) => panic!(
+                "expected `{}` to parse as `{}`, but it failed: {}",
+                $source,
+                stringify!($ty),
+                err
+            ),
+        }
+    };
+    ($ty:ty, $source:expr, $expected:expr) => {
+        assert_eq!($crate::assert_parses!($ty, $source), $expected)
+    };
+}
+
+/// Assert that the source text of a meta item is rejected by an options type.
+///
+/// # Usage
+/// The macro takes the options type, which must implement `FromMeta`, then the source text of
+/// the meta item. It evaluates to the returned `Error`, or panics if parsing succeeded.
+///
+/// An optional third argument checks the error:
+///
+/// * An expression is compared with the rendered error message.
+/// * `kind = <pattern>` passes if any of the errors has a kind matching the pattern.
+///
+/// ```rust
+/// # extern crate darling_core;
+/// # use darling_core::assert_rejects;
+/// use darling_core::error::ErrorKind;
+///
+/// assert_rejects!(u8, r#"count = "many""#);
+/// assert_rejects!(bool, "enabled(true)", "Unexpected meta-item format `list`");
+/// assert_rejects!(bool, "enabled(true)", kind = ErrorKind::UnexpectedFormat(_));
+/// ```
+#[macro_export]
+macro_rules! assert_rejects {
+    ($ty:ty, $source:expr) => {
+        match <$ty as $crate::FromMeta>::from_meta_str($source) {
+            ::std::result::Result::Ok(_) => panic!(
+                "expected `{}` to be rejected by `{}`, but it parsed",
+                $source,
+                stringify!($ty)
+            ),
+            ::std::result::Result::Err(err) => err,
+        }
+    };
+    ($ty:ty, $source:expr, kind = $kind:pat) => {{
+        let err = $crate::assert_rejects!($ty, $source);
+        let rendered = err.to_string();
+        let found = err.flatten().into_iter().any(|err| match *err.kind() {
+            $kind => true,
+            _ => false,
+        });
+        assert!(
+            found,
+            "expected an error of kind `{}`, but got: {}",
+            stringify!($kind),
+            rendered
+        );
+    }};
+    ($ty:ty, $source:expr, $message:expr) => {
+        assert_eq!($crate::assert_rejects!($ty, $source).to_string(), $message)
+    };
+}
@@ -0,0 +1,275 @@
+//! Structured descriptions of the options a type accepts.
+
+use std::borrow::Cow;
+use std::fmt::Write;
+
+/// A type which can describe the options it accepts at run time.
+///
+/// This is implemented with `#[derive(MetaSchema)]`, which reads the same `#[darling(...)]`
+/// attributes as `#[derive(FromMeta)]`. Frameworks can use the description to generate help
+/// text or to validate input in tooling, without parsing anything.
+///
+/// ```rust,ignore
+/// #[derive(FromMeta, MetaSchema)]
+/// struct Settings {
+///     name: String,
+///     #[darling(default)]
+///     retries: u8,
+/// }
+///
+/// let schema = Settings::meta_schema();
+/// assert!(schema.field("name").unwrap().required);
+/// ```
+pub trait MetaSchema {
+    /// Describe the options the type accepts.
+    fn meta_schema() -> TypeSchema;
+}
+
+/// The description of an options type returned by [`MetaSchema::meta_schema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeSchema {
+    /// The name of the type.
+    pub ident: &'static str,
+    pub body: SchemaBody,
+    /// Whether keys which don't match a field are ignored rather than rejected.
+    pub allow_unknown_fields: bool,
+}
+
+impl TypeSchema {
+    /// Get the field which is read from `name`, if the type is a struct with such a field.
+    pub fn field(&self, name: &str) -> Option<&FieldSchema> {
+        self.body.field(name)
+    }
+
+    /// Render the description as JSON, for editor plugins and other tooling which offer
+    /// completions for an attribute.
+    ///
+    /// The output is an object with the keys `ident`, `allow_unknown_fields` and `body`.
+    /// Bodies are objects whose `kind` is one of `unit`, `newtype` (with a `type`), `fields`
+    /// (with a `fields` array) or `variants` (with a `variants` array). Fields have the keys
+    /// `name`, `type`, `required`, `default`, `multiple` and `deprecated`; variants have
+    /// `name`, `body` and `deprecated`. Absent values are `null`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        json.push_str("{\"ident\":");
+        write_json_str(&mut json, self.ident);
+        write!(
+            json,
+            ",\"allow_unknown_fields\":{}",
+            self.allow_unknown_fields
+        )
+        .unwrap();
+        json.push_str(",\"body\":");
+        self.body.write_json(&mut json);
+        json.push('}');
+        json
+    }
+
+    /// Get the variant which is read from `name`, if the type is an enum with such a variant.
+    pub fn variant(&self, name: &str) -> Option<&VariantSchema> {
+        match self.body {
+            SchemaBody::Variants(ref variants) => {
+                variants.iter().find(|variant| variant.name == name)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The shape of the input accepted by a type or enum variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaBody {
+    /// A bare word, such as `skip`.
+    Unit,
+    /// Whatever the wrapped type accepts. The value is the wrapped type, as written in source.
+    Newtype(&'static str),
+    /// A list of `key = value` items or nested lists.
+    Fields(Vec<FieldSchema>),
+    /// Exactly one of the variants, written as a word, a string or a single-item list.
+    Variants(Vec<VariantSchema>),
+}
+
+impl SchemaBody {
+    /// Get the field which is read from `name`, if the body has named fields.
+    pub fn field(&self, name: &str) -> Option<&FieldSchema> {
+        match *self {
+            SchemaBody::Fields(ref fields) => fields.iter().find(|field| field.name == name),
+            _ => None,
+        }
+    }
+
+    fn write_json(&self, json: &mut String) {
+        match *self {
+            SchemaBody::Unit => json.push_str("{\"kind\":\"unit\"}"),
+            SchemaBody::Newtype(ty) => {
+                json.push_str("{\"kind\":\"newtype\",\"type\":");
+                write_json_str(json, ty);
+                json.push('}');
+            }
+            SchemaBody::Fields(ref fields) => {
+                json.push_str("{\"kind\":\"fields\",\"fields\":[");
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    field.write_json(json);
+                }
+                json.push_str("]}");
+            }
+            SchemaBody::Variants(ref variants) => {
+                json.push_str("{\"kind\":\"variants\",\"variants\":[");
+                for (i, variant) in variants.iter().enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    variant.write_json(json);
+                }
+                json.push_str("]}");
+            }
+        }
+    }
+}
+
+/// An option accepted by a struct or struct variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSchema {
+    /// The key the field is read from. This is computed at run time if the container sets
+    /// `rename_all_with`.
+    pub name: Cow<'static, str>,
+    /// The other keys the field can be read from.
+    pub aliases: &'static [&'static str],
+    /// The type of the field, as written in source.
+    pub ty: &'static str,
+    /// Whether omitting the field is an error.
+    pub required: bool,
+    /// The expression which provides the value when the field is omitted, if there is one.
+    pub default: Option<&'static str>,
+    /// Whether the field may appear more than once.
+    pub multiple: bool,
+    /// The note of the deprecation warning recorded when the field is used, if any.
+    pub deprecated: Option<&'static str>,
+}
+
+impl FieldSchema {
+    fn write_json(&self, json: &mut String) {
+        json.push_str("{\"name\":");
+        write_json_str(json, &self.name);
+        json.push_str(",\"aliases\":[");
+        for (i, alias) in self.aliases.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write_json_str(json, alias);
+        }
+        json.push_str("],\"type\":");
+        write_json_str(json, self.ty);
+        write!(json, ",\"required\":{},\"default\":", self.required).unwrap();
+        write_json_opt_str(json, self.default);
+        write!(json, ",\"multiple\":{},\"deprecated\":", self.multiple).unwrap();
+        write_json_opt_str(json, self.deprecated);
+        json.push('}');
+    }
+}
+
+/// A variant accepted by an enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantSchema {
+    /// The name the variant is read from. This is computed at run time if the enum sets
+    /// `rename_all_with`.
+    pub name: Cow<'static, str>,
+    pub body: SchemaBody,
+    /// The note of the deprecation warning recorded when the variant is used, if any.
+    pub deprecated: Option<&'static str>,
+}
+
+impl VariantSchema {
+    fn write_json(&self, json: &mut String) {
+        json.push_str("{\"name\":");
+        write_json_str(json, &self.name);
+        json.push_str(",\"body\":");
+        self.body.write_json(json);
+        json.push_str(",\"deprecated\":");
+        write_json_opt_str(json, self.deprecated);
+        json.push('}');
+    }
+}
+
+fn write_json_opt_str(json: &mut String, value: Option<&str>) {
+    match value {
+        Some(value) => write_json_str(json, value),
+        None => json.push_str("null"),
+    }
+}
+
+fn write_json_str(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FieldSchema, SchemaBody, TypeSchema, VariantSchema};
+
+    #[test]
+    fn renders_json() {
+        let schema = TypeSchema {
+            ident: "Mode",
+            body: SchemaBody::Variants(vec![
+                VariantSchema {
+                    name: "fast".into(),
+                    body: SchemaBody::Unit,
+                    deprecated: Some("use \"quick\""),
+                },
+                VariantSchema {
+                    name: "tuned".into(),
+                    body: SchemaBody::Fields(vec![FieldSchema {
+                        name: "level".into(),
+                        aliases: &["lvl"],
+                        ty: "Option<u8>",
+                        required: false,
+                        default: Some("Default::default()"),
+                        multiple: false,
+                        deprecated: None,
+                    }]),
+                    deprecated: None,
+                },
+                VariantSchema {
+                    name: "custom".into(),
+                    body: SchemaBody::Newtype("u8"),
+                    deprecated: None,
+                },
+            ]),
+            allow_unknown_fields: false,
+        };
+
+        assert_eq!(
+            schema.to_json(),
+            concat!(
+                r#"{"ident":"Mode","allow_unknown_fields":false,"body":{"kind":"variants","variants":["#,
+                r#"{"name":"fast","body":{"kind":"unit"},"deprecated":"use \"quick\""},"#,
+                r#"{"name":"tuned","body":{"kind":"fields","fields":[{"name":"level","aliases":["lvl"],"type":"Option<u8>","#,
+                r#""required":false,"default":"Default::default()","multiple":false,"deprecated":null}]},"#,
+                r#""deprecated":null},"#,
+                r#"{"name":"custom","body":{"kind":"newtype","type":"u8"},"deprecated":null}]}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn escapes_control_characters() {
+        let mut json = String::new();
+        super::write_json_str(&mut json, "a\u{1}\n\\");
+        assert_eq!(json, r#""a\u0001\n\\""#);
+    }
+}
@@ -3,20 +3,23 @@ use ident_case::RenameRule;
 use crate::ast::{Data, Fields, Style};
 use crate::codegen;
 use crate::codegen::PostfixTransform;
-use crate::options::{DefaultExpression, InputField, InputVariant, ParseAttribute, ParseData};
-use crate::util::Flag;
+use crate::options::{
+    DefaultExpression, ErrorSpan, InputField, InputVariant, ParseAttribute, ParseData,
+    RenamedField, SpanStrategy,
+};
+use crate::util::{Flag, CONFIG_FILE_KEY};
 use crate::{Error, FromMeta, Result};
 
 /// A struct or enum which should have `FromMeta` or `FromDeriveInput` implementations
 /// generated.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Core {
+pub struct Core<'a> {
     /// The type identifier.
-    pub ident: syn::Ident,
+    pub ident: &'a syn::Ident,
 
     /// The type's generics. If the type does not use any generics, this will
     /// be an empty instance.
-    pub generics: syn::Generics,
+    pub generics: &'a syn::Generics,
 
     /// Controls whether missing properties should cause errors or should be filled by
     /// the result of a function call. This can be overridden at the field level.
@@ -25,6 +28,14 @@ pub struct Core {
     /// The rule that should be used to rename all fields/variants in the container.
     pub rename_rule: RenameRule,
 
+    /// Whether `rename_rule` was written by the author, rather than being the default for
+    /// the body shape.
+    pub rename_all_is_set: bool,
+
+    /// A function which computes the name of each field or variant when the generated code
+    /// runs, used instead of `rename_rule` for casing schemes it doesn't cover.
+    pub rename_all_with: Option<syn::Path>,
+
     /// A transform which will be called on `darling::Result<Self>`. It must either be
     /// an `FnOnce(T) -> T` when `map` is used, or `FnOnce(T) -> darling::Result<T>` when
     /// `and_then` is used.
@@ -34,21 +45,66 @@ pub struct Core {
     pub post_transform: Option<codegen::PostfixTransform>,
 
     /// The body of the _deriving_ type.
-    pub data: Data<InputVariant, InputField>,
+    pub data: Data<InputVariant<'a>, InputField<'a>>,
 
     /// The custom bound to apply to the generated impl
     pub bound: Option<Vec<syn::WherePredicate>>,
 
     /// Whether or not unknown fields should produce an error at compilation time.
     pub allow_unknown_fields: Flag,
+
+    /// Which part of an item errors in the container's fields should point at. This can be
+    /// overridden at the field level.
+    pub error_span: Option<ErrorSpan>,
+
+    /// Field names which have been renamed. Using one of these produces an error naming
+    /// its replacement, rather than a generic unknown field error.
+    pub renamed: Vec<RenamedField>,
+
+    /// Whether named fields are read through `DynFieldSlot` trait objects rather than code
+    /// generated for each field.
+    pub dyn_fields: Flag,
+    /// Which spans the code generated for the container's fields should carry.
+    pub span: Option<SpanStrategy>,
+
+    /// Whether a `config = "path"` key loads options from a file.
+    pub config_file: Flag,
+
+    /// Whether collection fields without a default are empty when absent from the input.
+    pub default_collections: Flag,
+
+    /// Whether a `Default` impl is generated from the fields' default expressions.
+    pub derive_default: Flag,
+
+    /// Whether a builder type with a setter per field is generated.
+    pub builder: Flag,
+
+    /// Whether input which sets none of the fields, such as `#[my_attr()]`, is an error.
+    pub deny_empty: Flag,
+
+    /// Whether the generated impl notes what it reads in the active parse report.
+    pub report: Flag,
+
+    /// Whether the generated impls are printed to stderr during expansion.
+    pub debug: Flag,
+
+    /// The deriving type as written. The builder is declared from its fields and visibility.
+    pub input: &'a syn::DeriveInput,
 }
 
-impl Core {
+impl<'a> Core<'a> {
     /// Partially initializes `Core` by reading the identity, generics, and body shape.
-    pub fn start(di: &syn::DeriveInput) -> Result<Self> {
+    pub fn start(di: &'a syn::DeriveInput) -> Result<Self> {
+        // This deliberately doesn't set a span on the error message, as the error is most useful if
+        // applied to the call site of the offending macro. Given that the message is very generic,
+        // putting it on the union keyword ends up being confusing.
+        if let syn::Data::Union(_) = di.data {
+            return Err(Error::custom("Unions are not supported"));
+        }
+
         Ok(Core {
-            ident: di.ident.clone(),
-            generics: di.generics.clone(),
+            ident: &di.ident,
+            generics: &di.generics,
             data: Data::try_empty_from(&di.data)?,
             default: Default::default(),
             // See https://github.com/TedDriggs/darling/issues/10: We default to snake_case
@@ -58,9 +114,23 @@ impl Core {
             } else {
                 Default::default()
             },
+            rename_all_is_set: false,
+            rename_all_with: Default::default(),
             post_transform: Default::default(),
             bound: Default::default(),
             allow_unknown_fields: Default::default(),
+            error_span: Default::default(),
+            renamed: Default::default(),
+            dyn_fields: Default::default(),
+            span: Default::default(),
+            config_file: Default::default(),
+            default_collections: Default::default(),
+            derive_default: Default::default(),
+            builder: Default::default(),
+            deny_empty: Default::default(),
+            report: Default::default(),
+            debug: Default::default(),
+            input: di,
         })
     }
 
@@ -74,7 +144,7 @@ impl Core {
     }
 }
 
-impl ParseAttribute for Core {
+impl ParseAttribute for Core<'_> {
     fn parse_nested(&mut self, mi: &syn::Meta) -> Result<()> {
         let path = mi.path();
 
@@ -87,7 +157,30 @@ impl ParseAttribute for Core {
         } else if path.is_ident("rename_all") {
             // WARNING: This may have been set based on body shape previously,
             // so an overwrite may be permissible.
+            if self.rename_all_with.is_some() {
+                return Err(Error::custom(
+                    "Options `rename_all` and `rename_all_with` are mutually exclusive",
+                )
+                .with_span(mi));
+            }
+
             self.rename_rule = FromMeta::from_meta(mi)?;
+            self.rename_all_is_set = true;
+        } else if path.is_ident("rename_all_with") {
+            if self.rename_all_with.is_some() {
+                return Err(Error::duplicate_field("rename_all_with").with_span(mi));
+            }
+
+            if self.rename_all_is_set {
+                return Err(Error::custom(
+                    "Options `rename_all` and `rename_all_with` are mutually exclusive",
+                )
+                .with_span(mi));
+            }
+
+            self.rename_all_with = Some(FromMeta::from_meta(mi)?);
+            // The function receives the names as written, not the snake case enums default to.
+            self.rename_rule = RenameRule::None;
         } else if path.is_ident("map") || path.is_ident("and_then") {
             // This unwrap is safe because we just called is_ident above
             let transformer = path.get_ident().unwrap().clone();
@@ -114,6 +207,125 @@ impl ParseAttribute for Core {
             }
 
             self.allow_unknown_fields = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("error_span") {
+            if self.error_span.is_some() {
+                return Err(Error::duplicate_field("error_span").with_span(mi));
+            }
+
+            if self.dyn_fields.is_present() {
+                return Err(Error::custom(
+                    "Options `dyn_fields` and `error_span` are mutually exclusive",
+                )
+                .with_span(mi));
+            }
+
+            self.error_span = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("span") {
+            if self.span.is_some() {
+                return Err(Error::duplicate_field("span").with_span(mi));
+            }
+
+            self.span = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("renamed") {
+            self.renamed.push(FromMeta::from_meta(mi)?);
+        } else if path.is_ident("dyn_fields") {
+            if self.dyn_fields.is_present() {
+                return Err(Error::duplicate_field("dyn_fields").with_span(mi));
+            }
+
+            if self.error_span.is_some() {
+                return Err(Error::custom(
+                    "Options `dyn_fields` and `error_span` are mutually exclusive",
+                )
+                .with_span(mi));
+            }
+
+            if self.report.is_present() {
+                return Err(Error::custom(
+                    "Options `dyn_fields` and `report` are mutually exclusive",
+                )
+                .with_span(mi));
+            }
+
+            self.dyn_fields = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("config_file") {
+            if self.config_file.is_present() {
+                return Err(Error::duplicate_field("config_file").with_span(mi));
+            }
+
+            if let Data::Enum(_) = self.data {
+                return Err(
+                    Error::custom("`config_file` can only be used on structs").with_span(mi)
+                );
+            }
+
+            self.config_file = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("default_collections") {
+            if self.default_collections.is_present() {
+                return Err(Error::duplicate_field("default_collections").with_span(mi));
+            }
+
+            self.default_collections = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("derive_default") {
+            if self.derive_default.is_present() {
+                return Err(Error::duplicate_field("derive_default").with_span(mi));
+            }
+
+            if let Data::Enum(_) = self.data {
+                return Err(
+                    Error::custom("`derive_default` can only be used on structs").with_span(mi),
+                );
+            }
+
+            self.derive_default = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("builder") {
+            if self.builder.is_present() {
+                return Err(Error::duplicate_field("builder").with_span(mi));
+            }
+
+            match self.data {
+                Data::Struct(Fields {
+                    style: Style::Struct,
+                    ..
+                }) => {}
+                _ => {
+                    return Err(Error::custom(
+                        "`builder` can only be used on structs with named fields",
+                    )
+                    .with_span(mi))
+                }
+            }
+
+            self.builder = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("deny_empty") {
+            if self.deny_empty.is_present() {
+                return Err(Error::duplicate_field("deny_empty").with_span(mi));
+            }
+
+            if let Data::Enum(_) = self.data {
+                return Err(Error::custom("`deny_empty` can only be used on structs").with_span(mi));
+            }
+
+            self.deny_empty = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("report") {
+            if self.report.is_present() {
+                return Err(Error::duplicate_field("report").with_span(mi));
+            }
+
+            if self.dyn_fields.is_present() {
+                return Err(Error::custom(
+                    "Options `dyn_fields` and `report` are mutually exclusive",
+                )
+                .with_span(mi));
+            }
+
+            self.report = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("debug") {
+            if self.debug.is_present() {
+                return Err(Error::duplicate_field("debug").with_span(mi));
+            }
+
+            self.debug = FromMeta::from_meta(mi)?;
         } else {
             return Err(Error::unknown_field_path(path).with_span(mi));
         }
@@ -122,8 +334,8 @@ impl ParseAttribute for Core {
     }
 }
 
-impl ParseData for Core {
-    fn parse_variant(&mut self, variant: &syn::Variant) -> Result<()> {
+impl<'a> ParseData<'a> for Core<'a> {
+    fn parse_variant(&mut self, variant: &'a syn::Variant) -> Result<()> {
         let v = InputVariant::from_variant(variant, Some(self))?;
 
         match self.data {
@@ -131,40 +343,152 @@ impl ParseData for Core {
                 variants.push(v);
                 Ok(())
             }
-            Data::Struct(_) => panic!("Core::parse_variant should never be called for a struct"),
+            Data::Struct(_) | Data::Union(_) => {
+                panic!("Core::parse_variant should never be called for a struct")
+            }
         }
     }
 
-    fn parse_field(&mut self, field: &syn::Field) -> Result<()> {
-        let f = InputField::from_field(field, Some(self))?;
+    fn parse_field(&mut self, field: &'a syn::Field) -> Result<()> {
+        let index = match self.data {
+            Data::Struct(ref fields) => fields.len(),
+            Data::Enum(_) | Data::Union(_) => 0,
+        };
+        let mut f = InputField::from_field(field, index, Some(self))?;
+
+        // The magic field which receives the config files that were read isn't an option.
+        if self.config_file.is_present() && *f.ident == "config_files" {
+            f.skip = Some(true);
+            f.default = Some(DefaultExpression::Trait);
+            f.config_files = true;
+        }
+
+        if let Some(ref name) = f.attr_name {
+            if self.config_file.is_present() && name == CONFIG_FILE_KEY {
+                return Err(Error::custom(format!(
+                    "Field `{}` conflicts with the key which names the config file",
+                    name
+                ))
+                .with_span(field));
+            }
+
+            if self.renamed.iter().any(|renamed| renamed.old == *name) {
+                return Err(Error::custom(format!(
+                    "Field `{}` is also listed as a renamed field",
+                    name
+                ))
+                .with_span(field));
+            }
+        }
 
         match self.data {
             Data::Struct(Fields {
                 style: Style::Unit, ..
             }) => panic!("Core::parse_field should not be called on unit"),
             Data::Struct(Fields { ref mut fields, .. }) => {
+                if f.name_value.unwrap_or_default()
+                    && fields
+                        .iter()
+                        .any(|field| field.name_value.unwrap_or_default())
+                {
+                    return Err(
+                        Error::custom("Only one field can receive the name-value form")
+                            .with_span(field),
+                    );
+                }
+
                 fields.push(f);
                 Ok(())
             }
             Data::Enum(_) => panic!("Core::parse_field should never be called for an enum"),
+            Data::Union(_) => panic!("Core::parse_field should never be called for a union"),
         }
     }
 }
 
-impl<'a> From<&'a Core> for codegen::TraitImpl<'a> {
-    fn from(v: &'a Core) -> Self {
+impl<'a> From<&'a Core<'_>> for codegen::TraitImpl<'a> {
+    fn from(v: &'a Core<'_>) -> Self {
         codegen::TraitImpl {
-            ident: &v.ident,
-            generics: &v.generics,
+            ident: v.ident,
+            generics: v.generics,
             data: v
                 .data
                 .as_ref()
                 .map_struct_fields(InputField::as_codegen_field)
-                .map_enum_variants(|variant| variant.as_codegen_variant(&v.ident)),
+                .map_enum_variants(|variant| variant.as_codegen_variant(v.ident)),
             default: v.as_codegen_default(),
             post_transform: v.post_transform.as_ref(),
             bound: v.bound.as_ref().map(|i| i.as_slice()),
             allow_unknown_fields: v.allow_unknown_fields.into(),
+            renamed: &v.renamed,
+            config_file: v.config_file.into(),
+            derive_default: v.derive_default.into(),
+            builder: if v.builder.is_present() {
+                Some(v.input)
+            } else {
+                None
+            },
+            deny_empty: v.deny_empty.into(),
+            report: v.report.into(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::options::FromMetaOptions;
+
+    /// The message of the error from reading the options of `di`.
+    fn error_of(di: syn::DeriveInput) -> String {
+        match FromMetaOptions::new(&di) {
+            Ok(_) => panic!("Options should be rejected"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn dyn_fields_rejects_error_span() {
+        let di = parse_quote! {
+            #[darling(error_span = "key", dyn_fields)]
+            struct Lorem {
+                ipsum: u8,
+            }
+        };
+
+        assert_eq!(
+            error_of(di),
+            "Options `dyn_fields` and `error_span` are mutually exclusive"
+        );
+    }
+
+    #[test]
+    fn dyn_fields_rejects_report() {
+        let di = parse_quote! {
+            #[darling(report, dyn_fields)]
+            struct Lorem {
+                ipsum: u8,
+            }
+        };
+
+        assert_eq!(
+            error_of(di),
+            "Options `dyn_fields` and `report` are mutually exclusive"
+        );
+    }
+
+    #[test]
+    fn dyn_fields_rejects_field_options() {
+        let di = parse_quote! {
+            #[darling(dyn_fields)]
+            struct Lorem {
+                #[darling(multiple)]
+                ipsum: Vec<u8>,
+            }
+        };
+
+        assert_eq!(
+            error_of(di),
+            "Option `multiple` can't be used on fields of a `dyn_fields` container"
+        );
+    }
+}
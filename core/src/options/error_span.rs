@@ -0,0 +1,21 @@
+use crate::{Error, FromMeta, Result};
+
+/// Which part of a `key = value` or `key(...)` item a field's parsing errors should point at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorSpan {
+    /// Point at the key, such as `limit` in `limit = "a long value"`.
+    Key,
+    /// Point at the value: the literal after `=`, or the parenthesized list. Errors inside
+    /// a list which already point at one of its items keep their spans.
+    Value,
+}
+
+impl FromMeta for ErrorSpan {
+    fn from_string(value: &str) -> Result<Self> {
+        match value {
+            "key" => Ok(ErrorSpan::Key),
+            "value" => Ok(ErrorSpan::Value),
+            other => Err(Error::unknown_value(other)),
+        }
+    }
+}
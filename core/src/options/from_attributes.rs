@@ -1,25 +1,32 @@
 use quote::ToTokens;
 
-use crate::{ast::Data, codegen::FromAttributesImpl, Error, Result};
+use crate::{
+    ast::Data,
+    codegen::{BuilderImpl, DefaultImpl, FromAttributesImpl},
+    Error, Result,
+};
 
-use super::{OuterFrom, ParseAttribute, ParseData};
+use super::{ContainerOptions, Core, OuterFrom, ParseAttribute, ParseData};
 
 /// Receiver for derived `FromAttributes` impls.
-pub struct FromAttributesOptions {
+pub struct FromAttributesOptions<'a> {
     // Note: FromAttributes has no behaviors beyond those common
     // to all the `OuterFrom` traits.
-    pub base: OuterFrom,
+    pub base: OuterFrom<'a>,
 }
 
-impl FromAttributesOptions {
-    pub fn new(di: &syn::DeriveInput) -> Result<Self> {
+impl<'a> FromAttributesOptions<'a> {
+    pub fn new(di: &'a syn::DeriveInput) -> Result<Self> {
         let opts = (Self {
             base: OuterFrom::start(di)?,
         })
         .parse_attributes(&di.attrs)?
         .parse_body(&di.data)?;
 
-        if !opts.is_newtype() && opts.base.attr_names.is_empty() {
+        if !opts.is_newtype()
+            && opts.base.attr_names.is_empty()
+            && opts.base.attribute_prefix.is_none()
+        {
             Err(Error::custom(
                 "FromAttributes without attributes collects nothing",
             ))
@@ -37,33 +44,57 @@ impl FromAttributesOptions {
     }
 }
 
-impl ParseAttribute for FromAttributesOptions {
+impl ParseAttribute for FromAttributesOptions<'_> {
     fn parse_nested(&mut self, mi: &syn::Meta) -> Result<()> {
         self.base.parse_nested(mi)
     }
 }
 
-impl ParseData for FromAttributesOptions {
-    fn parse_variant(&mut self, variant: &syn::Variant) -> Result<()> {
+impl<'a> ParseData<'a> for FromAttributesOptions<'a> {
+    fn parse_variant(&mut self, variant: &'a syn::Variant) -> Result<()> {
         self.base.parse_variant(variant)
     }
 
-    fn parse_field(&mut self, field: &syn::Field) -> Result<()> {
+    fn parse_field(&mut self, field: &'a syn::Field) -> Result<()> {
         self.base.parse_field(field)
     }
+
+    fn validate_body(&self, errors: &mut crate::error::Accumulator) {
+        self.base.validate_body(errors);
+    }
 }
 
-impl<'a> From<&'a FromAttributesOptions> for FromAttributesImpl<'a> {
-    fn from(v: &'a FromAttributesOptions) -> Self {
+impl<'a> From<&'a FromAttributesOptions<'_>> for FromAttributesImpl<'a> {
+    fn from(v: &'a FromAttributesOptions<'_>) -> Self {
         FromAttributesImpl {
             base: (&v.base.container).into(),
             attr_names: &v.base.attr_names,
+            fallback_attr_names: &v.base.fallback_attr_names,
+            unwrap_cfg_attr: v.base.unwrap_cfg_attr,
+            expr_values: v.base.expr_values,
+            prefixed_attrs: v.base.as_prefixed_attrs(),
+            attr_span: v.base.attr_span,
+            warnings: v.base.warnings,
         }
     }
 }
 
-impl ToTokens for FromAttributesOptions {
+impl ToTokens for FromAttributesOptions<'_> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        FromAttributesImpl::from(self).to_tokens(tokens)
+        let imp = FromAttributesImpl::from(self);
+        imp.to_tokens(tokens);
+        let forwarded = vec![
+            imp.prefixed_attrs.as_ref().map(|p| p.field),
+            imp.attr_span,
+            imp.warnings,
+        ];
+        DefaultImpl::new(&imp.base, forwarded.clone()).to_tokens(tokens);
+        BuilderImpl::new(&imp.base, forwarded).to_tokens(tokens);
+    }
+}
+
+impl<'a> ContainerOptions<'a> for FromAttributesOptions<'a> {
+    fn container(&self) -> &Core<'a> {
+        &self.base.container
     }
 }
@@ -2,32 +2,41 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::Ident;
 
-use crate::codegen::FromDeriveInputImpl;
-use crate::options::{OuterFrom, ParseAttribute, ParseData, Shape};
-use crate::{FromMeta, Result};
+use crate::codegen::{BuilderImpl, DefaultImpl, FromDeriveInputImpl};
+use crate::options::{ContainerOptions, Core, OuterFrom, ParseAttribute, ParseData, Shape};
+use crate::{Error, FromMeta, Result};
 
 #[derive(Debug)]
-pub struct FdiOptions {
-    pub base: OuterFrom,
+pub struct FdiOptions<'a> {
+    pub base: OuterFrom<'a>,
 
     /// The field on the target struct which should receive the type visibility, if any.
-    pub vis: Option<Ident>,
+    pub vis: Option<&'a Ident>,
 
     /// The field on the target struct which should receive the type generics, if any.
-    pub generics: Option<Ident>,
+    pub generics: Option<&'a Ident>,
 
-    pub data: Option<Ident>,
+    pub data: Option<&'a Ident>,
+
+    /// Whether the whole input should be stored in the field named `input`.
+    pub forward_input: bool,
+
+    /// The field on the target struct which should receive a copy of the input. This is only
+    /// recognized if `forward_input` is set.
+    pub input: Option<&'a Ident>,
 
     pub supports: Option<Shape>,
 }
 
-impl FdiOptions {
-    pub fn new(di: &syn::DeriveInput) -> Result<Self> {
+impl<'a> FdiOptions<'a> {
+    pub fn new(di: &'a syn::DeriveInput) -> Result<Self> {
         (FdiOptions {
             base: OuterFrom::start(di)?,
             vis: Default::default(),
             generics: Default::default(),
             data: Default::default(),
+            forward_input: Default::default(),
+            input: Default::default(),
             supports: Default::default(),
         })
         .parse_attributes(&di.attrs)?
@@ -35,23 +44,26 @@ impl FdiOptions {
     }
 }
 
-impl ParseAttribute for FdiOptions {
+impl ParseAttribute for FdiOptions<'_> {
     fn parse_nested(&mut self, mi: &syn::Meta) -> Result<()> {
         if mi.path().is_ident("supports") {
             self.supports = FromMeta::from_meta(mi)?;
             Ok(())
+        } else if mi.path().is_ident("forward_input") {
+            self.forward_input = FromMeta::from_meta(mi)?;
+            Ok(())
         } else {
             self.base.parse_nested(mi)
         }
     }
 }
 
-impl ParseData for FdiOptions {
-    fn parse_variant(&mut self, variant: &syn::Variant) -> Result<()> {
+impl<'a> ParseData<'a> for FdiOptions<'a> {
+    fn parse_variant(&mut self, variant: &'a syn::Variant) -> Result<()> {
         self.base.parse_variant(variant)
     }
 
-    fn parse_field(&mut self, field: &syn::Field) -> Result<()> {
+    fn parse_field(&mut self, field: &'a syn::Field) -> Result<()> {
         match field
             .ident
             .as_ref()
@@ -60,41 +72,82 @@ impl ParseData for FdiOptions {
             .map(|v| v.as_str())
         {
             Some("vis") => {
-                self.vis = field.ident.clone();
+                self.vis = field.ident.as_ref();
                 Ok(())
             }
             Some("data") => {
-                self.data = field.ident.clone();
+                self.data = field.ident.as_ref();
+                Ok(())
+            }
+            Some("input") if self.forward_input => {
+                self.input = field.ident.as_ref();
                 Ok(())
             }
             Some("generics") => {
-                self.generics = field.ident.clone();
+                self.generics = field.ident.as_ref();
                 Ok(())
             }
             _ => self.base.parse_field(field),
         }
     }
+
+    fn validate_body(&self, errors: &mut crate::error::Accumulator) {
+        self.base.validate_body(errors);
+
+        if self.forward_input && self.input.is_none() {
+            errors.push(Error::custom(
+                "`forward_input` needs a field named `input` to store into",
+            ));
+        }
+    }
 }
 
-impl<'a> From<&'a FdiOptions> for FromDeriveInputImpl<'a> {
-    fn from(v: &'a FdiOptions) -> Self {
+impl<'a> From<&'a FdiOptions<'_>> for FromDeriveInputImpl<'a> {
+    fn from(v: &'a FdiOptions<'_>) -> Self {
         FromDeriveInputImpl {
             base: (&v.base.container).into(),
             attr_names: &v.base.attr_names,
+            fallback_attr_names: &v.base.fallback_attr_names,
             from_ident: v.base.from_ident,
-            ident: v.base.ident.as_ref(),
-            vis: v.vis.as_ref(),
-            data: v.data.as_ref(),
-            generics: v.generics.as_ref(),
-            attrs: v.base.attrs.as_ref(),
+            unwrap_cfg_attr: v.base.unwrap_cfg_attr,
+            expr_values: v.base.expr_values,
+            prefixed_attrs: v.base.as_prefixed_attrs(),
+            attr_span: v.base.attr_span,
+            warnings: v.base.warnings,
+            ident: v.base.ident,
+            vis: v.vis,
+            data: v.data,
+            input: v.input,
+            generics: v.generics,
+            attrs: v.base.attrs,
             forward_attrs: v.base.forward_attrs.as_ref(),
             supports: v.supports.as_ref(),
         }
     }
 }
 
-impl ToTokens for FdiOptions {
+impl ToTokens for FdiOptions<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        FromDeriveInputImpl::from(self).to_tokens(tokens)
+        let imp = FromDeriveInputImpl::from(self);
+        imp.to_tokens(tokens);
+        let forwarded = vec![
+            imp.ident,
+            imp.generics,
+            imp.vis,
+            imp.attrs,
+            imp.data,
+            imp.input,
+            imp.prefixed_attrs.as_ref().map(|p| p.field),
+            imp.attr_span,
+            imp.warnings,
+        ];
+        DefaultImpl::new(&imp.base, forwarded.clone()).to_tokens(tokens);
+        BuilderImpl::new(&imp.base, forwarded).to_tokens(tokens);
+    }
+}
+
+impl<'a> ContainerOptions<'a> for FdiOptions<'a> {
+    fn container(&self) -> &Core<'a> {
+        &self.base.container
     }
 }
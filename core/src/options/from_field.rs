@@ -2,41 +2,43 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::Ident;
 
-use crate::codegen::FromFieldImpl;
-use crate::options::{OuterFrom, ParseAttribute, ParseData};
+use crate::codegen::{BuilderImpl, DefaultImpl, FromFieldImpl};
+use crate::options::{ContainerOptions, Core, OuterFrom, ParseAttribute, ParseData};
 use crate::Result;
 
 #[derive(Debug)]
-pub struct FromFieldOptions {
-    pub base: OuterFrom,
-    pub vis: Option<Ident>,
-    pub ty: Option<Ident>,
+pub struct FromFieldOptions<'a> {
+    pub base: OuterFrom<'a>,
+    pub vis: Option<&'a Ident>,
+    pub ty: Option<&'a Ident>,
+    pub index: Option<&'a Ident>,
 }
 
-impl FromFieldOptions {
-    pub fn new(di: &syn::DeriveInput) -> Result<Self> {
+impl<'a> FromFieldOptions<'a> {
+    pub fn new(di: &'a syn::DeriveInput) -> Result<Self> {
         (FromFieldOptions {
             base: OuterFrom::start(di)?,
             vis: Default::default(),
             ty: Default::default(),
+            index: Default::default(),
         })
         .parse_attributes(&di.attrs)?
         .parse_body(&di.data)
     }
 }
 
-impl ParseAttribute for FromFieldOptions {
+impl ParseAttribute for FromFieldOptions<'_> {
     fn parse_nested(&mut self, mi: &syn::Meta) -> Result<()> {
         self.base.parse_nested(mi)
     }
 }
 
-impl ParseData for FromFieldOptions {
-    fn parse_variant(&mut self, variant: &syn::Variant) -> Result<()> {
+impl<'a> ParseData<'a> for FromFieldOptions<'a> {
+    fn parse_variant(&mut self, variant: &'a syn::Variant) -> Result<()> {
         self.base.parse_variant(variant)
     }
 
-    fn parse_field(&mut self, field: &syn::Field) -> Result<()> {
+    fn parse_field(&mut self, field: &'a syn::Field) -> Result<()> {
         match field
             .ident
             .as_ref()
@@ -45,35 +47,69 @@ impl ParseData for FromFieldOptions {
             .map(|v| v.as_str())
         {
             Some("vis") => {
-                self.vis = field.ident.clone();
+                self.vis = field.ident.as_ref();
                 Ok(())
             }
             Some("ty") => {
-                self.ty = field.ident.clone();
+                self.ty = field.ident.as_ref();
+                Ok(())
+            }
+            Some("index") => {
+                self.index = field.ident.as_ref();
                 Ok(())
             }
             _ => self.base.parse_field(field),
         }
     }
+
+    fn validate_body(&self, errors: &mut crate::error::Accumulator) {
+        self.base.validate_body(errors);
+    }
 }
 
-impl<'a> From<&'a FromFieldOptions> for FromFieldImpl<'a> {
-    fn from(v: &'a FromFieldOptions) -> Self {
+impl<'a> From<&'a FromFieldOptions<'_>> for FromFieldImpl<'a> {
+    fn from(v: &'a FromFieldOptions<'_>) -> Self {
         FromFieldImpl {
-            ident: v.base.ident.as_ref(),
-            vis: v.vis.as_ref(),
-            ty: v.ty.as_ref(),
-            attrs: v.base.attrs.as_ref(),
+            ident: v.base.ident,
+            vis: v.vis,
+            ty: v.ty,
+            index: v.index,
+            attrs: v.base.attrs,
             base: (&v.base.container).into(),
             attr_names: &v.base.attr_names,
+            fallback_attr_names: &v.base.fallback_attr_names,
             forward_attrs: v.base.forward_attrs.as_ref(),
             from_ident: v.base.from_ident,
+            unwrap_cfg_attr: v.base.unwrap_cfg_attr,
+            expr_values: v.base.expr_values,
+            prefixed_attrs: v.base.as_prefixed_attrs(),
+            attr_span: v.base.attr_span,
+            warnings: v.base.warnings,
         }
     }
 }
 
-impl ToTokens for FromFieldOptions {
+impl ToTokens for FromFieldOptions<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        FromFieldImpl::from(self).to_tokens(tokens)
+        let imp = FromFieldImpl::from(self);
+        imp.to_tokens(tokens);
+        let forwarded = vec![
+            imp.ident,
+            imp.vis,
+            imp.ty,
+            imp.index,
+            imp.attrs,
+            imp.prefixed_attrs.as_ref().map(|p| p.field),
+            imp.attr_span,
+            imp.warnings,
+        ];
+        DefaultImpl::new(&imp.base, forwarded.clone()).to_tokens(tokens);
+        BuilderImpl::new(&imp.base, forwarded).to_tokens(tokens);
+    }
+}
+
+impl<'a> ContainerOptions<'a> for FromFieldOptions<'a> {
+    fn container(&self) -> &Core<'a> {
+        &self.base.container
     }
 }
@@ -1,50 +1,159 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 
-use crate::codegen::FromMetaImpl;
-use crate::options::{Core, ParseAttribute, ParseData};
-use crate::Result;
+use crate::ast::Data;
+use crate::codegen::{BuilderImpl, DefaultImpl, FromMetaImpl, MetaSchemaImpl, ToMetaImpl};
+use crate::options::{ContainerOptions, Core, ParseAttribute, ParseData};
+use crate::{Error, FromMeta, Result};
 
-pub struct FromMetaOptions {
-    base: Core,
+pub struct FromMetaOptions<'a> {
+    base: Core<'a>,
+
+    /// The deepest nesting of meta lists the generated impl will accept.
+    max_depth: Option<usize>,
+
+    /// A function which produces the value when the type appears as a bare word.
+    from_word: Option<syn::Path>,
+
+    /// A function which produces the value when the type is absent from the input.
+    from_none: Option<syn::Path>,
 }
 
-impl FromMetaOptions {
-    pub fn new(di: &syn::DeriveInput) -> Result<Self> {
+impl<'a> FromMetaOptions<'a> {
+    pub fn new(di: &'a syn::DeriveInput) -> Result<Self> {
         (FromMetaOptions {
             base: Core::start(di)?,
+            max_depth: Default::default(),
+            from_word: Default::default(),
+            from_none: Default::default(),
         })
         .parse_attributes(&di.attrs)?
-        .parse_body(&di.data)
+        .parse_body(&di.data)?
+        .validate()
+    }
+
+    /// Reject hooks which the generated impl would never call.
+    fn validate(self) -> Result<Self> {
+        if let Some(ref from_word) = self.from_word {
+            if let Data::Struct(ref data) = self.base.data {
+                if data.style.is_unit() || data.is_newtype() {
+                    return Err(Error::custom(
+                        "`from_word` cannot be used on unit or newtype structs",
+                    )
+                    .with_span(from_word));
+                }
+            }
+        }
+
+        Ok(self)
     }
 }
 
-impl ParseAttribute for FromMetaOptions {
+impl ParseAttribute for FromMetaOptions<'_> {
     fn parse_nested(&mut self, mi: &syn::Meta) -> Result<()> {
-        self.base.parse_nested(mi)
+        if mi.path().is_ident("max_depth") {
+            if self.max_depth.is_some() {
+                return Err(Error::duplicate_field("max_depth").with_span(mi));
+            }
+
+            self.max_depth = FromMeta::from_meta(mi)?;
+            Ok(())
+        } else if mi.path().is_ident("from_word") {
+            if self.from_word.is_some() {
+                return Err(Error::duplicate_field("from_word").with_span(mi));
+            }
+
+            self.from_word = FromMeta::from_meta(mi)?;
+            Ok(())
+        } else if mi.path().is_ident("from_none") {
+            if self.from_none.is_some() {
+                return Err(Error::duplicate_field("from_none").with_span(mi));
+            }
+
+            self.from_none = FromMeta::from_meta(mi)?;
+            Ok(())
+        } else {
+            self.base.parse_nested(mi)
+        }
     }
 }
 
-impl ParseData for FromMetaOptions {
-    fn parse_variant(&mut self, variant: &syn::Variant) -> Result<()> {
+impl<'a> ParseData<'a> for FromMetaOptions<'a> {
+    fn parse_variant(&mut self, variant: &'a syn::Variant) -> Result<()> {
         self.base.parse_variant(variant)
     }
 
-    fn parse_field(&mut self, field: &syn::Field) -> Result<()> {
+    fn parse_field(&mut self, field: &'a syn::Field) -> Result<()> {
         self.base.parse_field(field)
     }
 }
 
-impl<'a> From<&'a FromMetaOptions> for FromMetaImpl<'a> {
-    fn from(v: &'a FromMetaOptions) -> Self {
+impl<'a> From<&'a FromMetaOptions<'_>> for FromMetaImpl<'a> {
+    fn from(v: &'a FromMetaOptions<'_>) -> Self {
         FromMetaImpl {
             base: (&v.base).into(),
+            max_depth: v.max_depth,
+            from_word: v.from_word.as_ref(),
+            from_none: v.from_none.as_ref(),
         }
     }
 }
 
-impl ToTokens for FromMetaOptions {
+impl ToTokens for FromMetaOptions<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let imp = FromMetaImpl::from(self);
+        imp.to_tokens(tokens);
+        DefaultImpl::new(&imp.base, None).to_tokens(tokens);
+        BuilderImpl::new(&imp.base, None).to_tokens(tokens);
+    }
+}
+
+/// Options for a `MetaSchema` impl, which are read from the same attributes as `FromMeta`
+/// so the description matches what the `FromMeta` impl accepts.
+pub struct MetaSchemaOptions<'a>(FromMetaOptions<'a>);
+
+impl<'a> MetaSchemaOptions<'a> {
+    pub fn new(di: &'a syn::DeriveInput) -> Result<Self> {
+        FromMetaOptions::new(di).map(MetaSchemaOptions)
+    }
+}
+
+impl ToTokens for MetaSchemaOptions<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        MetaSchemaImpl::new((&self.0.base).into()).to_tokens(tokens)
+    }
+}
+
+/// Options for a `ToMeta` impl, which are read from the same attributes as `FromMeta` so the
+/// output is in the syntax the `FromMeta` impl reads.
+pub struct ToMetaOptions<'a>(FromMetaOptions<'a>);
+
+impl<'a> ToMetaOptions<'a> {
+    pub fn new(di: &'a syn::DeriveInput) -> Result<Self> {
+        FromMetaOptions::new(di).map(ToMetaOptions)
+    }
+}
+
+impl ToTokens for ToMetaOptions<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        FromMetaImpl::from(self).to_tokens(tokens)
+        ToMetaImpl::new((&self.0.base).into()).to_tokens(tokens)
+    }
+}
+
+impl<'a> ContainerOptions<'a> for FromMetaOptions<'a> {
+    fn container(&self) -> &Core<'a> {
+        &self.base
+    }
+}
+
+impl<'a> ContainerOptions<'a> for MetaSchemaOptions<'a> {
+    fn container(&self) -> &Core<'a> {
+        self.0.container()
+    }
+}
+
+impl<'a> ContainerOptions<'a> for ToMetaOptions<'a> {
+    fn container(&self) -> &Core<'a> {
+        self.0.container()
     }
 }
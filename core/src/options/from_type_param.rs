@@ -2,19 +2,19 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::Ident;
 
-use crate::codegen::FromTypeParamImpl;
-use crate::options::{OuterFrom, ParseAttribute, ParseData};
+use crate::codegen::{BuilderImpl, DefaultImpl, FromTypeParamImpl};
+use crate::options::{ContainerOptions, Core, OuterFrom, ParseAttribute, ParseData};
 use crate::Result;
 
 #[derive(Debug)]
-pub struct FromTypeParamOptions {
-    pub base: OuterFrom,
-    pub bounds: Option<Ident>,
-    pub default: Option<Ident>,
+pub struct FromTypeParamOptions<'a> {
+    pub base: OuterFrom<'a>,
+    pub bounds: Option<&'a Ident>,
+    pub default: Option<&'a Ident>,
 }
 
-impl FromTypeParamOptions {
-    pub fn new(di: &syn::DeriveInput) -> Result<Self> {
+impl<'a> FromTypeParamOptions<'a> {
+    pub fn new(di: &'a syn::DeriveInput) -> Result<Self> {
         (FromTypeParamOptions {
             base: OuterFrom::start(di)?,
             bounds: None,
@@ -25,18 +25,18 @@ impl FromTypeParamOptions {
     }
 }
 
-impl ParseAttribute for FromTypeParamOptions {
+impl ParseAttribute for FromTypeParamOptions<'_> {
     fn parse_nested(&mut self, mi: &syn::Meta) -> Result<()> {
         self.base.parse_nested(mi)
     }
 }
 
-impl ParseData for FromTypeParamOptions {
-    fn parse_variant(&mut self, variant: &syn::Variant) -> Result<()> {
+impl<'a> ParseData<'a> for FromTypeParamOptions<'a> {
+    fn parse_variant(&mut self, variant: &'a syn::Variant) -> Result<()> {
         self.base.parse_variant(variant)
     }
 
-    fn parse_field(&mut self, field: &syn::Field) -> Result<()> {
+    fn parse_field(&mut self, field: &'a syn::Field) -> Result<()> {
         match field
             .ident
             .as_ref()
@@ -45,35 +45,63 @@ impl ParseData for FromTypeParamOptions {
             .map(|v| v.as_str())
         {
             Some("bounds") => {
-                self.bounds = field.ident.clone();
+                self.bounds = field.ident.as_ref();
                 Ok(())
             }
             Some("default") => {
-                self.default = field.ident.clone();
+                self.default = field.ident.as_ref();
                 Ok(())
             }
             _ => self.base.parse_field(field),
         }
     }
+
+    fn validate_body(&self, errors: &mut crate::error::Accumulator) {
+        self.base.validate_body(errors);
+    }
 }
 
-impl<'a> From<&'a FromTypeParamOptions> for FromTypeParamImpl<'a> {
-    fn from(v: &'a FromTypeParamOptions) -> Self {
+impl<'a> From<&'a FromTypeParamOptions<'_>> for FromTypeParamImpl<'a> {
+    fn from(v: &'a FromTypeParamOptions<'_>) -> Self {
         FromTypeParamImpl {
             base: (&v.base.container).into(),
-            ident: v.base.ident.as_ref(),
-            attrs: v.base.attrs.as_ref(),
-            bounds: v.bounds.as_ref(),
-            default: v.default.as_ref(),
+            ident: v.base.ident,
+            attrs: v.base.attrs,
+            bounds: v.bounds,
+            default: v.default,
             attr_names: &v.base.attr_names,
+            fallback_attr_names: &v.base.fallback_attr_names,
             forward_attrs: v.base.forward_attrs.as_ref(),
             from_ident: v.base.from_ident,
+            unwrap_cfg_attr: v.base.unwrap_cfg_attr,
+            expr_values: v.base.expr_values,
+            prefixed_attrs: v.base.as_prefixed_attrs(),
+            attr_span: v.base.attr_span,
+            warnings: v.base.warnings,
         }
     }
 }
 
-impl ToTokens for FromTypeParamOptions {
+impl ToTokens for FromTypeParamOptions<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        FromTypeParamImpl::from(self).to_tokens(tokens)
+        let imp = FromTypeParamImpl::from(self);
+        imp.to_tokens(tokens);
+        let forwarded = vec![
+            imp.ident,
+            imp.attrs,
+            imp.bounds,
+            imp.default,
+            imp.prefixed_attrs.as_ref().map(|p| p.field),
+            imp.attr_span,
+            imp.warnings,
+        ];
+        DefaultImpl::new(&imp.base, forwarded.clone()).to_tokens(tokens);
+        BuilderImpl::new(&imp.base, forwarded).to_tokens(tokens);
+    }
+}
+
+impl<'a> ContainerOptions<'a> for FromTypeParamOptions<'a> {
+    fn container(&self) -> &Core<'a> {
+        &self.base.container
     }
 }
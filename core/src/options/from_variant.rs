@@ -2,22 +2,22 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{DeriveInput, Field, Ident, Meta};
 
-use crate::codegen::FromVariantImpl;
-use crate::options::{DataShape, OuterFrom, ParseAttribute, ParseData};
+use crate::codegen::{BuilderImpl, DefaultImpl, FromVariantImpl};
+use crate::options::{ContainerOptions, Core, DataShape, OuterFrom, ParseAttribute, ParseData};
 use crate::{FromMeta, Result};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct FromVariantOptions {
-    pub base: OuterFrom,
+pub struct FromVariantOptions<'a> {
+    pub base: OuterFrom<'a>,
     /// The field on the deriving struct into which the discriminant expression
     /// should be placed by the derived `FromVariant` impl.
-    pub discriminant: Option<Ident>,
-    pub fields: Option<Ident>,
+    pub discriminant: Option<&'a Ident>,
+    pub fields: Option<&'a Ident>,
     pub supports: Option<DataShape>,
 }
 
-impl FromVariantOptions {
-    pub fn new(di: &DeriveInput) -> Result<Self> {
+impl<'a> FromVariantOptions<'a> {
+    pub fn new(di: &'a DeriveInput) -> Result<Self> {
         (FromVariantOptions {
             base: OuterFrom::start(di)?,
             discriminant: Default::default(),
@@ -29,23 +29,29 @@ impl FromVariantOptions {
     }
 }
 
-impl<'a> From<&'a FromVariantOptions> for FromVariantImpl<'a> {
-    fn from(v: &'a FromVariantOptions) -> Self {
+impl<'a> From<&'a FromVariantOptions<'_>> for FromVariantImpl<'a> {
+    fn from(v: &'a FromVariantOptions<'_>) -> Self {
         FromVariantImpl {
             base: (&v.base.container).into(),
-            ident: v.base.ident.as_ref(),
-            discriminant: v.discriminant.as_ref(),
-            fields: v.fields.as_ref(),
-            attrs: v.base.attrs.as_ref(),
+            ident: v.base.ident,
+            discriminant: v.discriminant,
+            fields: v.fields,
+            attrs: v.base.attrs,
             attr_names: &v.base.attr_names,
+            fallback_attr_names: &v.base.fallback_attr_names,
             forward_attrs: v.base.forward_attrs.as_ref(),
             from_ident: v.base.from_ident,
+            unwrap_cfg_attr: v.base.unwrap_cfg_attr,
+            expr_values: v.base.expr_values,
+            prefixed_attrs: v.base.as_prefixed_attrs(),
+            attr_span: v.base.attr_span,
+            warnings: v.base.warnings,
             supports: v.supports.as_ref(),
         }
     }
 }
 
-impl ParseAttribute for FromVariantOptions {
+impl ParseAttribute for FromVariantOptions<'_> {
     fn parse_nested(&mut self, mi: &Meta) -> Result<()> {
         if mi.path().is_ident("supports") {
             self.supports = FromMeta::from_meta(mi)?;
@@ -56,8 +62,8 @@ impl ParseAttribute for FromVariantOptions {
     }
 }
 
-impl ParseData for FromVariantOptions {
-    fn parse_field(&mut self, field: &Field) -> Result<()> {
+impl<'a> ParseData<'a> for FromVariantOptions<'a> {
+    fn parse_field(&mut self, field: &'a Field) -> Result<()> {
         match field
             .ident
             .as_ref()
@@ -66,20 +72,42 @@ impl ParseData for FromVariantOptions {
             .map(|v| v.as_str())
         {
             Some("discriminant") => {
-                self.discriminant = field.ident.clone();
+                self.discriminant = field.ident.as_ref();
                 Ok(())
             }
             Some("fields") => {
-                self.fields = field.ident.clone();
+                self.fields = field.ident.as_ref();
                 Ok(())
             }
             _ => self.base.parse_field(field),
         }
     }
+
+    fn validate_body(&self, errors: &mut crate::error::Accumulator) {
+        self.base.validate_body(errors);
+    }
 }
 
-impl ToTokens for FromVariantOptions {
+impl ToTokens for FromVariantOptions<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        FromVariantImpl::from(self).to_tokens(tokens)
+        let imp = FromVariantImpl::from(self);
+        imp.to_tokens(tokens);
+        let forwarded = vec![
+            imp.ident,
+            imp.fields,
+            imp.attrs,
+            imp.discriminant,
+            imp.prefixed_attrs.as_ref().map(|p| p.field),
+            imp.attr_span,
+            imp.warnings,
+        ];
+        DefaultImpl::new(&imp.base, forwarded.clone()).to_tokens(tokens);
+        BuilderImpl::new(&imp.base, forwarded).to_tokens(tokens);
+    }
+}
+
+impl<'a> ContainerOptions<'a> for FromVariantOptions<'a> {
+    fn container(&self) -> &Core<'a> {
+        &self.base.container
     }
 }
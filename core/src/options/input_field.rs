@@ -1,43 +1,105 @@
 use std::borrow::Cow;
 
 use crate::codegen;
-use crate::options::{Core, DefaultExpression, ParseAttribute};
+use crate::options::{
+    Core, DefaultExpression, ErrorSpan, ParseAttribute, RequiredField, SkippedInput, SpanStrategy,
+};
 use crate::{Error, FromMeta, Result};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct InputField {
-    pub ident: syn::Ident,
+pub struct InputField<'a> {
+    pub ident: Cow<'a, syn::Ident>,
     pub attr_name: Option<String>,
-    pub ty: syn::Type,
+    /// Other keys the field can be read from, in addition to `attr_name`.
+    pub aliases: Vec<String>,
+    /// The container's `rename_all_with` function, if it renames this field.
+    pub rename_with: Option<syn::Path>,
+    pub ty: &'a syn::Type,
     pub default: Option<DefaultExpression>,
     pub with: Option<syn::Path>,
 
     /// If `true`, generated code will not look for this field in the input meta item,
     /// instead always falling back to either `InputField::default` or `Default::default`.
     pub skip: Option<bool>,
+    /// What happens when the input sets a skipped field. The default depends on whether the
+    /// container allows unknown fields.
+    pub skipped_input: Option<SkippedInput>,
     pub post_transform: Option<codegen::PostfixTransform>,
     pub multiple: Option<bool>,
+    pub error_span: Option<ErrorSpan>,
+    /// If set, the field is always required, and its absence is reported with this guidance.
+    pub required: Option<RequiredField>,
+    /// If set, using this field records a deprecation warning with this note.
+    pub deprecated: Option<String>,
+    /// Whether the field is read through a `DynFieldSlot`. This is set by the container.
+    pub dyn_fields: bool,
+    /// If set, the field is read as a list from the keys which start with this prefix,
+    /// with the prefix removed.
+    pub prefix: Option<String>,
+    /// If `true`, the field receives the value of the name-value form of the attribute, such
+    /// as `#[my_attr = "value"]`.
+    pub name_value: Option<bool>,
+    /// The position of an unnamed field, which is read from the positional items of the
+    /// input rather than by name.
+    pub index: Option<usize>,
+    /// Which spans the generated code for this field carries. This is set by the container.
+    pub span: SpanStrategy,
+    /// Whether the field is noted in the parse report. This is set by the container.
+    pub report: bool,
+    /// If `true`, the field receives the config files read by a `config_file` container.
+    /// This is set by the container.
+    pub config_files: bool,
 }
 
-impl InputField {
+impl<'a> InputField<'a> {
     /// Generate a view into this field that can be used for code generation.
     pub fn as_codegen_field(&self) -> codegen::Field<'_> {
         codegen::Field {
             ident: &self.ident,
-            name_in_attr: self
-                .attr_name
-                .as_ref()
-                .map_or_else(|| Cow::Owned(self.ident.to_string()), Cow::Borrowed),
-            ty: &self.ty,
+            name_in_attr: self.attr_name.as_ref().map_or_else(
+                || Cow::Owned(self.ident.to_string()),
+                |s| Cow::Borrowed(s.as_str()),
+            ),
+            aliases: &self.aliases,
+            rename_with: self.rename_with.as_ref(),
+            ty: self.ty,
             default_expression: self.as_codegen_default(),
             with_path: self.with.as_ref().map_or_else(
-                || Cow::Owned(parse_quote!(::darling::FromMeta::from_meta)),
+                || {
+                    let span = codegen::field_span(self.ty, self.span == SpanStrategy::CallSite);
+                    Cow::Owned(codegen::default_with_path(span))
+                },
                 Cow::Borrowed,
             ),
+            default_with: self.with.is_none(),
             skip: self.skip.unwrap_or_default(),
+            skipped_input: self.skipped_input.unwrap_or_default(),
             post_transform: self.post_transform.as_ref(),
             multiple: self.multiple.unwrap_or_default(),
+            from_none: self.as_codegen_from_none(),
+            error_span: self.error_span,
+            call_site_span: self.span == SpanStrategy::CallSite,
+            deprecated: self.deprecated.as_ref().map(String::as_str),
+            dyn_fields: self.dyn_fields,
+            required: self.required.as_ref(),
+            prefix: self.prefix.as_ref().map(String::as_str),
+            name_value: self.name_value.unwrap_or_default(),
+            index: self.index,
+            report: self.report,
+            config_files: self.config_files,
+        }
+    }
+
+    /// Generate the fallback used when this field is absent from the input. Fields parsed
+    /// by a custom function or transformed after parsing can't rely on their own type's
+    /// `FromMeta` impl, so they don't get one.
+    fn as_codegen_from_none(&self) -> Option<syn::Expr> {
+        if self.with.is_some() || self.post_transform.is_some() || self.required.is_some() {
+            return None;
         }
+
+        let span = codegen::field_span(self.ty, self.span == SpanStrategy::CallSite);
+        Some(codegen::default_from_none(self.ty, span))
     }
 
     /// Generate a codegen::DefaultExpression for this field. This requires the field name
@@ -50,26 +112,166 @@ impl InputField {
         })
     }
 
-    fn new(ident: syn::Ident, ty: syn::Type) -> Self {
+    fn new(ident: Cow<'a, syn::Ident>, ty: &'a syn::Type) -> Self {
         InputField {
             ident,
             ty,
             attr_name: None,
+            aliases: Vec::new(),
+            rename_with: None,
             default: None,
             with: None,
             skip: None,
+            skipped_input: None,
             post_transform: Default::default(),
             multiple: None,
+            error_span: None,
+            span: SpanStrategy::default(),
+            deprecated: None,
+            dyn_fields: false,
+            required: None,
+            prefix: None,
+            name_value: None,
+            index: None,
+            report: false,
+            config_files: false,
         }
     }
 
-    pub fn from_field(f: &syn::Field, parent: Option<&Core>) -> Result<Self> {
-        let ident = f
-            .ident
-            .clone()
-            .unwrap_or_else(|| syn::Ident::new("__unnamed", ::proc_macro2::Span::call_site()));
-        let ty = f.ty.clone();
-        let base = Self::new(ident, ty).parse_attributes(&f.attrs)?;
+    /// Read the options of `f`, which is at position `index` of its parent.
+    pub fn from_field(f: &'a syn::Field, index: usize, parent: Option<&Core>) -> Result<Self> {
+        let ident = f.ident.as_ref().map_or_else(
+            || {
+                Cow::Owned(syn::Ident::new(
+                    &format!("__unnamed_{}", index),
+                    ::proc_macro2::Span::call_site(),
+                ))
+            },
+            Cow::Borrowed,
+        );
+        let mut base = Self::new(ident, &f.ty).parse_attributes(&f.attrs)?;
+        if f.ident.is_none() {
+            // Unnamed fields are read by position, so options about their name don't apply.
+            let conflict = if base.attr_name.is_some() {
+                Some("rename")
+            } else if !base.aliases.is_empty() {
+                Some("alias")
+            } else if base.with.is_some() {
+                Some("with")
+            } else if base.multiple.unwrap_or_default() {
+                Some("multiple")
+            } else if base.prefix.is_some() {
+                Some("prefix")
+            } else if base.name_value.unwrap_or_default() {
+                Some("name_value")
+            } else {
+                None
+            };
+
+            if let Some(conflict) = conflict {
+                return Err(Error::custom(format!(
+                    "Option `{}` can't be used on unnamed fields",
+                    conflict
+                ))
+                .with_span(f));
+            }
+
+            base.index = Some(index);
+            base.attr_name = Some(index.to_string());
+        }
+
+        if base.skipped_input.is_some() && !base.skip.unwrap_or_default() {
+            return Err(Error::custom("`skipped_input` can only be used with `skip`").with_span(f));
+        }
+
+        if base.required.is_some() {
+            let conflict = if base.default.is_some() {
+                Some("default")
+            } else if base.skip.unwrap_or_default() {
+                Some("skip")
+            } else if base.multiple.unwrap_or_default() {
+                Some("multiple")
+            } else {
+                None
+            };
+
+            if let Some(conflict) = conflict {
+                return Err(Error::custom(format!(
+                    "Options `required` and `{}` are mutually exclusive",
+                    conflict
+                ))
+                .with_span(f));
+            }
+        }
+
+        if base.prefix.is_some() {
+            let conflict = if base.with.is_some() {
+                Some("with")
+            } else if base.skip.unwrap_or_default() {
+                Some("skip")
+            } else if base.multiple.unwrap_or_default() {
+                Some("multiple")
+            } else if !base.aliases.is_empty() {
+                Some("alias")
+            } else {
+                None
+            };
+
+            if let Some(conflict) = conflict {
+                return Err(Error::custom(format!(
+                    "Options `prefix` and `{}` are mutually exclusive",
+                    conflict
+                ))
+                .with_span(f));
+            }
+        }
+
+        if base.name_value.unwrap_or_default() {
+            let conflict = if base.skip.unwrap_or_default() {
+                Some("skip")
+            } else if base.multiple.unwrap_or_default() {
+                Some("multiple")
+            } else if base.prefix.is_some() {
+                Some("prefix")
+            } else {
+                None
+            };
+
+            if let Some(conflict) = conflict {
+                return Err(Error::custom(format!(
+                    "Options `name_value` and `{}` are mutually exclusive",
+                    conflict
+                ))
+                .with_span(f));
+            }
+        }
+
+        if parent.map_or(false, |container| container.dyn_fields.is_present()) {
+            // These options need code generated for the field, which `dyn_fields` doesn't have.
+            let conflict = if base.with.is_some() {
+                Some("with")
+            } else if let Some(ref post_transform) = base.post_transform {
+                Some(if post_transform.transformer == "map" {
+                    "map"
+                } else {
+                    "and_then"
+                })
+            } else if base.multiple.unwrap_or_default() {
+                Some("multiple")
+            } else if base.error_span.is_some() {
+                Some("error_span")
+            } else {
+                None
+            };
+
+            if let Some(conflict) = conflict {
+                return Err(Error::custom(format!(
+                    "Option `{}` can't be used on fields of a `dyn_fields` container",
+                    conflict
+                ))
+                .with_span(f));
+            }
+        }
 
         Ok(if let Some(container) = parent {
             base.with_inherited(container)
@@ -85,8 +287,28 @@ impl InputField {
         // but in the absence of an explicit name we apply the rule.
         if self.attr_name.is_none() {
             self.attr_name = Some(parent.rename_rule.apply_to_field(self.ident.to_string()));
+
+            // Prefixed fields aren't matched by name, so there's nothing to rename.
+            if self.prefix.is_none() {
+                self.rename_with = parent.rename_all_with.clone();
+            }
+        }
+
+        if self.error_span.is_none() {
+            self.error_span = parent.error_span;
         }
 
+        self.dyn_fields = parent.dyn_fields.is_present();
+
+        // Containers which accept unknown fields have always accepted skipped ones, so
+        // they only get a warning.
+        if self.skipped_input.is_none() && parent.allow_unknown_fields.is_present() {
+            self.skipped_input = Some(SkippedInput::Warn);
+        }
+
+        self.span = parent.span.unwrap_or_default();
+        self.report = parent.report.is_present();
+
         // Determine the default expression for this field, based on three pieces of information:
         // 1. Will we look for this field in the attribute?
         // 1. Is there a locally-defined default?
@@ -99,6 +321,9 @@ impl InputField {
             // If we have a default, use it.
             (_, true, _) => self.default,
 
+            // Required fields don't fall back to the struct's default.
+            (_, false, true) if self.required.is_some() => None,
+
             // If there isn't an explicit default but the struct sets a default, we'll
             // inherit from that.
             (_, false, true) => Some(DefaultExpression::Inherit),
@@ -107,6 +332,13 @@ impl InputField {
             // use the ::darling::export::Default trait.
             (true, false, false) => Some(DefaultExpression::Trait),
 
+            // Collections are empty when absent if the container opts in.
+            (false, false, false)
+                if parent.default_collections.is_present() && is_collection(self.ty) =>
+            {
+                Some(DefaultExpression::Trait)
+            }
+
             // If we don't have or need a default, then leave it blank.
             (false, false, false) => None,
         };
@@ -115,7 +347,31 @@ impl InputField {
     }
 }
 
-impl ParseAttribute for InputField {
+/// The standard library collections which `default_collections` applies to.
+const COLLECTIONS: &[&str] = &[
+    "Vec",
+    "VecDeque",
+    "LinkedList",
+    "HashMap",
+    "HashSet",
+    "BTreeMap",
+    "BTreeSet",
+    "BinaryHeap",
+];
+
+/// Returns `true` if `ty` is written as one of the standard library collections.
+fn is_collection(ty: &syn::Type) -> bool {
+    match *ty {
+        syn::Type::Path(ref ty) if ty.qself.is_none() => {
+            ty.path.segments.last().map_or(false, |segment| {
+                COLLECTIONS.iter().any(|name| segment.ident == name)
+            })
+        }
+        _ => false,
+    }
+}
+
+impl ParseAttribute for InputField<'_> {
     fn parse_nested(&mut self, mi: &syn::Meta) -> Result<()> {
         let path = mi.path();
 
@@ -125,6 +381,9 @@ impl ParseAttribute for InputField {
             }
 
             self.attr_name = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("alias") {
+            // Each occurrence adds another key the field can be read from.
+            self.aliases.push(FromMeta::from_meta(mi)?);
         } else if path.is_ident("default") {
             if self.default.is_some() {
                 return Err(Error::duplicate_field_path(path).with_span(mi));
@@ -142,6 +401,12 @@ impl ParseAttribute for InputField {
             }
 
             self.skip = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("skipped_input") {
+            if self.skipped_input.is_some() {
+                return Err(Error::duplicate_field_path(path).with_span(mi));
+            }
+
+            self.skipped_input = Some(FromMeta::from_meta(mi)?);
         } else if path.is_ident("map") || path.is_ident("and_then") {
             let transformer = path.get_ident().unwrap().clone();
             if let Some(post_transform) = &self.post_transform {
@@ -166,6 +431,41 @@ impl ParseAttribute for InputField {
             }
 
             self.multiple = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("error_span") {
+            if self.error_span.is_some() {
+                return Err(Error::duplicate_field_path(path).with_span(mi));
+            }
+
+            self.error_span = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("required") {
+            if self.required.is_some() {
+                return Err(Error::duplicate_field_path(path).with_span(mi));
+            }
+
+            self.required = Some(FromMeta::from_meta(mi)?);
+        } else if path.is_ident("deprecated") {
+            if self.deprecated.is_some() {
+                return Err(Error::duplicate_field_path(path).with_span(mi));
+            }
+
+            self.deprecated = Some(FromMeta::from_meta(mi)?);
+        } else if path.is_ident("name_value") {
+            if self.name_value.is_some() {
+                return Err(Error::duplicate_field_path(path).with_span(mi));
+            }
+
+            self.name_value = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("prefix") {
+            if self.prefix.is_some() {
+                return Err(Error::duplicate_field_path(path).with_span(mi));
+            }
+
+            let prefix: String = FromMeta::from_meta(mi)?;
+            if prefix.is_empty() {
+                return Err(Error::custom("`prefix` can't be empty").with_span(mi));
+            }
+
+            self.prefix = Some(prefix);
         } else {
             return Err(Error::unknown_field_path(path).with_span(mi));
         }
@@ -6,37 +6,49 @@ use crate::options::{Core, InputField, ParseAttribute};
 use crate::{Error, FromMeta, Result};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct InputVariant {
-    ident: syn::Ident,
+pub struct InputVariant<'a> {
+    ident: &'a syn::Ident,
     attr_name: Option<String>,
-    data: Fields<InputField>,
+    /// The container's `rename_all_with` function, if it renames this variant.
+    rename_with: Option<syn::Path>,
+    data: Fields<InputField<'a>>,
     skip: Option<bool>,
     /// Whether or not unknown fields are acceptable in this
     allow_unknown_fields: Option<bool>,
+    /// Whether the variant's fields are noted in the parse report. This is set by the container.
+    report: bool,
+    /// If set, using this variant records a deprecation warning with this note.
+    deprecated: Option<String>,
 }
 
-impl InputVariant {
-    pub fn as_codegen_variant<'a>(&'a self, ty_ident: &'a syn::Ident) -> codegen::Variant<'a> {
+impl<'a> InputVariant<'a> {
+    pub fn as_codegen_variant<'b>(&'b self, ty_ident: &'b syn::Ident) -> codegen::Variant<'b> {
         codegen::Variant {
             ty_ident,
-            variant_ident: &self.ident,
-            name_in_attr: self
-                .attr_name
-                .as_ref()
-                .map_or_else(|| Cow::Owned(self.ident.to_string()), Cow::Borrowed),
+            variant_ident: self.ident,
+            name_in_attr: self.attr_name.as_ref().map_or_else(
+                || Cow::Owned(self.ident.to_string()),
+                |s| Cow::Borrowed(s.as_str()),
+            ),
+            rename_with: self.rename_with.as_ref(),
             data: self.data.as_ref().map(InputField::as_codegen_field),
             skip: self.skip.unwrap_or_default(),
             allow_unknown_fields: self.allow_unknown_fields.unwrap_or_default(),
+            report: self.report,
+            deprecated: self.deprecated.as_ref().map(String::as_str),
         }
     }
 
-    pub fn from_variant(v: &syn::Variant, parent: Option<&Core>) -> Result<Self> {
+    pub fn from_variant(v: &'a syn::Variant, parent: Option<&Core>) -> Result<Self> {
         let mut starter = (InputVariant {
-            ident: v.ident.clone(),
+            ident: &v.ident,
             attr_name: Default::default(),
+            rename_with: None,
             data: Fields::empty_from(&v.fields),
             skip: Default::default(),
             allow_unknown_fields: None,
+            report: false,
+            deprecated: None,
         })
         .parse_attributes(&v.attrs)?;
 
@@ -44,16 +56,16 @@ impl InputVariant {
             syn::Fields::Unit => vec![],
             syn::Fields::Unnamed(ref fields) => {
                 let mut items = Vec::with_capacity(fields.unnamed.len());
-                for item in &fields.unnamed {
-                    items.push(InputField::from_field(item, parent)?);
+                for (index, item) in fields.unnamed.iter().enumerate() {
+                    items.push(InputField::from_field(item, index, parent)?);
                 }
 
                 items
             }
             syn::Fields::Named(ref fields) => {
                 let mut items = Vec::with_capacity(fields.named.len());
-                for item in &fields.named {
-                    items.push(InputField::from_field(item, parent)?);
+                for (index, item) in fields.named.iter().enumerate() {
+                    items.push(InputField::from_field(item, index, parent)?);
                 }
 
                 items
@@ -70,17 +82,20 @@ impl InputVariant {
     fn with_inherited(mut self, parent: &Core) -> Self {
         if self.attr_name.is_none() {
             self.attr_name = Some(parent.rename_rule.apply_to_variant(self.ident.to_string()));
+            self.rename_with = parent.rename_all_with.clone();
         }
 
         if self.allow_unknown_fields.is_none() {
             self.allow_unknown_fields = Some(parent.allow_unknown_fields.is_some());
         }
 
+        self.report = parent.report.is_present();
+
         self
     }
 }
 
-impl ParseAttribute for InputVariant {
+impl ParseAttribute for InputVariant<'_> {
     fn parse_nested(&mut self, mi: &syn::Meta) -> Result<()> {
         let path = mi.path();
         if path.is_ident("rename") {
@@ -95,6 +110,12 @@ impl ParseAttribute for InputVariant {
             }
 
             self.skip = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("deprecated") {
+            if self.deprecated.is_some() {
+                return Err(Error::duplicate_field_path(path).with_span(mi));
+            }
+
+            self.deprecated = Some(FromMeta::from_meta(mi)?);
         } else {
             return Err(Error::unknown_field_path(path).with_span(mi));
         }
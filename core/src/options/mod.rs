@@ -1,6 +1,7 @@
 use crate::{Error, FromMeta, Result};
 
 mod core;
+mod error_span;
 mod forward_attrs;
 mod from_attributes;
 mod from_derive;
@@ -11,20 +12,30 @@ mod from_variant;
 mod input_field;
 mod input_variant;
 mod outer_from;
+mod renamed;
+mod required;
 mod shape;
+mod skipped_input;
+mod span_strategy;
 
 pub use self::core::Core;
+pub use self::error_span::ErrorSpan;
 pub use self::forward_attrs::ForwardAttrs;
 pub use self::from_attributes::FromAttributesOptions;
 pub use self::from_derive::FdiOptions;
 pub use self::from_field::FromFieldOptions;
-pub use self::from_meta::FromMetaOptions;
+pub use self::from_meta::{FromMetaOptions, MetaSchemaOptions, ToMetaOptions};
 pub use self::from_type_param::FromTypeParamOptions;
 pub use self::from_variant::FromVariantOptions;
 pub use self::input_field::InputField;
 pub use self::input_variant::InputVariant;
 pub use self::outer_from::OuterFrom;
+pub use self::renamed::RenamedField;
+pub use self::required::RequiredField;
+pub(crate) use self::shape::join_alternatives;
 pub use self::shape::{DataShape, Shape};
+pub use self::skipped_input::SkippedInput;
+pub use self::span_strategy::SpanStrategy;
 
 /// A default/fallback expression encountered in attributes during parsing.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -88,8 +99,8 @@ fn parse_attr<T: ParseAttribute>(attr: &syn::Attribute, target: &mut T) -> Resul
 /// Middleware for extracting values from the body of the derive input. Implementers are
 /// expected to override `parse_field` or `parse_variant` as appropriate for their use-case,
 /// while `parse_body` dispatches to the appropriate methods and handles error collection.
-pub trait ParseData: Sized {
-    fn parse_body(mut self, body: &syn::Data) -> Result<Self> {
+pub trait ParseData<'a>: Sized {
+    fn parse_body(mut self, body: &'a syn::Data) -> Result<Self> {
         use syn::{Data, Fields};
 
         let mut errors = Error::accumulator();
@@ -116,18 +127,32 @@ pub trait ParseData: Sized {
             Data::Union(_) => unreachable!(),
         };
 
+        self.validate_body(&mut errors);
+
         errors.finish_with(self)
     }
 
     /// Apply the next found variant to the object, returning an error
     /// if parsing goes wrong.
-    fn parse_variant(&mut self, variant: &syn::Variant) -> Result<()> {
+    fn parse_variant(&mut self, variant: &'a syn::Variant) -> Result<()> {
         Err(Error::unsupported_format("enum variant").with_span(variant))
     }
 
     /// Apply the next found struct field to the object, returning an error
     /// if parsing goes wrong.
-    fn parse_field(&mut self, field: &syn::Field) -> Result<()> {
+    fn parse_field(&mut self, field: &'a syn::Field) -> Result<()> {
         Err(Error::unsupported_format("struct field").with_span(field))
     }
+
+    /// Check the options once every field and variant has been parsed, for rules which
+    /// depend on more than one of them.
+    fn validate_body(&self, errors: &mut crate::error::Accumulator) {
+        let _ = errors;
+    }
+}
+
+/// The options for one of darling's derives, which hold the container-level options of the
+/// deriving type.
+pub trait ContainerOptions<'a> {
+    fn container(&self) -> &Core<'a>;
 }
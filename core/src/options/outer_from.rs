@@ -1,57 +1,121 @@
 use syn::{Field, Ident, Meta};
 
+use crate::codegen::PrefixedAttrs;
 use crate::options::{Core, DefaultExpression, ForwardAttrs, ParseAttribute, ParseData};
-use crate::util::PathList;
-use crate::{FromMeta, Result};
+use crate::util::{path_to_string, PathList};
+use crate::{Error, FromMeta, Result};
 
 /// Reusable base for `FromDeriveInput`, `FromVariant`, `FromField`, and other top-level
 /// `From*` traits.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct OuterFrom {
+pub struct OuterFrom<'a> {
     /// The field on the target struct which should receive the type identifier, if any.
-    pub ident: Option<Ident>,
+    pub ident: Option<&'a Ident>,
 
     /// The field on the target struct which should receive the type attributes, if any.
-    pub attrs: Option<Ident>,
+    pub attrs: Option<&'a Ident>,
 
-    pub container: Core,
+    pub container: Core<'a>,
 
     /// The attribute names that should be searched.
     pub attr_names: PathList,
 
+    /// The attribute names searched for keys which don't appear in any of `attr_names`.
+    pub fallback_attr_names: PathList,
+
     /// The attribute names that should be forwarded. The presence of the word with no additional
     /// filtering will cause _all_ attributes to be cloned and exposed to the struct after parsing.
     pub forward_attrs: Option<ForwardAttrs>,
 
+    /// The prefix of attribute names which are collected into `prefixed_attrs`, if any.
+    pub attribute_prefix: Option<String>,
+
+    /// The field on the target struct which should receive the attributes whose names start
+    /// with `attribute_prefix`. This is only recognized if a prefix is set.
+    pub prefixed_attrs: Option<&'a Ident>,
+
+    /// The field on the target struct which should receive the span of the first attribute
+    /// matching `attr_names`, if any.
+    pub attr_span: Option<&'a Ident>,
+
+    /// Whether the warnings found while parsing should be stored in the field named `warnings`.
+    pub forward_warnings: bool,
+
+    /// The field on the target struct which should receive the warnings found while parsing.
+    /// This is only recognized if `forward_warnings` is set.
+    pub warnings: Option<&'a Ident>,
+
     /// Whether or not the container can be made through conversion from the type `Ident`.
     pub from_ident: bool,
+
+    /// Whether attributes wrapped in `cfg_attr` should be unwrapped before they're searched.
+    pub unwrap_cfg_attr: bool,
+
+    /// Whether attribute values can be expressions, such as `limit = some::CONST`, rather than
+    /// only literals.
+    pub expr_values: bool,
 }
 
-impl OuterFrom {
-    pub fn start(di: &syn::DeriveInput) -> Result<Self> {
+impl<'a> OuterFrom<'a> {
+    /// The attributes collected by name prefix, if the container asks for them.
+    pub fn as_prefixed_attrs(&self) -> Option<PrefixedAttrs<'_>> {
+        match (self.attribute_prefix.as_ref(), self.prefixed_attrs) {
+            (Some(prefix), Some(field)) => Some(PrefixedAttrs { prefix, field }),
+            _ => None,
+        }
+    }
+
+    pub fn start(di: &'a syn::DeriveInput) -> Result<Self> {
         Ok(OuterFrom {
             container: Core::start(di)?,
             attrs: Default::default(),
             ident: Default::default(),
             attr_names: Default::default(),
+            fallback_attr_names: Default::default(),
             forward_attrs: Default::default(),
+            attribute_prefix: Default::default(),
+            prefixed_attrs: Default::default(),
+            attr_span: Default::default(),
+            forward_warnings: Default::default(),
+            warnings: Default::default(),
             from_ident: Default::default(),
+            unwrap_cfg_attr: Default::default(),
+            expr_values: Default::default(),
         })
     }
 }
 
-impl ParseAttribute for OuterFrom {
+impl ParseAttribute for OuterFrom<'_> {
     fn parse_nested(&mut self, mi: &Meta) -> Result<()> {
         let path = mi.path();
         if path.is_ident("attributes") {
             self.attr_names = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("fallback_attributes") {
+            self.fallback_attr_names = FromMeta::from_meta(mi)?;
         } else if path.is_ident("forward_attrs") {
             self.forward_attrs = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("attribute_prefix") {
+            if self.attribute_prefix.is_some() {
+                return Err(Error::duplicate_field_path(path).with_span(mi));
+            }
+
+            let prefix: String = FromMeta::from_meta(mi)?;
+            if prefix.is_empty() {
+                return Err(Error::custom("`attribute_prefix` can't be empty").with_span(mi));
+            }
+
+            self.attribute_prefix = Some(prefix);
         } else if path.is_ident("from_ident") {
             // HACK: Declaring that a default is present will cause fields to
             // generate correct code, but control flow isn't that obvious.
             self.container.default = Some(DefaultExpression::Trait);
             self.from_ident = true;
+        } else if path.is_ident("forward_warnings") {
+            self.forward_warnings = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("unwrap_cfg_attr") {
+            self.unwrap_cfg_attr = FromMeta::from_meta(mi)?;
+        } else if path.is_ident("expr_values") {
+            self.expr_values = FromMeta::from_meta(mi)?;
         } else {
             return self.container.parse_nested(mi);
         }
@@ -59,8 +123,8 @@ impl ParseAttribute for OuterFrom {
     }
 }
 
-impl ParseData for OuterFrom {
-    fn parse_field(&mut self, field: &Field) -> Result<()> {
+impl<'a> ParseData<'a> for OuterFrom<'a> {
+    fn parse_field(&mut self, field: &'a Field) -> Result<()> {
         match field
             .ident
             .as_ref()
@@ -69,14 +133,58 @@ impl ParseData for OuterFrom {
             .map(|v| v.as_str())
         {
             Some("ident") => {
-                self.ident = field.ident.clone();
+                self.ident = field.ident.as_ref();
                 Ok(())
             }
             Some("attrs") => {
-                self.attrs = field.ident.clone();
+                self.attrs = field.ident.as_ref();
+                Ok(())
+            }
+            Some("attr_span") => {
+                self.attr_span = field.ident.as_ref();
+                Ok(())
+            }
+            Some("warnings") if self.forward_warnings => {
+                self.warnings = field.ident.as_ref();
+                Ok(())
+            }
+            Some("prefixed_attrs") if self.attribute_prefix.is_some() => {
+                self.prefixed_attrs = field.ident.as_ref();
                 Ok(())
             }
             _ => self.container.parse_field(field),
         }
     }
+
+    fn validate_body(&self, errors: &mut crate::error::Accumulator) {
+        if !self.fallback_attr_names.is_empty() && self.attr_names.is_empty() {
+            errors.push(Error::custom(
+                "`fallback_attributes` needs `attributes` to fall back from",
+            ));
+        }
+
+        for name in self.fallback_attr_names.iter() {
+            if self.attr_names.contains(name) {
+                errors.push(
+                    Error::custom(format!(
+                        "`{}` is in both `attributes` and `fallback_attributes`",
+                        path_to_string(name)
+                    ))
+                    .with_span(name),
+                );
+            }
+        }
+
+        if self.forward_warnings && self.warnings.is_none() {
+            errors.push(Error::custom(
+                "`forward_warnings` needs a field named `warnings` to store into",
+            ));
+        }
+
+        if self.attribute_prefix.is_some() && self.prefixed_attrs.is_none() {
+            errors.push(Error::custom(
+                "`attribute_prefix` needs a field named `prefixed_attrs` to collect into",
+            ));
+        }
+    }
 }
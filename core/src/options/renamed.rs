@@ -0,0 +1,70 @@
+use syn::NestedMeta;
+
+use crate::util::path_to_string;
+use crate::{Error, FromMeta, Result};
+
+/// A field name which is no longer accepted because it was renamed, such as
+/// `renamed(old = "with_fn", new = "with", since = "0.2")`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamedField {
+    /// The name callers may still be using.
+    pub old: String,
+    /// The name callers should use instead.
+    pub new: String,
+    /// The version in which the rename happened, if the author provided it.
+    pub since: Option<String>,
+}
+
+impl FromMeta for RenamedField {
+    fn from_list(items: &[NestedMeta]) -> Result<Self> {
+        let mut errors = Error::accumulator();
+        let mut old = None;
+        let mut new = None;
+        let mut since = None;
+
+        for item in items {
+            if let NestedMeta::Meta(ref mi) = *item {
+                let slot = if mi.path().is_ident("old") {
+                    &mut old
+                } else if mi.path().is_ident("new") {
+                    &mut new
+                } else if mi.path().is_ident("since") {
+                    &mut since
+                } else {
+                    errors.push(
+                        Error::unknown_field_with_alts(
+                            &path_to_string(mi.path()),
+                            &["old", "new", "since"],
+                        )
+                        .with_span(mi),
+                    );
+                    continue;
+                };
+
+                if slot.is_some() {
+                    errors.push(Error::duplicate_field_path(mi.path()).with_span(mi));
+                } else {
+                    *slot = errors.handle(FromMeta::from_meta(mi));
+                }
+            } else {
+                errors.push(Error::unsupported_format("literal").with_span(item));
+            }
+        }
+
+        if old.is_none() {
+            errors.push(Error::missing_field("old"));
+        }
+
+        if new.is_none() {
+            errors.push(Error::missing_field("new"));
+        }
+
+        errors.finish()?;
+
+        Ok(RenamedField {
+            old: old.unwrap(),
+            new: new.unwrap(),
+            since,
+        })
+    }
+}
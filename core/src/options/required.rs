@@ -0,0 +1,57 @@
+use syn::NestedMeta;
+
+use crate::util::path_to_string;
+use crate::{Error, FromMeta, Result};
+
+/// Guidance added to the missing field error reported when a field is absent, such as
+/// `required(message = "a format is needed", help = "add `format = \"json\"`")`.
+///
+/// The bare word `required` makes the field required without changing its error, which is
+/// useful on fields whose type would otherwise fill in a value.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RequiredField {
+    /// An explanation of the error, added to it as a note.
+    pub message: Option<String>,
+    /// A suggestion of how to fix the problem, added to the error as help.
+    pub help: Option<String>,
+}
+
+impl FromMeta for RequiredField {
+    fn from_word() -> Result<Self> {
+        Ok(RequiredField::default())
+    }
+
+    fn from_list(items: &[NestedMeta]) -> Result<Self> {
+        let mut errors = Error::accumulator();
+        let mut required = RequiredField::default();
+
+        for item in items {
+            if let NestedMeta::Meta(ref mi) = *item {
+                let slot = if mi.path().is_ident("message") {
+                    &mut required.message
+                } else if mi.path().is_ident("help") {
+                    &mut required.help
+                } else {
+                    errors.push(
+                        Error::unknown_field_with_alts(
+                            &path_to_string(mi.path()),
+                            &["message", "help"],
+                        )
+                        .with_span(mi),
+                    );
+                    continue;
+                };
+
+                if slot.is_some() {
+                    errors.push(Error::duplicate_field_path(mi.path()).with_span(mi));
+                } else {
+                    *slot = errors.handle(FromMeta::from_meta(mi));
+                }
+            } else {
+                errors.push(Error::unsupported_format("literal").with_span(item));
+            }
+        }
+
+        errors.finish_with(required)
+    }
+}
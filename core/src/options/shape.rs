@@ -19,11 +19,14 @@ use crate::{Error, FromMeta, Result};
 /// ```rust,ignore
 /// #[ignore(any, struct_named, enum_newtype)]
 /// ```
+///
+/// `any` accepts every struct and enum; unions are only accepted with the `union` word.
 #[derive(Debug, Clone)]
 pub struct Shape {
     enum_values: DataShape,
     struct_values: DataShape,
     any: bool,
+    union: bool,
 }
 
 impl Default for Shape {
@@ -32,6 +35,7 @@ impl Default for Shape {
             enum_values: DataShape::new("enum_"),
             struct_values: DataShape::new("struct_"),
             any: Default::default(),
+            union: Default::default(),
         }
     }
 }
@@ -45,6 +49,8 @@ impl FromMeta for Shape {
                 let word = ident.to_string();
                 if word == "any" {
                     new.any = true;
+                } else if word == "union" {
+                    new.union = true;
                 } else if word.starts_with("enum_") {
                     new.enum_values
                         .set_word(&word)
@@ -65,25 +71,57 @@ impl FromMeta for Shape {
     }
 }
 
+impl Shape {
+    /// The words which were set on this shape, used to describe the accepted shapes in errors.
+    fn supported_words(&self) -> Vec<String> {
+        let mut words = if self.any {
+            vec!["any".to_string()]
+        } else {
+            let mut words = self.struct_values.supported_words();
+            words.extend(self.enum_values.supported_words());
+            words
+        };
+        if self.union {
+            words.push("union".to_string());
+        }
+        words
+    }
+}
+
 impl ToTokens for Shape {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let fn_body = if self.any {
+        let expected = join_alternatives(&self.supported_words());
+        let expected = expected.as_ref().map(String::as_str);
+        let union_validation = if self.union {
             quote!(::darling::export::Ok(()))
         } else {
-            let en = &self.enum_values;
-            let st = &self.struct_values;
+            let err = unsupported_shape("union", expected);
+            quote!(::darling::export::Err(#err))
+        };
+
+        let fn_body = if self.any {
+            quote! {
+                match *__body {
+                    ::darling::export::syn::Data::Union(_) => #union_validation,
+                    _ => ::darling::export::Ok(()),
+                }
+            }
+        } else {
+            let en = self.enum_values.validation(expected);
+            let st = self.struct_values.validation(expected);
 
-            let enum_validation = if en.supports_none() {
-                let ty = en.prefix.trim_end_matches('_');
-                quote!(return ::darling::export::Err(::darling::Error::unsupported_shape(#ty));)
+            let enum_validation = if self.enum_values.supports_none() {
+                let ty = self.enum_values.prefix.trim_end_matches('_');
+                let err = unsupported_shape(ty, expected);
+                quote!(return ::darling::export::Err(#err);)
             } else {
                 quote! {
-                    fn validate_variant(data: &::syn::Fields) -> ::darling::Result<()> {
+                    fn validate_variant(data: &::darling::export::syn::Fields) -> ::darling::export::Result<(), ::darling::export::Error> {
                         #en
                     }
 
                     for variant in &data.variants {
-                        validate_variant(&variant.fields)?;
+                        validate_variant(&variant.fields).map_err(|e| e.with_span(&variant.ident))?;
                     }
 
                     Ok(())
@@ -92,21 +130,21 @@ impl ToTokens for Shape {
 
             quote! {
                 match *__body {
-                    ::syn::Data::Enum(ref data) => {
+                    ::darling::export::syn::Data::Enum(ref data) => {
                         #enum_validation
                     }
-                    ::syn::Data::Struct(ref struct_data) => {
+                    ::darling::export::syn::Data::Struct(ref struct_data) => {
                         let data = &struct_data.fields;
                         #st
                     }
-                    ::syn::Data::Union(_) => unreachable!(),
+                    ::darling::export::syn::Data::Union(_) => #union_validation,
                 }
             }
         };
 
         tokens.append_all(quote! {
             #[allow(unused_variables)]
-            fn __validate_body(__body: &::syn::Data) -> ::darling::Result<()> {
+            fn __validate_body(__body: &::darling::export::syn::Data) -> ::darling::export::Result<(), ::darling::export::Error> {
                 #fn_body
             }
         });
@@ -168,6 +206,60 @@ impl DataShape {
             _ => Err(Error::unknown_value(word)),
         }
     }
+
+    /// The words which were set on this shape, including their prefix.
+    fn supported_words(&self) -> Vec<String> {
+        if self.any {
+            return vec![format!("{}any", self.prefix)];
+        }
+
+        let flags = [
+            ("newtype", self.newtype),
+            ("named", self.named),
+            ("tuple", self.tuple),
+            ("unit", self.unit),
+        ];
+
+        flags
+            .iter()
+            .filter(|&&(_, is_set)| is_set)
+            .map(|&(word, _)| format!("{}{}", self.prefix, word))
+            .collect()
+    }
+
+    /// Generate the expression which validates `data`. If `expected` is set, errors will
+    /// name it as the accepted shapes.
+    fn validation(&self, expected: Option<&str>) -> TokenStream {
+        if self.any {
+            quote!(::darling::export::Ok(()))
+        } else if self.supports_none() {
+            let ty = self.prefix.trim_end_matches('_');
+            let err = unsupported_shape(ty, expected);
+            quote!(::darling::export::Err(#err))
+        } else {
+            let unit = self.match_arm("unit", self.unit, expected);
+            let newtype = self.match_arm("newtype", self.newtype, expected);
+            let named = self.match_arm("named", self.named, expected);
+            let tuple = self.match_arm("tuple", self.tuple, expected);
+            quote! {
+                match *data {
+                    ::darling::export::syn::Fields::Unit => #unit,
+                    ::darling::export::syn::Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => #newtype,
+                    ::darling::export::syn::Fields::Unnamed(_) => #tuple,
+                    ::darling::export::syn::Fields::Named(_) => #named,
+                }
+            }
+        }
+    }
+
+    fn match_arm(&self, name: &str, is_supported: bool, expected: Option<&str>) -> TokenStream {
+        if is_supported {
+            quote!(::darling::export::Ok(()))
+        } else {
+            let err = unsupported_shape(&format!("{}{}", self.prefix, name), expected);
+            quote!(::darling::export::Err(#err))
+        }
+    }
 }
 
 impl FromMeta for DataShape {
@@ -189,31 +281,14 @@ impl FromMeta for DataShape {
 
 impl ToTokens for DataShape {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let body = if self.any {
-            quote!(::darling::export::Ok(()))
-        } else if self.supports_none() {
-            let ty = self.prefix.trim_end_matches('_');
-            quote!(::darling::export::Err(::darling::Error::unsupported_shape(#ty)))
-        } else {
-            let unit = match_arm("unit", self.unit);
-            let newtype = match_arm("newtype", self.newtype);
-            let named = match_arm("named", self.named);
-            let tuple = match_arm("tuple", self.tuple);
-            quote! {
-                match *data {
-                    ::syn::Fields::Unit => #unit,
-                    ::syn::Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => #newtype,
-                    ::syn::Fields::Unnamed(_) => #tuple,
-                    ::syn::Fields::Named(_) => #named,
-                }
-            }
-        };
+        let expected = join_alternatives(&self.supported_words());
+        let body = self.validation(expected.as_ref().map(String::as_str));
 
         if self.embedded {
             body.to_tokens(tokens);
         } else {
             tokens.append_all(quote! {
-                fn __validate_data(data: &::syn::Fields) -> ::darling::Result<()> {
+                fn __validate_data(data: &::darling::export::syn::Fields) -> ::darling::export::Result<(), ::darling::export::Error> {
                     #body
                 }
             });
@@ -221,11 +296,27 @@ impl ToTokens for DataShape {
     }
 }
 
-fn match_arm(name: &'static str, is_supported: bool) -> TokenStream {
-    if is_supported {
-        quote!(::darling::export::Ok(()))
-    } else {
-        quote!(::darling::export::Err(::darling::Error::unsupported_shape(#name)))
+/// Generate the error for an input of the unsupported shape `observed`.
+fn unsupported_shape(observed: &str, expected: Option<&str>) -> TokenStream {
+    match expected {
+        Some(expected) => {
+            quote!(::darling::export::Error::unsupported_shape_with_expected(#observed, &#expected))
+        }
+        None => quote!(::darling::export::Error::unsupported_shape(#observed)),
+    }
+}
+
+/// Join words into a human-readable list of alternatives, such as `a, b, or c`.
+pub(crate) fn join_alternatives(words: &[String]) -> Option<String> {
+    match words.len() {
+        0 => None,
+        1 => Some(words[0].clone()),
+        2 => Some(format!("{} or {}", words[0], words[1])),
+        len => Some(format!(
+            "{}, or {}",
+            words[..len - 1].join(", "),
+            words[len - 1]
+        )),
     }
 }
 
@@ -253,6 +344,13 @@ mod tests {
         assert!(decl.any);
     }
 
+    #[test]
+    fn supports_union() {
+        let decl = fm::<Shape>(quote!(ignore(struct_named, union)));
+        assert!(decl.union);
+        assert_eq!(decl.supported_words(), vec!["struct_named", "union"]);
+    }
+
     #[test]
     fn supports_struct() {
         let decl = fm::<Shape>(quote!(ignore(struct_any, struct_newtype)));
@@ -260,6 +358,29 @@ mod tests {
         assert!(decl.struct_values.newtype);
     }
 
+    #[test]
+    fn supported_words_in_order() {
+        let decl = fm::<Shape>(quote!(ignore(enum_unit, struct_tuple, struct_named)));
+        assert_eq!(
+            decl.supported_words(),
+            vec!["struct_named", "struct_tuple", "enum_unit"]
+        );
+    }
+
+    #[test]
+    fn join_alternatives() {
+        let words = |w: &[&str]| w.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(super::join_alternatives(&[]), None);
+        assert_eq!(
+            super::join_alternatives(&words(&["a", "b"])),
+            Some("a or b".to_string())
+        );
+        assert_eq!(
+            super::join_alternatives(&words(&["a", "b", "c"])),
+            Some("a, b, or c".to_string())
+        );
+    }
+
     #[test]
     fn supports_mixed() {
         let decl = fm::<Shape>(quote!(ignore(struct_newtype, enum_newtype, enum_tuple)));
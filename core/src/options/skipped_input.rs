@@ -0,0 +1,29 @@
+use crate::{Error, FromMeta, Result};
+
+/// What happens when the input sets a field marked `skip`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkippedInput {
+    /// Report an error explaining that the option is computed internally.
+    Error,
+    /// Record a warning with the same explanation, and otherwise ignore the value.
+    Warn,
+    /// Ignore the value without telling the caller.
+    Ignore,
+}
+
+impl Default for SkippedInput {
+    fn default() -> Self {
+        SkippedInput::Error
+    }
+}
+
+impl FromMeta for SkippedInput {
+    fn from_string(value: &str) -> Result<Self> {
+        match value {
+            "error" => Ok(SkippedInput::Error),
+            "warn" => Ok(SkippedInput::Warn),
+            "ignore" => Ok(SkippedInput::Ignore),
+            other => Err(Error::unknown_value(other)),
+        }
+    }
+}
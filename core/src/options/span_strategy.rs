@@ -0,0 +1,29 @@
+use crate::{Error, FromMeta, Result};
+
+/// Which spans the generated code for a container's fields should carry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanStrategy {
+    /// Use the spans of the input, so errors in the generated code point at the field types.
+    Input,
+    /// Use the call-site span, so errors in the generated code point at the derive.
+    CallSite,
+}
+
+impl Default for SpanStrategy {
+    fn default() -> Self {
+        SpanStrategy::Input
+    }
+}
+
+impl FromMeta for SpanStrategy {
+    fn from_string(value: &str) -> Result<Self> {
+        match value {
+            "input" => Ok(SpanStrategy::Input),
+            "call_site" => Ok(SpanStrategy::CallSite),
+            other => Err(Error::unknown_value_with_alts(
+                other,
+                &["input", "call_site"],
+            )),
+        }
+    }
+}
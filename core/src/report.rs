@@ -0,0 +1,325 @@
+//! Recording where each parsed option came from.
+//!
+//! Derived impls on containers marked `#[darling(report)]` note which attributes they read,
+//! which meta item supplied each field, which fields fell back to a default, and which unknown
+//! keys were ignored. Containers without the option generate no reporting code at all. The
+//! notes are only kept while a [`record`] call is running on the current thread, so parsing
+//! outside one isn't affected.
+//! Macros can use the resulting [`ParseReport`] to explain how their configuration was read.
+//!
+//! ```rust,ignore
+//! let (opts, report) = darling::report::record(|| MyOptions::from_derive_input(&input));
+//! for entry in report.iter() {
+//!     // ...
+//! }
+//! ```
+
+use std::cell::RefCell;
+
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = RefCell::new(None);
+}
+
+#[derive(Default)]
+struct Recorder {
+    /// The names of the fields currently being parsed, outermost first.
+    path: Vec<String>,
+    entries: Vec<ReportEntry>,
+}
+
+impl Recorder {
+    fn path_to(&self, name: &str) -> String {
+        let mut path = self.path.join(".");
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(name);
+        path
+    }
+}
+
+/// Something which happened to a field while parsing. Paths join the names of nested fields
+/// with `.`, such as `inner.name`.
+#[derive(Debug, Clone)]
+pub enum ReportEntry {
+    /// An attribute with the name `path` at `span` was read.
+    Attribute { path: String, span: Span },
+    /// The field was read from the meta item at `span`.
+    Supplied { path: String, span: Span },
+    /// The field was absent, so its value came from a default or from its type.
+    Defaulted { path: String },
+    /// The key was unknown and the container allows unknown fields, so it was skipped.
+    Ignored { path: String, span: Span },
+}
+
+impl ReportEntry {
+    /// The path of the field or key the entry is about, or the name of the attribute.
+    pub fn path(&self) -> &str {
+        match *self {
+            ReportEntry::Attribute { ref path, .. }
+            | ReportEntry::Supplied { ref path, .. }
+            | ReportEntry::Defaulted { ref path }
+            | ReportEntry::Ignored { ref path, .. } => path,
+        }
+    }
+}
+
+/// The entries recorded by [`record`], in the order they happened.
+#[derive(Debug, Clone, Default)]
+pub struct ParseReport {
+    entries: Vec<ReportEntry>,
+}
+
+impl ParseReport {
+    /// Iterate over the entries in the order they were recorded.
+    pub fn iter(&self) -> std::slice::Iter<'_, ReportEntry> {
+        self.entries.iter()
+    }
+
+    /// The number of entries recorded.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if nothing was recorded, such as when no `#[darling(report)]` impl ran.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The spans of the attributes named `name` which were read, in order.
+    pub fn attributes<'a>(&'a self, name: &'a str) -> impl Iterator<Item = Span> + 'a {
+        self.entries.iter().filter_map(move |entry| match *entry {
+            ReportEntry::Attribute { ref path, span } if path == name => Some(span),
+            _ => None,
+        })
+    }
+
+    /// The span of the meta item which supplied the field at `path`, if it appeared in the input.
+    pub fn supplied(&self, path: &str) -> Option<Span> {
+        self.entries.iter().find_map(|entry| match *entry {
+            ReportEntry::Supplied { path: ref p, span } if p == path => Some(span),
+            _ => None,
+        })
+    }
+
+    /// Returns `true` if the field at `path` fell back to a default.
+    pub fn defaulted(&self, path: &str) -> bool {
+        self.entries.iter().any(|entry| match *entry {
+            ReportEntry::Defaulted { path: ref p } => p == path,
+            _ => false,
+        })
+    }
+
+    /// The paths of the unknown keys which were ignored.
+    pub fn ignored(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().filter_map(|entry| match *entry {
+            ReportEntry::Ignored { ref path, .. } => Some(path.as_str()),
+            _ => None,
+        })
+    }
+}
+
+impl IntoIterator for ParseReport {
+    type Item = ReportEntry;
+    type IntoIter = std::vec::IntoIter<ReportEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+/// Run `f`, recording what derived impls parse on the current thread while it runs.
+///
+/// Calls can be nested; the inner call's entries are only returned by the inner call. If `f`
+/// panics, the recorder of any enclosing call is restored as the panic unwinds.
+pub fn record<T, F: FnOnce() -> T>(f: F) -> (T, ParseReport) {
+    let guard = RestoreRecorder {
+        outer: Some(RECORDER.with(|recorder| recorder.replace(Some(Recorder::default())))),
+    };
+    let value = f();
+
+    let entries = guard
+        .restore()
+        .map(|recorder| recorder.entries)
+        .unwrap_or_default();
+    (value, ParseReport { entries })
+}
+
+/// Puts back the recorder which was active before a call to [`record`], even if the call
+/// unwinds.
+struct RestoreRecorder {
+    outer: Option<Option<Recorder>>,
+}
+
+impl RestoreRecorder {
+    /// Put back the outer recorder, returning the one it replaces.
+    fn restore(mut self) -> Option<Recorder> {
+        self.swap()
+    }
+
+    fn swap(&mut self) -> Option<Recorder> {
+        match self.outer.take() {
+            Some(outer) => RECORDER.with(|recorder| recorder.replace(outer)),
+            None => None,
+        }
+    }
+}
+
+impl Drop for RestoreRecorder {
+    fn drop(&mut self) {
+        self.swap();
+    }
+}
+
+/// Removes the name pushed by [`scope`] from the path once its parser returns or unwinds.
+struct PopPath {
+    pushed: bool,
+}
+
+impl Drop for PopPath {
+    fn drop(&mut self) {
+        if self.pushed {
+            with_recorder(|recorder| {
+                recorder.path.pop();
+            });
+        }
+    }
+}
+
+fn with_recorder<F: FnOnce(&mut Recorder)>(f: F) {
+    RECORDER.with(|recorder| {
+        if let Some(ref mut recorder) = *recorder.borrow_mut() {
+            f(recorder);
+        }
+    })
+}
+
+/// Note that the field `name` was supplied by `item`, then run `parse` with `name` added to
+/// the path of any fields it parses. Derived impls call this around each field's parser.
+pub fn scope<T, S: Spanned, F: FnOnce() -> T>(name: &str, item: &S, parse: F) -> T {
+    let mut guard = PopPath { pushed: false };
+    with_recorder(|recorder| {
+        let path = recorder.path_to(name);
+        recorder.entries.push(ReportEntry::Supplied {
+            path,
+            span: item.span(),
+        });
+        recorder.path.push(name.to_string());
+        guard.pushed = true;
+    });
+
+    parse()
+}
+
+/// Note that `attr` was read. Derived impls call this for each attribute they parse.
+pub fn attribute(attr: &syn::Attribute) {
+    with_recorder(|recorder| {
+        recorder.entries.push(ReportEntry::Attribute {
+            path: crate::util::path_to_string(&attr.path),
+            span: attr.span(),
+        });
+    });
+}
+
+/// Note that the field `name` was absent and fell back to a default.
+pub fn defaulted(name: &str) {
+    with_recorder(|recorder| {
+        let path = recorder.path_to(name);
+        recorder.entries.push(ReportEntry::Defaulted { path });
+    });
+}
+
+/// Note that the unknown key `name` at `item` was ignored.
+pub fn ignored<S: Spanned>(name: &str, item: &S) {
+    with_recorder(|recorder| {
+        let path = recorder.path_to(name);
+        recorder.entries.push(ReportEntry::Ignored {
+            path,
+            span: item.span(),
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{attribute, defaulted, ignored, record, scope};
+    use proc_macro2::Span;
+    use std::panic;
+    use syn::parse_quote;
+
+    #[test]
+    fn nothing_recorded_outside_record() {
+        let span = syn::Ident::new("a", Span::call_site());
+        scope("a", &span, || defaulted("b"));
+        let ((), report) = record(|| {});
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn nested_paths() {
+        let item = syn::Ident::new("a", Span::call_site());
+        let ((), report) = record(|| {
+            scope("outer", &item, || {
+                scope("name", &item, || ());
+                defaulted("count");
+                ignored("extra", &item);
+            });
+            defaulted("top");
+        });
+
+        let paths: Vec<&str> = report.iter().map(|entry| entry.path()).collect();
+        assert_eq!(
+            paths,
+            vec!["outer", "outer.name", "outer.count", "outer.extra", "top"]
+        );
+        assert!(report.supplied("outer.name").is_some());
+        assert!(report.defaulted("top"));
+        assert_eq!(report.ignored().collect::<Vec<_>>(), vec!["outer.extra"]);
+    }
+
+    #[test]
+    fn nested_records_are_separate() {
+        let ((inner, ()), outer) = record(|| (record(|| defaulted("inner")).1, defaulted("outer")));
+        assert!(inner.defaulted("inner"));
+        assert!(!outer.defaulted("inner"));
+        assert!(outer.defaulted("outer"));
+    }
+
+    #[test]
+    fn attributes_are_recorded() {
+        let first: syn::Attribute = parse_quote!(#[opts(a)]);
+        let second: syn::Attribute = parse_quote!(#[opts(b)]);
+        let ((), report) = record(|| {
+            attribute(&first);
+            attribute(&second);
+        });
+
+        assert_eq!(report.attributes("opts").count(), 2);
+        assert_eq!(report.attributes("other").count(), 0);
+    }
+
+    #[test]
+    fn panic_restores_outer_recorder_and_path() {
+        let item = syn::Ident::new("a", Span::call_site());
+        let ((), report) = record(|| {
+            scope("outer", &item, || {
+                let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                    record(|| scope("inner", &item, || panic!("parser failed")))
+                }));
+                assert!(result.is_err());
+
+                let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                    scope("name", &item, || panic!("parser failed"))
+                }));
+                assert!(result.is_err());
+                defaulted("count");
+            });
+        });
+
+        let paths: Vec<&str> = report.iter().map(|entry| entry.path()).collect();
+        assert_eq!(paths, vec!["outer", "outer.name", "outer.count"]);
+    }
+}
@@ -0,0 +1,469 @@
+//! Support for snapshot tests of the errors an options type reports.
+//!
+//! Macro authors can lock down their user-facing diagnostics by rendering the result of
+//! parsing a set of attribute inputs with [`render_errors`] and comparing it to a file
+//! checked into their repository with [`assert_snapshot`].
+//!
+//! ```rust,ignore
+//! #[test]
+//! fn diagnostics() {
+//!     let rendered = render_errors::<MyOptions, _>(&[
+//!         "my_attr(rename = 1)",
+//!         "my_attr(renamed = \"x\")",
+//!     ]);
+//!     assert_snapshot("tests/snapshots/diagnostics.txt", &rendered);
+//! }
+//! ```
+//!
+//! [`CompileFailCase`] declares an attribute together with the errors it should produce.
+//! It can be checked directly against an options type, or written out as a fixture for a
+//! trybuild-style runner and checked against the compiler's output. Either way, messages
+//! are compared after [`normalize_message`], so tests survive small wording changes.
+//!
+//! [`bench_parse`] and [`bench_expansion`] time a derived impl on a corpus of inputs and
+//! the derive itself, so performance regressions in generated code can be tracked.
+
+use std::env;
+use std::fmt;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::derive::expand_str;
+use crate::FromMeta;
+
+/// The environment variable which makes [`assert_snapshot`] overwrite snapshot files
+/// instead of comparing against them.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "DARLING_UPDATE_SNAPSHOTS";
+
+/// Parse each meta item in `inputs` with `T`, and render the outcomes as text.
+///
+/// Each input is listed after a `>` marker, followed by `ok` if it parsed or by one line per
+/// error if it didn't. Only the outcome is rendered, so changes to the parsed value don't
+/// affect the output.
+pub fn render_errors<'a, T, I>(inputs: I) -> String
+where
+    T: FromMeta,
+    I: IntoIterator<Item = &'a &'a str>,
+{
+    let mut rendered = String::new();
+    for input in inputs {
+        writeln!(rendered, "> {}", input).unwrap();
+        match T::from_meta_str(input) {
+            Ok(_) => rendered.push_str("ok\n"),
+            Err(err) => {
+                for err in err.flatten() {
+                    writeln!(rendered, "error: {}", err).unwrap();
+                }
+            }
+        }
+    }
+
+    rendered
+}
+
+/// Compare `actual` with the contents of the snapshot file at `path`.
+///
+/// If the file doesn't exist, or the `DARLING_UPDATE_SNAPSHOTS` environment variable is set,
+/// the file is written with `actual` instead.
+///
+/// # Panics
+/// Panics if the snapshot differs from `actual`, or if the file can't be read or written.
+pub fn assert_snapshot<P: AsRef<Path>>(path: P, actual: &str) {
+    let path = path.as_ref();
+    if env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() || !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .unwrap_or_else(|e| panic!("Unable to create {}: {}", parent.display(), e));
+        }
+        fs::write(path, actual)
+            .unwrap_or_else(|e| panic!("Unable to write {}: {}", path.display(), e));
+        return;
+    }
+
+    let expected = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Unable to read {}: {}", path.display(), e));
+    if expected != actual {
+        panic!(
+            "Snapshot {} does not match; set {} to update it.\n\n--- expected\n{}\n--- actual\n{}",
+            path.display(),
+            UPDATE_SNAPSHOTS_VAR,
+            expected,
+            actual
+        );
+    }
+}
+
+/// The placeholder in a [`CompileFailCase::write_fixture`] template which is replaced with
+/// the case's attribute.
+pub const ATTR_PLACEHOLDER: &str = "{attr}";
+
+/// Reduce an error message to the parts which matter for comparison.
+///
+/// Case, backticks, quotes, a final period, and runs of whitespace are ignored, and so are
+/// "Did you mean" suggestions, which depend on the `suggestions` feature.
+pub fn normalize_message(message: &str) -> String {
+    let message = match message.find("Did you mean") {
+        Some(index) => &message[..index],
+        None => message,
+    };
+
+    let words: Vec<String> = message
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| *c != '`' && *c != '"' && *c != '\'')
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    words.join(" ").trim_end_matches('.').to_string()
+}
+
+/// Extract the normalized messages of the errors in compiler output, one per line.
+///
+/// Each `error: ...` or `error[E...]: ...` line is kept and passed through
+/// [`normalize_message`], while file paths, line numbers, source snippets and the final
+/// "aborting due to" summary are dropped.
+pub fn normalize_stderr(stderr: &str) -> String {
+    let mut messages = vec![];
+    for line in stderr.lines() {
+        if !line.starts_with("error") {
+            continue;
+        }
+
+        let rest = &line["error".len()..];
+        let message = match rest.find(": ") {
+            Some(index) if rest[..index].starts_with('[') || index == 0 => &rest[index + 2..],
+            _ => continue,
+        };
+
+        if !message.starts_with("aborting due to") && !message.starts_with("could not compile") {
+            messages.push(normalize_message(message));
+        }
+    }
+
+    messages.join("\n")
+}
+
+/// An attribute which should be rejected, and the errors it should be rejected with.
+///
+/// ```rust,ignore
+/// CompileFailCase::new("typo", "my_attr(nmae = \"x\")")
+///     .expect_error("Unknown field: `nmae`")
+///     .check::<MyOptions>();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileFailCase {
+    name: String,
+    attr: String,
+    expected: Vec<String>,
+}
+
+impl CompileFailCase {
+    /// Declare a case named `name` for the source text of the meta item `attr`. The name is
+    /// used for the fixture file and in failure messages.
+    pub fn new(name: impl Into<String>, attr: impl Into<String>) -> Self {
+        CompileFailCase {
+            name: name.into(),
+            attr: attr.into(),
+            expected: vec![],
+        }
+    }
+
+    /// Expect an error with `message`. Cases with several expected errors pass only if the
+    /// input produces exactly those errors, in any order.
+    pub fn expect_error(mut self, message: impl Into<String>) -> Self {
+        self.expected.push(message.into());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn attr(&self) -> &str {
+        &self.attr
+    }
+
+    /// Check that `T` rejects the attribute with the expected errors.
+    ///
+    /// # Panics
+    /// Panics if `T` accepts the attribute or reports different errors.
+    pub fn check<T: FromMeta>(&self) {
+        match T::from_meta_str(&self.attr) {
+            Ok(_) => panic!(
+                "Case `{}`: expected `{}` to be rejected, but it parsed",
+                self.name, self.attr
+            ),
+            Err(err) => {
+                let actual: Vec<String> =
+                    err.flatten().into_iter().map(|e| e.to_string()).collect();
+                self.compare(&actual);
+            }
+        }
+    }
+
+    /// Check that compiler output for the case's fixture contains the expected errors.
+    ///
+    /// Errors in `stderr` which aren't expected are ignored, since the compiler may report
+    /// follow-on errors of its own.
+    ///
+    /// # Panics
+    /// Panics if an expected error is missing from `stderr`.
+    pub fn check_stderr(&self, stderr: &str) {
+        let normalized = normalize_stderr(stderr);
+        let actual: Vec<&str> = normalized.lines().collect();
+        for expected in &self.expected {
+            if !actual.contains(&normalize_message(expected).as_str()) {
+                panic!(
+                    "Case `{}`: expected error `{}` in compiler output:\n{}",
+                    self.name, expected, stderr
+                );
+            }
+        }
+    }
+
+    /// Write the case as `<dir>/<name>.rs`, for a runner which compiles each file in `dir`.
+    ///
+    /// The file contains `template` with `{attr}` replaced by the case's attribute, so the
+    /// template decides how the attribute is applied:
+    ///
+    /// ```text
+    /// use my_crate::MyTrait;
+    ///
+    /// #[derive(MyTrait)]
+    /// #[{attr}]
+    /// struct Example;
+    ///
+    /// fn main() {}
+    /// ```
+    pub fn write_fixture<P: AsRef<Path>>(&self, dir: P, template: &str) -> io::Result<PathBuf> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}.rs", self.name));
+        fs::write(&path, template.replace(ATTR_PLACEHOLDER, &self.attr))?;
+        Ok(path)
+    }
+
+    fn compare(&self, actual: &[String]) {
+        let mut unmatched: Vec<String> = actual.iter().map(|msg| normalize_message(msg)).collect();
+        let mut missing = vec![];
+        for expected in &self.expected {
+            match unmatched
+                .iter()
+                .position(|msg| *msg == normalize_message(expected))
+            {
+                Some(index) => {
+                    unmatched.remove(index);
+                }
+                None => missing.push(expected.as_str()),
+            }
+        }
+
+        if !missing.is_empty() || !unmatched.is_empty() {
+            panic!(
+                "Case `{}`: errors for `{}` don't match.\n\nexpected:\n{}\n\nactual:\n{}",
+                self.name,
+                self.attr,
+                self.expected.join("\n"),
+                actual.join("\n")
+            );
+        }
+    }
+}
+
+/// The timings measured by [`bench_parse`] or [`bench_expansion`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchReport {
+    /// The name the benchmark was run with.
+    pub name: String,
+    /// The number of inputs run by each iteration.
+    pub inputs: usize,
+    pub iterations: usize,
+    /// The time taken by all iterations together.
+    pub total: Duration,
+    /// The time taken by the fastest iteration.
+    pub fastest: Duration,
+    /// The number of inputs which succeeded in each iteration.
+    pub succeeded: usize,
+}
+
+impl BenchReport {
+    /// The mean time taken by one input, over all iterations.
+    pub fn per_input(&self) -> Duration {
+        let runs = (self.inputs * self.iterations) as u32;
+        if runs == 0 {
+            Duration::default()
+        } else {
+            self.total / runs
+        }
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} inputs x {} iterations, {:?} per input ({:?} fastest iteration, {} succeeded)",
+            self.name,
+            self.inputs,
+            self.iterations,
+            self.per_input(),
+            self.fastest,
+            self.succeeded
+        )
+    }
+}
+
+/// Time parsing each meta item in `corpus` with `T`, `iterations` times over.
+///
+/// The corpus is parsed into meta items before timing starts, so only `T::from_meta` is
+/// measured. Inputs which `T` rejects are timed like the rest, since error paths matter too.
+///
+/// # Panics
+/// Panics if an input isn't a valid meta item.
+pub fn bench_parse<T: FromMeta>(name: &str, corpus: &[&str], iterations: usize) -> BenchReport {
+    let metas: Vec<syn::Meta> = corpus
+        .iter()
+        .map(|input| {
+            syn::parse_str(input).unwrap_or_else(|e| panic!("Invalid input `{}`: {}", input, e))
+        })
+        .collect();
+
+    run_bench(name, metas.len(), iterations, || {
+        metas
+            .iter()
+            .filter(|meta| T::from_meta(meta).is_ok())
+            .count()
+    })
+}
+
+/// Time expanding the derives on each item in `corpus`, `iterations` times over.
+///
+/// Items are expanded with [`expand_str`], so this includes parsing the item's source.
+pub fn bench_expansion(name: &str, corpus: &[&str], iterations: usize) -> BenchReport {
+    run_bench(name, corpus.len(), iterations, || {
+        corpus
+            .iter()
+            .filter(|source| expand_str(source).is_ok())
+            .count()
+    })
+}
+
+fn run_bench<F: FnMut() -> usize>(
+    name: &str,
+    inputs: usize,
+    iterations: usize,
+    mut run: F,
+) -> BenchReport {
+    // One untimed run, so first-use costs aren't attributed to the first iteration.
+    let succeeded = run();
+    let mut total = Duration::default();
+    let mut fastest = None;
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let count = run();
+        let elapsed = start.elapsed();
+        assert_eq!(
+            count, succeeded,
+            "Benchmark `{}` is not deterministic",
+            name
+        );
+
+        total += elapsed;
+        fastest = Some(fastest.map_or(elapsed, |fastest: Duration| fastest.min(elapsed)));
+    }
+
+    BenchReport {
+        name: name.to_string(),
+        inputs,
+        iterations,
+        total,
+        fastest: fastest.unwrap_or_default(),
+        succeeded,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bench_expansion, bench_parse, normalize_message, normalize_stderr, render_errors,
+        CompileFailCase,
+    };
+
+    #[test]
+    fn renders_outcomes() {
+        let rendered = render_errors::<bool, _>(&["skip", "skip = \"maybe\"", "skip(1, 2)"]);
+        assert_eq!(
+            rendered,
+            "> skip\n\
+             ok\n\
+             > skip = \"maybe\"\n\
+             error: Unknown literal value `maybe`\n\
+             > skip(1, 2)\n\
+             error: Unexpected meta-item format `list`\n"
+        );
+    }
+
+    #[test]
+    fn bench_counts_successes() {
+        let report = bench_parse::<bool>("bools", &["skip", "skip = true", "skip = 1"], 3);
+        assert_eq!(report.inputs, 3);
+        assert_eq!(report.iterations, 3);
+        assert_eq!(report.succeeded, 2);
+        assert!(report
+            .to_string()
+            .starts_with("bools: 3 inputs x 3 iterations"));
+
+        let report = bench_expansion("derives", &["#[derive(FromMeta)] struct A;"], 1);
+        assert_eq!(report.succeeded, 1);
+    }
+
+    #[test]
+    fn normalizes_messages() {
+        assert_eq!(
+            normalize_message("Unknown field: `nmae`. Did you mean `name`?"),
+            "unknown field: nmae"
+        );
+        assert_eq!(
+            normalize_message("  Missing   field `a`."),
+            normalize_message("missing field \"a\"")
+        );
+    }
+
+    #[test]
+    fn normalizes_stderr() {
+        let stderr = "error: Unknown field: `nmae`\n \
+                      --> tests/ui/typo.rs:4:9\n  \
+                      |\n\n\
+                      error[E0277]: the trait bound is not satisfied\n\n\
+                      error: aborting due to 2 previous errors\n";
+        assert_eq!(
+            normalize_stderr(stderr),
+            "unknown field: nmae\nthe trait bound is not satisfied"
+        );
+    }
+
+    #[test]
+    fn checks_cases() {
+        CompileFailCase::new("list", "skip(true)")
+            .expect_error("unexpected meta-item format list")
+            .check::<bool>();
+
+        CompileFailCase::new("stderr", "skip(true)")
+            .expect_error("Unexpected meta-item format `list`")
+            .check_stderr("error: Unexpected meta-item format `list`\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "don't match")]
+    fn check_rejects_extra_errors() {
+        CompileFailCase::new("none", "skip(true)").check::<bool>();
+    }
+}
@@ -0,0 +1,402 @@
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::{Lit, LitBool, LitChar, LitFloat, LitInt, LitStr, Meta, NestedMeta, Path};
+
+use crate::util::{path_to_string, render_tokens, Flag, PathList, SpannedValue};
+
+/// Write a value back out as attribute syntax which its `FromMeta` impl would read.
+///
+/// This is the counterpart of `FromMeta`, and can be derived for types which derive
+/// `FromMeta`. It lets a macro forward normalized options to another macro, and makes
+/// round-trip tests possible. Derived impls write every field which isn't skipped, so
+/// default values appear explicitly in the output.
+///
+/// ```rust
+/// # extern crate darling_core;
+/// # extern crate quote;
+/// # extern crate syn;
+/// use darling_core::ToMeta;
+/// use quote::ToTokens;
+///
+/// let meta = "x".to_string().to_meta(&syn::parse_quote!(rename)).unwrap();
+/// assert_eq!(meta.into_token_stream().to_string(), "rename = \"x\"");
+/// ```
+pub trait ToMeta {
+    /// Write the value as the meta item named `name`.
+    ///
+    /// Returns `None` if the value is expressed by leaving the item out, such as an absent
+    /// `Flag` or an `Option` which is `None`.
+    fn to_meta(&self, name: &Path) -> Option<Meta>;
+
+    /// Render the value as the meta item named `name`, such as `my_attr(rename = "x", skip)`.
+    ///
+    /// This is meant for error messages and debug output, where users recognize attribute
+    /// syntax more readily than `Debug` formatting. A value which is expressed by leaving the
+    /// item out renders as an empty string.
+    ///
+    /// # Panics
+    /// Panics if `name` isn't a valid path.
+    fn to_meta_string(&self, name: &str) -> String {
+        self.to_meta(&parse_name(name))
+            .map(|meta| meta_to_string(&meta))
+            .unwrap_or_default()
+    }
+}
+
+/// Create the meta list `name(items...)`.
+pub fn meta_list<I: IntoIterator<Item = NestedMeta>>(name: &Path, items: I) -> Meta {
+    Meta::List(syn::MetaList {
+        path: name.clone(),
+        paren_token: Default::default(),
+        nested: items.into_iter().collect(),
+    })
+}
+
+/// Write `value` as the item named `name`, and push it to `items` unless it's left out.
+///
+/// # Panics
+/// Panics if `name` isn't a valid path.
+pub fn push_meta<T: ToMeta + ?Sized>(items: &mut Vec<NestedMeta>, name: &str, value: &T) {
+    let name: Path =
+        syn::parse_str(name).unwrap_or_else(|_| panic!("`{}` is not a valid meta item name", name));
+    if let Some(meta) = value.to_meta(&name) {
+        items.push(NestedMeta::Meta(meta));
+    }
+}
+
+/// Render a meta item the way it would usually be written, such as `my_attr(rename = "x", skip)`.
+pub fn meta_to_string(meta: &Meta) -> String {
+    let mut rendered = String::new();
+    write_meta(&mut rendered, meta);
+    rendered
+}
+
+fn write_meta(rendered: &mut String, meta: &Meta) {
+    rendered.push_str(&path_to_string(meta.path()));
+    match *meta {
+        Meta::Path(_) => {}
+        Meta::NameValue(ref nv) => {
+            rendered.push_str(" = ");
+            rendered.push_str(&nv.lit.to_token_stream().to_string());
+        }
+        Meta::List(ref list) => {
+            rendered.push('(');
+            for (i, item) in list.nested.iter().enumerate() {
+                if i > 0 {
+                    rendered.push_str(", ");
+                }
+                match *item {
+                    NestedMeta::Meta(ref meta) => write_meta(rendered, meta),
+                    NestedMeta::Lit(ref lit) => {
+                        rendered.push_str(&lit.to_token_stream().to_string())
+                    }
+                }
+            }
+            rendered.push(')');
+        }
+    }
+}
+
+fn parse_name(name: &str) -> Path {
+    syn::parse_str(name).unwrap_or_else(|_| panic!("`{}` is not a valid meta item name", name))
+}
+
+fn name_value(name: &Path, lit: Lit) -> Option<Meta> {
+    Some(Meta::NameValue(syn::MetaNameValue {
+        path: name.clone(),
+        eq_token: Default::default(),
+        lit,
+    }))
+}
+
+fn str_value(name: &Path, value: &str) -> Option<Meta> {
+    name_value(name, Lit::Str(LitStr::new(value, Span::call_site())))
+}
+
+impl ToMeta for () {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        Some(Meta::Path(name.clone()))
+    }
+}
+
+impl ToMeta for bool {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        name_value(name, Lit::Bool(LitBool::new(*self, Span::call_site())))
+    }
+}
+
+impl ToMeta for char {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        name_value(name, Lit::Char(LitChar::new(*self, Span::call_site())))
+    }
+}
+
+impl ToMeta for String {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        str_value(name, self)
+    }
+}
+
+impl ToMeta for str {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        str_value(name, self)
+    }
+}
+
+/// Integers are written as literals, except negative numbers, which `FromMeta` only reads
+/// from strings.
+macro_rules! to_meta_num {
+    ($($ty:ident),*) => {
+        $(
+            impl ToMeta for $ty {
+                fn to_meta(&self, name: &Path) -> Option<Meta> {
+                    let value = self.to_string();
+                    if value.starts_with('-') {
+                        str_value(name, &value)
+                    } else {
+                        name_value(name, Lit::Int(LitInt::new(&value, Span::call_site())))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+to_meta_num!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Finite floats are written as literals; other values are written as the strings which
+/// `FromMeta` parses them from.
+macro_rules! to_meta_float {
+    ($($ty:ident),*) => {
+        $(
+            impl ToMeta for $ty {
+                fn to_meta(&self, name: &Path) -> Option<Meta> {
+                    let value = format!("{:?}", self);
+                    if self.is_finite() && !value.starts_with('-') {
+                        name_value(name, Lit::Float(LitFloat::new(&value, Span::call_site())))
+                    } else {
+                        str_value(name, &value)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+to_meta_float!(f32, f64);
+
+impl<T: ToMeta> ToMeta for Option<T> {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        self.as_ref().and_then(|value| value.to_meta(name))
+    }
+}
+
+impl<T: ToMeta + ?Sized> ToMeta for &T {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        (**self).to_meta(name)
+    }
+}
+
+impl<T: ToMeta + ?Sized> ToMeta for Box<T> {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        (**self).to_meta(name)
+    }
+}
+
+impl<T: ToMeta + ?Sized> ToMeta for Rc<T> {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        (**self).to_meta(name)
+    }
+}
+
+impl<T: ToMeta + ?Sized> ToMeta for Arc<T> {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        (**self).to_meta(name)
+    }
+}
+
+/// Entries are written in key order, so the output doesn't depend on the hasher.
+impl<V: ToMeta, S: BuildHasher> ToMeta for HashMap<String, V, S> {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut items = vec![];
+        for (key, value) in entries {
+            push_meta(&mut items, key, value);
+        }
+
+        Some(meta_list(name, items))
+    }
+}
+
+/// Entries are written in the map's order, which is the order they were read in.
+#[cfg(feature = "indexmap")]
+impl<V: ToMeta, S: BuildHasher> ToMeta for indexmap::IndexMap<String, V, S> {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        let mut items = vec![];
+        for (key, value) in self {
+            push_meta(&mut items, key, value);
+        }
+
+        Some(meta_list(name, items))
+    }
+}
+
+impl ToMeta for Lit {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        name_value(name, self.clone())
+    }
+}
+
+impl ToMeta for Meta {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        let mut meta = self.clone();
+        match meta {
+            Meta::Path(ref mut path) => *path = name.clone(),
+            Meta::List(ref mut list) => list.path = name.clone(),
+            Meta::NameValue(ref mut nv) => nv.path = name.clone(),
+        }
+        Some(meta)
+    }
+}
+
+impl ToMeta for syn::Ident {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        str_value(name, &self.to_string())
+    }
+}
+
+impl ToMeta for Path {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        str_value(name, &path_to_string(self))
+    }
+}
+
+/// Values which `FromMeta` parses from a string of Rust syntax are written back as one.
+macro_rules! to_meta_syn {
+    ($($ty:path),*) => {
+        $(
+            impl ToMeta for $ty {
+                fn to_meta(&self, name: &Path) -> Option<Meta> {
+                    str_value(name, &render_tokens(self))
+                }
+            }
+        )*
+    };
+}
+
+to_meta_syn!(
+    syn::Expr,
+    syn::ExprArray,
+    syn::ExprPath,
+    syn::Type,
+    syn::TypePath,
+    syn::Visibility,
+    syn::WhereClause
+);
+
+impl ToMeta for Flag {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        if self.is_present() {
+            Some(Meta::Path(name.clone()))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: ToMeta> ToMeta for SpannedValue<T> {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        (**self).to_meta(name)
+    }
+}
+
+impl ToMeta for PathList {
+    fn to_meta(&self, name: &Path) -> Option<Meta> {
+        Some(meta_list(
+            name,
+            self.iter()
+                .cloned()
+                .map(|path| NestedMeta::Meta(Meta::Path(path))),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use quote::ToTokens;
+    use syn::parse_quote;
+
+    use super::ToMeta;
+    use crate::FromMeta;
+
+    fn render<T: ToMeta>(value: T) -> String {
+        value
+            .to_meta(&parse_quote!(key))
+            .map(|meta| meta.into_token_stream().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Values should be read back unchanged by their `FromMeta` impls.
+    fn round_trip<T: ToMeta + FromMeta + PartialEq + std::fmt::Debug>(value: T) {
+        let meta = value.to_meta(&parse_quote!(key)).unwrap();
+        assert_eq!(T::from_meta(&meta).unwrap(), value);
+    }
+
+    #[test]
+    fn renders_values() {
+        assert_eq!(render(true), "key = true");
+        assert_eq!(render(3u8), "key = 3");
+        assert_eq!(render(-3i32), "key = \"-3\"");
+        assert_eq!(render(0.5f64), "key = 0.5");
+        assert_eq!(render(Some('x')), "key = 'x'");
+        assert_eq!(render(None::<String>), "");
+        assert_eq!(render(()), "key");
+    }
+
+    #[test]
+    fn renders_meta_strings() {
+        let meta: syn::Meta = parse_quote!(my_attr(rename = "x", skip, inner(a = 1, b::c), "lit"));
+        assert_eq!(
+            super::meta_to_string(&meta),
+            r#"my_attr(rename = "x", skip, inner(a = 1, b::c), "lit")"#
+        );
+        assert_eq!(Some(3u8).to_meta_string("count"), "count = 3");
+        assert_eq!(None::<u8>.to_meta_string("count"), "");
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip(false);
+        round_trip(-7i64);
+        round_trip(2.0f32);
+        round_trip("hi".to_string());
+        round_trip::<syn::Path>(parse_quote!(a::b));
+        round_trip::<syn::Type>(parse_quote!(Vec<&'static str>));
+        round_trip(crate::util::Flag::present());
+
+        let mut map = HashMap::new();
+        map.insert("b".to_string(), 2u8);
+        map.insert("a".to_string(), 1u8);
+        assert_eq!(render(map.clone()), "key (a = 1 , b = 2)");
+        round_trip(map);
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn index_map_keeps_order() {
+        let mut map = indexmap::IndexMap::<String, u8>::new();
+        map.insert("b".to_string(), 2);
+        map.insert("a".to_string(), 1);
+        assert_eq!(render(map.clone()), "key (b = 2 , a = 1)");
+        round_trip(map);
+    }
+}
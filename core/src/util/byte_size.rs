@@ -0,0 +1,148 @@
+use std::fmt;
+use std::ops::Deref;
+
+use syn::{Lit, Meta};
+
+use crate::{Error, FromMeta, Result};
+
+/// The units a size can be written in, with the number of bytes in each.
+const UNITS: &[(&str, u64)] = &[
+    ("b", 1),
+    ("kb", 1_000),
+    ("mb", 1_000_000),
+    ("gb", 1_000_000_000),
+    ("tb", 1_000_000_000_000),
+    ("kib", 1 << 10),
+    ("mib", 1 << 20),
+    ("gib", 1 << 30),
+    ("tib", 1 << 40),
+];
+
+/// A number of bytes, which can be written as a human size such as `"64KiB"` or `"2MB"`.
+///
+/// Units ignore case and may be separated from the number by spaces. `KB`, `MB`, `GB` and `TB`
+/// are powers of 1000, while `KiB`, `MiB`, `GiB` and `TiB` are powers of 1024. A string with
+/// no unit, or an integer literal, is a number of bytes.
+///
+/// Use `ByteSize` as the type of a field, or read a `u64` field with
+/// `#[darling(with = "darling::util::byte_size")]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(pub u64);
+
+impl ByteSize {
+    /// Get the number of bytes.
+    pub fn bytes(self) -> u64 {
+        self.0
+    }
+
+    fn parse(s: &str) -> Result<Self> {
+        let trimmed = s.trim();
+        let split = trimmed
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(trimmed.len());
+        let (number, unit) = trimmed.split_at(split);
+        let unit = unit.trim_start().to_lowercase();
+
+        let multiplier = if unit.is_empty() {
+            Some(1)
+        } else {
+            UNITS
+                .iter()
+                .find(|&&(name, _)| name == unit)
+                .map(|&(_, multiplier)| multiplier)
+        };
+
+        match (number.parse::<u64>(), multiplier) {
+            (Ok(number), Some(multiplier)) => number
+                .checked_mul(multiplier)
+                .map(ByteSize)
+                .ok_or_else(|| out_of_range(s)),
+            // The digits alone didn't fit in a `u64`.
+            (Err(_), Some(_)) if !number.is_empty() => Err(out_of_range(s)),
+            _ => Err(Error::unknown_value(s)),
+        }
+    }
+}
+
+fn out_of_range(value: &str) -> Error {
+    Error::out_of_range(value, "u64", 0, ::std::u64::MAX)
+}
+
+impl Deref for ByteSize {
+    type Target = u64;
+
+    fn deref(&self) -> &u64 {
+        &self.0
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(v: ByteSize) -> u64 {
+        v.0
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(v: u64) -> Self {
+        ByteSize(v)
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}B", self.0)
+    }
+}
+
+impl FromMeta for ByteSize {
+    fn from_string(value: &str) -> Result<Self> {
+        ByteSize::parse(value)
+    }
+
+    fn from_value(value: &Lit) -> Result<Self> {
+        (match *value {
+            Lit::Str(ref s) => Self::from_string(&s.value()),
+            Lit::Int(_) => u64::from_value(value).map(ByteSize),
+            _ => Err(Error::unexpected_lit_type(value)),
+        })
+        .map_err(|e| e.with_span(value))
+    }
+}
+
+/// Read a `u64` number of bytes which can be written as a human size, as [`ByteSize`] is.
+pub fn byte_size(meta: &Meta) -> Result<u64> {
+    ByteSize::from_meta(meta).map(u64::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteSize;
+    use crate::FromMeta;
+
+    fn size(value: &str) -> crate::Result<u64> {
+        ByteSize::from_string(value).map(u64::from)
+    }
+
+    #[test]
+    fn reads_units() {
+        assert_eq!(size("64KiB").unwrap(), 65_536);
+        assert_eq!(size("2MB").unwrap(), 2_000_000);
+        assert_eq!(size("1 gib").unwrap(), 1 << 30);
+        assert_eq!(size("512").unwrap(), 512);
+        assert_eq!(size("3b").unwrap(), 3);
+    }
+
+    #[test]
+    fn rejects_bad_sizes() {
+        assert_eq!(
+            size("2 parsecs").unwrap_err().to_string(),
+            "Unknown literal value `2 parsecs`"
+        );
+        assert!(size("KiB").is_err());
+        assert!(size("1.5MB").is_err());
+        assert_eq!(
+            size("20000000TiB").unwrap_err().to_string(),
+            "Value `20000000TiB` is out of range for `u64`: Expected 0 to 18446744073709551615"
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{Expr, ExprClosure, Path};
+
+use crate::{Error, FromMeta, Result};
+
+/// A function which can be written either as a path or as an inline closure, such as
+/// `default = "Vec::new"` or `map = "|v: u8| v * 2"`.
+///
+/// Both forms produce tokens which can be called directly: `#callable(args)`. Closures
+/// are wrapped in parentheses so they're called rather than parsed as part of the closure body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Callable {
+    /// A path to a function.
+    Path(Path),
+    /// An inline closure.
+    Closure(ExprClosure),
+}
+
+impl Callable {
+    /// The function path, if this was written as one.
+    pub fn as_path(&self) -> Option<&Path> {
+        match *self {
+            Callable::Path(ref path) => Some(path),
+            Callable::Closure(_) => None,
+        }
+    }
+
+    /// The closure, if this was written as one.
+    pub fn as_closure(&self) -> Option<&ExprClosure> {
+        match *self {
+            Callable::Path(_) => None,
+            Callable::Closure(ref closure) => Some(closure),
+        }
+    }
+}
+
+impl ToTokens for Callable {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match *self {
+            Callable::Path(ref path) => path.to_tokens(tokens),
+            Callable::Closure(ref closure) => tokens.extend(quote!((#closure))),
+        }
+    }
+}
+
+impl From<Path> for Callable {
+    fn from(path: Path) -> Self {
+        Callable::Path(path)
+    }
+}
+
+impl From<ExprClosure> for Callable {
+    fn from(closure: ExprClosure) -> Self {
+        Callable::Closure(closure)
+    }
+}
+
+impl FromMeta for Callable {
+    fn from_string(value: &str) -> Result<Self> {
+        match syn::parse_str::<Expr>(value) {
+            Ok(Expr::Path(ref expr)) if expr.qself.is_none() && expr.attrs.is_empty() => {
+                Ok(Callable::Path(expr.path.clone()))
+            }
+            Ok(Expr::Closure(closure)) => Ok(Callable::Closure(closure)),
+            _ => Err(Error::custom(format!(
+                "Expected a function path or closure, found `{}`",
+                value
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Callable;
+    use crate::FromMeta;
+    use quote::ToTokens;
+    use syn::parse_quote;
+
+    fn callable(meta: syn::Meta) -> crate::Result<Callable> {
+        Callable::from_meta(&meta)
+    }
+
+    #[test]
+    fn path() {
+        let c = callable(parse_quote!(default = "Vec::new")).unwrap();
+        assert_eq!(c.as_path(), Some(&parse_quote!(Vec::new)));
+        assert_eq!(c.to_token_stream().to_string(), "Vec :: new");
+    }
+
+    #[test]
+    fn closure() {
+        let c = callable(parse_quote!(map = "|v: u8| v * 2")).unwrap();
+        assert!(c.as_closure().is_some());
+        assert_eq!(c.to_token_stream().to_string(), "(| v : u8 | v * 2)");
+    }
+
+    #[test]
+    fn rejects_other_expressions() {
+        let err = callable(parse_quote!(map = "1 + 2")).unwrap_err();
+        assert!(err.has_span());
+        assert_eq!(
+            err.to_string(),
+            "Expected a function path or closure, found `1 + 2`"
+        );
+    }
+}
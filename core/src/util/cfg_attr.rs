@@ -0,0 +1,179 @@
+use proc_macro2::{TokenStream, TokenTree};
+use syn::parse::{ParseStream, Parser};
+use syn::{Attribute, Meta, Path, Token};
+
+use crate::{Error, Result};
+
+/// The contents of a `#[cfg_attr(condition, attrs...)]` attribute.
+///
+/// The compiler expands `cfg_attr` before a derive sees its input, but attributes which were
+/// re-emitted as tokens (for example by another macro) can still reach darling in this form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CfgAttr {
+    /// The predicate which controls whether `attrs` apply, such as `feature = "x"`.
+    pub condition: Meta,
+    /// The wrapped attributes. These have the same style and delimiters as the `cfg_attr`.
+    pub attrs: Vec<Attribute>,
+}
+
+impl CfgAttr {
+    /// Parse `attr` if it is a `cfg_attr`. Any other attribute produces `Ok(None)`.
+    pub fn try_from_attribute(attr: &Attribute) -> Result<Option<Self>> {
+        if !attr.path.is_ident("cfg_attr") {
+            return Ok(None);
+        }
+
+        let parser = |input: ParseStream| {
+            let content;
+            syn::parenthesized!(content in input);
+            let condition = content.parse()?;
+            content.parse::<Token![,]>()?;
+
+            let mut attrs = vec![];
+            while !content.is_empty() {
+                let path = content.call(Path::parse_mod_style)?;
+                let mut tokens = TokenStream::new();
+                while !content.is_empty() && !content.peek(Token![,]) {
+                    tokens.extend(Some(content.parse::<TokenTree>()?));
+                }
+
+                attrs.push(Attribute {
+                    path,
+                    tokens,
+                    ..attr.clone()
+                });
+
+                if !content.is_empty() {
+                    content.parse::<Token![,]>()?;
+                }
+            }
+
+            Ok(CfgAttr { condition, attrs })
+        };
+
+        parser.parse2(attr.tokens.clone()).map(Some).map_err(|e| {
+            Error::custom(format!("Unable to parse `cfg_attr`: {}", e)).with_span(attr)
+        })
+    }
+
+    /// Wrap `attr` in a `cfg_attr` with this condition, keeping the style of `outer`.
+    fn wrap(&self, outer: &Attribute, attr: &Attribute) -> Attribute {
+        let condition = &self.condition;
+        let path = &attr.path;
+        let tokens = &attr.tokens;
+        Attribute {
+            tokens: quote!((#condition, #path #tokens)),
+            ..outer.clone()
+        }
+    }
+}
+
+/// Replace each `cfg_attr` in `attrs` with the attributes it wraps, ignoring its condition.
+/// Nested `cfg_attr` attributes are unwrapped as well, and all other attributes are kept as-is.
+pub fn unwrap_cfg_attrs(attrs: &[Attribute]) -> Result<Vec<Attribute>> {
+    Ok(unwrap_cfg_attrs_with_sources(attrs)?
+        .into_iter()
+        .map(|(attr, _)| attr)
+        .collect())
+}
+
+/// Like [`unwrap_cfg_attrs`], but pairs each attribute with its source: the attribute itself
+/// if it wasn't wrapped, or a `cfg_attr` with the same condition which wraps only it. Passing
+/// on the source instead of the attribute keeps the condition it was written under.
+pub fn unwrap_cfg_attrs_with_sources(attrs: &[Attribute]) -> Result<Vec<(Attribute, Attribute)>> {
+    let mut unwrapped = Vec::with_capacity(attrs.len());
+    for attr in attrs {
+        match unwrap_cfg_attr(attr)? {
+            Some(inner) => unwrapped.extend(inner),
+            None => unwrapped.push((attr.clone(), attr.clone())),
+        }
+    }
+
+    Ok(unwrapped)
+}
+
+/// Unwrap a single attribute as [`unwrap_cfg_attrs_with_sources`] does, or return `Ok(None)`
+/// if it isn't a `cfg_attr`. This lets generated code unwrap each attribute as it reaches it.
+pub fn unwrap_cfg_attr(attr: &Attribute) -> Result<Option<Vec<(Attribute, Attribute)>>> {
+    let cfg_attr = match CfgAttr::try_from_attribute(attr)? {
+        Some(cfg_attr) => cfg_attr,
+        None => return Ok(None),
+    };
+
+    let mut unwrapped = Vec::with_capacity(cfg_attr.attrs.len());
+    for (inner, source) in unwrap_cfg_attrs_with_sources(&cfg_attr.attrs)? {
+        let source = cfg_attr.wrap(attr, &source);
+        unwrapped.push((inner, source));
+    }
+
+    Ok(Some(unwrapped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{unwrap_cfg_attrs, unwrap_cfg_attrs_with_sources, CfgAttr};
+    use syn::{parse_quote, Attribute, Meta};
+
+    #[test]
+    fn parse_cfg_attr() {
+        let attr: Attribute =
+            parse_quote!(#[cfg_attr(feature = "x", my_attr(opt = 1), doc = "Hi")]);
+        let cfg_attr = CfgAttr::try_from_attribute(&attr).unwrap().unwrap();
+
+        let condition: Meta = parse_quote!(feature = "x");
+        assert_eq!(cfg_attr.condition, condition);
+
+        let expected: Vec<Attribute> = vec![
+            parse_quote!(#[my_attr(opt = 1)]),
+            parse_quote!(#[doc = "Hi"]),
+        ];
+        assert_eq!(cfg_attr.attrs, expected);
+    }
+
+    #[test]
+    fn other_attributes_are_not_cfg_attr() {
+        let attr: Attribute = parse_quote!(#[my_attr(opt = 1)]);
+        assert!(CfgAttr::try_from_attribute(&attr).unwrap().is_none());
+    }
+
+    #[test]
+    fn nested_cfg_attr_is_unwrapped() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[inline]),
+            parse_quote!(#[cfg_attr(unix, cfg_attr(test, my_attr(opt)), other)]),
+        ];
+
+        let expected: Vec<Attribute> = vec![
+            parse_quote!(#[inline]),
+            parse_quote!(#[my_attr(opt)]),
+            parse_quote!(#[other]),
+        ];
+        assert_eq!(unwrap_cfg_attrs(&attrs).unwrap(), expected);
+    }
+
+    #[test]
+    fn sources_keep_conditions() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[inline]),
+            parse_quote!(#[cfg_attr(unix, cfg_attr(test, my_attr(opt)), other)]),
+        ];
+
+        let sources: Vec<Attribute> = unwrap_cfg_attrs_with_sources(&attrs)
+            .unwrap()
+            .into_iter()
+            .map(|(_, source)| source)
+            .collect();
+        let expected: Vec<Attribute> = vec![
+            parse_quote!(#[inline]),
+            parse_quote!(#[cfg_attr(unix, cfg_attr(test, my_attr(opt)))]),
+            parse_quote!(#[cfg_attr(unix, other)]),
+        ];
+        assert_eq!(sources, expected);
+    }
+
+    #[test]
+    fn malformed_cfg_attr_is_an_error() {
+        let attr: Attribute = parse_quote!(#[cfg_attr(feature = "x")]);
+        CfgAttr::try_from_attribute(&attr).unwrap_err();
+    }
+}
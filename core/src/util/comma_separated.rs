@@ -0,0 +1,83 @@
+use syn::{Lit, Meta, NestedMeta};
+
+use crate::{Error, FromMeta, Result};
+
+/// Read a list which may be written as one comma-separated string, such as
+/// `features = "a, b, c"`, as is common in values pasted from other tools. Use this with
+/// `#[darling(with = "darling::util::comma_separated")]` on a `Vec` field.
+///
+/// Each piece is trimmed and read with the item type's `from_string`, and empty pieces are
+/// skipped, so `""` is an empty list and a trailing comma is allowed. The list form
+/// `features("a", "b")` is also accepted, with each literal read by the item type.
+pub fn comma_separated<T: FromMeta>(meta: &Meta) -> Result<Vec<T>> {
+    match *meta {
+        Meta::NameValue(ref nv) => match nv.lit {
+            Lit::Str(ref s) => {
+                let mut errors = Error::accumulator();
+                let items = s
+                    .value()
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|piece| !piece.is_empty())
+                    .enumerate()
+                    .filter_map(|(index, piece)| {
+                        errors.handle(T::from_string(piece).map_err(|e| e.at(index)))
+                    })
+                    .collect();
+                errors.finish_with(items).map_err(|e| e.with_span(&nv.lit))
+            }
+            ref lit => Err(Error::unexpected_lit_type(lit).with_span(lit)),
+        },
+        Meta::List(ref list) => {
+            let mut errors = Error::accumulator();
+            let items =
+                list.nested
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, item)| match *item {
+                        NestedMeta::Lit(ref lit) => errors
+                            .handle(T::from_value(lit).map_err(|e| e.with_span(lit).at(index))),
+                        NestedMeta::Meta(ref inner) => {
+                            errors.push(Error::unsupported_format("non-literal").with_span(inner));
+                            None
+                        }
+                    })
+                    .collect();
+            errors.finish_with(items)
+        }
+        Meta::Path(_) => Err(Error::unsupported_format("word").with_span(meta)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::comma_separated;
+    use syn::parse_quote;
+
+    #[test]
+    fn splits_strings() {
+        let items: Vec<String> = comma_separated(&parse_quote!(features = "a, b,c ,")).unwrap();
+        assert_eq!(items, vec!["a", "b", "c"]);
+
+        let items: Vec<String> = comma_separated(&parse_quote!(features = "")).unwrap();
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn reads_items_with_their_type() {
+        let items: Vec<u8> = comma_separated(&parse_quote!(ports = "1, 2")).unwrap();
+        assert_eq!(items, vec![1, 2]);
+
+        let err = comma_separated::<u8>(&parse_quote!(ports = "1, x")).unwrap_err();
+        assert!(err.to_string().ends_with("at 1"), "{}", err);
+    }
+
+    #[test]
+    fn reads_lists() {
+        let items: Vec<String> = comma_separated(&parse_quote!(features("a", "b"))).unwrap();
+        assert_eq!(items, vec!["a", "b"]);
+
+        assert!(comma_separated::<String>(&parse_quote!(features(a))).is_err());
+        assert!(comma_separated::<String>(&parse_quote!(features)).is_err());
+    }
+}
@@ -0,0 +1,279 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{Lit, Meta, NestedMeta};
+
+use crate::util::{hidden_item_ident, parse_attribute_args, path_to_string};
+use crate::{Error, Result};
+
+/// The key which names a config file in containers using `#[darling(config_file)]`.
+pub const CONFIG_FILE_KEY: &str = "config";
+
+/// A config file which was read by [`merge_config_file`].
+///
+/// The compiler doesn't know that a macro read the file, so editing it won't rebuild the
+/// crates which use the macro. The tokens of a `ConfigFile` are a `const` which includes the
+/// file; a macro which emits them in its output makes the compiler track the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigFile {
+    path: PathBuf,
+}
+
+impl ConfigFile {
+    /// The path the file was read from, after resolving it against the crate's directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl ToTokens for ConfigFile {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let path = self.path.display().to_string();
+        let name = hidden_item_ident("CONFIG_FILE", &path);
+        tokens.extend(quote!(
+            #[allow(dead_code)]
+            const #name: &str = include_str!(#path);
+        ));
+    }
+}
+
+/// Replace a `config = "path"` item in `items` with the options in the file at `path`.
+///
+/// The file is not TOML: it holds the same syntax as the inside of the attribute, such as
+/// `rename = "x", skip, nested(a = 1)`, with items separated by commas. Options set in
+/// `items` take precedence, so the file only fills in the keys which `items` doesn't set;
+/// when both set a key to a list, the lists are merged the same way. Relative paths are
+/// resolved against the directory of the crate being compiled.
+///
+/// Each file which is read is pushed to `files`. Problems with the file are pushed to
+/// `errors` and point at the `config` item, and the explicit items are returned unchanged.
+pub fn merge_config_file<'a, I>(
+    items: I,
+    errors: &mut Vec<Error>,
+    files: &mut Vec<ConfigFile>,
+) -> Vec<NestedMeta>
+where
+    I: IntoIterator<Item = &'a NestedMeta>,
+{
+    let mut explicit = vec![];
+    let mut config = None;
+
+    for item in items {
+        match *item {
+            NestedMeta::Meta(ref meta) if meta.path().is_ident(CONFIG_FILE_KEY) => {
+                if config.is_some() {
+                    errors.push(Error::duplicate_field(CONFIG_FILE_KEY).with_span(meta));
+                } else {
+                    config = Some(meta);
+                }
+            }
+            _ => explicit.push(item.clone()),
+        }
+    }
+
+    let config = match config {
+        Some(config) => config,
+        None => return explicit,
+    };
+
+    match read_config_file(config) {
+        Ok((file, from_file)) => {
+            merge_items(&mut explicit, from_file);
+            files.push(file);
+        }
+        Err(err) => errors.push(err.at(CONFIG_FILE_KEY)),
+    }
+
+    explicit
+}
+
+/// Add the items of `from_file` whose keys aren't set by `explicit`. When both set a key to a
+/// list, the items of the list from the file are merged into the explicit one.
+fn merge_items(explicit: &mut Vec<NestedMeta>, from_file: Vec<NestedMeta>) {
+    // Only the explicit items take precedence; repeated keys within the file are kept, so
+    // they're reported as duplicates.
+    let explicit_len = explicit.len();
+    for item in from_file {
+        let existing = item_key(&item).and_then(|key| {
+            explicit[..explicit_len]
+                .iter_mut()
+                .find(|existing| item_key(existing).as_ref() == Some(&key))
+        });
+
+        match (existing, item) {
+            (None, item) => explicit.push(item),
+            (
+                Some(&mut NestedMeta::Meta(Meta::List(ref mut existing))),
+                NestedMeta::Meta(Meta::List(list)),
+            ) => {
+                let mut nested: Vec<NestedMeta> = existing.nested.iter().cloned().collect();
+                merge_items(&mut nested, list.nested.into_iter().collect());
+                existing.nested = nested.into_iter().collect();
+            }
+            (Some(_), _) => {}
+        }
+    }
+}
+
+fn item_key(item: &NestedMeta) -> Option<String> {
+    match *item {
+        NestedMeta::Meta(ref meta) => Some(path_to_string(meta.path())),
+        NestedMeta::Lit(_) => None,
+    }
+}
+
+fn read_config_file(config: &Meta) -> Result<(ConfigFile, Vec<NestedMeta>)> {
+    let lit = match *config {
+        Meta::NameValue(ref nv) => match nv.lit {
+            Lit::Str(ref lit) => lit,
+            ref other => return Err(Error::unexpected_lit_type(other)),
+        },
+        Meta::Path(_) => return Err(Error::unsupported_format("word").with_span(config)),
+        Meta::List(_) => return Err(Error::unsupported_format("list").with_span(config)),
+    };
+
+    let mut path = PathBuf::from(lit.value());
+    if path.is_relative() {
+        if let Some(dir) = env::var_os("CARGO_MANIFEST_DIR") {
+            path = PathBuf::from(dir).join(path);
+        }
+    }
+
+    let contents = fs::read_to_string(&path).map_err(|e| {
+        Error::custom(format!(
+            "Unable to read config file `{}`: {}",
+            path.display(),
+            e
+        ))
+        .with_span(lit)
+    })?;
+
+    let tokens: TokenStream = contents.parse().map_err(|_| {
+        Error::custom(format!("Unable to parse config file `{}`", path.display())).with_span(lit)
+    })?;
+
+    // Tokens from the file have no location in the crate's source, so errors in their values
+    // point at the `config` item instead.
+    let items = parse_attribute_args(respan(tokens, lit.span())).map_err(|e| e.with_span(lit))?;
+    Ok((ConfigFile { path }, items))
+}
+
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(ref group) = token {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                token = TokenTree::Group(respanned);
+            } else {
+                token.set_span(span);
+            }
+            token
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use syn::{parse_quote, NestedMeta};
+
+    use super::{merge_config_file, ConfigFile};
+    use crate::util::path_to_string;
+
+    fn keys(items: &[NestedMeta]) -> Vec<String> {
+        items
+            .iter()
+            .map(|item| match *item {
+                NestedMeta::Meta(ref meta) => path_to_string(meta.path()),
+                NestedMeta::Lit(_) => "<lit>".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn explicit_items_take_precedence() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("darling-config-{}.txt", std::process::id()));
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(b"name = \"from file\", level = 3")
+            .unwrap();
+
+        let config = path.display().to_string();
+        let items: Vec<NestedMeta> = vec![
+            parse_quote!(name = "explicit"),
+            parse_quote!(config = #config),
+        ];
+        let mut errors = vec![];
+        let mut files = vec![];
+        let merged = merge_config_file(&items, &mut errors, &mut files);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(errors.is_empty());
+        assert_eq!(keys(&merged), vec!["name", "level"]);
+        assert_eq!(merged[0], items[0]);
+        assert_eq!(files, vec![ConfigFile { path }]);
+    }
+
+    #[test]
+    fn lists_are_merged() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("darling-config-nested-{}.txt", std::process::id()));
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(b"retry(max = 3, delay(ms = 10, jitter)), name = \"file\"")
+            .unwrap();
+
+        let config = path.display().to_string();
+        let items: Vec<NestedMeta> = vec![
+            parse_quote!(retry(max = 5, delay(ms = 20))),
+            parse_quote!(config = #config),
+        ];
+        let mut errors = vec![];
+        let merged = merge_config_file(&items, &mut errors, &mut vec![]);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(errors.is_empty());
+        let expected: Vec<NestedMeta> = vec![
+            parse_quote!(retry(max = 5, delay(ms = 20, jitter))),
+            parse_quote!(name = "file"),
+        ];
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn tokens_include_the_file() {
+        let file = ConfigFile {
+            path: "/tmp/options.darling".into(),
+        };
+        let tokens = quote::ToTokens::into_token_stream(file).to_string();
+        assert!(tokens.contains("const __DARLING_CONFIG_FILE_"));
+        assert!(tokens.ends_with("include_str ! (\"/tmp/options.darling\") ;"));
+    }
+
+    #[test]
+    fn missing_file_is_reported() {
+        let items: Vec<NestedMeta> = vec![parse_quote!(config = "does/not/exist.txt")];
+        let mut errors = vec![];
+        let merged = merge_config_file(&items, &mut errors, &mut vec![]);
+
+        assert!(merged.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0]
+            .to_string()
+            .starts_with("Unable to read config file"));
+    }
+
+    #[test]
+    fn without_config_items_are_unchanged() {
+        let items: Vec<NestedMeta> = vec![parse_quote!(a = 1), parse_quote!(b)];
+        let mut errors = vec![];
+        assert_eq!(merge_config_file(&items, &mut errors, &mut vec![]), items);
+    }
+}
@@ -0,0 +1,318 @@
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+
+use crate::error::Warning;
+use crate::{Error, FromMeta, Result};
+
+/// Storage for a single-value field while its container is being parsed.
+///
+/// Derived impls declare one slot per field and delegate duplicate detection, missing-field
+/// reporting and error collection to it, which keeps the generated code small for types
+/// with many fields. Manual `FromMeta` impls can use it the same way.
+#[derive(Debug)]
+pub struct FieldSlot<T> {
+    /// The span of the key of the first occurrence, kept so a duplicate can point back at it.
+    first: Option<Span>,
+    /// The key of the first occurrence, if it wasn't the field's name.
+    first_alias: Option<String>,
+    seen: bool,
+    value: Option<T>,
+}
+
+impl<T> FieldSlot<T> {
+    /// Create a slot for a field which hasn't been seen yet.
+    pub fn new() -> Self {
+        FieldSlot {
+            first: None,
+            first_alias: None,
+            seen: false,
+            value: None,
+        }
+    }
+
+    /// Returns `true` if the field has appeared in the input, even if its value was invalid.
+    pub fn is_seen(&self) -> bool {
+        self.seen
+    }
+
+    /// Record an occurrence of the field named `name` at `meta`, whose key is `key`, using
+    /// `parse` to read it.
+    ///
+    /// A repeated occurrence is reported as a duplicate without being parsed, or as a
+    /// conflict if it used a different key, such as an alias of the first one. Parsing errors
+    /// are pushed to `errors` rather than returned, so parsing of the container can continue.
+    pub fn parse<F>(
+        &mut self,
+        name: &str,
+        key: &str,
+        meta: &syn::Meta,
+        errors: &mut Vec<Error>,
+        parse: F,
+    ) where
+        F: FnOnce() -> Result<T>,
+    {
+        if let Some(first) = self.first {
+            let first_key = self.first_alias.as_ref().map_or(name, String::as_str);
+            errors.push(
+                if first_key == key {
+                    Error::duplicate_field(key).with_span(meta)
+                } else {
+                    Error::conflicting_keys(name, first_key, key).with_span(meta.path())
+                }
+                .with_first_span(first),
+            );
+            return;
+        }
+
+        self.first = Some(meta.path().span());
+        if key != name {
+            self.first_alias = Some(key.to_string());
+        }
+        self.parse_items(errors, parse);
+    }
+
+    /// Record a value which is read from several meta items rather than one, such as a field
+    /// whose keys share a prefix. Parsing errors are pushed to `errors`.
+    pub fn parse_items<F>(&mut self, errors: &mut Vec<Error>, parse: F)
+    where
+        F: FnOnce() -> Result<T>,
+    {
+        self.seen = true;
+        match parse() {
+            Ok(value) => self.value = Some(value),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    /// Check that the field appeared in the input. If it didn't, the value from `from_none` is
+    /// used; if that is also `None`, a missing field error for `name` is pushed to `errors`.
+    pub fn require<F>(self, name: &str, errors: &mut Vec<Error>, from_none: F) -> Self
+    where
+        F: FnOnce() -> Option<T>,
+    {
+        self.require_or_else(errors, from_none, || Error::missing_field(name))
+    }
+
+    /// Like [`FieldSlot::require`], but reports a missing field with the error returned by
+    /// `missing`.
+    pub fn require_or_else<F, E>(
+        mut self,
+        errors: &mut Vec<Error>,
+        from_none: F,
+        missing: E,
+    ) -> Self
+    where
+        F: FnOnce() -> Option<T>,
+        E: FnOnce() -> Error,
+    {
+        if !self.is_seen() {
+            match from_none() {
+                Some(value) => self.value = Some(value),
+                None => errors.push(missing()),
+            }
+        }
+
+        self
+    }
+
+    /// Take the parsed value, if there is one.
+    pub fn into_value(self) -> Option<T> {
+        self.value
+    }
+
+    /// Take the parsed value of a field which was checked with [`FieldSlot::require`].
+    ///
+    /// # Panics
+    /// Panics if the slot is empty, which only happens if errors from `require` were ignored.
+    pub fn unwrap(self) -> T {
+        self.value
+            .expect("Uninitialized fields without defaults were already checked")
+    }
+}
+
+impl<T> Default for FieldSlot<T> {
+    fn default() -> Self {
+        FieldSlot::new()
+    }
+}
+
+/// A field slot which reads its value with the field type's `FromMeta` impl.
+///
+/// Derived impls with `#[darling(dyn_fields)]` parse their fields through this trait, so the
+/// parsing code is instantiated once per field type instead of once per field.
+pub trait DynFieldSlot {
+    /// Record an occurrence of the field named `name` at `meta`, whose key is `key`, as
+    /// [`FieldSlot::parse`] does. Parsing errors point at `meta` and are located at `name`,
+    /// and warnings from the field's value are pushed to `warnings`.
+    fn parse_dyn(
+        &mut self,
+        name: &str,
+        key: &str,
+        meta: &syn::Meta,
+        errors: &mut Vec<Error>,
+        warnings: &mut Vec<Warning>,
+    );
+}
+
+impl<T: FromMeta> DynFieldSlot for FieldSlot<T> {
+    fn parse_dyn(
+        &mut self,
+        name: &str,
+        key: &str,
+        meta: &syn::Meta,
+        errors: &mut Vec<Error>,
+        warnings: &mut Vec<Warning>,
+    ) {
+        self.parse(name, key, meta, errors, || {
+            T::from_meta_with_warnings(meta, warnings).map_err(|e| e.with_span(meta).at(name))
+        });
+    }
+}
+
+/// Record an occurrence of a field through its slot's [`DynFieldSlot`] impl.
+///
+/// Taking the slot as a trait object keeps this function, and the code which calls it, the
+/// same for every field.
+pub fn parse_dyn_field(
+    slot: &mut dyn DynFieldSlot,
+    name: &str,
+    key: &str,
+    meta: &syn::Meta,
+    errors: &mut Vec<Error>,
+    warnings: &mut Vec<Warning>,
+) {
+    slot.parse_dyn(name, key, meta, errors, warnings);
+}
+
+/// Record a parsed value of a field which can appear more than once, pushing it to `values`
+/// or its error to `errors`.
+pub fn push_multiple<T>(values: &mut Vec<T>, errors: &mut Vec<Error>, value: Result<T>) {
+    match value {
+        Ok(value) => values.push(value),
+        Err(err) => errors.push(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_dyn_field, FieldSlot};
+    use crate::{Error, FromMeta};
+    use syn::parse_quote;
+
+    #[test]
+    fn reports_duplicates() {
+        let first: syn::Meta = parse_quote!(name = "a");
+        let second: syn::Meta = parse_quote!(name = "b");
+        let mut errors = vec![];
+        let mut slot = FieldSlot::<String>::new();
+
+        slot.parse("name", "name", &first, &mut errors, || {
+            String::from_meta(&first)
+        });
+        slot.parse("name", "name", &second, &mut errors, || {
+            String::from_meta(&second)
+        });
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "Duplicate field `name`");
+        assert!(errors[0].first_span().is_some());
+        assert_eq!(slot.unwrap(), "a");
+    }
+
+    #[test]
+    fn reports_conflicting_keys() {
+        let first: syn::Meta = parse_quote!(colour = "red");
+        let second: syn::Meta = parse_quote!(color = "blue");
+        let mut errors = vec![];
+        let mut slot = FieldSlot::<String>::new();
+
+        slot.parse("color", "colour", &first, &mut errors, || {
+            String::from_meta(&first)
+        });
+        slot.parse("color", "color", &second, &mut errors, || {
+            String::from_meta(&second)
+        });
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "Keys `colour` and `color` both set the field `color`; use only one of them"
+        );
+        assert_eq!(slot.unwrap(), "red");
+    }
+
+    #[test]
+    fn repeated_alias_is_a_duplicate() {
+        let first: syn::Meta = parse_quote!(colour = "red");
+        let second: syn::Meta = parse_quote!(colour = "blue");
+        let mut errors = vec![];
+        let mut slot = FieldSlot::<String>::new();
+
+        slot.parse("color", "colour", &first, &mut errors, || {
+            String::from_meta(&first)
+        });
+        slot.parse("color", "colour", &second, &mut errors, || {
+            String::from_meta(&second)
+        });
+
+        assert_eq!(errors[0].to_string(), "Duplicate field `colour`");
+    }
+
+    #[test]
+    fn invalid_value_is_still_seen() {
+        let meta: syn::Meta = parse_quote!(count = "x");
+        let mut errors = vec![];
+        let mut slot = FieldSlot::<u8>::new();
+
+        slot.parse("count", "count", &meta, &mut errors, || {
+            u8::from_meta(&meta)
+        });
+        let slot = slot.require("count", &mut errors, || None);
+
+        assert!(slot.is_seen());
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn require_uses_from_none() {
+        let mut errors: Vec<Error> = vec![];
+
+        let slot = FieldSlot::<u8>::new().require("a", &mut errors, || Some(1));
+        assert_eq!(slot.into_value(), Some(1));
+        assert!(errors.is_empty());
+
+        let slot = FieldSlot::<u8>::new().require("b", &mut errors, || None);
+        assert_eq!(slot.into_value(), None);
+        assert_eq!(errors[0].to_string(), "Missing field `b`");
+    }
+
+    #[test]
+    fn dyn_slots_parse_and_report_duplicates() {
+        let first: syn::Meta = parse_quote!(count = 1);
+        let second: syn::Meta = parse_quote!(count = "x");
+        let mut errors = vec![];
+        let mut warnings = vec![];
+        let mut slot = FieldSlot::<u8>::new();
+
+        parse_dyn_field(
+            &mut slot,
+            "count",
+            "count",
+            &first,
+            &mut errors,
+            &mut warnings,
+        );
+        parse_dyn_field(
+            &mut slot,
+            "count",
+            "count",
+            &second,
+            &mut errors,
+            &mut warnings,
+        );
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "Duplicate field `count`");
+        assert_eq!(slot.unwrap(), 1);
+    }
+}
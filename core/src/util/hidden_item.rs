@@ -0,0 +1,37 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use proc_macro2::Span;
+use syn::Ident;
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Create a name such as `__DARLING_WARNING_1A2B3C_0` for an item which generated code only
+/// adds for its side effects.
+///
+/// Anonymous `const _` items need Rust 1.37, so each item gets a name of its own instead. The
+/// counter keeps names apart within one proc-macro; the hash of `seed` keeps them apart from
+/// those of other proc-macros built on darling, which count separately.
+pub(crate) fn hidden_item_ident(kind: &str, seed: &str) -> Ident {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    Ident::new(
+        &format!("__DARLING_{}_{:X}_{}", kind, hasher.finish(), id),
+        Span::call_site(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hidden_item_ident;
+
+    #[test]
+    fn names_are_unique() {
+        let first = hidden_item_ident("WARNING", "stop");
+        let second = hidden_item_ident("WARNING", "stop");
+        assert!(first.to_string().starts_with("__DARLING_WARNING_"));
+        assert_ne!(first, second);
+    }
+}
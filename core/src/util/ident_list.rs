@@ -0,0 +1,112 @@
+use std::ops::Deref;
+
+use syn::{Ident, Meta, NestedMeta};
+
+use crate::{Error, FromMeta, Result};
+
+/// A list of `syn::Ident` instances. This type is used to extract a list of single identifiers
+/// from an attribute, such as `#[sorted(fields(a, b, c))]`.
+///
+/// Unlike [`PathList`](super::PathList), each item must be a single identifier, and keywords
+/// such as `type` are rejected unless written as raw identifiers like `r#type`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IdentList(Vec<Ident>);
+
+impl IdentList {
+    /// Create a new list.
+    pub fn new<T: Into<Ident>>(vals: Vec<T>) -> Self {
+        IdentList(vals.into_iter().map(T::into).collect())
+    }
+
+    /// Create a new `Vec` containing the string representation of each ident.
+    pub fn to_strings(&self) -> Vec<String> {
+        self.0.iter().map(Ident::to_string).collect()
+    }
+}
+
+impl Deref for IdentList {
+    type Target = Vec<Ident>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for IdentList {
+    type Item = Ident;
+    type IntoIter = ::std::vec::IntoIter<Ident>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a IdentList {
+    type Item = &'a Ident;
+    type IntoIter = ::std::slice::Iter<'a, Ident>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl From<Vec<Ident>> for IdentList {
+    fn from(v: Vec<Ident>) -> Self {
+        IdentList(v)
+    }
+}
+
+impl FromMeta for IdentList {
+    fn from_list(v: &[NestedMeta]) -> Result<Self> {
+        let mut errors = Error::accumulator();
+        let mut idents = Vec::with_capacity(v.len());
+
+        for nmi in v {
+            let ident = match *nmi {
+                NestedMeta::Meta(Meta::Path(ref path)) => path.get_ident(),
+                _ => None,
+            };
+
+            match ident {
+                // Attribute syntax accepts keywords as words, but they can't be used as identifiers
+                // in generated code. Raw identifiers parse successfully here.
+                Some(ident) if syn::parse_str::<Ident>(&ident.to_string()).is_err() => {
+                    errors.push(
+                        Error::custom(format!("`{}` is a keyword, not an identifier", ident))
+                            .with_span(ident),
+                    );
+                }
+                Some(ident) => idents.push(ident.clone()),
+                None => errors.push(Error::unexpected_type("non-ident").with_span(nmi)),
+            }
+        }
+
+        errors.finish_with(IdentList(idents))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IdentList;
+    use crate::FromMeta;
+    use syn::parse_quote;
+
+    #[test]
+    fn succeeds() {
+        let idents = IdentList::from_meta(&parse_quote!(fields(a, b, r#type))).unwrap();
+        assert_eq!(idents.to_strings(), vec!["a", "b", "r#type"]);
+    }
+
+    #[test]
+    fn rejects_paths() {
+        let err = IdentList::from_meta(&parse_quote!(fields(a, b::c, d = 1))).unwrap_err();
+        assert_eq!(err.len(), 2);
+    }
+
+    #[test]
+    fn rejects_keywords() {
+        let err = IdentList::from_meta(&parse_quote!(fields(a, type))).unwrap_err();
+        assert_eq!(err.to_string(), "`type` is a keyword, not an identifier");
+        assert!(err.has_span());
+    }
+}
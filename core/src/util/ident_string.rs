@@ -1,5 +1,6 @@
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::ops::Deref;
 
 use proc_macro2::{Span, TokenStream};
 use quote::ToTokens;
@@ -67,6 +68,14 @@ impl AsRef<str> for IdentString {
     }
 }
 
+impl Deref for IdentString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 impl From<Ident> for IdentString {
     fn from(ident: Ident) -> Self {
         IdentString::new(ident)
@@ -99,7 +108,7 @@ impl PartialEq<String> for IdentString {
     }
 }
 
-impl<'a> PartialEq<&'a str> for IdentString {
+impl PartialEq<&str> for IdentString {
     fn eq(&self, rhs: &&str) -> bool {
         self.as_str() == *rhs
     }
@@ -152,4 +161,11 @@ mod tests {
         assert_eq!(after, "var_my");
         assert_eq!(after, String::from("var_my"));
     }
+
+    #[test]
+    fn deref_to_str() {
+        let i = IdentString::new(parse_quote!(my_field));
+        assert!(i.starts_with("my_"));
+        assert_eq!(i.len(), 8);
+    }
 }
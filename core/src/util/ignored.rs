@@ -1,7 +1,7 @@
 use crate::{
     usage::{self, UsesLifetimes, UsesTypeParams},
-    FromDeriveInput, FromField, FromGenericParam, FromGenerics, FromMeta, FromTypeParam,
-    FromVariant, Result,
+    FromAttributes, FromConstParam, FromDeriveInput, FromField, FromGenericParam, FromGenerics,
+    FromMeta, FromTypeParam, FromVariant, Result,
 };
 
 /// An efficient way of discarding data from a syntax element.
@@ -21,6 +21,7 @@ macro_rules! ignored {
     };
 }
 
+ignored!(FromConstParam, from_const_param, syn::ConstParam);
 ignored!(FromGenericParam, from_generic_param, syn::GenericParam);
 ignored!(FromGenerics, from_generics, syn::Generics);
 ignored!(FromTypeParam, from_type_param, syn::TypeParam);
@@ -29,6 +30,12 @@ ignored!(FromDeriveInput, from_derive_input, syn::DeriveInput);
 ignored!(FromField, from_field, syn::Field);
 ignored!(FromVariant, from_variant, syn::Variant);
 
+impl FromAttributes for Ignored {
+    fn from_attributes(_: &[syn::Attribute]) -> Result<Self> {
+        Ok(Ignored)
+    }
+}
+
 impl UsesTypeParams for Ignored {
     fn uses_type_params<'a>(
         &self,
@@ -0,0 +1,111 @@
+use syn::{Ident, Meta, NestedMeta, Path};
+
+use crate::{Error, Result, ToMeta};
+
+/// Remove `prefix` from the key of `meta`, so `retry_max = 3` becomes `max = 3` for the
+/// prefix `retry_`. The shortened key keeps the span of the original one.
+///
+/// Returns an unknown field error if nothing valid is left of the key after the prefix.
+pub fn strip_key_prefix(meta: &Meta, prefix: &str) -> Result<NestedMeta> {
+    let key = match meta.path().get_ident() {
+        Some(key) => key,
+        None => return Err(Error::unknown_field_path(meta.path()).with_span(meta)),
+    };
+
+    let name = key.to_string();
+    let rest = if name.starts_with(prefix) {
+        &name[prefix.len()..]
+    } else {
+        &name[..]
+    };
+
+    if syn::parse_str::<Ident>(rest).is_err() {
+        return Err(Error::unknown_field(&name).with_span(meta));
+    }
+
+    Ok(NestedMeta::Meta(with_key(
+        meta.clone(),
+        Ident::new(rest, key.span()),
+    )))
+}
+
+/// Write `value` as a list and push each of its items to `items` with `prefix` added to the
+/// key, so a field with `prefix = "retry_"` writes `retry_max = 3` rather than `max = 3`.
+pub fn push_prefixed_meta<T: ToMeta + ?Sized>(
+    items: &mut Vec<NestedMeta>,
+    prefix: &str,
+    value: &T,
+) {
+    let list = match value.to_meta(&Path::from(Ident::new(
+        "group",
+        proc_macro2::Span::call_site(),
+    ))) {
+        Some(Meta::List(list)) => list,
+        _ => return,
+    };
+
+    for item in list.nested {
+        items.push(match item {
+            NestedMeta::Meta(meta) => match meta.path().get_ident().cloned() {
+                Some(key) => NestedMeta::Meta(with_key(
+                    meta,
+                    Ident::new(&format!("{}{}", prefix, key), key.span()),
+                )),
+                None => NestedMeta::Meta(meta),
+            },
+            NestedMeta::Lit(lit) => NestedMeta::Lit(lit),
+        });
+    }
+}
+
+fn with_key(mut meta: Meta, key: Ident) -> Meta {
+    let key = Path::from(key);
+    match meta {
+        Meta::Path(ref mut path) => *path = key,
+        Meta::List(ref mut list) => list.path = key,
+        Meta::NameValue(ref mut nv) => nv.path = key,
+    }
+    meta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{push_prefixed_meta, strip_key_prefix};
+    use syn::{parse_quote, Meta, NestedMeta};
+
+    #[test]
+    fn strips_prefix() {
+        let meta: Meta = parse_quote!(retry_max = 3);
+        let expected: NestedMeta = parse_quote!(max = 3);
+        assert_eq!(strip_key_prefix(&meta, "retry_").unwrap(), expected);
+    }
+
+    #[test]
+    fn bare_prefix_is_unknown() {
+        let meta: Meta = parse_quote!(retry_);
+        assert_eq!(
+            strip_key_prefix(&meta, "retry_").unwrap_err().to_string(),
+            "Unknown field: `retry_`"
+        );
+    }
+
+    #[test]
+    fn pushes_prefixed_items() {
+        let mut items = vec![];
+        push_prefixed_meta(&mut items, "retry_", &Some(5u8));
+        assert!(items.is_empty());
+
+        let expected: Vec<NestedMeta> = vec![parse_quote!(retry_max = 3)];
+        let mut items = vec![];
+        push_prefixed_meta(&mut items, "retry_", &Wrapper);
+        assert_eq!(items, expected);
+    }
+
+    struct Wrapper;
+
+    impl crate::ToMeta for Wrapper {
+        fn to_meta(&self, name: &syn::Path) -> Option<Meta> {
+            Some(crate::util::meta_list(name, vec![parse_quote!(max = 3)]))
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use syn::{Lit, Meta};
+
+use crate::{Error, FromMeta, Result};
+
+const TRUE_WORDS: &[&str] = &["true", "yes", "on", "1"];
+const FALSE_WORDS: &[&str] = &["false", "no", "off", "0"];
+
+/// Read a `bool` which may also be spelled `"yes"`/`"no"`, `"on"`/`"off"` or `"1"`/`"0"`, as
+/// is common in config files. Use this with `#[darling(with = "darling::util::lenient_bool")]`
+/// on a `bool` or `Option<bool>` field.
+///
+/// Besides everything `bool` accepts, this reads those spellings as strings, ignoring case,
+/// and the integer literals `1` and `0`.
+pub fn lenient_bool<T: FromMeta + From<bool>>(meta: &Meta) -> Result<T> {
+    if let Meta::NameValue(ref nv) = *meta {
+        let value = match nv.lit {
+            Lit::Str(ref s) => Some(s.value().to_lowercase()),
+            Lit::Int(ref i) => Some(i.base10_digits().to_string()),
+            _ => None,
+        };
+
+        if let Some(value) = value {
+            return if TRUE_WORDS.contains(&value.as_str()) {
+                Ok(T::from(true))
+            } else if FALSE_WORDS.contains(&value.as_str()) {
+                Ok(T::from(false))
+            } else {
+                Err(
+                    Error::unknown_value_with_alts(&value, TRUE_WORDS.iter().chain(FALSE_WORDS))
+                        .with_span(&nv.lit),
+                )
+            };
+        }
+    }
+
+    T::from_meta(meta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lenient_bool;
+    use syn::parse_quote;
+
+    #[test]
+    fn reads_config_spellings() {
+        assert!(lenient_bool::<bool>(&parse_quote!(enabled = "yes")).unwrap());
+        assert!(lenient_bool::<bool>(&parse_quote!(enabled = "ON")).unwrap());
+        assert!(lenient_bool::<bool>(&parse_quote!(enabled = 1)).unwrap());
+        assert!(!lenient_bool::<bool>(&parse_quote!(enabled = "off")).unwrap());
+        assert_eq!(
+            lenient_bool::<Option<bool>>(&parse_quote!(enabled = 0)).unwrap(),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn keeps_bool_forms() {
+        assert!(lenient_bool::<bool>(&parse_quote!(enabled)).unwrap());
+        assert!(!lenient_bool::<bool>(&parse_quote!(enabled = false)).unwrap());
+    }
+
+    #[test]
+    fn rejects_other_values() {
+        let err = lenient_bool::<bool>(&parse_quote!(enabled = "maybe")).unwrap_err();
+        assert!(err.to_string().starts_with("Unknown literal value `maybe`"));
+        assert!(lenient_bool::<bool>(&parse_quote!(enabled = 2)).is_err());
+    }
+}
@@ -2,47 +2,135 @@
 
 use std::ops::{BitAnd, BitOr, Deref, Not};
 
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+
 use crate::{FromMeta, Result};
 
+mod byte_size;
+mod callable;
+mod cfg_attr;
+mod comma_separated;
+mod config_file;
+mod field_slot;
+mod hidden_item;
+mod ident_list;
 mod ident_string;
 mod ignored;
+mod key_prefix;
+mod lenient_bool;
+mod nesting;
 mod over_ride;
 mod parse_attribute;
 mod path_list;
 mod path_to_string;
+mod render_tokens;
+mod schema;
+mod shape;
+mod source_text;
 mod spanned_value;
+mod special_float;
 mod with_original;
-
+mod word_set;
+
+pub use self::byte_size::{byte_size, ByteSize};
+pub use self::callable::Callable;
+pub use self::cfg_attr::{
+    unwrap_cfg_attr, unwrap_cfg_attrs, unwrap_cfg_attrs_with_sources, CfgAttr,
+};
+pub use self::comma_separated::comma_separated;
+pub use self::config_file::{merge_config_file, ConfigFile, CONFIG_FILE_KEY};
+pub use self::field_slot::{parse_dyn_field, push_multiple, DynFieldSlot, FieldSlot};
+pub(crate) use self::hidden_item::hidden_item_ident;
+pub use self::ident_list::IdentList;
 pub use self::ident_string::IdentString;
 pub use self::ignored::Ignored;
+pub use self::key_prefix::{push_prefixed_meta, strip_key_prefix};
+pub use self::lenient_bool::lenient_bool;
+pub use self::nesting::{NestingGuard, DEFAULT_MAX_DEPTH};
 pub use self::over_ride::Override;
-pub use self::parse_attribute::parse_attribute_to_meta_list;
+pub(crate) use self::parse_attribute::expr_value;
+pub use self::parse_attribute::{
+    name_value_item, parse_attribute_args, parse_attribute_to_meta_list,
+    parse_attribute_to_meta_list_with_exprs, parse_attribute_to_meta_list_with_value,
+    parse_attribute_to_meta_list_with_value_and_exprs, parse_macro_body, parse_named_attribute,
+};
 pub use self::path_list::PathList;
 pub use self::path_to_string::path_to_string;
+pub(crate) use self::render_tokens::render_tokens;
+pub use self::schema::{Schema, Value, ValueKind, ValueMap};
+pub use self::shape::{Shape, ShapeSet};
+pub use self::source_text::SourceText;
 pub use self::spanned_value::SpannedValue;
+pub(crate) use self::special_float::non_finite;
+pub use self::special_float::{special_float, SpecialFloat};
 pub use self::with_original::WithOriginal;
-
-/// Marker type equivalent to `Option<()>` for use in attribute parsing.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct Flag(Option<()>);
+pub use self::word_set::WordSet;
+pub use crate::to_meta::{meta_list, meta_to_string, push_meta};
+pub use ident_case::RenameRule;
+
+/// Marker type equivalent to `Option<()>` for use in attribute parsing. A flag read from
+/// the input also records the span of the word that set it, so errors can point there.
+///
+/// Flags compare equal if they're both present or both absent, regardless of their spans.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Flag {
+    present: Option<()>,
+    span: Option<Span>,
+}
 
 impl Flag {
     /// Creates a new `Flag` which corresponds to the presence of a value.
     pub fn present() -> Self {
-        Flag(Some(()))
+        Flag {
+            present: Some(()),
+            span: None,
+        }
+    }
+
+    /// Creates a new `Flag` which corresponds to a value present at `span`.
+    pub fn from_span(span: Span) -> Self {
+        Flag {
+            present: Some(()),
+            span: Some(span),
+        }
+    }
+
+    /// Returns `true` if the flag was set.
+    pub fn is_present(&self) -> bool {
+        self.present.is_some()
+    }
+
+    /// The location of the word which set this flag, if it was read from the input.
+    pub fn span(&self) -> Option<Span> {
+        self.span
     }
 }
 
 impl Deref for Flag {
     type Target = Option<()>;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.present
     }
 }
 
+impl PartialEq for Flag {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.present == rhs.present
+    }
+}
+
+impl Eq for Flag {}
+
 impl FromMeta for Flag {
     fn from_meta(mi: &syn::Meta) -> Result<Self> {
-        FromMeta::from_meta(mi).map(Flag)
+        <Option<()> as FromMeta>::from_meta(mi)?;
+        Ok(Flag::from_span(mi.span()))
+    }
+
+    /// An absent flag is simply not set.
+    fn from_none() -> Option<Self> {
+        Some(Flag::default())
     }
 }
 
@@ -57,7 +145,7 @@ impl From<bool> for Flag {
         if v {
             Flag::present()
         } else {
-            Flag(None)
+            Flag::default()
         }
     }
 }
@@ -70,13 +158,13 @@ impl From<Option<()>> for Flag {
 
 impl PartialEq<Option<()>> for Flag {
     fn eq(&self, rhs: &Option<()>) -> bool {
-        self.0 == *rhs
+        self.present == *rhs
     }
 }
 
 impl PartialEq<Flag> for Option<()> {
     fn eq(&self, rhs: &Flag) -> bool {
-        *self == rhs.0
+        *self == rhs.present
     }
 }
 
@@ -91,7 +179,7 @@ impl Not for Flag {
 
     fn not(self) -> Self {
         if self.is_some() {
-            Flag(None)
+            Flag::default()
         } else {
             Flag::present()
         }
@@ -0,0 +1,109 @@
+use std::cell::Cell;
+
+use crate::{Error, Result};
+
+/// The nesting limit used by derived `FromMeta` impls which don't set `max_depth`.
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+thread_local! {
+    static DEPTH: Cell<usize> = Cell::new(0);
+
+    /// The depth at which the tightest enclosing limit is reached, and that limit.
+    static LIMIT: Cell<Option<(usize, usize)>> = Cell::new(None);
+}
+
+/// Tracks how deeply nested the meta list currently being parsed is.
+///
+/// Derived `FromMeta` impls enter a guard before parsing a list, so a type which contains
+/// itself (for example through `Option<Box<Self>>`) cannot exhaust the proc-macro stack
+/// when given a pathologically nested attribute. Each guard counts levels from the depth at
+/// which it was entered, so a type's `max_depth` limits the nesting below its own list even
+/// when that list is itself nested deeply in another type. The limits of enclosing guards
+/// keep applying to everything parsed within them, and each guard releases its level when
+/// it is dropped.
+///
+/// When the limit is exceeded, the returned error is passed back up through each enclosing
+/// field, so its location describes the full nesting path.
+#[derive(Debug)]
+pub struct NestingGuard {
+    enclosing_limit: Option<(usize, usize)>,
+}
+
+impl NestingGuard {
+    /// Enter one more level of nesting, failing if that would put the input more than
+    /// `max_depth` levels below this guard, or exceed the limit of an enclosing guard.
+    pub fn enter(max_depth: usize) -> Result<Self> {
+        let current = Self::current_depth();
+        let enclosing_limit = LIMIT.with(Cell::get);
+        let own_limit = (current.saturating_add(max_depth), max_depth);
+        let limit = match enclosing_limit {
+            Some(enclosing) if enclosing.0 < own_limit.0 => enclosing,
+            _ => own_limit,
+        };
+
+        if current >= limit.0 {
+            return Err(Error::nesting_limit_exceeded(limit.1));
+        }
+
+        DEPTH.with(|depth| depth.set(current + 1));
+        LIMIT.with(|cell| cell.set(Some(limit)));
+        Ok(NestingGuard { enclosing_limit })
+    }
+
+    /// The number of levels currently entered on this thread.
+    pub fn current_depth() -> usize {
+        DEPTH.with(Cell::get)
+    }
+}
+
+impl Drop for NestingGuard {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| depth.set(depth.get() - 1));
+        LIMIT.with(|cell| cell.set(self.enclosing_limit));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NestingGuard;
+
+    #[test]
+    fn guard_releases_depth_on_drop() {
+        let outer = NestingGuard::enter(2).unwrap();
+        {
+            let _inner = NestingGuard::enter(2).unwrap();
+            assert_eq!(NestingGuard::current_depth(), 2);
+            NestingGuard::enter(2).unwrap_err();
+        }
+
+        assert_eq!(NestingGuard::current_depth(), 1);
+        drop(outer);
+        assert_eq!(NestingGuard::current_depth(), 0);
+    }
+
+    #[test]
+    fn limits_count_from_where_the_guard_was_entered() {
+        let _outer = NestingGuard::enter(8).unwrap();
+        let _middle = NestingGuard::enter(8).unwrap();
+
+        // The inner limit counts its levels from depth 2, not from the start.
+        let _inner = NestingGuard::enter(2).unwrap();
+        let _nested = NestingGuard::enter(8).unwrap();
+        let err = NestingGuard::enter(8).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Nesting limit exceeded: Expected no more than 2 levels"
+        );
+    }
+
+    #[test]
+    fn enclosing_limit_still_applies() {
+        let _outer = NestingGuard::enter(2).unwrap();
+        let _inner = NestingGuard::enter(8).unwrap();
+        let err = NestingGuard::enter(8).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Nesting limit exceeded: Expected no more than 2 levels"
+        );
+    }
+}
@@ -144,4 +144,35 @@ impl<T: FromMeta> FromMeta for Override<T> {
     fn from_value(lit: &Lit) -> Result<Self> {
         Ok(Explicit(FromMeta::from_value(lit)?))
     }
+
+    fn from_expr(expr: &syn::Expr) -> Result<Self> {
+        Ok(Explicit(FromMeta::from_expr(expr)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Override;
+    use crate::FromMeta;
+    use syn::parse_quote;
+
+    #[test]
+    fn word_inherits() {
+        let ov = Override::<String>::from_meta(&parse_quote!(default)).unwrap();
+        assert_eq!(ov, Override::Inherit);
+        assert!(!ov.is_explicit());
+    }
+
+    #[test]
+    fn value_is_explicit() {
+        let ov = Override::<String>::from_meta(&parse_quote!(default = "hello")).unwrap();
+        assert_eq!(ov, Override::Explicit("hello".to_string()));
+        assert_eq!(ov.explicit(), Some("hello".to_string()));
+    }
+
+    #[test]
+    fn inherit_falls_back() {
+        assert_eq!(Override::Inherit.unwrap_or(3), 3);
+        assert_eq!(Override::Explicit(1).unwrap_or(3), 1);
+    }
 }
@@ -1,13 +1,63 @@
 use crate::{util::SpannedValue, Error, Result};
+use proc_macro2::{Delimiter, Group, Ident, Literal, Spacing, TokenStream, TokenTree};
 use std::fmt;
-use syn::{punctuated::Pair, spanned::Spanned, token, Attribute, Meta, MetaList, Path};
+use syn::parse::Parser;
+use syn::punctuated::{Pair, Punctuated};
+use syn::{spanned::Spanned, token, Attribute, Expr, Lit, Meta, MetaList, NestedMeta, Path, Token};
+
+/// The suffix of the string literal which carries an expression value through `syn::Meta`.
+const EXPR_SUFFIX: &str = "__darling_expr";
 
 /// Try to parse an attribute into a meta list. Path-type meta values are accepted and returned
 /// as empty lists with their passed-in path. Name-value meta values and non-meta attributes
 /// will cause errors to be returned.
+///
+/// # Dotted Keys
+/// Keys made of dot-separated identifiers are expanded into nested lists before parsing,
+/// so `#[attr(limits.max = 10, limits.min = 1)]` is read as `#[attr(limits(max = 10, min = 1))]`.
+/// Dotted keys that share a prefix are merged into a single nested list, which is placed
+/// where the first of them appeared. A nested list with the same key, as in
+/// `#[attr(limits(max = 10), limits.min = 1)]`, is merged with them too. Merging only happens
+/// within a single attribute.
+///
+/// Values must be literals; see [`parse_attribute_to_meta_list_with_exprs`] to also accept
+/// expressions.
 pub fn parse_attribute_to_meta_list(attr: &Attribute) -> Result<MetaList> {
+    parse_meta_list(attr, false)
+}
+
+/// Like [`parse_attribute_to_meta_list`], but values can also be expressions.
+///
+/// # Expression Values
+/// Values which aren't a single literal, such as `#[attr(limit = some::CONST)]` or
+/// `#[attr(offset = -1)]`, are kept as a `syn::Expr` and passed to
+/// [`FromMeta::from_expr`](crate::FromMeta::from_expr), so only types which opt into
+/// expressions accept them. The tokens of the expression are spanned at its first token.
+/// Attributes made only of literal values are parsed without rewriting them.
+pub fn parse_attribute_to_meta_list_with_exprs(attr: &Attribute) -> Result<MetaList> {
+    parse_meta_list(attr, true)
+}
+
+fn parse_meta_list(attr: &Attribute, exprs: bool) -> Result<MetaList> {
+    let expanded;
+    let expand = has_dotted_keys(attr.tokens.clone()) || (exprs && attr.parse_meta().is_err());
+    let attr = if expand {
+        expanded = Attribute {
+            tokens: expand_dotted_keys(attr.tokens.clone(), exprs),
+            ..attr.clone()
+        };
+        &expanded
+    } else {
+        attr
+    };
+
     match attr.parse_meta() {
-        Ok(Meta::List(list)) => Ok(list),
+        Ok(Meta::List(mut list)) => {
+            if expand && exprs {
+                list.nested.iter_mut().for_each(restore_expr_values);
+            }
+            Ok(list)
+        }
         Ok(Meta::NameValue(nv)) => Err(Error::custom(format!(
             "Name-value arguments are not supported. Use #[{}(...)]",
             DisplayPath(&nv.path)
@@ -23,6 +73,417 @@ pub fn parse_attribute_to_meta_list(attr: &Attribute) -> Result<MetaList> {
     }
 }
 
+/// Like [`parse_attribute_to_meta_list`], but also accepts the name-value form
+/// `#[attr = value]`, which is read as `#[attr(key = value)]`. `key` is spanned at the
+/// attribute's name.
+pub fn parse_attribute_to_meta_list_with_value(attr: &Attribute, key: &str) -> Result<MetaList> {
+    parse_meta_list(&name_value_as_list(attr, key), false)
+}
+
+/// Like [`parse_attribute_to_meta_list_with_value`], but values can also be expressions, as
+/// in [`parse_attribute_to_meta_list_with_exprs`].
+pub fn parse_attribute_to_meta_list_with_value_and_exprs(
+    attr: &Attribute,
+    key: &str,
+) -> Result<MetaList> {
+    parse_meta_list(&name_value_as_list(attr, key), true)
+}
+
+/// Rewrite `#[attr = value]` as `#[attr(key = value)]`, leaving other attributes alone.
+fn name_value_as_list(attr: &Attribute, key: &str) -> Attribute {
+    let mut tokens = attr.tokens.clone().into_iter();
+    match tokens.next() {
+        Some(TokenTree::Punct(ref eq)) if eq.as_char() == '=' => {
+            let key = Ident::new(key, attr.path.span());
+            let mut group = Group::new(Delimiter::Parenthesis, quote!(#key #eq #(#tokens)*));
+            group.set_span(attr.tokens.span());
+            Attribute {
+                tokens: TokenTree::Group(group).into(),
+                ..attr.clone()
+            }
+        }
+        _ => attr.clone(),
+    }
+}
+
+/// Create the item `key = value`, which is how a type with a `name_value` field reads a
+/// literal. `key` is spanned at the value.
+///
+/// # Panics
+/// Panics if `key` isn't a valid identifier.
+pub fn name_value_item(key: &str, value: &syn::Lit) -> NestedMeta {
+    NestedMeta::Meta(Meta::NameValue(syn::MetaNameValue {
+        path: Ident::new(key, value.span()).into(),
+        eq_token: Default::default(),
+        lit: value.clone(),
+    }))
+}
+
+/// Check that `attr` is named `name`, then parse it with [`parse_attribute_to_meta_list`] and
+/// return its nested meta items. This is the usual first step of a manual `FromAttributes`
+/// or `FromMeta` implementation.
+///
+/// The name is compared using [`path_to_string`](super::path_to_string), so multi-segment
+/// names such as `my_crate::opts` are supported.
+pub fn parse_named_attribute(attr: &Attribute, name: &str) -> Result<Vec<NestedMeta>> {
+    let actual = super::path_to_string(&attr.path);
+    if actual != name {
+        return Err(
+            Error::custom(format!("Expected attribute `{}`, found `{}`", name, actual))
+                .with_span(&attr.path),
+        );
+    }
+
+    parse_attribute_to_meta_list(attr).map(|list| list.nested.into_iter().collect())
+}
+
+/// Parse the arguments of an attribute macro, which is the `TokenStream` passed before the item,
+/// into a list of nested meta items. The result can be passed to `FromMeta::from_list`.
+///
+/// Dotted keys and expression values are handled the same way as in
+/// [`parse_attribute_to_meta_list_with_exprs`].
+pub fn parse_attribute_args(tokens: TokenStream) -> Result<Vec<NestedMeta>> {
+    parse_items(tokens, "attribute arguments")
+}
+
+/// Parse the body of a function-like macro, such as the `key = "value", nested(x = 1)` in
+/// `my_macro! { key = "value", nested(x = 1) }`, into a list of nested meta items. The result
+/// can be passed to `FromMeta::from_list`.
+///
+/// The body is read like the arguments of an attribute macro, with a trailing comma allowed.
+pub fn parse_macro_body(tokens: TokenStream) -> Result<Vec<NestedMeta>> {
+    parse_items(tokens, "macro input")
+}
+
+/// Parse comma-separated items, naming what was being parsed if they're malformed.
+fn parse_items(tokens: TokenStream, what: &str) -> Result<Vec<NestedMeta>> {
+    Punctuated::<NestedMeta, Token![,]>::parse_terminated
+        .parse2(expand_list(tokens, true))
+        .map(|nested| {
+            let mut nested: Vec<NestedMeta> = nested.into_iter().collect();
+            nested.iter_mut().for_each(restore_expr_values);
+            nested
+        })
+        .map_err(|e| {
+            Error::custom(format!("Unable to parse {}: {}", what, e))
+                .with_span(&SpannedValue::new((), e.span()))
+        })
+}
+
+/// Returns `true` if any parenthesized list in `tokens` contains an item that starts with
+/// `ident.`.
+fn has_dotted_keys(tokens: TokenStream) -> bool {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(tt) = tokens.next() {
+        match tt {
+            TokenTree::Group(ref group)
+                if group.delimiter() == Delimiter::Parenthesis
+                    && has_dotted_keys(group.stream()) =>
+            {
+                return true;
+            }
+            TokenTree::Ident(_) => {
+                if let Some(TokenTree::Punct(p)) = tokens.peek() {
+                    if p.as_char() == '.' && p.spacing() == Spacing::Alone {
+                        return true;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// Rewrite the tokens of an attribute so that every dotted key in a parenthesized list
+/// becomes a nested list, merging keys that share the same first segment. If `exprs` is set,
+/// expression values are also marked with [`mark_expr_value`].
+fn expand_dotted_keys(tokens: TokenStream, exprs: bool) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                let mut expanded =
+                    Group::new(Delimiter::Parenthesis, expand_list(group.stream(), exprs));
+                expanded.set_span(group.span());
+                TokenTree::Group(expanded)
+            }
+            other => other,
+        })
+        .collect()
+}
+
+/// An entry in a comma-separated list which is being rewritten.
+enum ListItem {
+    /// An item which did not start with a dotted key.
+    Plain(Vec<TokenTree>),
+    /// The merged remainders of all items which started with `ident.`, along with the items of
+    /// a nested list `ident(...)` in the same list.
+    Dotted(Ident, Vec<Vec<TokenTree>>),
+}
+
+fn expand_list(tokens: TokenStream, exprs: bool) -> TokenStream {
+    let mark = |item: Vec<TokenTree>| if exprs { mark_expr_value(item) } else { item };
+
+    let entries = split_on_commas(tokens);
+    let dotted_heads: Vec<Ident> = entries
+        .iter()
+        .filter_map(|item| dotted_head(item).cloned())
+        .collect();
+    let mut items: Vec<ListItem> = Vec::new();
+
+    for item in entries {
+        let (head, rests) = match split_dotted_head(item) {
+            Ok((head, rest)) => (head, vec![rest]),
+            // A nested list whose key is also used as a dotted key is merged with those keys.
+            Err(item) => match split_nested_list(item, &dotted_heads) {
+                Ok(nested) => nested,
+                Err(item) => {
+                    items.push(ListItem::Plain(mark(item)));
+                    continue;
+                }
+            },
+        };
+
+        let existing = items.iter_mut().find(|existing| match **existing {
+            ListItem::Dotted(ref ident, _) => *ident == head,
+            ListItem::Plain(_) => false,
+        });
+
+        match existing {
+            Some(&mut ListItem::Dotted(_, ref mut existing)) => existing.extend(rests),
+            _ => items.push(ListItem::Dotted(head, rests)),
+        }
+    }
+
+    let mut output = TokenStream::new();
+    for (index, item) in items.into_iter().enumerate() {
+        if index > 0 {
+            output.extend(quote!(,));
+        }
+
+        match item {
+            ListItem::Plain(tokens) => {
+                output.extend(expand_dotted_keys(tokens.into_iter().collect(), exprs))
+            }
+            ListItem::Dotted(head, rests) => {
+                let mut nested = TokenStream::new();
+                for (index, rest) in rests.into_iter().enumerate() {
+                    if index > 0 {
+                        nested.extend(quote!(,));
+                    }
+                    nested.extend(mark(rest));
+                }
+
+                let mut group = Group::new(Delimiter::Parenthesis, expand_list(nested, exprs));
+                group.set_span(head.span());
+                output.extend(vec![TokenTree::Ident(head), TokenTree::Group(group)]);
+            }
+        }
+    }
+
+    output
+}
+
+/// Split a list's tokens into its top-level comma-separated items. Empty items,
+/// such as the one after a trailing comma, are dropped.
+///
+/// A comma inside the value of a `path = value` item, such as in `f = |a, b| a + b` or
+/// `x = foo::<A, B>()`, doesn't end the item: if the value before the comma isn't a complete
+/// expression, the following items are added to it as long as that completes the expression.
+fn split_on_commas(tokens: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut pieces = vec![];
+    let mut current = vec![];
+    for tt in tokens {
+        if let TokenTree::Punct(ref p) = tt {
+            if p.as_char() == ',' {
+                pieces.push((std::mem::replace(&mut current, vec![]), Some(tt.clone())));
+                continue;
+            }
+        }
+
+        current.push(tt);
+    }
+
+    pieces.push((current, None));
+
+    let mut items = vec![];
+    let mut index = 0;
+    while index < pieces.len() {
+        let (item, end) = join_expr_value(&pieces[index..]);
+        items.push(item);
+        index += end;
+    }
+
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+/// Join the first of `pieces` with as many of the following ones as it takes to make its
+/// value a complete expression, returning the item and the number of pieces it used. The
+/// first piece is returned alone if it has no value, if its value is already complete, or
+/// if no number of pieces completes it.
+fn join_expr_value(pieces: &[(Vec<TokenTree>, Option<TokenTree>)]) -> (Vec<TokenTree>, usize) {
+    let first = &pieces[0].0;
+    let value_start = match value_index(first) {
+        Some(index) if index < first.len() && !is_expr(&first[index..]) => index,
+        _ => return (first.clone(), 1),
+    };
+
+    let mut joined = first.clone();
+    for (index, piece) in pieces.iter().enumerate().skip(1) {
+        joined.extend(pieces[index - 1].1.clone());
+        joined.extend(piece.0.iter().cloned());
+        if is_expr(&joined[value_start..]) {
+            return (joined, index + 1);
+        }
+    }
+
+    (first.clone(), 1)
+}
+
+fn is_expr(tokens: &[TokenTree]) -> bool {
+    syn::parse2::<Expr>(tokens.iter().cloned().collect()).is_ok()
+}
+
+/// The index at which the value of a `path = value` item starts, or `None` if the item has
+/// another form. The key is a path, so only idents and `::` may lead up to the `=`.
+fn value_index(item: &[TokenTree]) -> Option<usize> {
+    for (index, tt) in item.iter().enumerate() {
+        match *tt {
+            TokenTree::Ident(_) => {}
+            TokenTree::Punct(ref p) if p.as_char() == ':' => {}
+            TokenTree::Punct(ref p)
+                if p.as_char() == '=' && p.spacing() == Spacing::Alone && index > 0 =>
+            {
+                return Some(index + 1);
+            }
+            _ => return None,
+        }
+    }
+
+    None
+}
+
+/// If an item is `path = value` and its value isn't a single literal, replace the value
+/// with a string literal of its tokens, suffixed with [`EXPR_SUFFIX`]. Once the items are
+/// parsed, [`restore_expr_values`] turns these literals back into expressions. Other items are
+/// returned unchanged.
+fn mark_expr_value(mut item: Vec<TokenTree>) -> Vec<TokenTree> {
+    let value_start = match value_index(&item) {
+        Some(index) => index,
+        None => return item,
+    };
+
+    let is_literal = match item[value_start..] {
+        [TokenTree::Literal(_)] => true,
+        [TokenTree::Ident(ref ident)] => ident == "true" || ident == "false",
+        _ => false,
+    };
+
+    if is_literal || value_start == item.len() {
+        return item;
+    }
+
+    // Values which aren't valid expressions are left alone, so that parsing reports them.
+    let value_tokens: TokenStream = item[value_start..].iter().cloned().collect();
+    let expr = match syn::parse2::<Expr>(value_tokens.clone()) {
+        Ok(expr) => expr,
+        Err(_) => return item,
+    };
+
+    let repr = format!(
+        "{}{}",
+        Literal::string(&value_tokens.to_string()),
+        EXPR_SUFFIX
+    );
+    let mut lit: Literal = repr
+        .parse()
+        .expect("a suffixed string literal is a valid literal");
+    // Cover the whole expression, as far as the compiler can join spans.
+    lit.set_span(expr.span());
+    item.truncate(value_start);
+    item.push(TokenTree::Literal(lit));
+    item
+}
+
+/// Replace the literals made by [`mark_expr_value`] with `Lit::Verbatim`, so that types which
+/// read string literals don't mistake an expression for a string.
+fn restore_expr_values(item: &mut NestedMeta) {
+    match *item {
+        NestedMeta::Meta(Meta::List(ref mut list)) => {
+            list.nested.iter_mut().for_each(restore_expr_values)
+        }
+        NestedMeta::Meta(Meta::NameValue(ref mut nv)) => {
+            let token = match nv.lit {
+                Lit::Str(ref s) if s.suffix() == EXPR_SUFFIX => s.token(),
+                _ => return,
+            };
+            nv.lit = Lit::Verbatim(token);
+        }
+        _ => {}
+    }
+}
+
+/// Read the expression held by a value parsed from an attribute with expressions enabled.
+/// Returns `None` if `lit` is an ordinary literal.
+pub(crate) fn expr_value(lit: &Lit) -> Option<Result<Expr>> {
+    let token = match *lit {
+        Lit::Verbatim(ref token) => token,
+        _ => return None,
+    };
+
+    match Lit::new(token.clone()) {
+        Lit::Str(ref s) if s.suffix() == EXPR_SUFFIX => Some(s.parse().map_err(Error::from)),
+        _ => None,
+    }
+}
+
+/// The ident an item starts with, if it starts with `ident.`.
+fn dotted_head(item: &[TokenTree]) -> Option<&Ident> {
+    match (item.first(), item.get(1), item.get(2)) {
+        (Some(TokenTree::Ident(head)), Some(TokenTree::Punct(p)), Some(TokenTree::Ident(_)))
+            if p.as_char() == '.' && p.spacing() == Spacing::Alone =>
+        {
+            Some(head)
+        }
+        _ => None,
+    }
+}
+
+/// If an item starts with `ident.`, split it into that ident and the remaining tokens.
+/// Otherwise, the item is returned unchanged as the error value.
+fn split_dotted_head(
+    mut item: Vec<TokenTree>,
+) -> ::std::result::Result<(Ident, Vec<TokenTree>), Vec<TokenTree>> {
+    if dotted_head(&item).is_none() {
+        return Err(item);
+    }
+
+    let rest = item.split_off(2);
+    match item.swap_remove(0) {
+        TokenTree::Ident(head) => Ok((head, rest)),
+        _ => unreachable!("dotted key head was checked to be an ident"),
+    }
+}
+
+/// If an item is a nested list `ident(...)` whose ident is one of `heads`, split it into that
+/// ident and the items of the list. Otherwise, the item is returned unchanged as the error value.
+fn split_nested_list(
+    item: Vec<TokenTree>,
+    heads: &[Ident],
+) -> ::std::result::Result<(Ident, Vec<Vec<TokenTree>>), Vec<TokenTree>> {
+    match item[..] {
+        [TokenTree::Ident(ref head), TokenTree::Group(ref group)]
+            if group.delimiter() == Delimiter::Parenthesis && heads.contains(head) =>
+        {
+            Ok((head.clone(), split_on_commas(group.stream())))
+        }
+        _ => Err(item),
+    }
+}
+
 struct DisplayPath<'a>(&'a Path);
 
 impl fmt::Display for DisplayPath<'_> {
@@ -44,8 +505,23 @@ impl fmt::Display for DisplayPath<'_> {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_attribute_to_meta_list;
-    use syn::{parse_quote, spanned::Spanned, Ident};
+    use super::{
+        expr_value, parse_attribute_args, parse_attribute_to_meta_list,
+        parse_attribute_to_meta_list_with_exprs, parse_attribute_to_meta_list_with_value,
+        parse_attribute_to_meta_list_with_value_and_exprs, parse_named_attribute,
+    };
+    use crate::FromMeta;
+    use syn::{parse_quote, spanned::Spanned, Expr, Ident, Meta, NestedMeta};
+
+    /// Get the expression held by the value of `item`, which must be `key = value`.
+    fn expr_of(item: &NestedMeta) -> Expr {
+        match *item {
+            NestedMeta::Meta(Meta::NameValue(ref nv)) => expr_value(&nv.lit)
+                .expect("value should be an expression")
+                .unwrap(),
+            _ => panic!("expected a name-value item"),
+        }
+    }
 
     #[test]
     fn parse_list() {
@@ -70,4 +546,185 @@ mod tests {
         let err = parse_attribute_to_meta_list(&parse_quote!(#[bar = 4])).unwrap_err();
         assert!(err.to_string().contains("#[bar(...)]"));
     }
+
+    #[test]
+    fn parse_name_value_with_key() {
+        let expected: NestedMeta = parse_quote!(note = "old");
+        let meta =
+            parse_attribute_to_meta_list_with_value(&parse_quote!(#[bar = "old"]), "note").unwrap();
+        assert!(meta.path.is_ident("bar"));
+        assert_eq!(meta.nested.into_iter().collect::<Vec<_>>(), vec![expected]);
+
+        parse_attribute_to_meta_list_with_value(&parse_quote!(#[bar = some::CONST]), "note")
+            .unwrap_err();
+        let meta = parse_attribute_to_meta_list_with_value_and_exprs(
+            &parse_quote!(#[bar = some::CONST]),
+            "note",
+        )
+        .unwrap();
+        let expected: Expr = parse_quote!(some::CONST);
+        assert_eq!(expr_of(&meta.nested[0]), expected);
+
+        let meta =
+            parse_attribute_to_meta_list_with_value(&parse_quote!(#[bar(note = "x")]), "note")
+                .unwrap();
+        assert_eq!(meta.nested.len(), 1);
+    }
+
+    #[test]
+    fn parse_dotted_keys() {
+        let meta = parse_attribute_to_meta_list(&parse_quote!(
+            #[bar(limits.max = 10, name = "x", limits.min = 1, deep.er.still = true)]
+        ))
+        .unwrap();
+
+        let expected: Meta = parse_quote!(bar(
+            limits(max = 10, min = 1),
+            name = "x",
+            deep(er(still = true))
+        ));
+        assert_eq!(Meta::List(meta), expected);
+    }
+
+    #[test]
+    fn parse_dotted_keys_merge_with_nested_list() {
+        let meta = parse_attribute_to_meta_list(&parse_quote!(
+            #[bar(limits.min = 1, name = "x", limits(max = 10), other(max = 2))]
+        ))
+        .unwrap();
+
+        let expected: Meta =
+            parse_quote!(bar(limits(min = 1, max = 10), name = "x", other(max = 2)));
+        assert_eq!(Meta::List(meta), expected);
+    }
+
+    #[test]
+    fn parse_dotted_keys_inside_list() {
+        let meta =
+            parse_attribute_to_meta_list(&parse_quote!(#[bar(outer(inner.flag, inner.n = 2.5))]))
+                .unwrap();
+
+        let expected: NestedMeta = parse_quote!(outer(inner(flag, n = 2.5)));
+        assert_eq!(meta.nested[0], expected);
+    }
+
+    #[test]
+    fn parse_named() {
+        let nested = parse_named_attribute(&parse_quote!(#[bar(baz = 4, qux)]), "bar").unwrap();
+        assert_eq!(nested.len(), 2);
+
+        let nested = parse_named_attribute(&parse_quote!(#[my::bar]), "my::bar").unwrap();
+        assert!(nested.is_empty());
+    }
+
+    #[test]
+    fn parse_named_wrong_name() {
+        let err = parse_named_attribute(&parse_quote!(#[baz(qux)]), "bar").unwrap_err();
+        assert_eq!(err.to_string(), "Expected attribute `bar`, found `baz`");
+        assert!(err.has_span());
+    }
+
+    #[test]
+    fn parse_named_name_value() {
+        let err = parse_named_attribute(&parse_quote!(#[bar = 4]), "bar").unwrap_err();
+        assert!(err.to_string().contains("#[bar(...)]"));
+    }
+
+    #[test]
+    fn parse_expression_values() {
+        let meta = parse_attribute_to_meta_list_with_exprs(&parse_quote!(
+            #[bar(limit = some::CONST, offset = -1, name = "x", inner(sum = 1 + 2))]
+        ))
+        .unwrap();
+
+        let limit: Expr = parse_quote!(some::CONST);
+        let offset: Expr = parse_quote!(-1);
+        let name: NestedMeta = parse_quote!(name = "x");
+        let sum: Expr = parse_quote!(1 + 2);
+        assert_eq!(expr_of(&meta.nested[0]), limit);
+        assert_eq!(expr_of(&meta.nested[1]), offset);
+        assert_eq!(meta.nested[2], name);
+        match meta.nested[3] {
+            NestedMeta::Meta(Meta::List(ref inner)) => assert_eq!(expr_of(&inner.nested[0]), sum),
+            _ => panic!("expected a list"),
+        }
+    }
+
+    #[test]
+    fn expression_values_keep_their_commas() {
+        let meta = parse_attribute_to_meta_list_with_exprs(&parse_quote!(
+            #[bar(f = |a, b| a + b, x = foo::<A, B>(), flag)]
+        ))
+        .unwrap();
+
+        assert_eq!(meta.nested.len(), 3);
+        let closure: Expr = parse_quote!(|a, b| a + b);
+        let turbofish: Expr = parse_quote!(foo::<A, B>());
+        let flag: NestedMeta = parse_quote!(flag);
+        assert_eq!(expr_of(&meta.nested[0]), closure);
+        assert_eq!(expr_of(&meta.nested[1]), turbofish);
+        assert_eq!(meta.nested[2], flag);
+    }
+
+    #[test]
+    fn expression_values_span_the_whole_expression() {
+        let di: syn::DeriveInput = syn::parse_str("#[bar(sum = 1 + 2)]\nstruct Foo;").unwrap();
+        let meta = parse_attribute_to_meta_list_with_exprs(&di.attrs[0]).unwrap();
+        let span = match meta.nested[0] {
+            NestedMeta::Meta(Meta::NameValue(ref nv)) => nv.lit.span(),
+            _ => panic!("expected a name-value item"),
+        };
+
+        assert_eq!((span.start().column, span.end().column), (12, 17));
+    }
+
+    #[test]
+    fn expression_values_need_opt_in() {
+        parse_attribute_to_meta_list(&parse_quote!(#[bar(limit = some::CONST)])).unwrap_err();
+    }
+
+    #[test]
+    fn expression_values_only_reach_types_which_accept_them() {
+        let meta =
+            parse_attribute_to_meta_list_with_exprs(&parse_quote!(#[bar(limit = some::CONST)]))
+                .unwrap();
+        let expected: syn::Path = parse_quote!(some::CONST);
+        assert_eq!(
+            syn::Path::from_nested_meta(&meta.nested[0]).unwrap(),
+            expected
+        );
+
+        let err = String::from_nested_meta(&meta.nested[0]).unwrap_err();
+        assert_eq!(err.to_string(), "Unexpected meta-item format `expression`");
+        syn::LitStr::from_nested_meta(&meta.nested[0]).unwrap_err();
+        syn::Lit::from_nested_meta(&meta.nested[0]).unwrap_err();
+    }
+
+    #[test]
+    fn parse_args() {
+        let args =
+            parse_attribute_args(quote!(path = "/", limits.max = 3, limits.min = 1,)).unwrap();
+        let expected: Vec<NestedMeta> = vec![
+            parse_quote!(path = "/"),
+            parse_quote!(limits(max = 3, min = 1)),
+        ];
+        assert_eq!(args, expected);
+
+        let args = parse_attribute_args(quote!(limit = some::CONST)).unwrap();
+        let expected: Expr = parse_quote!(some::CONST);
+        assert_eq!(expr_of(&args[0]), expected);
+    }
+
+    #[test]
+    fn parse_empty_args() {
+        assert!(parse_attribute_args(quote!()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_invalid_args_returns_error() {
+        let err = parse_attribute_args(quote!(path = )).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Unable to parse attribute arguments"));
+    }
 }
@@ -40,6 +40,24 @@ impl Deref for PathList {
     }
 }
 
+impl IntoIterator for PathList {
+    type Item = Path;
+    type IntoIter = ::std::vec::IntoIter<Path>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a PathList {
+    type Item = &'a Path;
+    type IntoIter = ::std::slice::Iter<'a, Path>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 impl From<Vec<Path>> for PathList {
     fn from(v: Vec<Path>) -> Self {
         PathList(v)
@@ -92,6 +110,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn iterates() {
+        let paths = fm::<PathList>(quote!(ignore(Debug, my::Trait)));
+        let mut count = 0;
+        for path in &paths {
+            assert!(!path.segments.is_empty());
+            count += 1;
+        }
+
+        assert_eq!(count, 2);
+        assert_eq!(paths.into_iter().last(), Some(parse_quote!(my::Trait)));
+    }
+
     /// Check that the parser rejects non-word members of the list, and that the error
     /// has an associated span.
     #[test]
@@ -0,0 +1,45 @@
+use quote::ToTokens;
+
+/// Render tokens the way they would usually be written, rather than with a space between
+/// every token.
+pub(crate) fn render_tokens<T: ToTokens>(tokens: &T) -> String {
+    let mut rendered = tokens.to_token_stream().to_string();
+    for &(from, to) in &[
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" < ", "<"),
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        ("& ", "&"),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" ;", ";"),
+    ] {
+        rendered = rendered.replace(from, to);
+    }
+
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::render_tokens as render;
+
+    #[test]
+    fn renders_types_compactly() {
+        let ty: syn::Type = parse_quote!(::std::collections::HashMap<String, Vec<&'static str>>);
+        assert_eq!(
+            render(&ty),
+            "::std::collections::HashMap<String, Vec<&'static str>>"
+        );
+
+        let ty: syn::Type = parse_quote!([u8; 4]);
+        assert_eq!(render(&ty), "[u8; 4]");
+    }
+}
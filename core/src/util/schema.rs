@@ -0,0 +1,293 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use syn::{Meta, NestedMeta};
+
+use crate::util::{path_to_string, Flag};
+use crate::{Error, FromMeta, Result};
+
+/// The kind of value a [`Schema`] field accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueKind {
+    /// A bare word, such as `skip`.
+    Flag,
+    /// A string literal.
+    Str,
+    /// A boolean literal, or a bare word meaning `true`.
+    Bool,
+    /// An integer literal, or a string containing one.
+    Int,
+    /// A float literal, or a string containing one.
+    Float,
+    /// A path, such as `path::to::function`, written as a string.
+    Path,
+    /// A nested list parsed with its own schema.
+    Nested(Schema),
+}
+
+/// A value read by a [`Schema`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Flag(Flag),
+    Str(String),
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Path(syn::Path),
+    Nested(ValueMap),
+}
+
+/// The values read by a [`Schema`], keyed by field name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValueMap(BTreeMap<String, Value>);
+
+impl ValueMap {
+    /// Get the value of a field, or `None` if it was absent from the input.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0.get(name)
+    }
+
+    /// Returns `true` if the field appeared in the input.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the fields which appeared in the input, in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.0.iter().map(|(name, value)| (name.as_str(), value))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SchemaField {
+    name: String,
+    kind: ValueKind,
+    required: bool,
+}
+
+/// A description of accepted options which is only known at run time, such as the options
+/// registered by plugins of a macro.
+///
+/// Parsing reports the same errors as a derived `FromMeta` impl: unknown fields with
+/// suggestions, duplicates, missing required fields and malformed values are all
+/// collected, and each one points at the offending item.
+///
+/// # Example
+/// ```rust
+/// # extern crate darling_core;
+/// # extern crate syn;
+/// use darling_core::util::{Schema, Value, ValueKind};
+/// use syn::parse_quote;
+///
+/// let schema = Schema::new()
+///     .required("name", ValueKind::Str)
+///     .optional("retries", ValueKind::Int);
+///
+/// let meta: syn::Meta = parse_quote!(plugin(name = "cache", retries = 3));
+/// let values = match meta {
+///     syn::Meta::List(list) => schema.parse(&list.nested.into_iter().collect::<Vec<_>>()),
+///     _ => unreachable!(),
+/// }
+/// .unwrap();
+///
+/// assert_eq!(values.get("retries"), Some(&Value::Int(3)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Schema {
+    fields: Vec<SchemaField>,
+    allow_unknown_fields: bool,
+}
+
+impl Schema {
+    /// Create a schema which accepts no fields.
+    pub fn new() -> Self {
+        Schema::default()
+    }
+
+    /// Accept a field which must appear in the input.
+    pub fn required(self, name: impl Into<String>, kind: ValueKind) -> Self {
+        self.field(name.into(), kind, true)
+    }
+
+    /// Accept a field which may be omitted from the input.
+    pub fn optional(self, name: impl Into<String>, kind: ValueKind) -> Self {
+        self.field(name.into(), kind, false)
+    }
+
+    /// Ignore fields which the schema doesn't declare instead of reporting them.
+    pub fn allow_unknown_fields(mut self, allow: bool) -> Self {
+        self.allow_unknown_fields = allow;
+        self
+    }
+
+    fn field(mut self, name: String, kind: ValueKind, required: bool) -> Self {
+        self.fields.push(SchemaField {
+            name,
+            kind,
+            required,
+        });
+        self
+    }
+
+    /// Parse the contents of a meta list, such as the items passed to `FromMeta::from_list`.
+    pub fn parse(&self, items: &[NestedMeta]) -> Result<ValueMap> {
+        let mut errors = Error::accumulator();
+        let mut values = BTreeMap::new();
+        // Names are recorded even when their value fails to parse, so repeats are still reported.
+        let mut seen = BTreeSet::new();
+
+        for item in items {
+            let meta = match *item {
+                NestedMeta::Meta(ref meta) => meta,
+                NestedMeta::Lit(ref lit) => {
+                    errors.push(Error::unsupported_format("literal").with_span(lit));
+                    continue;
+                }
+            };
+
+            let name = path_to_string(meta.path());
+            let field = match self.fields.iter().find(|field| field.name == name) {
+                Some(field) => field,
+                None => {
+                    if !self.allow_unknown_fields {
+                        let alts = self.fields.iter().map(|field| &field.name);
+                        errors.push(Error::unknown_field_with_alts(&name, alts).with_span(meta));
+                    }
+                    continue;
+                }
+            };
+
+            if !seen.insert(name.clone()) {
+                errors.push(Error::duplicate_field(&name).with_span(meta));
+                continue;
+            }
+
+            if let Some(value) =
+                errors.handle(parse_value(&field.kind, meta).map_err(|e| e.at(&name)))
+            {
+                values.insert(name, value);
+            }
+        }
+
+        for field in &self.fields {
+            if field.required && !values.contains_key(&field.name) {
+                errors.push(Error::missing_field(&field.name));
+            }
+        }
+
+        errors.finish_with(ValueMap(values))
+    }
+}
+
+fn parse_value(kind: &ValueKind, meta: &Meta) -> Result<Value> {
+    Ok(match *kind {
+        ValueKind::Flag => Value::Flag(FromMeta::from_meta(meta)?),
+        ValueKind::Str => Value::Str(FromMeta::from_meta(meta)?),
+        ValueKind::Bool => Value::Bool(FromMeta::from_meta(meta)?),
+        ValueKind::Int => Value::Int(FromMeta::from_meta(meta)?),
+        ValueKind::Float => Value::Float(FromMeta::from_meta(meta)?),
+        ValueKind::Path => Value::Path(FromMeta::from_meta(meta)?),
+        ValueKind::Nested(ref schema) => match *meta {
+            Meta::List(ref list) => {
+                Value::Nested(schema.parse(&list.nested.iter().cloned().collect::<Vec<_>>())?)
+            }
+            _ => return Err(Error::unsupported_format("non-list").with_span(meta)),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Schema, Value, ValueKind};
+    use syn::{parse_quote, NestedMeta};
+
+    fn items(meta: syn::Meta) -> Vec<NestedMeta> {
+        match meta {
+            syn::Meta::List(list) => list.nested.into_iter().collect(),
+            _ => panic!("expected a list"),
+        }
+    }
+
+    fn schema() -> Schema {
+        Schema::new()
+            .required("name", ValueKind::Str)
+            .optional("skip", ValueKind::Flag)
+            .optional("ratio", ValueKind::Float)
+            .optional(
+                "inner",
+                ValueKind::Nested(Schema::new().required("path", ValueKind::Path)),
+            )
+    }
+
+    #[test]
+    fn parses_values() {
+        let values = schema()
+            .parse(&items(parse_quote!(opts(
+                name = "x",
+                skip,
+                ratio = 0.5,
+                inner(path = "a::b")
+            ))))
+            .unwrap();
+
+        assert_eq!(values.len(), 4);
+        assert_eq!(values.get("name"), Some(&Value::Str("x".into())));
+        assert_eq!(values.get("ratio"), Some(&Value::Float(0.5)));
+        match values.get("inner") {
+            Some(Value::Nested(inner)) => {
+                assert_eq!(inner.get("path"), Some(&Value::Path(parse_quote!(a::b))))
+            }
+            other => panic!("unexpected value {:?}", other),
+        }
+    }
+
+    #[test]
+    fn omitted_optional_field_is_absent() {
+        let values = schema()
+            .parse(&items(parse_quote!(opts(name = "x"))))
+            .unwrap();
+        assert!(!values.contains("skip"));
+    }
+
+    #[test]
+    fn collects_errors() {
+        let err = schema()
+            .parse(&items(parse_quote!(opts(
+                nmae = "x",
+                ratio = "fast",
+                ratio = 1.0,
+                inner(path = "a"),
+                "lit"
+            ))))
+            .unwrap_err();
+
+        // Unknown field, bad float, duplicate, literal, and missing `name`.
+        assert_eq!(err.len(), 5);
+        #[cfg(feature = "suggestions")]
+        assert!(err.to_string().contains("Did you mean `name`?"));
+    }
+
+    #[test]
+    fn nested_errors_have_location() {
+        let err = schema()
+            .parse(&items(parse_quote!(opts(name = "x", inner()))))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Missing field `path` at inner");
+    }
+
+    #[test]
+    fn allows_unknown_fields() {
+        let values = Schema::new()
+            .allow_unknown_fields(true)
+            .parse(&items(parse_quote!(opts(anything = 1))))
+            .unwrap();
+        assert!(values.is_empty());
+    }
+}
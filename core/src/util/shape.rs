@@ -0,0 +1,381 @@
+use std::iter::FromIterator;
+use std::ops::BitOr;
+
+use syn::{Data, Fields, Meta, NestedMeta};
+
+use crate::options::join_alternatives;
+use crate::{Error, FromMeta, Result};
+
+/// The shape of a struct, enum variant or union body, using the same names as `#[darling(supports(...))]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shape {
+    StructNewtype,
+    StructNamed,
+    StructTuple,
+    StructUnit,
+    EnumNewtype,
+    EnumNamed,
+    EnumTuple,
+    EnumUnit,
+    Union,
+}
+
+/// The shapes included in `any`. Unions must be requested explicitly.
+const ALL: [Shape; 8] = [
+    Shape::StructNewtype,
+    Shape::StructNamed,
+    Shape::StructTuple,
+    Shape::StructUnit,
+    Shape::EnumNewtype,
+    Shape::EnumNamed,
+    Shape::EnumTuple,
+    Shape::EnumUnit,
+];
+
+impl Shape {
+    /// The shape of a struct with `fields`.
+    pub fn of_struct(fields: &Fields) -> Self {
+        match *fields {
+            Fields::Unit => Shape::StructUnit,
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => Shape::StructNewtype,
+            Fields::Unnamed(_) => Shape::StructTuple,
+            Fields::Named(_) => Shape::StructNamed,
+        }
+    }
+
+    /// The shape of an enum variant with `fields`.
+    pub fn of_variant(fields: &Fields) -> Self {
+        match *fields {
+            Fields::Unit => Shape::EnumUnit,
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => Shape::EnumNewtype,
+            Fields::Unnamed(_) => Shape::EnumTuple,
+            Fields::Named(_) => Shape::EnumNamed,
+        }
+    }
+
+    /// The word for this shape in `supports(...)`, such as `struct_named`.
+    pub fn as_word(&self) -> &'static str {
+        match *self {
+            Shape::StructNewtype => "struct_newtype",
+            Shape::StructNamed => "struct_named",
+            Shape::StructTuple => "struct_tuple",
+            Shape::StructUnit => "struct_unit",
+            Shape::EnumNewtype => "enum_newtype",
+            Shape::EnumNamed => "enum_named",
+            Shape::EnumTuple => "enum_tuple",
+            Shape::EnumUnit => "enum_unit",
+            Shape::Union => "union",
+        }
+    }
+
+    fn is_struct(&self) -> bool {
+        match *self {
+            Shape::StructNewtype | Shape::StructNamed | Shape::StructTuple | Shape::StructUnit => {
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn is_variant(&self) -> bool {
+        match *self {
+            Shape::EnumNewtype | Shape::EnumNamed | Shape::EnumTuple | Shape::EnumUnit => true,
+            _ => false,
+        }
+    }
+
+    fn bit(self) -> u16 {
+        1 << (self as u8)
+    }
+}
+
+/// A set of [`Shape`]s, which can be used to check an input the same way `supports(...)` does.
+///
+/// # Usage
+/// `ShapeSet` implements `FromMeta`, accepting the same words as `supports`:
+///
+/// ```rust,ignore
+/// #[my_attr(shapes(struct_named, enum_any))]
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ShapeSet(u16);
+
+impl ShapeSet {
+    /// Creates a set containing every struct and enum shape. Like `supports(any)`, this
+    /// doesn't include unions.
+    pub fn any() -> Self {
+        ALL.iter().cloned().collect()
+    }
+
+    /// Adds a shape to the set.
+    pub fn insert(&mut self, shape: Shape) {
+        self.0 |= shape.bit();
+    }
+
+    /// Returns a set containing every shape in either set.
+    pub fn union(&self, other: &ShapeSet) -> Self {
+        ShapeSet(self.0 | other.0)
+    }
+
+    /// Returns `true` if the set contains `shape`.
+    pub fn contains(&self, shape: Shape) -> bool {
+        self.0 & shape.bit() != 0
+    }
+
+    /// Returns `true` if the set contains no shapes.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterate over the shapes in the set, structs first and unions last.
+    pub fn iter(&self) -> impl Iterator<Item = Shape> + '_ {
+        ALL.iter()
+            .cloned()
+            .chain(Some(Shape::Union))
+            .filter(move |shape| self.contains(*shape))
+    }
+
+    /// Check that a derive input body only uses shapes in this set. For an enum, every
+    /// variant is checked. The error matches the one produced by `supports(...)`.
+    pub fn check(&self, data: &Data) -> Result<()> {
+        let observed = match *data {
+            Data::Struct(ref data) => {
+                if !self.iter().any(|shape| shape.is_struct()) {
+                    return Err(self.unsupported("struct"));
+                }
+
+                Shape::of_struct(&data.fields)
+            }
+            Data::Enum(ref data) => {
+                if !self.iter().any(|shape| shape.is_variant()) {
+                    return Err(self.unsupported("enum"));
+                }
+
+                match data
+                    .variants
+                    .iter()
+                    .map(|variant| Shape::of_variant(&variant.fields))
+                    .find(|shape| !self.contains(*shape))
+                {
+                    Some(shape) => shape,
+                    None => return Ok(()),
+                }
+            }
+            Data::Union(_) => Shape::Union,
+        };
+
+        if self.contains(observed) {
+            Ok(())
+        } else {
+            Err(self.unsupported(observed.as_word()))
+        }
+    }
+
+    /// The words describing this set, collapsing a complete group into `struct_any` or `enum_any`.
+    fn words(&self) -> Vec<String> {
+        let mut words = Vec::new();
+        for &(prefix, is_struct) in &[("struct", true), ("enum", false)] {
+            let group: Vec<Shape> = ALL
+                .iter()
+                .cloned()
+                .filter(|shape| shape.is_struct() == is_struct)
+                .collect();
+
+            if group.iter().all(|shape| self.contains(*shape)) {
+                words.push(format!("{}_any", prefix));
+            } else {
+                words.extend(
+                    group
+                        .into_iter()
+                        .filter(|shape| self.contains(*shape))
+                        .map(|shape| shape.as_word().to_string()),
+                );
+            }
+        }
+
+        if self.contains(Shape::Union) {
+            words.push(Shape::Union.as_word().to_string());
+        }
+
+        words
+    }
+
+    fn unsupported(&self, observed: &str) -> Error {
+        match join_alternatives(&self.words()) {
+            Some(expected) => Error::unsupported_shape_with_expected(observed, &expected),
+            None => Error::unsupported_shape(observed),
+        }
+    }
+
+    fn insert_word(&mut self, word: &str) -> Result<()> {
+        let group = |is_struct: bool| {
+            ALL.iter()
+                .cloned()
+                .filter(move |s| s.is_struct() == is_struct)
+        };
+        match word {
+            "any" => *self = self.union(&ShapeSet::any()),
+            "struct_any" => self.extend(group(true)),
+            "enum_any" => self.extend(group(false)),
+            _ => match ALL
+                .iter()
+                .chain(Some(&Shape::Union))
+                .find(|shape| shape.as_word() == word)
+            {
+                Some(shape) => self.insert(*shape),
+                None => return Err(Error::unknown_value(word)),
+            },
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Shape> for ShapeSet {
+    fn from(shape: Shape) -> Self {
+        ShapeSet(shape.bit())
+    }
+}
+
+impl BitOr for ShapeSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(&rhs)
+    }
+}
+
+impl BitOr<Shape> for ShapeSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Shape) -> Self {
+        self.union(&rhs.into())
+    }
+}
+
+impl Extend<Shape> for ShapeSet {
+    fn extend<I: IntoIterator<Item = Shape>>(&mut self, iter: I) {
+        for shape in iter {
+            self.insert(shape);
+        }
+    }
+}
+
+impl FromIterator<Shape> for ShapeSet {
+    fn from_iter<I: IntoIterator<Item = Shape>>(iter: I) -> Self {
+        let mut set = ShapeSet::default();
+        set.extend(iter);
+        set
+    }
+}
+
+impl FromMeta for ShapeSet {
+    fn from_list(items: &[NestedMeta]) -> Result<Self> {
+        let mut errors = Error::accumulator();
+        let mut set = ShapeSet::default();
+
+        for item in items {
+            if let NestedMeta::Meta(Meta::Path(ref path)) = *item {
+                let word = super::path_to_string(path);
+                errors.handle(set.insert_word(&word).map_err(|e| e.with_span(path)));
+            } else {
+                errors.push(Error::unsupported_format("non-word").with_span(item));
+            }
+        }
+
+        errors.finish_with(set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Shape, ShapeSet};
+    use crate::FromMeta;
+    use syn::{parse_quote, DeriveInput};
+
+    fn set(meta: syn::Meta) -> ShapeSet {
+        ShapeSet::from_meta(&meta).unwrap()
+    }
+
+    #[test]
+    fn parse_words() {
+        let shapes = set(parse_quote!(shapes(struct_named, enum_any)));
+        assert!(shapes.contains(Shape::StructNamed));
+        assert!(!shapes.contains(Shape::StructTuple));
+        assert_eq!(shapes.iter().filter(|s| !s.is_struct()).count(), 4);
+    }
+
+    #[test]
+    fn union_and_contains() {
+        let shapes = ShapeSet::from(Shape::StructUnit) | Shape::EnumUnit;
+        assert!(shapes.contains(Shape::EnumUnit));
+        assert_eq!(shapes.union(&ShapeSet::any()), ShapeSet::any());
+        assert!(ShapeSet::default().is_empty());
+    }
+
+    #[test]
+    fn check_matches_supports() {
+        let shapes = set(parse_quote!(shapes(struct_named, enum_unit)));
+        let tuple: DeriveInput = parse_quote!(
+            struct Foo(u8, u8);
+        );
+        assert_eq!(
+            shapes.check(&tuple.data).unwrap_err().to_string(),
+            "Unsupported shape `struct_tuple`. Expected struct_named or enum_unit."
+        );
+
+        let enm: DeriveInput = parse_quote!(
+            enum Foo {
+                A,
+                B(u8),
+            }
+        );
+        assert_eq!(
+            shapes.check(&enm.data).unwrap_err().to_string(),
+            "Unsupported shape `enum_newtype`. Expected struct_named or enum_unit."
+        );
+
+        let named: DeriveInput = parse_quote!(
+            struct Foo {
+                bar: u8,
+            }
+        );
+        shapes.check(&named.data).unwrap();
+    }
+
+    #[test]
+    fn check_missing_kind() {
+        let shapes = set(parse_quote!(shapes(struct_any)));
+        let enm: DeriveInput = parse_quote!(
+            enum Foo {
+                A,
+            }
+        );
+        assert_eq!(
+            shapes.check(&enm.data).unwrap_err().to_string(),
+            "Unsupported shape `enum`. Expected struct_any."
+        );
+    }
+
+    #[test]
+    fn check_union() {
+        let union: DeriveInput = parse_quote!(
+            union Foo {
+                a: u8,
+            }
+        );
+        assert_eq!(
+            ShapeSet::any().check(&union.data).unwrap_err().to_string(),
+            "Unsupported shape `union`. Expected struct_any or enum_any."
+        );
+
+        let shapes = set(parse_quote!(shapes(struct_named, union)));
+        shapes.check(&union.data).unwrap();
+        assert_eq!(shapes.iter().last(), Some(Shape::Union));
+    }
+
+    #[test]
+    fn unknown_word() {
+        ShapeSet::from_meta(&parse_quote!(shapes(struct_fancy))).unwrap_err();
+    }
+}
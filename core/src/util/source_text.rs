@@ -0,0 +1,161 @@
+use std::fmt;
+use std::ops::Deref;
+
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::{Lit, LitStr};
+
+use crate::{Error, FromMeta, Result};
+
+/// The text of a string literal exactly as it was written, for fields which need it verbatim.
+///
+/// Every other string-consuming impl reads the literal's value, so `"a\"b"`, `r#"a"b"#` and
+/// `"a\u{22}b"` are all the same string to them. A `SourceText` keeps the characters between
+/// the quotes instead, without processing any escapes.
+#[derive(Clone)]
+pub struct SourceText {
+    lit: LitStr,
+    text: String,
+}
+
+impl SourceText {
+    /// Create a new `SourceText` from a string literal.
+    pub fn new(lit: LitStr) -> Self {
+        let text = contents(&lit.token().to_string(), lit.suffix()).to_string();
+        SourceText { lit, text }
+    }
+
+    /// Get the text between the quotes of the literal, with escapes left as written.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the value of the literal, with escapes processed as every other impl does.
+    pub fn value(&self) -> String {
+        self.lit.value()
+    }
+
+    /// Whether the literal was written as a raw string, such as `r#"..."#`.
+    pub fn is_raw(&self) -> bool {
+        self.lit.token().to_string().starts_with('r')
+    }
+
+    /// Get the literal this was read from.
+    pub fn as_lit(&self) -> &LitStr {
+        &self.lit
+    }
+
+    /// Get the location of the literal in source.
+    pub fn span(&self) -> Span {
+        self.lit.span()
+    }
+}
+
+/// Strip the quotes, and for raw strings the `r` and `#`s, from the source of a literal.
+fn contents<'a>(repr: &'a str, suffix: &str) -> &'a str {
+    let repr = &repr[..repr.len() - suffix.len()];
+    let repr = if repr.starts_with('r') {
+        &repr[1..]
+    } else {
+        repr
+    };
+    let hashes = repr.len() - repr.trim_start_matches('#').len();
+    &repr[hashes + 1..repr.len() - hashes - 1]
+}
+
+impl AsRef<str> for SourceText {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Deref for SourceText {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<LitStr> for SourceText {
+    fn from(lit: LitStr) -> Self {
+        SourceText::new(lit)
+    }
+}
+
+impl Eq for SourceText {}
+
+impl PartialEq for SourceText {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.text == rhs.text
+    }
+}
+
+impl PartialEq<&str> for SourceText {
+    fn eq(&self, rhs: &&str) -> bool {
+        self.as_str() == *rhs
+    }
+}
+
+impl ToTokens for SourceText {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.lit.to_tokens(tokens);
+    }
+}
+
+impl fmt::Debug for SourceText {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.text)
+    }
+}
+
+impl fmt::Display for SourceText {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.text.fmt(f)
+    }
+}
+
+impl FromMeta for SourceText {
+    fn from_value(value: &Lit) -> Result<Self> {
+        if let Lit::Str(ref lit) = *value {
+            Ok(SourceText::new(lit.clone()))
+        } else {
+            Err(Error::unexpected_lit_type(value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SourceText;
+    use crate::FromMeta;
+    use syn::parse_quote;
+
+    fn text(meta: syn::Meta) -> SourceText {
+        SourceText::from_meta(&meta).unwrap()
+    }
+
+    #[test]
+    fn keeps_escapes() {
+        let cooked = text(parse_quote!(ignore = "a\tb\u{41}"));
+        assert_eq!(cooked, r"a\tb\u{41}");
+        assert_eq!(cooked.value(), "a\tbA");
+        assert!(!cooked.is_raw());
+    }
+
+    #[test]
+    fn strips_raw_delimiters() {
+        let raw = text(parse_quote!(ignore = r#"say "hi"\n"#));
+        assert_eq!(raw, r#"say "hi"\n"#);
+        assert_eq!(raw.value(), raw.as_str());
+        assert!(raw.is_raw());
+
+        assert_eq!(text(parse_quote!(ignore = r"x")), "x");
+    }
+
+    #[test]
+    fn rejects_other_literals() {
+        let err = SourceText::from_meta(&parse_quote!(ignore = 1)).unwrap_err();
+        assert_eq!(err.to_string(), "Unexpected literal type `int`");
+    }
+}
@@ -3,8 +3,8 @@ use std::ops::{Deref, DerefMut};
 use syn::spanned::Spanned;
 
 use crate::{
-    FromDeriveInput, FromField, FromGenericParam, FromGenerics, FromMeta, FromTypeParam,
-    FromVariant, Result,
+    FromConstParam, FromDeriveInput, FromField, FromGenericParam, FromGenerics, FromMeta,
+    FromTypeParam, FromVariant, Result,
 };
 
 /// A value and an associated position in source code. The main use case for this is
@@ -86,14 +86,28 @@ macro_rules! spanned {
     };
 }
 
+spanned!(FromConstParam, from_const_param, syn::ConstParam);
 spanned!(FromGenericParam, from_generic_param, syn::GenericParam);
 spanned!(FromGenerics, from_generics, syn::Generics);
 spanned!(FromTypeParam, from_type_param, syn::TypeParam);
-spanned!(FromMeta, from_meta, syn::Meta);
 spanned!(FromDeriveInput, from_derive_input, syn::DeriveInput);
 spanned!(FromField, from_field, syn::Field);
 spanned!(FromVariant, from_variant, syn::Variant);
 
+impl<T: FromMeta> FromMeta for SpannedValue<T> {
+    fn from_meta(value: &syn::Meta) -> Result<Self> {
+        Ok(SpannedValue::new(
+            FromMeta::from_meta(value).map_err(|e| e.with_span(value))?,
+            value.span(),
+        ))
+    }
+
+    /// An absent value has no source location, so its span is `Span::call_site()`.
+    fn from_none() -> Option<Self> {
+        T::from_none().map(|value| SpannedValue::new(value, Span::call_site()))
+    }
+}
+
 impl<T: Spanned> From<T> for SpannedValue<T> {
     fn from(value: T) -> Self {
         let span = value.span();
@@ -112,4 +126,10 @@ mod tests {
         let test = SpannedValue::new("hello", Span::call_site());
         assert_eq!("hello", test.trim());
     }
+
+    #[test]
+    fn from_meta_keeps_value() {
+        let value = SpannedValue::<String>::from_meta(&syn::parse_quote!(name = "hello")).unwrap();
+        assert_eq!(*value, "hello");
+    }
 }
@@ -0,0 +1,119 @@
+use syn::{Lit, Meta, NestedMeta};
+
+use crate::{Error, FromMeta, Result};
+
+/// A float type which has infinite and not-a-number values.
+pub trait SpecialFloat: FromMeta {
+    const INFINITY: Self;
+    const NEG_INFINITY: Self;
+    const NAN: Self;
+}
+
+impl SpecialFloat for f32 {
+    const INFINITY: Self = ::std::f32::INFINITY;
+    const NEG_INFINITY: Self = ::std::f32::NEG_INFINITY;
+    const NAN: Self = ::std::f32::NAN;
+}
+
+impl SpecialFloat for f64 {
+    const INFINITY: Self = ::std::f64::INFINITY;
+    const NEG_INFINITY: Self = ::std::f64::NEG_INFINITY;
+    const NAN: Self = ::std::f64::NAN;
+}
+
+/// An optional float reads a special value as `Some`.
+impl<T: SpecialFloat> SpecialFloat for Option<T> {
+    const INFINITY: Self = Some(T::INFINITY);
+    const NEG_INFINITY: Self = Some(T::NEG_INFINITY);
+    const NAN: Self = Some(T::NAN);
+}
+
+/// Read a float which may also be infinite or not a number. Float fields only accept finite
+/// values, so use this with `#[darling(with = "darling::util::special_float")]` on a field
+/// which needs the others.
+///
+/// Besides everything the float's `FromMeta` impl accepts, this reads `inf`, `-inf` and `nan`
+/// as strings, such as `max = "-inf"`, or as a word in a list, such as `max(inf)`. The names
+/// ignore case, and `infinity` can be spelled out.
+pub fn special_float<T: SpecialFloat>(meta: &Meta) -> Result<T> {
+    let special = match *meta {
+        Meta::NameValue(ref nv) => match nv.lit {
+            Lit::Str(ref s) => special_value(&s.value()),
+            _ => None,
+        },
+        Meta::List(ref list) if list.nested.len() == 1 => match list.nested[0] {
+            NestedMeta::Meta(Meta::Path(ref path)) => path
+                .get_ident()
+                .and_then(|ident| special_value(&ident.to_string())),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    match special {
+        Some(value) => Ok(value),
+        None => T::from_meta(meta),
+    }
+}
+
+fn special_value<T: SpecialFloat>(name: &str) -> Option<T> {
+    let name = name.to_lowercase();
+    let (negative, name) = if name.starts_with('-') {
+        (true, &name[1..])
+    } else if name.starts_with('+') {
+        (false, &name[1..])
+    } else {
+        (false, &name[..])
+    };
+
+    match (negative, name) {
+        (false, "inf") | (false, "infinity") => Some(T::INFINITY),
+        (true, "inf") | (true, "infinity") => Some(T::NEG_INFINITY),
+        (_, "nan") => Some(T::NAN),
+        _ => None,
+    }
+}
+
+/// The error for a non-finite value given to a float type which doesn't accept one.
+pub(crate) fn non_finite(value: &str) -> Error {
+    Error::custom(format!(
+        "`{}` is not a finite number; use `with = \"darling::util::special_float\"` to allow it",
+        value
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::special_float;
+    use syn::parse_quote;
+
+    #[test]
+    fn reads_special_values() {
+        assert_eq!(
+            special_float::<f64>(&parse_quote!(max = "inf")).unwrap(),
+            ::std::f64::INFINITY
+        );
+        assert_eq!(
+            special_float::<f32>(&parse_quote!(max = "-Infinity")).unwrap(),
+            ::std::f32::NEG_INFINITY
+        );
+        assert!(special_float::<f64>(&parse_quote!(max = "NaN"))
+            .unwrap()
+            .is_nan());
+        assert_eq!(
+            special_float::<f64>(&parse_quote!(max(inf))).unwrap(),
+            ::std::f64::INFINITY
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)] // we want exact equality
+    fn reads_finite_values() {
+        assert_eq!(special_float::<f64>(&parse_quote!(max = 1.5)).unwrap(), 1.5);
+        assert_eq!(
+            special_float::<f64>(&parse_quote!(max = "-2.5")).unwrap(),
+            -2.5
+        );
+        assert!(special_float::<f64>(&parse_quote!(max = "infinite")).is_err());
+    }
+}
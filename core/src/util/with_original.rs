@@ -1,6 +1,6 @@
 use crate::{
-    FromDeriveInput, FromField, FromGenericParam, FromGenerics, FromMeta, FromTypeParam,
-    FromVariant, Result,
+    FromAttributes, FromConstParam, FromDeriveInput, FromField, FromGenericParam, FromGenerics,
+    FromMeta, FromTypeParam, FromVariant, Result,
 };
 
 /// A container to parse some syntax and retain access to the original.
@@ -26,6 +26,7 @@ macro_rules! with_original {
     };
 }
 
+with_original!(FromConstParam, from_const_param, syn::ConstParam);
 with_original!(FromDeriveInput, from_derive_input, syn::DeriveInput);
 with_original!(FromField, from_field, syn::Field);
 with_original!(FromGenerics, from_generics, syn::Generics);
@@ -33,3 +34,12 @@ with_original!(FromGenericParam, from_generic_param, syn::GenericParam);
 with_original!(FromMeta, from_meta, syn::Meta);
 with_original!(FromTypeParam, from_type_param, syn::TypeParam);
 with_original!(FromVariant, from_variant, syn::Variant);
+
+impl<T: FromAttributes> FromAttributes for WithOriginal<T, Vec<syn::Attribute>> {
+    fn from_attributes(attrs: &[syn::Attribute]) -> Result<Self> {
+        Ok(WithOriginal::new(
+            FromAttributes::from_attributes(attrs)?,
+            attrs.to_vec(),
+        ))
+    }
+}
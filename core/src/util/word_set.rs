@@ -0,0 +1,102 @@
+use std::ops::Deref;
+
+use syn::{Meta, NestedMeta};
+
+use crate::{Error, FromMeta, Result};
+
+use super::path_to_string;
+
+/// A set of words read from a list, such as `#[opts(flags(read, write, create))]`.
+///
+/// Each word is parsed with `T::from_string`, so an enum of unit variants deriving `FromMeta`
+/// works as the set of valid flags. Unknown words produce errors which list the valid ones,
+/// and a word which appears twice produces a duplicate field error. Words are kept in the
+/// order they appear.
+///
+/// # Usage
+/// ```rust,ignore
+/// #[derive(FromMeta, PartialEq)]
+/// enum Permission {
+///     Read,
+///     Write,
+///     Create,
+/// }
+///
+/// #[derive(FromDeriveInput)]
+/// #[darling(attributes(opts))]
+/// struct Opts {
+///     flags: WordSet<Permission>,
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordSet<T>(Vec<T>);
+
+impl<T: PartialEq> WordSet<T> {
+    /// Returns `true` if `word` was in the input.
+    pub fn contains(&self, word: &T) -> bool {
+        self.0.contains(word)
+    }
+}
+
+impl<T> Default for WordSet<T> {
+    fn default() -> Self {
+        WordSet(Vec::new())
+    }
+}
+
+impl<T> Deref for WordSet<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> IntoIterator for WordSet<T> {
+    type Item = T;
+    type IntoIter = ::std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a WordSet<T> {
+    type Item = &'a T;
+    type IntoIter = ::std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T: FromMeta + PartialEq> FromMeta for WordSet<T> {
+    fn from_list(items: &[NestedMeta]) -> Result<Self> {
+        let mut errors = Error::accumulator();
+        let mut words = Vec::with_capacity(items.len());
+
+        for item in items {
+            if let NestedMeta::Meta(Meta::Path(ref path)) = *item {
+                let word = path_to_string(path);
+                if let Some(value) =
+                    errors.handle(T::from_string(&word).map_err(|e| e.with_span(path)))
+                {
+                    if words.contains(&value) {
+                        errors.push(Error::duplicate_field(&word).with_span(path));
+                    } else {
+                        words.push(value);
+                    }
+                }
+            } else {
+                errors.push(Error::unsupported_format("non-word").with_span(item));
+            }
+        }
+
+        errors.finish_with(WordSet(words))
+    }
+
+    /// An absent set is empty.
+    fn from_none() -> Option<Self> {
+        Some(WordSet::default())
+    }
+}
@@ -41,3 +41,13 @@ pub fn derive_type_param(input: TokenStream) -> TokenStream {
 pub fn derive_variant(input: TokenStream) -> TokenStream {
     derive::from_variant(&parse_macro_input!(input)).into()
 }
+
+#[proc_macro_derive(MetaSchema, attributes(darling))]
+pub fn derive_meta_schema(input: TokenStream) -> TokenStream {
+    derive::meta_schema(&parse_macro_input!(input)).into()
+}
+
+#[proc_macro_derive(ToMeta, attributes(darling))]
+pub fn derive_to_meta(input: TokenStream) -> TokenStream {
+    derive::to_meta(&parse_macro_input!(input)).into()
+}
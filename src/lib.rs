@@ -11,17 +11,64 @@
 //! Proc-macro crates should provide their own structs which implement or derive `FromDeriveInput`,
 //! `FromField`, `FromVariant`, `FromGenerics`, _et alia_ to gather settings relevant to their operation.
 //!
+//! Darling needs `std`, and there is no `no_std` mode. It reads `syn` and `proc-macro2` types, which
+//! need `std` themselves, and its errors hold their spans. This doesn't limit where the macros can be
+//! used: proc-macros and build scripts run on the host, so a crate using them can still be `no_std`.
+//!
 //! ## Attributes
 //! There are a number of attributes that `darling` exposes to enable finer-grained control over the code
 //! it generates.
 //!
 //! * **Field renaming**: You can use `#[darling(rename="new_name")]` on a field to change the name Darling looks for.
 //!   You can also use `#[darling(rename_all="...")]` at the struct or enum level to apply a casing rule to all fields or variants.
+//!   The rules are available as `darling::util::RenameRule`. For a casing scheme it doesn't cover, use
+//!   `#[darling(rename_all_with="path::to::function")]`, where the function is a `fn(&str) -> String` called with
+//!   each field or variant name when parsing. `MetaSchema` lists the renamed names; `ATTRIBUTE_DOCS` is a constant, so it
+//!   lists the names before renaming and names the function.
 //! * **Map function**: You can use `#[darling(map="path::to::function")]` to run code on a field before its stored in the struct.
 //! * **Default values**: You can use `#[darling(default)]` at the type or field level to use that type's default value to fill
 //!   in values not specified by the caller.
 //! * **Skipped fields**: You can skip a variant or field using `#[darling(skip)]`. Fields marked with this will fall back to
 //!   `Default::default()` for their value, but you can override that with an explicit default or a value from the type-level default.
+//! * **Word and absence hooks**: A type deriving `FromMeta` can use `#[darling(from_word = "path::to::function")]` to decide what a bare
+//!   word such as `#[attr(cache)]` means, and `#[darling(from_none = "path::to::function")]` to supply a value when it's omitted entirely.
+//! * **`cfg_attr` unwrapping**: Use `#[darling(unwrap_cfg_attr)]` alongside `attributes(...)` to also read attributes wrapped in
+//!   `#[cfg_attr(condition, ...)]`, regardless of the condition. Forwarded attributes keep their condition, and `darling::util::CfgAttr` exposes it for manual inspection.
+//! * **Expression values**: Use `#[darling(expr_values)]` alongside `attributes(...)` to accept values such as
+//!   `limit = some::CONST` or `offset = -1`. Only types which implement `FromMeta::from_expr`, such as `syn::Path`,
+//!   `syn::Expr` and the numeric types, accept an expression; a `String` field still needs a string literal.
+//! * **Error spans**: Use `#[darling(error_span = "key")]` or `#[darling(error_span = "value")]` on a field to choose whether its
+//!   parsing errors point at the key or the value of the item. With `"value"`, errors inside a nested list keep pointing at
+//!   the items they're about. Setting it on the container applies it to every field.
+//! * **Deprecation**: Use `#[darling(deprecated = "use `new_name` instead")]` on a field or variant to keep accepting it while
+//!   producing a `darling::error::Warning` each time it's used. Add `#[darling(forward_warnings)]` and a field named
+//!   `warnings` to a top-level struct to receive them, or call `FromMeta::from_list_with_warnings`, then add
+//!   `Warning::write_warning` to the macro's output so the compiler reports them.
+//! * **Renamed fields**: Use `#[darling(renamed(old = "with_fn", new = "with", since = "0.2"))]` on a struct to reject a field's
+//!   old name with an error pointing to its new name. `since` is optional, and `renamed` can appear more than once.
+//! * **Shared field parsing**: `#[darling(dyn_fields)]` on a struct or enum reads each named field through the
+//!   `darling::util::DynFieldSlot` trait object of its slot, so the parsing code is compiled once per field type rather
+//!   than once per field. This can speed up builds of crates with many option structs, at the cost of a virtual call per
+//!   item. Fields using `with`, `map`, `and_then`, `multiple` or `error_span`, and containers using `report` or
+//!   `error_span`, need code of their own and are rejected.
+//! * **Generated code spans**: Code generated to parse each field carries the span of the field's type, so a type that
+//!   doesn't implement `FromMeta` is reported at the field. Use `#[darling(span = "call_site")]` on a struct or enum to
+//!   attribute those errors to the derive instead; `#[darling(span = "input")]` is the default.
+//! * **Schemas**: `#[derive(MetaSchema)]` reads the same attributes as `#[derive(FromMeta)]` and implements
+//!   `darling::MetaSchema`, which describes each accepted field or variant at run time for help text and tooling.
+//! * **Writing options back out**: `#[derive(ToMeta)]` implements `darling::ToMeta`, which writes a parsed value in the
+//!   attribute syntax its derived `FromMeta` impl reads, for forwarding options to other macros and for round-trip tests.
+//! * **Parse reports**: Add `#[darling(report)]` to a container and run parsing inside `darling::report::record` to
+//!   find out which attributes were read, which meta item supplied each field, which fields fell back to defaults, and
+//!   which unknown keys were ignored.
+//! * **Emitting errors**: `darling::emit::expand` runs the body of a proc-macro and turns its errors into
+//!   `compile_error!` invocations, a panic, or nightly diagnostics, chosen by the `diagnostics` feature and the
+//!   `DARLING_EMIT` environment variable.
+//! * **Config files**: `#[darling(config_file)]` on a struct accepts a `config = "path"` key naming a file, relative to
+//!   the crate being compiled, which holds more options. The file is not TOML: it uses the syntax of the inside of the
+//!   attribute, such as `level = 3, retry(max = 5)`. Keys written in the attribute override the file, and lists set in
+//!   both are merged. A field named `config_files` of type `Vec<darling::util::ConfigFile>` receives each file that was
+//!   read; emit them in the macro's output so the compiler rebuilds the crate when a file changes.
 //!
 //! ## Forwarded Fields
 //! All derivable traits except `FromMeta` support forwarding some fields from the input AST to the derived struct.
@@ -39,6 +86,7 @@
 //! |`generics`|`T: darling::FromGenerics`|The generics of the passed-in type. This can be `syn::Generics`, `darling::ast::Generics`, or any compatible type.|
 //! |`data`|`darling::ast::Data`|The body of the passed-in type|
 //! |`attrs`|`Vec<syn::Attribute>`|The forwarded attributes from the passed in type. These are controlled using the `forward_attrs` attribute.|
+//! |`input`|`syn::DeriveInput`|A copy of the whole passed-in type. This is only filled in when the container sets `forward_input`.|
 //!
 //! ### `FromField`
 //! |Field name|Type|Meaning|
@@ -46,6 +94,7 @@
 //! |`ident`|`Option<syn::Ident>`|The identifier of the passed-in field, or `None` for tuple fields|
 //! |`vis`|`syn::Visibility`|The visibility of the passed-in field|
 //! |`ty`|`syn::Type`|The type of the passed-in field|
+//! |`index`|`Option<usize>`|The position of the passed-in field in its parent, such as the `1` of `self.1`. It's `None` when `from_field` is called directly instead of through `ast::Fields`.|
 //! |`attrs`|`Vec<syn::Attribute>`|The forwarded attributes from the passed in field. These are controlled using the `forward_attrs` attribute.|
 //!
 //! ### `FromTypeParam`
@@ -63,6 +112,15 @@
 //! |`discriminant`|`Option<syn::Expr>`|For a variant such as `Example = 2`, the `2`|
 //! |`fields`|`Option<darling::ast::Fields<__>>`|The fields associated with the variant|
 //! |`attrs`|`Vec<syn::Attribute>`|The forwarded attributes from the passed in variant. These are controlled using the `forward_attrs` attribute.|
+//!
+//! Every one of these traits, and `FromAttributes`, also recognizes `prefixed_attrs` when the
+//! container sets `attribute_prefix = "x_"`. The field can be any collection of
+//! `(String, T)` pairs, such as `HashMap<String, T>`, where `T: FromMeta`; each attribute like
+//! `#[x_cache(...)]` is read as a `T` and keyed by the rest of its name, `cache`.
+//!
+//! They also recognize `attr_span`, a `proc_macro2::Span` holding the span of the first
+//! attribute matching `attributes(...)`, or the call site if there was none. Validation done
+//! after parsing can point its errors there when no field has a better location.
 
 extern crate core;
 
@@ -75,32 +133,68 @@ pub use darling_macro::*;
 
 #[doc(inline)]
 pub use darling_core::{
-    FromAttributes, FromDeriveInput, FromField, FromGenericParam, FromGenerics, FromMeta,
-    FromTypeParam, FromVariant,
+    FromAttributes, FromConstParam, FromDeriveInput, FromField, FromGenericParam, FromGenerics,
+    FromMeta, FromTypeParam, FromVariant, MetaSchema, ToMeta,
 };
 
 #[doc(inline)]
 pub use darling_core::{Error, Result};
 
 #[doc(inline)]
-pub use darling_core::{ast, error, usage, util};
+pub use darling_core::{ast, codegen, emit, error, meta_schema, report, usage, util};
 
-// XXX exported so that `ExtractAttribute::extractor` can convert a path into tokens.
-// This is likely to change in the future, so only generated code should depend on this export.
+#[doc(inline)]
+pub use darling_core::derive::expand_str;
+
+#[cfg(feature = "testing")]
+#[doc(inline)]
+pub use darling_core::testing;
+
+#[cfg(feature = "fuzzing")]
+#[doc(inline)]
+pub use darling_core::fuzz;
+
+// XXX previously used by `ExtractAttribute::extractor` to convert a path into tokens.
+// Generated code no longer depends on this export; it is kept for compatibility.
 #[doc(hidden)]
 pub use darling_core::ToTokens;
 
-/// Core/std trait re-exports. This should help produce generated code which doesn't
-/// depend on `std` unnecessarily, and avoids problems caused by aliasing `std` or any
-/// of the referenced types.
+/// Everything generated code refers to. This should help produce generated code which
+/// doesn't depend on `std` unnecessarily, and avoids problems caused by aliasing `std` or
+/// any of the referenced types. Generated code only names items through this module, so
+/// the rest of the crate can be reorganized without changing expansion output.
 #[doc(hidden)]
 pub mod export {
     pub use core::convert::From;
     pub use core::default::Default;
+    pub use core::iter::FromIterator;
+    pub use core::mem;
     pub use core::option::Option::{self, None, Some};
     pub use core::result::Result::{self, Err, Ok};
-    pub use std::string::ToString;
+    pub use std::borrow::Cow;
+    pub use std::format;
+    pub use std::string::{String, ToString};
+    pub use std::vec;
     pub use std::vec::Vec;
+
+    pub use proc_macro2::Span;
+    pub use syn;
+
+    pub use darling_core::ast::{Data, Fields};
+    pub use darling_core::error::Warning;
+    pub use darling_core::meta_schema::{FieldSchema, SchemaBody, TypeSchema, VariantSchema};
+    pub use darling_core::report;
+    pub use darling_core::util::{
+        merge_config_file, meta_list, name_value_item, parse_attribute_to_meta_list,
+        parse_attribute_to_meta_list_with_exprs, parse_attribute_to_meta_list_with_value,
+        parse_attribute_to_meta_list_with_value_and_exprs, parse_dyn_field, path_to_string,
+        push_meta, push_multiple, push_prefixed_meta, strip_key_prefix, unwrap_cfg_attr,
+        ConfigFile, FieldSlot, NestingGuard, SpannedValue, DEFAULT_MAX_DEPTH,
+    };
+    pub use darling_core::{
+        Error, FromAttributes, FromDeriveInput, FromField, FromGenerics, FromMeta, FromTypeParam,
+        FromVariant, MetaSchema, ToMeta,
+    };
 }
 
 #[macro_use]
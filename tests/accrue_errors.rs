@@ -80,9 +80,14 @@ fn error_in_enum_fields() {
 
     let s_err = Month::from_derive_input(&input).unwrap_err();
     assert_eq!(2, s_err.len());
-    let err = s_err.flatten();
-    // TODO add tests to check location path is correct
-    println!("{}", err);
+    let messages: Vec<String> = s_err.flatten().into_iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "Unknown literal value `yes` at schedule/tuesday/morning",
+            "Missing field `afternoon` at schedule/tuesday",
+        ]
+    );
 }
 
 #[test]
@@ -97,6 +102,8 @@ fn error_in_newtype_variant() {
 
     let s_err = Month::from_derive_input(&input).unwrap_err();
     assert_eq!(1, s_err.len());
-    println!("{}", s_err);
-    println!("{}", s_err.flatten());
+    assert_eq!(
+        s_err.to_string(),
+        "Unknown literal value `yes` at schedule/wednesday/sit"
+    );
 }
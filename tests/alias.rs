@@ -0,0 +1,63 @@
+//! `#[darling(alias = "...")]` lets a field be read from other keys as well as its own.
+
+use darling::{FromMeta, MetaSchema};
+use syn::parse_quote;
+
+#[derive(Debug, FromMeta, MetaSchema)]
+struct Style {
+    #[darling(alias = "colour", alias = "hue")]
+    color: String,
+    #[darling(default)]
+    bold: bool,
+}
+
+#[test]
+fn reads_any_key() {
+    let style = Style::from_list(&[parse_quote!(color = "red")]).unwrap();
+    assert_eq!(style.color, "red");
+
+    let style = Style::from_list(&[parse_quote!(colour = "red"), parse_quote!(bold)]).unwrap();
+    assert_eq!(style.color, "red");
+    assert!(style.bold);
+
+    let style = Style::from_list(&[parse_quote!(hue = "red")]).unwrap();
+    assert_eq!(style.color, "red");
+}
+
+#[test]
+fn alias_and_canonical_conflict() {
+    let err =
+        Style::from_list(&[parse_quote!(colour = "x"), parse_quote!(color = "y")]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Keys `colour` and `color` both set the field `color`; use only one of them"
+    );
+}
+
+#[test]
+fn repeated_alias_is_a_duplicate() {
+    let err =
+        Style::from_list(&[parse_quote!(colour = "x"), parse_quote!(colour = "y")]).unwrap_err();
+    assert_eq!(err.to_string(), "Duplicate field `colour`");
+}
+
+#[test]
+fn documents_aliases() {
+    assert!(Style::ATTRIBUTE_DOCS.starts_with("* `color`: `String`, aliases `colour`, `hue`\n"));
+    assert_eq!(
+        Style::meta_schema().field("color").unwrap().aliases,
+        &["colour", "hue"]
+    );
+}
+
+#[derive(Debug, FromMeta)]
+struct Tags {
+    #[darling(multiple, alias = "label")]
+    tag: Vec<String>,
+}
+
+#[test]
+fn multiple_reads_all_keys() {
+    let tags = Tags::from_list(&[parse_quote!(tag = "a"), parse_quote!(label = "b")]).unwrap();
+    assert_eq!(tags.tag, vec!["a", "b"]);
+}
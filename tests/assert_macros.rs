@@ -0,0 +1,57 @@
+//! The `assert_parses!` and `assert_rejects!` test macros.
+
+use darling::error::ErrorKind;
+use darling::{assert_parses, assert_rejects, FromMeta};
+
+#[derive(Debug, PartialEq, FromMeta)]
+struct Settings {
+    a: u8,
+    #[darling(default)]
+    b: String,
+}
+
+#[test]
+fn parses() {
+    let settings = assert_parses!(Settings, "my_attr(a = 1)");
+    assert_eq!(settings.a, 1);
+
+    assert_parses!(
+        Settings,
+        r#"my_attr(a = 2, b = "x")"#,
+        Settings {
+            a: 2,
+            b: "x".to_string()
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "to parse as `Settings`")]
+fn parses_panics_on_error() {
+    assert_parses!(Settings, "my_attr(b = \"x\")");
+}
+
+#[test]
+fn rejects() {
+    let err = assert_rejects!(Settings, "my_attr(a = 1, c)");
+    assert_eq!(err.len(), 1);
+
+    assert_rejects!(Settings, "my_attr()", "Missing field `a`");
+    assert_rejects!(
+        Settings,
+        "my_attr(a = \"x\", c)",
+        kind = ErrorKind::UnknownField(_)
+    );
+}
+
+#[test]
+#[should_panic(expected = "but it parsed")]
+fn rejects_panics_on_success() {
+    assert_rejects!(Settings, "my_attr(a = 1)");
+}
+
+#[test]
+#[should_panic(expected = "expected an error of kind")]
+fn rejects_panics_on_wrong_kind() {
+    assert_rejects!(Settings, "my_attr()", kind = ErrorKind::UnknownField(_));
+}
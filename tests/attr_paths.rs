@@ -0,0 +1,47 @@
+//! Attribute names may be multi-segment paths, such as namespaced tool attributes.
+
+use darling::{FromDeriveInput, FromField};
+use syn::parse_quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(my_crate::opts), forward_attrs(my_crate::docs))]
+struct Namespaced {
+    name: String,
+    attrs: Vec<syn::Attribute>,
+}
+
+#[derive(Debug, FromField)]
+#[darling(attributes(my_crate::field))]
+struct NamespacedField {
+    skip: Option<bool>,
+}
+
+#[test]
+fn multi_segment_attribute_is_parsed() {
+    let di = parse_quote! {
+        #[my_crate::opts(name = "hello")]
+        #[my_crate::docs(hidden)]
+        #[opts(name = "ignored")]
+        #[other::opts(name = "ignored")]
+        struct Foo;
+    };
+
+    let parsed = Namespaced::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.name, "hello");
+
+    let forwarded: syn::Attribute = parse_quote!(#[my_crate::docs(hidden)]);
+    assert_eq!(parsed.attrs, vec![forwarded]);
+}
+
+#[test]
+fn multi_segment_attribute_on_field() {
+    let item: syn::ItemStruct = parse_quote! {
+        struct Foo {
+            #[my_crate::field(skip)]
+            bar: u8,
+        }
+    };
+
+    let field = item.fields.iter().next().unwrap();
+    assert_eq!(NamespacedField::from_field(field).unwrap().skip, Some(true));
+}
@@ -0,0 +1,74 @@
+//! The `attr_span` magic field receives the span of the first attribute darling read.
+
+use darling::{FromAttributes, FromDeriveInput, FromField};
+use proc_macro2::Span;
+use syn::parse_quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(lorem))]
+struct Lorem {
+    attr_span: Span,
+    ipsum: Option<bool>,
+}
+
+#[test]
+fn derive_input_without_attribute() {
+    let lorem = Lorem::from_derive_input(&parse_quote! {
+        #[other]
+        struct Example;
+    })
+    .unwrap();
+    assert_eq!(lorem.ipsum, None);
+}
+
+#[test]
+fn derive_input_with_attribute() {
+    let lorem = Lorem::from_derive_input(&parse_quote! {
+        #[lorem(ipsum)]
+        #[lorem]
+        struct Example;
+    })
+    .unwrap();
+    assert_eq!(lorem.ipsum, Some(true));
+
+    // Outside a proc-macro every span is the call site, so this only checks the span is usable.
+    let err = syn::Error::new(lorem.attr_span, "`ipsum` is not allowed here");
+    assert_eq!(err.to_string(), "`ipsum` is not allowed here");
+}
+
+#[allow(dead_code)]
+#[derive(FromField)]
+#[darling(attributes(lorem))]
+struct LoremField {
+    ident: Option<syn::Ident>,
+    attr_span: Span,
+}
+
+#[test]
+fn field() {
+    let fields: syn::FieldsNamed = parse_quote!({
+        #[lorem]
+        dolor: u8
+    });
+    let field = LoremField::from_field(&fields.named[0]).unwrap();
+    assert_eq!(field.ident.unwrap(), "dolor");
+}
+
+#[allow(dead_code)]
+#[derive(FromAttributes)]
+#[darling(attributes(lorem))]
+struct LoremAttrs {
+    attr_span: Span,
+    #[darling(default)]
+    ipsum: bool,
+}
+
+#[test]
+fn attributes() {
+    let attrs: Vec<syn::Attribute> = vec![parse_quote!(#[lorem(ipsum)])];
+    let parsed = LoremAttrs::from_attributes(&attrs).unwrap();
+    assert!(parsed.ipsum);
+
+    let parsed = LoremAttrs::from_attributes(&[]).unwrap();
+    assert!(!parsed.ipsum);
+}
@@ -0,0 +1,34 @@
+//! Attribute macro arguments can be parsed straight from their tokens.
+
+use darling::FromMeta;
+use quote::quote;
+
+#[derive(Debug, FromMeta, PartialEq, Eq)]
+struct MacroArgs {
+    #[darling(default)]
+    timeout_ms: Option<u16>,
+    path: String,
+}
+
+#[test]
+fn parse_from_tokens() {
+    let args = MacroArgs::from_attribute_args(quote!(path = "hello", timeout_ms = 15)).unwrap();
+    assert_eq!(
+        args,
+        MacroArgs {
+            timeout_ms: Some(15),
+            path: "hello".to_string(),
+        }
+    );
+}
+
+#[test]
+fn missing_field_from_empty_tokens() {
+    let err = MacroArgs::from_attribute_args(quote!()).unwrap_err();
+    assert_eq!(err.to_string(), "Missing field `path`");
+}
+
+#[test]
+fn malformed_tokens_are_an_error() {
+    MacroArgs::from_attribute_args(quote!(path = "hello" timeout_ms)).unwrap_err();
+}
@@ -0,0 +1,67 @@
+//! The `ATTRIBUTE_DOCS` list generated for derived `FromMeta` impls.
+
+use darling::FromMeta;
+
+fn three() -> u8 {
+    3
+}
+
+#[derive(FromMeta)]
+#[allow(dead_code)]
+struct Settings {
+    #[darling(rename = "label")]
+    name: String,
+    #[darling(default = "three")]
+    retries: u8,
+    #[darling(default)]
+    timeout: Option<u32>,
+    #[darling(multiple)]
+    tag: Vec<String>,
+    #[darling(skip)]
+    internal: bool,
+}
+
+#[derive(FromMeta)]
+#[allow(dead_code)]
+enum Mode {
+    Fast,
+    #[darling(skip)]
+    Hidden,
+    Custom(u8),
+}
+
+#[derive(FromMeta)]
+#[allow(dead_code)]
+#[darling(allow_unknown_fields)]
+struct Lenient {
+    name: String,
+}
+
+#[test]
+fn struct_docs() {
+    assert_eq!(
+        Settings::ATTRIBUTE_DOCS,
+        "* `label`: `String`\n\
+         * `retries`: `u8`, default `three()`\n\
+         * `timeout`: `Option<u32>`, default `Default::default()`\n\
+         * `tag`: `Vec<String>`, may be repeated\n"
+    );
+}
+
+#[test]
+fn enum_docs() {
+    assert_eq!(Mode::ATTRIBUTE_DOCS, "* `fast`\n* `custom(...)`\n");
+}
+
+#[test]
+fn unknown_fields_are_noted() {
+    assert_eq!(
+        Lenient::ATTRIBUTE_DOCS,
+        "* `name`: `String`\n\nUnknown options are ignored.\n"
+    );
+}
+
+#[test]
+fn manual_impls_are_empty() {
+    assert_eq!(String::ATTRIBUTE_DOCS, "");
+}
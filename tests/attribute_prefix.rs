@@ -0,0 +1,69 @@
+//! `attribute_prefix` collects a family of attributes into the `prefixed_attrs` magic field.
+
+use std::collections::{BTreeMap, HashMap};
+
+use darling::{FromAttributes, FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, PartialEq, FromMeta)]
+struct Plugin {
+    level: u8,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(x), attribute_prefix = "x_", forward_attrs(doc))]
+struct Host {
+    name: Option<String>,
+    prefixed_attrs: HashMap<String, Plugin>,
+    attrs: Vec<syn::Attribute>,
+}
+
+#[test]
+fn collects_by_suffix() {
+    let di = parse_quote! {
+        /// Docs
+        #[x(name = "host")]
+        #[x_cache(level = 1)]
+        #[x_log(level = 3)]
+        #[y_other(level = 4)]
+        struct Example;
+    };
+
+    let host = Host::from_derive_input(&di).unwrap();
+    assert_eq!(host.name, Some("host".to_string()));
+    assert_eq!(host.prefixed_attrs.len(), 2);
+    assert_eq!(host.prefixed_attrs["cache"], Plugin { level: 1 });
+    assert_eq!(host.prefixed_attrs["log"], Plugin { level: 3 });
+    assert_eq!(host.attrs.len(), 1);
+}
+
+#[test]
+fn reports_duplicates_and_bad_values() {
+    let di = parse_quote! {
+        #[x_cache(level = 1)]
+        #[x_cache(level = 2)]
+        #[x_log(level = "high")]
+        struct Example;
+    };
+
+    let err = Host::from_derive_input(&di).unwrap_err();
+    let errors: Vec<String> = err.flatten().into_iter().map(|e| e.to_string()).collect();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0], "Duplicate field `x_cache`");
+    assert!(errors[1].ends_with("at x_log/level"), "{}", errors[1]);
+}
+
+#[derive(Debug, FromAttributes)]
+#[darling(attribute_prefix = "plugin_")]
+struct Plugins {
+    prefixed_attrs: BTreeMap<String, syn::Meta>,
+}
+
+#[test]
+fn from_attributes_without_names() {
+    let attrs: Vec<syn::Attribute> = vec![parse_quote!(#[plugin_a]), parse_quote!(#[plugin_b = 1])];
+
+    let plugins = Plugins::from_attributes(&attrs).unwrap();
+    let keys: Vec<&str> = plugins.prefixed_attrs.keys().map(String::as_str).collect();
+    assert_eq!(keys, vec!["a", "b"]);
+}
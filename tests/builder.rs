@@ -0,0 +1,128 @@
+//! `#[darling(builder)]` generates an `XxxBuilder` with a setter per field.
+
+use darling::{FromDeriveInput, FromMeta};
+
+fn default_retries() -> u8 {
+    3
+}
+
+#[derive(Debug, PartialEq, FromMeta)]
+#[darling(builder)]
+struct Options {
+    name: String,
+    #[darling(default = "default_retries")]
+    retries: u8,
+    #[darling(rename = "type")]
+    kind: Option<String>,
+    #[darling(rename = "log_level")]
+    level: Option<u8>,
+    #[darling(multiple)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn fills_unset_fields_like_parsing() {
+    let built = Options::builder().name("a".to_string()).build().unwrap();
+    let parsed = Options::from_list(&[syn::parse_quote!(name = "a")]).unwrap();
+    assert_eq!(built, parsed);
+    assert_eq!(built.retries, 3);
+}
+
+#[test]
+fn setters_use_attribute_keys() {
+    let built = Options::builder()
+        .name("a".to_string())
+        .retries(1)
+        .log_level(Some(2))
+        .kind(Some("fast".to_string()))
+        .tags(vec!["x".to_string()])
+        .build()
+        .unwrap();
+    assert_eq!(built.retries, 1);
+    assert_eq!(built.level, Some(2));
+    assert_eq!(built.kind, Some("fast".to_string()));
+    assert_eq!(built.tags, vec!["x".to_string()]);
+}
+
+#[test]
+fn reports_missing_fields() {
+    let err = Options::builder().build().unwrap_err();
+    assert_eq!(err.to_string(), "Missing field `name`");
+}
+
+fn base() -> Limits {
+    Limits { min: 1, max: 10 }
+}
+
+#[derive(Debug, PartialEq, FromMeta)]
+#[darling(default = "base", builder, and_then = "Limits::check")]
+struct Limits {
+    min: u8,
+    max: u8,
+}
+
+impl Limits {
+    fn check(self) -> darling::Result<Self> {
+        if self.min > self.max {
+            Err(darling::Error::custom("`min` is larger than `max`"))
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+#[test]
+fn container_default_and_post_transform() {
+    assert_eq!(
+        Limits::builder().max(5).build().unwrap(),
+        Limits { min: 1, max: 5 }
+    );
+    assert!(Limits::builder().min(20).build().is_err());
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(opts), builder)]
+struct Receiver {
+    ident: syn::Ident,
+    #[darling(default)]
+    enabled: bool,
+}
+
+#[test]
+fn forwarded_fields_must_be_set() {
+    let err = ReceiverBuilder::default().build().unwrap_err();
+    assert_eq!(err.to_string(), "Missing field `ident`");
+
+    let receiver = Receiver::builder()
+        .ident(syn::parse_quote!(Example))
+        .build()
+        .unwrap();
+    assert_eq!(receiver.ident, "Example");
+    assert!(!receiver.enabled);
+}
+
+#[test]
+fn tuple_structs_are_rejected() {
+    let expanded = darling::expand_str(
+        r#"
+        #[derive(FromMeta)]
+        #[darling(builder)]
+        struct Pair(u8, u8);
+        "#,
+    )
+    .unwrap();
+
+    assert!(expanded.contains("`builder` can only be used on structs with named fields"));
+}
+
+#[derive(Debug, FromMeta)]
+#[darling(builder)]
+struct Wrapper<T> {
+    value: T,
+}
+
+#[test]
+fn generic_builder() {
+    let wrapper = Wrapper::builder().value(4u8).build().unwrap();
+    assert_eq!(wrapper.value, 4);
+}
@@ -0,0 +1,35 @@
+//! Human sizes such as `"64KiB"` can be read as a number of bytes.
+
+use darling::util::ByteSize;
+use darling::FromMeta;
+use syn::parse_quote;
+
+#[derive(Debug, FromMeta)]
+struct Buffer {
+    capacity: ByteSize,
+    #[darling(with = "darling::util::byte_size")]
+    limit: u64,
+}
+
+#[test]
+fn reads_sizes() {
+    let buffer = Buffer::from_list(&[
+        parse_quote!(capacity = "64KiB"),
+        parse_quote!(limit = "2MB"),
+    ])
+    .unwrap();
+    assert_eq!(buffer.capacity, ByteSize(65_536));
+    assert_eq!(buffer.limit, 2_000_000);
+
+    let buffer =
+        Buffer::from_list(&[parse_quote!(capacity = 128), parse_quote!(limit = "1")]).unwrap();
+    assert_eq!(buffer.capacity.bytes(), 128);
+    assert_eq!(buffer.limit, 1);
+}
+
+#[test]
+fn unknown_unit() {
+    let err = Buffer::from_list(&[parse_quote!(capacity = "1 ZiB"), parse_quote!(limit = 1)])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Unknown literal value `1 ZiB` at capacity");
+}
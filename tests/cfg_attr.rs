@@ -0,0 +1,95 @@
+//! `unwrap_cfg_attr` should make attributes wrapped in `cfg_attr` visible to darling.
+
+use darling::FromDeriveInput;
+use syn::parse_quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(my_attr), forward_attrs(serde), unwrap_cfg_attr)]
+struct Unwrapped {
+    opt: Option<u8>,
+    attrs: Vec<syn::Attribute>,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(my_attr))]
+struct Wrapped {
+    opt: Option<u8>,
+}
+
+#[test]
+fn cfg_attr_is_unwrapped() {
+    let di = parse_quote! {
+        #[cfg_attr(feature = "x", my_attr(opt = 1), serde(rename = "foo"))]
+        struct Foo;
+    };
+
+    let parsed = Unwrapped::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.opt, Some(1));
+
+    let forwarded: syn::Attribute = parse_quote!(#[cfg_attr(feature = "x", serde(rename = "foo"))]);
+    assert_eq!(parsed.attrs, vec![forwarded]);
+}
+
+#[test]
+fn plain_attributes_are_forwarded_unchanged() {
+    let di = parse_quote! {
+        #[serde(rename = "foo")]
+        struct Foo;
+    };
+
+    let parsed = Unwrapped::from_derive_input(&di).unwrap();
+    let forwarded: syn::Attribute = parse_quote!(#[serde(rename = "foo")]);
+    assert_eq!(parsed.attrs, vec![forwarded]);
+}
+
+#[test]
+fn duplicate_across_cfg_attr_is_reported() {
+    let di = parse_quote! {
+        #[my_attr(opt = 1)]
+        #[cfg_attr(feature = "x", my_attr(opt = 2))]
+        struct Foo;
+    };
+
+    let err = Unwrapped::from_derive_input(&di).unwrap_err();
+    assert_eq!(err.to_string(), "Duplicate field `opt`");
+}
+
+#[test]
+fn cfg_attr_is_ignored_by_default() {
+    let di = parse_quote! {
+        #[cfg_attr(feature = "x", my_attr(opt = 1))]
+        struct Foo;
+    };
+
+    let parsed = Wrapped::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.opt, None);
+}
+
+#[test]
+fn malformed_cfg_attr_is_reported() {
+    let di = parse_quote! {
+        #[cfg_attr(feature = "x")]
+        #[my_attr(opt = 2)]
+        struct Foo;
+    };
+
+    let err = Unwrapped::from_derive_input(&di).unwrap_err();
+    assert!(err.to_string().starts_with("Unable to parse `cfg_attr`"));
+}
+
+#[test]
+fn cfg_attr_after_malformed_one_is_read() {
+    let di = parse_quote! {
+        #[cfg_attr(feature = "x")]
+        #[cfg_attr(feature = "x", my_attr(opt = 1))]
+        #[my_attr(opt = 2)]
+        struct Foo;
+    };
+
+    let errors = Unwrapped::from_derive_input(&di).unwrap_err().flatten();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(
+        errors.into_iter().last().unwrap().to_string(),
+        "Duplicate field `opt`"
+    );
+}
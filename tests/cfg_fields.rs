@@ -0,0 +1,28 @@
+//! Fields gated with `#[cfg(...)]` need no support from darling: the compiler removes
+//! disabled fields before running derives, so the parser only knows about enabled ones.
+
+use darling::FromMeta;
+use syn::parse_quote;
+
+#[derive(Debug, FromMeta)]
+struct Lorem {
+    ipsum: bool,
+    #[cfg(not(test))]
+    dolor: u8,
+    #[cfg(test)]
+    #[darling(default)]
+    amet: u8,
+}
+
+#[test]
+fn enabled_field_parses() {
+    let lorem = Lorem::from_list(&[parse_quote!(ipsum), parse_quote!(amet = 2)]).unwrap();
+    assert!(lorem.ipsum);
+    assert_eq!(lorem.amet, 2);
+}
+
+#[test]
+fn disabled_field_is_unknown() {
+    let err = Lorem::from_list(&[parse_quote!(ipsum), parse_quote!(dolor = 1)]).unwrap_err();
+    assert!(err.to_string().starts_with("Unknown field: `dolor`"));
+}
@@ -0,0 +1,30 @@
+//! Test that the code generators can be driven without a derive.
+
+use std::borrow::Cow;
+
+use darling::ast::{Data, Fields, Style};
+use darling::codegen::{Field, FromMetaImpl, TraitImpl};
+use quote::ToTokens;
+use syn::parse_quote;
+
+#[test]
+fn emits_from_meta_impl() {
+    let ident: syn::Ident = parse_quote!(Opts);
+    let generics = syn::Generics::default();
+    let field_ident: syn::Ident = parse_quote!(name);
+    let field_ty: syn::Type = parse_quote!(String);
+
+    let mut field = Field::new(&field_ident, &field_ty);
+    field.name_in_attr = Cow::Borrowed("label");
+
+    let base = TraitImpl::new(
+        &ident,
+        &generics,
+        Data::Struct(Fields::new(Style::Struct, vec![field])),
+    );
+    let item: syn::ItemImpl = syn::parse2(FromMetaImpl::new(base).into_token_stream()).unwrap();
+
+    let expected: syn::Path = parse_quote!(::darling::export::FromMeta);
+    assert_eq!(item.trait_.as_ref().unwrap().1, expected);
+    assert!(item.into_token_stream().to_string().contains("\"label\""));
+}
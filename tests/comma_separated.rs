@@ -0,0 +1,25 @@
+//! `util::comma_separated` reads a list written as one comma-separated string.
+
+use darling::FromMeta;
+use syn::parse_quote;
+
+#[derive(Debug, FromMeta)]
+struct Build {
+    #[darling(with = "darling::util::comma_separated", default)]
+    features: Vec<String>,
+}
+
+#[test]
+fn reads_pasted_values() {
+    let build = Build::from_list(&[parse_quote!(features = "serde, derive , std")]).unwrap();
+    assert_eq!(build.features, vec!["serde", "derive", "std"]);
+
+    let build = Build::from_list(&[]).unwrap();
+    assert!(build.features.is_empty());
+}
+
+#[test]
+fn errors_name_the_field() {
+    let err = Build::from_list(&[parse_quote!(features = 1)]).unwrap_err();
+    assert_eq!(err.to_string(), "Unexpected literal type `int` at features");
+}
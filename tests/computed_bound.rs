@@ -1,4 +1,6 @@
-use darling::{FromDeriveInput, FromMeta};
+use std::marker::PhantomData;
+
+use darling::{FromDeriveInput, FromMeta, ToMeta};
 
 fn parse<T: FromDeriveInput>(src: &str) -> T {
     let ast = syn::parse_str(src).unwrap();
@@ -40,3 +42,34 @@ fn skipped_field() {
     );
     assert_eq!(parsed.max_volume, Volume::Shout);
 }
+
+/// Doesn't implement `FromMeta` or `ToMeta`, so it can only appear in skipped fields.
+#[derive(Debug)]
+struct Opaque;
+
+#[derive(Debug, FromMeta, ToMeta)]
+struct Marked<T> {
+    level: u8,
+    #[darling(skip)]
+    marker: PhantomData<T>,
+}
+
+#[derive(Debug, FromMeta, ToMeta)]
+enum MarkedEnum<T> {
+    Plain(u8),
+    Marked {
+        level: u8,
+        #[darling(skip)]
+        marker: PhantomData<T>,
+    },
+}
+
+#[test]
+fn skipped_fields_add_no_bounds() {
+    let marked = Marked::<Opaque>::from_list(&[syn::parse_quote!(level = 2)]).unwrap();
+    assert_eq!(marked.level, 2);
+    assert_eq!(marked.to_meta_string("marked"), "marked(level = 2)");
+
+    let marked = MarkedEnum::<Opaque>::from_list(&[syn::parse_quote!(marked(level = 2))]).unwrap();
+    assert_eq!(marked.to_meta_string("marked"), "marked(marked(level = 2))");
+}
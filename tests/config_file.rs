@@ -0,0 +1,125 @@
+//! `#[darling(config_file)]` fills in options from a file named in the attribute.
+
+use darling::util::ConfigFile;
+use darling::{FromDeriveInput, FromMeta};
+use quote::ToTokens;
+use syn::parse_quote;
+
+#[derive(Debug, Default, FromMeta)]
+struct Nested {
+    #[darling(default)]
+    enabled: bool,
+    #[darling(default)]
+    depth: u8,
+}
+
+#[derive(Debug, FromMeta)]
+#[darling(config_file)]
+struct Settings {
+    name: String,
+    #[darling(default)]
+    level: u8,
+    #[darling(default)]
+    nested: Nested,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(settings), config_file)]
+struct Outer {
+    name: String,
+    #[darling(default)]
+    level: u8,
+    config_files: Vec<ConfigFile>,
+}
+
+#[test]
+fn reads_file() {
+    let settings =
+        Settings::from_list(&[parse_quote!(config = "tests/fixtures/config_file.darling")])
+            .unwrap();
+
+    assert_eq!(settings.name, "from file");
+    assert_eq!(settings.level, 3);
+    assert!(settings.nested.enabled);
+}
+
+#[test]
+fn explicit_keys_take_precedence() {
+    let settings = Settings::from_list(&[
+        parse_quote!(config = "tests/fixtures/config_file.darling"),
+        parse_quote!(name = "explicit"),
+    ])
+    .unwrap();
+
+    assert_eq!(settings.name, "explicit");
+    assert_eq!(settings.level, 3);
+}
+
+#[test]
+fn nested_lists_are_merged() {
+    let settings = Settings::from_list(&[
+        parse_quote!(config = "tests/fixtures/config_file.darling"),
+        parse_quote!(nested(depth = 2)),
+    ])
+    .unwrap();
+
+    assert!(settings.nested.enabled);
+    assert_eq!(settings.nested.depth, 2);
+}
+
+#[test]
+fn without_file() {
+    let settings = Settings::from_list(&[parse_quote!(name = "explicit")]).unwrap();
+    assert_eq!(settings.level, 0);
+}
+
+#[test]
+fn from_derive_input() {
+    let di = parse_quote! {
+        #[settings(level = 7, config = "tests/fixtures/config_file_outer.darling")]
+        struct Example;
+    };
+
+    let outer = Outer::from_derive_input(&di).unwrap();
+    assert_eq!(outer.name, "from file");
+    assert_eq!(outer.level, 7);
+
+    // Emitting the files in the macro's output makes the compiler track them.
+    assert_eq!(outer.config_files.len(), 1);
+    let path = outer.config_files[0].path();
+    assert!(path.ends_with("tests/fixtures/config_file_outer.darling"));
+    assert!(path.is_absolute());
+    let tracked = outer.config_files[0].to_token_stream().to_string();
+    assert!(tracked.contains(": & str = include_str !"));
+}
+
+#[test]
+fn without_config_no_files_are_tracked() {
+    let di = parse_quote! {
+        #[settings(name = "explicit")]
+        struct Example;
+    };
+
+    assert!(Outer::from_derive_input(&di)
+        .unwrap()
+        .config_files
+        .is_empty());
+}
+
+#[test]
+fn missing_file() {
+    let err = Settings::from_list(&[
+        parse_quote!(name = "explicit"),
+        parse_quote!(config = "tests/fixtures/missing.darling"),
+    ])
+    .unwrap_err();
+
+    assert!(err.to_string().starts_with("Unable to read config file"));
+}
+
+#[test]
+fn config_must_be_string() {
+    let err =
+        Settings::from_list(&[parse_quote!(name = "x"), parse_quote!(config = 1)]).unwrap_err();
+    assert_eq!(err.to_string(), "Unexpected literal type `int` at config");
+}
@@ -0,0 +1,26 @@
+//! `#[darling(debug)]` prints the generated impls without changing them.
+
+#[test]
+fn expansion_is_unchanged() {
+    let plain = darling::expand_str(
+        r#"
+        #[derive(FromMeta)]
+        struct Opts {
+            name: String,
+        }
+        "#,
+    )
+    .unwrap();
+    let debugged = darling::expand_str(
+        r#"
+        #[derive(FromMeta)]
+        #[darling(debug)]
+        struct Opts {
+            name: String,
+        }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(plain, debugged);
+}
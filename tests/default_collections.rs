@@ -0,0 +1,61 @@
+//! `#[darling(default_collections)]` makes absent collection fields empty.
+
+use std::collections::HashMap;
+
+use darling::FromMeta;
+use syn::parse_quote;
+
+#[derive(Debug, FromMeta)]
+#[darling(default_collections)]
+struct Settings {
+    #[allow(dead_code)]
+    name: String,
+    numbers: Vec<u8>,
+    flags: HashMap<String, bool>,
+    #[darling(default = "Settings::default_level")]
+    levels: Vec<u8>,
+}
+
+impl Settings {
+    fn default_level() -> Vec<u8> {
+        vec![1]
+    }
+}
+
+#[derive(Debug, FromMeta)]
+struct Strict {
+    #[allow(dead_code)]
+    numbers: Vec<u8>,
+}
+
+#[test]
+fn absent_collections_are_empty() {
+    let settings = Settings::from_list(&[parse_quote!(name = "x")]).unwrap();
+    assert!(settings.numbers.is_empty());
+    assert!(settings.flags.is_empty());
+    assert_eq!(settings.levels, vec![1]);
+}
+
+#[test]
+fn present_collections_are_read() {
+    let settings = Settings::from_list(&[
+        parse_quote!(name = "x"),
+        parse_quote!(numbers = "[1, 2]"),
+        parse_quote!(flags(a = true)),
+    ])
+    .unwrap();
+    assert_eq!(settings.numbers, vec![1, 2]);
+    assert_eq!(settings.flags.get("a"), Some(&true));
+}
+
+#[test]
+fn other_fields_are_still_required() {
+    let err = Settings::from_list(&[]).unwrap_err();
+    assert_eq!(err.to_string(), "Missing field `name`");
+}
+
+#[test]
+fn collections_are_required_without_opt_in() {
+    let err = Strict::from_list(&[]).unwrap_err();
+    assert_eq!(err.to_string(), "Missing field `numbers`");
+}
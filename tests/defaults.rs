@@ -26,6 +26,40 @@ fn path_default() {
     assert_eq!(speaker.first_word, "hello");
 }
 
+#[derive(FromDeriveInput)]
+#[darling(attributes(speak))]
+pub struct OptionalOpts {
+    volume: Option<u8>,
+    #[darling(default = "OptionalOpts::default_pitch")]
+    pitch: Option<u8>,
+}
+
+impl OptionalOpts {
+    fn default_pitch() -> Option<u8> {
+        Some(4)
+    }
+}
+
+#[test]
+fn option_is_optional() {
+    let opts = OptionalOpts::from_derive_input(&parse_quote! {
+        struct Foo;
+    })
+    .expect("Option fields should not be required");
+
+    assert_eq!(opts.volume, None);
+    assert_eq!(opts.pitch, Some(4));
+
+    let opts = OptionalOpts::from_derive_input(&parse_quote! {
+        #[speak(volume = 3, pitch = 5)]
+        struct Foo;
+    })
+    .unwrap();
+
+    assert_eq!(opts.volume, Some(3));
+    assert_eq!(opts.pitch, Some(5));
+}
+
 /// Tests in this module capture the somewhat-confusing behavior observed when defaults
 /// are set at both the field and container level.
 ///
@@ -104,6 +138,47 @@ mod stacked_defaults {
         assert_eq!(person.name.last, "Doe");
     }
 
+    /// A bare `name` is read the same way as `name()`.
+    #[test]
+    fn name_bare_word() {
+        let person = Person::from_derive_input(&parse_quote! {
+            #[person(name, age = 5)]
+            struct Foo;
+        })
+        .unwrap();
+
+        assert_eq!(person.name.first, "Jane");
+        assert_eq!(person.name.middle, "");
+        assert_eq!(person.name.last, "Doe");
+    }
+
+    /// A struct with a field that must be set still rejects the word itself, instead of
+    /// reporting the field as missing.
+    #[test]
+    fn bare_word_needs_all_defaults() {
+        #[derive(Debug, FromMeta)]
+        struct Required {
+            #[allow(dead_code)]
+            value: u8,
+        }
+
+        let err = Required::from_meta(&parse_quote!(required)).unwrap_err();
+        assert_eq!(err.to_string(), "Unexpected meta-item format `word`");
+    }
+
+    #[test]
+    fn bare_word_with_optional_fields() {
+        #[derive(Debug, FromMeta)]
+        struct Optional {
+            value: Option<u8>,
+            enabled: darling::util::Flag,
+        }
+
+        let optional = Optional::from_meta(&parse_quote!(optional)).unwrap();
+        assert_eq!(optional.value, None);
+        assert!(!optional.enabled.is_present());
+    }
+
     #[test]
     fn no_name() {
         let person = Person::from_derive_input(&parse_quote! {
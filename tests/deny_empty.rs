@@ -0,0 +1,88 @@
+//! `#[darling(deny_empty)]` rejects an attribute which sets none of the fields.
+
+use darling::{FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, FromMeta)]
+#[darling(deny_empty, allow_unknown_fields)]
+struct Cache {
+    #[darling(default)]
+    size: Option<u32>,
+    #[darling(default)]
+    ttl: Option<u32>,
+}
+
+#[test]
+fn reads_set_fields() {
+    let cache = Cache::from_list(&[parse_quote!(ttl = 30)]).unwrap();
+    assert_eq!(cache.ttl, Some(30));
+    assert_eq!(cache.size, None);
+}
+
+#[test]
+fn rejects_empty_list() {
+    let err = Cache::from_list(&[]).unwrap_err();
+    assert_eq!(err.to_string(), "Too few items: Expected at least 1");
+
+    // The word form reads an empty list.
+    assert!(Cache::from_meta(&parse_quote!(cache)).is_err());
+}
+
+#[test]
+fn unknown_keys_are_not_recognized() {
+    let err = Cache::from_list(&[parse_quote!(sise = 30)]).unwrap_err();
+    assert_eq!(err.to_string(), "Too few items: Expected at least 1");
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(cached), deny_empty)]
+#[allow(dead_code)]
+struct Cached {
+    ident: syn::Ident,
+    #[darling(default)]
+    size: Option<u32>,
+}
+
+#[test]
+fn derive_input_without_attribute() {
+    let cached = Cached::from_derive_input(&parse_quote! {
+        struct Example;
+    })
+    .unwrap();
+    assert_eq!(cached.size, None);
+}
+
+#[test]
+fn derive_input_with_empty_attribute() {
+    let err = Cached::from_derive_input(&parse_quote! {
+        #[cached()]
+        struct Example;
+    })
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Too few items: Expected at least 1");
+    assert!(err.has_span());
+
+    let cached = Cached::from_derive_input(&parse_quote! {
+        #[cached()]
+        #[cached(size = 4)]
+        struct Example;
+    })
+    .unwrap();
+    assert_eq!(cached.size, Some(4));
+}
+
+#[test]
+fn enums_are_rejected() {
+    let expanded = darling::expand_str(
+        r#"
+        #[derive(FromMeta)]
+        #[darling(deny_empty)]
+        enum Mode {
+            Fast,
+        }
+        "#,
+    )
+    .unwrap();
+
+    assert!(expanded.contains("`deny_empty` can only be used on structs"));
+}
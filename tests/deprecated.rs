@@ -0,0 +1,153 @@
+//! Fields and variants marked `deprecated` still parse, but produce a warning when used.
+
+use darling::error::Warning;
+use darling::{FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, PartialEq, Eq, FromMeta)]
+enum Mode {
+    Fast,
+    #[darling(deprecated = "use `fast` instead")]
+    Quick,
+}
+
+#[derive(Debug, FromMeta)]
+struct Inner {
+    #[darling(default, deprecated = "remove it")]
+    old: bool,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(demo), forward_warnings)]
+struct Demo {
+    warnings: Vec<Warning>,
+    #[darling(default)]
+    with: Option<String>,
+    #[darling(default, deprecated = "use `with` instead")]
+    with_fn: Option<String>,
+    #[darling(default)]
+    mode: Option<Mode>,
+    #[darling(default)]
+    inner: Option<Inner>,
+}
+
+#[test]
+fn deprecated_field_warns() {
+    let di = parse_quote! {
+        #[demo(with_fn = "path::to::fn")]
+        pub struct Foo;
+    };
+
+    let parsed = Demo::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.with_fn, Some("path::to::fn".to_string()));
+    assert_eq!(parsed.with, None);
+
+    assert_eq!(parsed.warnings.len(), 1);
+    assert_eq!(
+        parsed.warnings[0].to_string(),
+        "`with_fn` is deprecated: use `with` instead"
+    );
+    assert!(parsed.warnings[0].span().is_some());
+}
+
+#[test]
+fn deprecated_variant_warns() {
+    let di = parse_quote! {
+        #[demo(mode = "quick")]
+        pub struct Foo;
+    };
+
+    let parsed = Demo::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.mode, Some(Mode::Quick));
+
+    assert_eq!(parsed.warnings.len(), 1);
+    assert_eq!(
+        parsed.warnings[0].to_string(),
+        "`quick` is deprecated: use `fast` instead"
+    );
+}
+
+#[test]
+fn deprecated_variant_warning_points_at_value() {
+    let di: syn::DeriveInput =
+        syn::parse_str("#[demo(mode = \"quick\")]\npub struct Foo;").unwrap();
+
+    let parsed = Demo::from_derive_input(&di).unwrap();
+    let span = parsed.warnings[0].span().unwrap();
+    assert_eq!(
+        (span.start().line, span.start().column, span.end().column),
+        (1, 14, 21)
+    );
+}
+
+#[test]
+fn nested_warnings_reach_the_container() {
+    let di = parse_quote! {
+        #[demo(inner(old))]
+        pub struct Foo;
+    };
+
+    let parsed = Demo::from_derive_input(&di).unwrap();
+    assert!(parsed.inner.unwrap().old);
+    assert_eq!(parsed.warnings.len(), 1);
+    assert_eq!(
+        parsed.warnings[0].to_string(),
+        "`old` is deprecated: remove it"
+    );
+}
+
+#[test]
+fn current_names_do_not_warn() {
+    let di = parse_quote! {
+        #[demo(with = "path::to::fn", mode = "fast")]
+        pub struct Foo;
+    };
+
+    assert!(Demo::from_derive_input(&di).unwrap().warnings.is_empty());
+}
+
+#[test]
+fn warnings_are_not_shared_between_parses() {
+    let used: syn::DeriveInput = parse_quote! {
+        #[demo(with_fn = "path::to::fn")]
+        pub struct Foo;
+    };
+    let unused: syn::DeriveInput = parse_quote! {
+        #[demo(with = "path::to::fn")]
+        pub struct Bar;
+    };
+
+    Demo::from_derive_input(&used).unwrap();
+    assert!(Demo::from_derive_input(&unused)
+        .unwrap()
+        .warnings
+        .is_empty());
+}
+
+#[test]
+fn from_list_collects_warnings() {
+    let items = darling::util::parse_attribute_args(quote::quote!(old)).unwrap();
+    let mut warnings = Vec::new();
+    let inner = Inner::from_list_with_warnings(&items, &mut warnings).unwrap();
+    assert!(inner.old);
+    assert_eq!(warnings.len(), 1);
+
+    // Plain `from_list` reads the same input without collecting anything.
+    assert!(Inner::from_list(&items).unwrap().old);
+}
+
+#[test]
+fn forward_warnings_needs_field() {
+    let expanded = darling::expand_str(
+        r#"
+        #[derive(FromDeriveInput)]
+        #[darling(forward_warnings)]
+        struct Lorem {
+            ident: syn::Ident,
+        }
+        "#,
+    )
+    .unwrap();
+
+    assert!(expanded.contains("`forward_warnings` needs a field named `warnings` to store into"));
+}
@@ -0,0 +1,97 @@
+//! `#[darling(derive_default)]` generates a `Default` impl from the fields' default expressions.
+
+use darling::{FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+fn default_retries() -> u8 {
+    3
+}
+
+#[derive(Debug, PartialEq, FromMeta)]
+#[darling(derive_default)]
+struct Options {
+    #[darling(default = "default_retries")]
+    retries: u8,
+    name: Option<String>,
+    #[darling(multiple)]
+    tags: Vec<String>,
+    #[darling(skip)]
+    computed: bool,
+}
+
+#[test]
+fn matches_parsing_empty_input() {
+    let expected = Options {
+        retries: 3,
+        name: None,
+        tags: vec![],
+        computed: false,
+    };
+    assert_eq!(Options::default(), expected);
+    assert_eq!(Options::from_list(&[]).unwrap(), expected);
+}
+
+fn base() -> Limits {
+    Limits { min: 1, max: 10 }
+}
+
+#[derive(Debug, PartialEq, FromMeta)]
+#[darling(default = "base", derive_default)]
+struct Limits {
+    min: u8,
+    max: u8,
+}
+
+#[test]
+fn inherits_container_default() {
+    assert_eq!(Limits::default(), base());
+}
+
+#[derive(Debug, PartialEq, FromMeta)]
+#[darling(default, derive_default)]
+struct Flags(bool, #[darling(default = "default_retries")] u8);
+
+#[test]
+fn tuple_struct_with_trait_default() {
+    assert_eq!(Flags::default(), Flags(false, 3));
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(opts), derive_default)]
+struct Receiver {
+    generics: syn::Generics,
+    attrs: Vec<syn::Attribute>,
+    #[darling(default = "default_retries")]
+    retries: u8,
+}
+
+#[test]
+fn forwarded_fields_use_their_default() {
+    let receiver = Receiver::default();
+    assert!(receiver.generics.params.is_empty());
+    assert!(receiver.attrs.is_empty());
+    assert_eq!(receiver.retries, 3);
+
+    let parsed = Receiver::from_derive_input(&parse_quote! {
+        #[opts(retries = 1)]
+        struct Example;
+    })
+    .unwrap();
+    assert_eq!(parsed.retries, 1);
+}
+
+#[test]
+fn enums_are_rejected() {
+    let expanded = darling::expand_str(
+        r#"
+        #[derive(FromMeta)]
+        #[darling(derive_default)]
+        enum Mode {
+            Fast,
+        }
+        "#,
+    )
+    .unwrap();
+
+    assert!(expanded.contains("`derive_default` can only be used on structs"));
+}
@@ -0,0 +1,72 @@
+//! Dotted keys, such as `limits.max`, should be routed into nested `FromMeta` structs.
+
+use darling::{FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, Default, FromMeta, PartialEq, Eq)]
+#[darling(default)]
+struct Limits {
+    min: u8,
+    max: u8,
+}
+
+#[derive(Debug, FromDeriveInput, PartialEq, Eq)]
+#[darling(attributes(dotted))]
+struct Lorem {
+    name: String,
+    limits: Limits,
+}
+
+#[test]
+fn dotted_keys_fill_nested_struct() {
+    let di = parse_quote! {
+        #[dotted(limits.max = 10, name = "hello", limits.min = 1)]
+        pub struct Foo;
+    };
+
+    assert_eq!(
+        Lorem::from_derive_input(&di).unwrap(),
+        Lorem {
+            name: "hello".to_string(),
+            limits: Limits { min: 1, max: 10 },
+        }
+    );
+}
+
+#[test]
+fn dotted_keys_mix_with_nested_lists() {
+    let di = parse_quote! {
+        #[dotted(name = "hello", limits(max = 10), limits.min = 1)]
+        pub struct Foo;
+    };
+
+    assert_eq!(
+        Lorem::from_derive_input(&di).unwrap().limits,
+        Limits { min: 1, max: 10 }
+    );
+}
+
+#[test]
+fn dotted_keys_in_another_attribute_are_a_duplicate() {
+    let di = parse_quote! {
+        #[dotted(name = "hello", limits(max = 10))]
+        #[dotted(limits.min = 1)]
+        pub struct Foo;
+    };
+
+    // Each attribute is parsed on its own, and a field that two attributes set is a duplicate
+    // however it's written, just as `limits(max = 10)` in both would be.
+    let err = Lorem::from_derive_input(&di).unwrap_err();
+    assert!(err.to_string().contains("Duplicate field `limits`"));
+}
+
+#[test]
+fn dotted_keys_report_unknown_nested_field() {
+    let di = parse_quote! {
+        #[dotted(name = "hello", limits.maximum = 10)]
+        pub struct Foo;
+    };
+
+    let err = Lorem::from_derive_input(&di).unwrap_err();
+    assert!(err.to_string().contains("Unknown field: `maximum`"));
+}
@@ -0,0 +1,78 @@
+//! `#[darling(dyn_fields)]` reads named fields through `DynFieldSlot` trait objects, and
+//! otherwise parses the same input as a container without it.
+
+use darling::{FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, Default, PartialEq, FromMeta)]
+#[darling(dyn_fields)]
+struct Inner {
+    #[darling(alias = "n")]
+    count: u8,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(opts), dyn_fields)]
+struct Opts {
+    retry_limit: u8,
+    #[darling(default)]
+    label: Option<String>,
+    #[darling(default)]
+    inner: Inner,
+}
+
+#[derive(Debug, FromMeta)]
+#[darling(dyn_fields)]
+enum Mode {
+    Fixed { size: u8 },
+}
+
+fn parse(di: syn::DeriveInput) -> darling::Result<Opts> {
+    Opts::from_derive_input(&di)
+}
+
+#[test]
+fn fields_parse() {
+    let opts = parse(parse_quote! {
+        #[opts(retry_limit = 3, label = "x", inner(n = 2))]
+        struct Foo;
+    })
+    .unwrap();
+
+    assert_eq!(opts.retry_limit, 3);
+    assert_eq!(opts.label, Some("x".to_string()));
+    assert_eq!(opts.inner, Inner { count: 2 });
+}
+
+#[test]
+fn errors_match_generated_fields() {
+    let err = parse(parse_quote! {
+        #[opts(retry_limit = 3, retry_limit = 4, label = 1, bogus)]
+        struct Foo;
+    })
+    .unwrap_err();
+
+    let messages: Vec<String> = err.flatten().into_iter().map(|e| e.to_string()).collect();
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[0], "Duplicate field `retry_limit`");
+    assert_eq!(messages[1], "Unexpected literal type `int` at label");
+    assert!(messages[2].starts_with("Unknown field: `bogus`"));
+}
+
+#[test]
+fn missing_field_is_reported() {
+    let err = parse(parse_quote! {
+        #[opts(label = "x")]
+        struct Foo;
+    })
+    .unwrap_err();
+
+    assert_eq!(err.to_string(), "Missing field `retry_limit`");
+}
+
+#[test]
+fn variant_fields_parse() {
+    let item: syn::Meta = parse_quote!(mode(fixed(size = 4)));
+    let Mode::Fixed { size } = Mode::from_meta(&item).unwrap();
+    assert_eq!(size, 4);
+}
@@ -1,5 +1,7 @@
 //! Test expansion of enum variants which have no associated data.
 
+#![allow(dead_code)]
+
 use darling::FromMeta;
 
 #[derive(Debug, FromMeta)]
@@ -12,3 +14,26 @@ enum Pattern {
 
 #[test]
 fn expansion() {}
+
+#[derive(Debug, FromMeta)]
+#[darling(rename_all = "kebab-case")]
+enum Level {
+    VeryLow,
+    #[darling(rename = "mid")]
+    Medium,
+    High,
+    #[darling(skip)]
+    Internal,
+    Custom(String),
+}
+
+#[test]
+fn unknown_value_lists_every_spelling() {
+    let err = Level::from_string("very_low").unwrap_err();
+    let message = err.to_string();
+    assert!(
+        message.ends_with("Expected one of `very-low`, `mid`, `high`"),
+        "{}",
+        message
+    );
+}
@@ -52,3 +52,27 @@ fn missing_field() {
 
     println!("{}", Lorem::from_derive_input(&di).unwrap_err());
 }
+
+#[test]
+fn error_kinds_can_be_matched() {
+    use darling::error::ErrorKind;
+
+    let di = parse_quote! {
+        #[hello(ipsum(amet = true, wrld = true))]
+        pub struct Foo;
+    };
+
+    let err = Lorem::from_derive_input(&di).unwrap_err().flatten();
+    let mut unknown = vec![];
+    let mut missing = vec![];
+    for error in err {
+        match *error.kind() {
+            ErrorKind::UnknownField(ref field) => unknown.push(field.name().to_string()),
+            ErrorKind::MissingField(ref name) => missing.push(name.clone()),
+            _ => panic!("Unexpected error: {}", error),
+        }
+    }
+
+    assert_eq!(unknown, vec!["wrld"]);
+    assert_eq!(missing, vec!["world"]);
+}
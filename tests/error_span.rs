@@ -0,0 +1,91 @@
+//! Fields can choose whether their parsing errors point at the key or the value.
+
+use darling::{FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, FromMeta)]
+struct Limits {
+    max: u32,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(demo), error_span = "value")]
+struct Demo {
+    #[darling(error_span = "key")]
+    name: String,
+    limits: Limits,
+    #[darling(default)]
+    count: u32,
+}
+
+#[test]
+fn key_span_still_reports_error() {
+    let di = parse_quote! {
+        #[demo(name = 4, limits(max = 1))]
+        pub struct Foo;
+    };
+
+    let err = Demo::from_derive_input(&di).unwrap_err();
+    assert!(err.has_span());
+    assert_eq!(err.to_string(), "Unexpected literal type `int` at name");
+}
+
+#[test]
+fn value_span_applies_to_nested_errors() {
+    let di = parse_quote! {
+        #[demo(name = "x", limits(max = "big", min = 1), count = "many")]
+        pub struct Foo;
+    };
+
+    let err = Demo::from_derive_input(&di).unwrap_err();
+    assert_eq!(err.len(), 3);
+    assert!(err.into_iter().all(|e| e.has_span()));
+}
+
+/// The start and end columns of the text each of the errors for `source` points at.
+fn columns(source: &str) -> Vec<(usize, usize)> {
+    let di: syn::DeriveInput = syn::parse_str(source).unwrap();
+    Demo::from_derive_input(&di)
+        .unwrap_err()
+        .flatten()
+        .into_iter()
+        .map(|err| {
+            let span = syn::Error::from(err).span();
+            (span.start().column, span.end().column)
+        })
+        .collect()
+}
+
+#[test]
+fn errors_point_at_chosen_part() {
+    let spans = columns(r#"#[demo(name = 4, limits(max = "big"), count = "many")] struct Foo;"#);
+    // `name` points at its key, and `count` at its literal.
+    assert!(spans.contains(&(7, 11)), "{:?}", spans);
+    assert!(spans.contains(&(46, 52)), "{:?}", spans);
+}
+
+#[test]
+fn value_span_keeps_inner_spans() {
+    let spans = columns(r#"#[demo(name = "x", limits(max = "big"))] struct Foo;"#);
+    assert_eq!(spans, vec![(32, 37)]);
+}
+
+#[test]
+fn value_span_fills_missing_spans() {
+    // The missing field has no span of its own, so it points at the list it's missing from.
+    let spans = columns(r#"#[demo(name = "x", limits())] struct Foo;"#);
+    assert_eq!(spans, vec![(19, 27)]);
+}
+
+#[test]
+fn valid_input_is_unaffected() {
+    let di = parse_quote! {
+        #[demo(name = "x", limits(max = 3))]
+        pub struct Foo;
+    };
+
+    let parsed = Demo::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.name, "x");
+    assert_eq!(parsed.limits.max, 3);
+    assert_eq!(parsed.count, 0);
+}
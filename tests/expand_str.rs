@@ -0,0 +1,97 @@
+//! Snapshotting generated code with `darling::expand_str`.
+
+use darling::expand_str;
+
+#[test]
+fn expands_each_darling_derive() {
+    let expanded = expand_str(
+        r#"
+        #[derive(Debug, FromMeta, darling::FromDeriveInput)]
+        struct Opts {
+            name: String,
+        }
+        "#,
+    )
+    .unwrap();
+
+    let meta = expanded
+        .find("impl :: darling :: export :: FromMeta for Opts")
+        .expect("FromMeta impl");
+    let fdi = expanded
+        .find("impl :: darling :: export :: FromDeriveInput for Opts")
+        .expect("FromDeriveInput impl");
+    assert!(meta < fdi, "impls should follow the order of the derives");
+    assert!(!expanded.contains("Debug"));
+}
+
+#[test]
+fn expansion_is_stable() {
+    let source = r#"
+        #[derive(FromMeta)]
+        struct Opts {
+            #[darling(default)]
+            name: String,
+            #[darling(multiple)]
+            tag: Vec<String>,
+        }
+    "#;
+
+    assert_eq!(expand_str(source).unwrap(), expand_str(source).unwrap());
+}
+
+#[test]
+fn invalid_options_expand_to_compile_errors() {
+    let expanded = expand_str(
+        r#"
+        #[derive(FromMeta)]
+        struct Opts {
+            #[darling(nonsense)]
+            name: String,
+        }
+        "#,
+    )
+    .unwrap();
+
+    assert!(expanded.contains("compile_error"));
+}
+
+#[test]
+fn requires_a_darling_derive() {
+    let err = expand_str("#[derive(Debug)] struct Opts;").unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("`Opts` doesn't derive any of FromMeta"));
+}
+
+#[test]
+fn rejects_invalid_source() {
+    assert!(expand_str("struct").is_err());
+}
+
+/// Bounds are added to type parameters in declaration order, regardless of how the set of
+/// used parameters is stored.
+#[test]
+fn bounds_follow_declaration_order() {
+    let expanded = expand_str(
+        r#"
+        #[derive(FromMeta)]
+        struct Opts<Z, Y, X, W, V, U> {
+            z: Z, y: Y, x: X, w: W, v: V, u: U,
+        }
+        "#,
+    )
+    .unwrap();
+
+    let positions: Vec<usize> = ["Z", "Y", "X", "W", "V", "U"]
+        .iter()
+        .map(|param| {
+            expanded
+                .find(&format!("{} : :: darling :: export :: FromMeta", param))
+                .expect("bound on type parameter")
+        })
+        .collect();
+
+    let mut sorted = positions.clone();
+    sorted.sort_unstable();
+    assert_eq!(positions, sorted);
+}
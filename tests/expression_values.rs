@@ -0,0 +1,72 @@
+//! Test that values which aren't literals are read from derive input attributes which allow
+//! them, and only by types which accept expressions.
+
+use darling::FromDeriveInput;
+use syn::parse_quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(opts), expr_values)]
+struct Opts {
+    limit: syn::Path,
+    offset: i32,
+    #[darling(default)]
+    size: Option<syn::Expr>,
+    #[darling(default)]
+    name: Option<String>,
+}
+
+#[test]
+fn path_and_negative_number() {
+    let di = parse_quote! {
+        #[opts(limit = crate::LIMIT, offset = -4, size = 2 * 8)]
+        struct Foo;
+    };
+
+    let opts = Opts::from_derive_input(&di).unwrap();
+    assert_eq!(opts.limit, parse_quote!(crate::LIMIT));
+    assert_eq!(opts.offset, -4);
+    assert_eq!(opts.size, Some(parse_quote!(2 * 8)));
+}
+
+#[test]
+fn literal_values_unchanged() {
+    let di = parse_quote! {
+        #[opts(limit = "LIMIT", offset = 3, name = "x")]
+        struct Foo;
+    };
+
+    let opts = Opts::from_derive_input(&di).unwrap();
+    assert_eq!(opts.limit, parse_quote!(LIMIT));
+    assert_eq!(opts.offset, 3);
+    assert!(opts.size.is_none());
+    assert_eq!(opts.name, Some("x".to_string()));
+}
+
+#[test]
+fn expression_for_number_is_reported() {
+    let di = parse_quote! {
+        #[opts(limit = crate::LIMIT, offset = some::CONST)]
+        struct Foo;
+    };
+
+    let err = Opts::from_derive_input(&di).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unexpected meta-item format `expression` at offset"
+    );
+}
+
+#[test]
+fn string_rejects_expression() {
+    let di = parse_quote! {
+        #[opts(limit = crate::LIMIT, offset = 1, name = RocketEngine)]
+        struct Foo;
+    };
+
+    let err = Opts::from_derive_input(&di).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unexpected meta-item format `expression` at name"
+    );
+    assert!(err.has_span());
+}
@@ -0,0 +1,116 @@
+//! `fallback_attributes(...)` reads keys which the claimed attributes don't set from a shared
+//! attribute on the same item.
+
+use darling::{FromDeriveInput, FromField};
+use syn::parse_quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(server), fallback_attributes(common))]
+struct Server {
+    krate: Option<String>,
+    #[darling(default)]
+    port: u16,
+    #[darling(default)]
+    verbose: bool,
+}
+
+#[test]
+fn absent_keys_come_from_fallback() {
+    let server = Server::from_derive_input(&parse_quote! {
+        #[common(krate = "shared", port = 80)]
+        #[server(port = 8080)]
+        struct Example;
+    })
+    .unwrap();
+
+    assert_eq!(server.krate, Some("shared".to_string()));
+    assert_eq!(server.port, 8080);
+    assert!(!server.verbose);
+}
+
+#[test]
+fn fallback_alone() {
+    let server = Server::from_derive_input(&parse_quote! {
+        #[common(verbose)]
+        struct Example;
+    })
+    .unwrap();
+
+    assert!(server.verbose);
+    assert_eq!(server.krate, None);
+}
+
+#[test]
+fn fallback_keys_are_checked() {
+    let err = Server::from_derive_input(&parse_quote! {
+        #[common(prot = 80)]
+        struct Example;
+    })
+    .unwrap_err();
+
+    assert!(
+        err.to_string().starts_with("Unknown field: `prot`"),
+        "{}",
+        err
+    );
+}
+
+#[derive(Debug, FromField)]
+#[darling(attributes(column), fallback_attributes(table), allow_unknown_fields)]
+struct Column {
+    #[darling(default)]
+    nullable: bool,
+}
+
+#[test]
+fn works_on_fields() {
+    let fields: syn::FieldsNamed = parse_quote!({
+        #[table(nullable, engine = "x")]
+        #[column]
+        a: u8
+    });
+    let column = Column::from_field(&fields.named[0]).unwrap();
+    assert!(column.nullable);
+    let fields: syn::FieldsNamed = parse_quote!({
+        #[table(nullable)]
+        #[column(nullable = false)]
+        a: u8
+    });
+    let column = Column::from_field(&fields.named[0]).unwrap();
+    assert!(!column.nullable);
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(server), fallback_attributes(common))]
+struct Aliased {
+    #[darling(default, alias = "p")]
+    port: u16,
+}
+
+#[test]
+fn alias_in_claimed_attribute_takes_precedence() {
+    let aliased = Aliased::from_derive_input(&parse_quote! {
+        #[common(port = 80)]
+        #[server(p = 8080)]
+        struct Example;
+    })
+    .unwrap();
+
+    assert_eq!(aliased.port, 8080);
+}
+
+#[test]
+fn overlap_with_attributes_is_rejected() {
+    let expanded = darling::expand_str(
+        r#"
+        #[derive(FromDeriveInput)]
+        #[darling(attributes(server, common), fallback_attributes(common))]
+        struct Lorem {
+            port: u16,
+        }
+        "#,
+    )
+    .unwrap();
+
+    assert!(expanded.contains("`common` is in both `attributes` and `fallback_attributes`"));
+}
@@ -0,0 +1,66 @@
+//! The `index` magic field of `FromField` receives the position of each field.
+
+use darling::ast::Data;
+use darling::{FromDeriveInput, FromField};
+use syn::parse_quote;
+
+#[derive(Debug, FromField)]
+#[darling(attributes(column))]
+struct Column {
+    ident: Option<syn::Ident>,
+    index: Option<usize>,
+    #[darling(default)]
+    skip: bool,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(supports(struct_tuple, struct_named))]
+struct Table {
+    data: Data<(), Column>,
+}
+
+fn columns(di: syn::DeriveInput) -> Vec<Column> {
+    Table::from_derive_input(&di)
+        .unwrap()
+        .data
+        .take_struct()
+        .unwrap()
+        .fields
+}
+
+#[test]
+fn tuple_fields_have_positions() {
+    let columns = columns(parse_quote! {
+        struct Row(u8, #[column(skip)] String, bool);
+    });
+
+    let indices: Vec<Option<usize>> = columns.iter().map(|column| column.index).collect();
+    assert_eq!(indices, vec![Some(0), Some(1), Some(2)]);
+    assert!(columns[1].skip);
+    assert!(columns.iter().all(|column| column.ident.is_none()));
+}
+
+#[test]
+fn named_fields_have_positions() {
+    let columns = columns(parse_quote! {
+        struct Row {
+            id: u8,
+            name: String,
+        }
+    });
+
+    let indices: Vec<Option<usize>> = columns.iter().map(|column| column.index).collect();
+    assert_eq!(indices, vec![Some(0), Some(1)]);
+}
+
+#[test]
+fn direct_call_has_no_index() {
+    let fields: syn::FieldsUnnamed = parse_quote!(
+        (#[column(skip)]
+        u8)
+    );
+    let field = &fields.unnamed[0];
+    let column = Column::from_field(field).unwrap();
+    assert_eq!(column.index, None);
+    assert!(column.skip);
+}
@@ -0,0 +1,68 @@
+//! `util::Flag` records whether a word was present, and where.
+
+use darling::{util::Flag, FromDeriveInput};
+use syn::parse_quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(demo))]
+struct Demo {
+    quiet: Flag,
+    #[darling(default)]
+    verbose: Flag,
+}
+
+#[test]
+fn present_flag_has_span() {
+    let di = parse_quote! {
+        #[demo(quiet)]
+        pub struct Foo;
+    };
+
+    let parsed = Demo::from_derive_input(&di).unwrap();
+    assert!(parsed.quiet.is_present());
+    assert!(parsed.quiet.span().is_some());
+    assert_eq!(parsed.quiet, Flag::present());
+}
+
+#[test]
+fn absent_flag_is_not_missing() {
+    let di = parse_quote! {
+        #[demo(verbose)]
+        pub struct Foo;
+    };
+
+    let parsed = Demo::from_derive_input(&di).unwrap();
+    assert!(!parsed.quiet.is_present());
+    assert!(parsed.quiet.span().is_none());
+    assert!(parsed.verbose.is_present());
+}
+
+#[test]
+fn value_is_rejected() {
+    let di = parse_quote! {
+        #[demo(quiet = true)]
+        pub struct Foo;
+    };
+
+    let err = Demo::from_derive_input(&di).unwrap_err();
+    assert!(err.has_span());
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(demo))]
+struct Strict {
+    #[darling(required)]
+    #[allow(dead_code)]
+    quiet: Flag,
+}
+
+#[test]
+fn required_flag_is_missing() {
+    let di = parse_quote! {
+        #[demo()]
+        pub struct Foo;
+    };
+
+    let err = Strict::from_derive_input(&di).unwrap_err();
+    assert_eq!(err.to_string(), "Missing field `quiet`");
+}
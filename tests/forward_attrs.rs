@@ -0,0 +1,51 @@
+//! `forward_attrs` should control which attributes populate the `attrs` magic field.
+
+use darling::{FromDeriveInput, FromField};
+use syn::parse_quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(lorem), forward_attrs(allow, doc, cfg))]
+struct OnlySome {
+    attrs: Vec<syn::Attribute>,
+}
+
+#[derive(Debug, FromField)]
+#[darling(forward_attrs)]
+struct All {
+    attrs: Vec<syn::Attribute>,
+}
+
+#[test]
+fn forward_only_listed_attrs() {
+    let di = parse_quote! {
+        /// Documented
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        #[lorem]
+        #[serde(rename = "foo")]
+        struct Foo;
+    };
+
+    let parsed = OnlySome::from_derive_input(&di).unwrap();
+    let names: Vec<String> = parsed
+        .attrs
+        .iter()
+        .map(|attr| darling::util::path_to_string(&attr.path))
+        .collect();
+    assert_eq!(names, vec!["doc", "allow"]);
+}
+
+#[test]
+fn forward_all_attrs() {
+    let item: syn::ItemStruct = parse_quote! {
+        struct Foo {
+            #[serde(rename = "bar")]
+            #[doc = "Hello"]
+            field: u8,
+        }
+    };
+
+    let field = item.fields.iter().next().unwrap();
+    let parsed = All::from_field(field).unwrap();
+    assert_eq!(parsed.attrs, field.attrs);
+}
@@ -0,0 +1,60 @@
+//! `#[darling(forward_input)]` stores the whole `DeriveInput` next to the parsed options.
+
+use darling::FromDeriveInput;
+use syn::parse_quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(lorem), forward_input)]
+struct Lorem {
+    ident: syn::Ident,
+    input: syn::DeriveInput,
+    ipsum: Option<bool>,
+}
+
+#[test]
+fn stores_input() {
+    let di: syn::DeriveInput = parse_quote! {
+        #[lorem(ipsum)]
+        struct Example {
+            field: u8,
+        }
+    };
+
+    let lorem = Lorem::from_derive_input(&di).unwrap();
+    assert_eq!(lorem.ident, "Example");
+    assert_eq!(lorem.ipsum, Some(true));
+    assert_eq!(lorem.input, di);
+}
+
+#[allow(dead_code)]
+#[derive(FromDeriveInput)]
+#[darling(attributes(lorem))]
+struct PlainInput {
+    input: Option<String>,
+}
+
+#[test]
+fn input_is_a_normal_field_without_option() {
+    let plain = PlainInput::from_derive_input(&parse_quote! {
+        #[lorem(input = "dolor")]
+        struct Example;
+    })
+    .unwrap();
+    assert_eq!(plain.input, Some("dolor".to_string()));
+}
+
+#[test]
+fn missing_input_field() {
+    let expanded = darling::expand_str(
+        r#"
+        #[derive(FromDeriveInput)]
+        #[darling(forward_input)]
+        struct Lorem {
+            ident: syn::Ident,
+        }
+        "#,
+    )
+    .unwrap();
+
+    assert!(expanded.contains("`forward_input` needs a field named `input` to store into"));
+}
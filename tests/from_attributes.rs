@@ -0,0 +1,109 @@
+//! `FromAttributes` should work on items which aren't derive inputs, such as functions.
+
+use darling::FromAttributes;
+use syn::parse_quote;
+
+#[derive(Debug, Default, FromAttributes, PartialEq, Eq)]
+#[darling(attributes(route), default)]
+struct Route {
+    path: String,
+    method: Option<String>,
+}
+
+#[test]
+fn parse_fn_attributes() {
+    let item: syn::ItemFn = parse_quote! {
+        #[route(path = "/index")]
+        #[inline]
+        #[route(method = "GET")]
+        fn index() {}
+    };
+
+    assert_eq!(
+        Route::from_attributes(&item.attrs).unwrap(),
+        Route {
+            path: "/index".to_string(),
+            method: Some("GET".to_string()),
+        }
+    );
+}
+
+#[test]
+fn parse_without_matching_attributes() {
+    let item: syn::ItemFn = parse_quote! {
+        #[inline]
+        fn index() {}
+    };
+
+    assert_eq!(
+        Route::from_attributes(&item.attrs).unwrap(),
+        Route::default()
+    );
+}
+
+#[test]
+fn unknown_field_is_reported() {
+    let item: syn::ItemStatic = parse_quote! {
+        #[route(paht = "/index")]
+        static INDEX: () = ();
+    };
+
+    let err = Route::from_attributes(&item.attrs).unwrap_err();
+    assert!(err.to_string().starts_with("Unknown field: `paht`"));
+}
+
+#[test]
+fn parse_inner_attributes() {
+    let item: syn::ItemMod = parse_quote! {
+        #[route(method = "POST")]
+        mod api {
+            #![route(path = "/api")]
+        }
+    };
+
+    assert_eq!(
+        Route::from_attributes(&item.attrs).unwrap(),
+        Route {
+            path: "/api".to_string(),
+            method: Some("POST".to_string()),
+        }
+    );
+}
+
+#[test]
+fn parse_file_inner_attributes() {
+    let file: syn::File = parse_quote! {
+        #![route(path = "/")]
+
+        fn index() {}
+    };
+
+    assert_eq!(Route::from_attributes(&file.attrs).unwrap().path, "/");
+}
+
+#[test]
+fn parse_item_helpers() {
+    let item: syn::ItemFn = parse_quote! {
+        #[route(path = "/fn")]
+        fn index() {}
+    };
+    assert_eq!(Route::from_item_fn(&item).unwrap().path, "/fn");
+
+    let item: syn::ImplItem = parse_quote! {
+        #[route(path = "/impl")]
+        fn index(&self) {}
+    };
+    assert_eq!(Route::from_impl_item(&item).unwrap().path, "/impl");
+
+    let item: syn::TraitItem = parse_quote! {
+        #[route(path = "/trait")]
+        fn index(&self);
+    };
+    assert_eq!(Route::from_trait_item(&item).unwrap().path, "/trait");
+
+    let item: syn::Item = parse_quote! {
+        #[route(path = "/const")]
+        const INDEX: u8 = 0;
+    };
+    assert_eq!(Route::from_item(&item).unwrap().path, "/const");
+}
@@ -174,3 +174,42 @@ fn ignored() {
 
     assert_eq!(Ignored, rec.generics);
 }
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(lorem))]
+struct ConstReceiver {
+    pub generics:
+        ast::Generics<ast::GenericParam<syn::Ident, syn::LifetimeDef, Vec<syn::Attribute>>>,
+}
+
+#[test]
+fn const_params() {
+    let rec: ConstReceiver = fdi(r#"
+        struct Baz<T, #[lorem(len)] const N: usize, const M: u8 = 1>([T; N]);
+    "#)
+    .expect("Input is well-formed");
+
+    let attrs: Vec<usize> = rec.generics.const_params().map(Vec::len).collect();
+    assert_eq!(attrs, vec![1, 0]);
+    assert_eq!(rec.generics.type_params().count(), 1);
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(lorem))]
+struct SynParamsReceiver {
+    pub generics: ast::Generics<ast::GenericParam>,
+}
+
+#[test]
+fn const_params_are_re_emitted() {
+    let rec: SynParamsReceiver = fdi(r#"
+        struct Baz<#[lorem] const N: usize = 3>([u8; N]);
+    "#)
+    .expect("Input is well-formed");
+
+    let param = &rec.generics.params[0];
+    assert_eq!(
+        quote::quote!(#param).to_string(),
+        quote::quote!(#[lorem] const N: usize = 3).to_string()
+    );
+}
@@ -0,0 +1,67 @@
+//! Parsing options from source text, as done in unit tests of option types.
+
+use darling::{FromAttributes, FromDeriveInput, FromMeta};
+
+#[derive(Debug, PartialEq, FromMeta)]
+struct Settings {
+    a: u8,
+    #[darling(default)]
+    b: String,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(settings))]
+struct Container {
+    ident: syn::Ident,
+    level: u8,
+}
+
+#[derive(Debug, PartialEq, FromAttributes)]
+#[darling(attributes(marker))]
+struct Marker {
+    #[darling(default)]
+    skip: bool,
+    #[darling(default)]
+    name: Option<String>,
+}
+
+#[test]
+fn meta_from_str() {
+    assert_eq!(
+        Settings::from_meta_str(r#"my_attr(a = 1, b = "x")"#).unwrap(),
+        Settings {
+            a: 1,
+            b: "x".to_string()
+        }
+    );
+}
+
+#[test]
+fn meta_from_str_reports_errors() {
+    let err = Settings::from_meta_str("my_attr(b = \"x\")").unwrap_err();
+    assert_eq!(err.to_string(), "Missing field `a`");
+}
+
+#[test]
+fn meta_from_invalid_str() {
+    assert!(Settings::from_meta_str("my_attr(").is_err());
+}
+
+#[test]
+fn derive_input_from_str() {
+    let container = Container::from_derive_input_str("#[settings(level = 3)] struct Foo;").unwrap();
+    assert_eq!(container.ident, "Foo");
+    assert_eq!(container.level, 3);
+}
+
+#[test]
+fn attributes_from_str() {
+    assert_eq!(
+        Marker::from_attributes_str(r#"#[marker(skip)] #[doc = "hi"] #[marker(name = "m")]"#)
+            .unwrap(),
+        Marker {
+            skip: true,
+            name: Some("m".to_string())
+        }
+    );
+}
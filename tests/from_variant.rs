@@ -55,3 +55,72 @@ fn discriminant() {
         panic!("Data should be enum");
     }
 }
+
+#[test]
+fn no_discriminant() {
+    let variant: syn::Variant = syn::parse_quote!(Implicit);
+    let lorem = Lorem::from_variant(&variant).unwrap();
+    assert_eq!(lorem.discriminant, None);
+}
+
+#[test]
+fn expression_discriminant() {
+    let variant: syn::Variant = syn::parse_quote!(Shifted = 1 << 4);
+    let lorem = Lorem::from_variant(&variant).unwrap();
+    let expected: syn::Expr = syn::parse_quote!(1 << 4);
+    assert_eq!(lorem.discriminant, Some(expected));
+}
+
+#[derive(Debug, darling::FromField)]
+#[darling(attributes(hello))]
+struct FieldOpts {
+    ident: Option<syn::Ident>,
+    #[darling(default)]
+    rename: Option<String>,
+}
+
+#[derive(Debug, FromVariant)]
+#[darling(attributes(hello))]
+struct VariantOpts {
+    fields: darling::ast::Fields<FieldOpts>,
+}
+
+#[test]
+fn fields_use_from_field() {
+    let variant: syn::Variant = syn::parse_quote! {
+        Works {
+            #[hello(rename = "first")]
+            a: u8,
+            b: u8,
+        }
+    };
+
+    let opts = VariantOpts::from_variant(&variant).unwrap();
+    assert!(opts.fields.is_struct());
+    let renames: Vec<_> = opts
+        .fields
+        .iter()
+        .map(|field| {
+            (
+                field.ident.as_ref().unwrap().to_string(),
+                field.rename.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        renames,
+        vec![("a".into(), Some("first".into())), ("b".into(), None)]
+    );
+}
+
+#[test]
+fn fields_report_errors() {
+    let variant: syn::Variant = syn::parse_quote! {
+        Works {
+            #[hello(rename = 1)]
+            a: u8,
+        }
+    };
+
+    VariantOpts::from_variant(&variant).unwrap_err();
+}
@@ -0,0 +1,113 @@
+//! Derived `FromMeta` types can customize how they're built from a bare word or from nothing.
+
+use darling::{FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, FromMeta, PartialEq, Eq)]
+#[darling(from_word = "Cache::enabled", from_none = "Cache::disabled")]
+struct Cache {
+    enabled: bool,
+    #[darling(default)]
+    ttl: Option<u32>,
+}
+
+impl Cache {
+    fn enabled() -> darling::Result<Self> {
+        Ok(Cache {
+            enabled: true,
+            ttl: None,
+        })
+    }
+
+    fn disabled() -> Option<Self> {
+        Some(Cache {
+            enabled: false,
+            ttl: None,
+        })
+    }
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(hooks))]
+struct Receiver {
+    cache: Cache,
+}
+
+#[test]
+fn bare_word_uses_from_word() {
+    let di = parse_quote! {
+        #[hooks(cache)]
+        struct Foo;
+    };
+
+    let parsed = Receiver::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.cache, Cache::enabled().unwrap());
+}
+
+#[test]
+fn list_still_parses_fields() {
+    let di = parse_quote! {
+        #[hooks(cache(enabled = true, ttl = 30))]
+        struct Foo;
+    };
+
+    let parsed = Receiver::from_derive_input(&di).unwrap();
+    assert_eq!(
+        parsed.cache,
+        Cache {
+            enabled: true,
+            ttl: Some(30)
+        }
+    );
+}
+
+#[test]
+fn absent_uses_from_none() {
+    let di = parse_quote! {
+        #[hooks()]
+        struct Foo;
+    };
+
+    let parsed = Receiver::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.cache, Cache::disabled().unwrap());
+}
+
+#[test]
+fn absent_without_from_none_is_missing() {
+    #[derive(Debug, FromMeta)]
+    struct NoHooks {
+        #[allow(dead_code)]
+        enabled: bool,
+    }
+
+    #[derive(Debug, FromDeriveInput)]
+    #[darling(attributes(hooks))]
+    struct NoHooksReceiver {
+        #[allow(dead_code)]
+        cache: NoHooks,
+    }
+
+    let di = parse_quote! {
+        #[hooks()]
+        struct Foo;
+    };
+
+    let err = NoHooksReceiver::from_derive_input(&di).unwrap_err();
+    assert_eq!(err.to_string(), "Missing field `cache`");
+}
+
+#[test]
+fn from_word_on_newtype_points_at_hook() {
+    let input =
+        syn::parse_str("#[darling(from_word = \"Wrapper::new\")]\nstruct Wrapper(bool);").unwrap();
+
+    let error = darling_core::derive::from_meta(&input)
+        .into_iter()
+        .find(|token| match *token {
+            proc_macro2::TokenTree::Ident(ref ident) => ident == "compile_error",
+            _ => false,
+        })
+        .expect("the derive should fail");
+    let start = error.span().start();
+    assert_eq!((start.line, start.column), (1, 22));
+}
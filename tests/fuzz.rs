@@ -0,0 +1,76 @@
+//! Derived parsers never panic on generated input with the `fuzzing` feature.
+#![cfg(feature = "fuzzing")]
+
+use darling::fuzz::{check_parser, MetaGenerator};
+use darling::util::Flag;
+use darling::{FromMeta, MetaSchema};
+
+#[derive(Debug, FromMeta, MetaSchema)]
+#[allow(dead_code)]
+enum Mode {
+    Fast,
+    Careful { retries: Option<String> },
+}
+
+impl Default for Mode {
+    fn default() -> Self {
+        Mode::Fast
+    }
+}
+
+#[derive(Debug, Default, FromMeta, MetaSchema)]
+#[allow(dead_code)]
+struct Inner {
+    path: Option<syn::Path>,
+    #[darling(default)]
+    ratio: f64,
+}
+
+#[derive(Debug, FromMeta, MetaSchema)]
+#[allow(dead_code)]
+struct Settings {
+    name: String,
+    #[darling(default)]
+    enabled: bool,
+    skip: Flag,
+    #[darling(default)]
+    mode: Mode,
+    #[darling(default)]
+    inner: Inner,
+    #[darling(multiple)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn settings_never_panic() {
+    let generator = MetaGenerator::new(7)
+        .with_schema::<Mode>()
+        .with_schema::<Inner>();
+    let summary = check_parser::<Settings>(generator, 2000);
+
+    // The generator should produce a mix of valid and invalid input.
+    assert!(summary.parsed > 0, "{:?}", summary);
+    assert!(summary.rejected > 0, "{:?}", summary);
+}
+
+#[test]
+#[should_panic(expected = "panicked on input")]
+fn reports_panics() {
+    #[derive(Debug, FromMeta, MetaSchema)]
+    #[allow(dead_code)]
+    struct Touchy {
+        #[darling(and_then = "Touchy::explode")]
+        name: String,
+    }
+
+    impl Touchy {
+        fn explode(name: String) -> darling::Result<String> {
+            if name.is_empty() {
+                panic!("empty name");
+            }
+            Ok(name)
+        }
+    }
+
+    check_parser::<Touchy>(MetaGenerator::new(1), 2000);
+}
@@ -0,0 +1,113 @@
+//! Errors from derived impls point at the part of the input they're about, rather than at the
+//! derive.
+
+use darling::ast::Data;
+use darling::error::Result;
+use darling::util::Ignored;
+use darling::{Error, FromDeriveInput, FromMeta, FromVariant};
+
+#[derive(Debug, FromMeta)]
+struct Inner {
+    #[allow(dead_code)]
+    a: u8,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(opts), supports(struct_named))]
+#[allow(dead_code)]
+struct Opts {
+    req: String,
+    inner: Option<Inner>,
+}
+
+#[derive(Debug, FromVariant)]
+#[allow(dead_code)]
+struct Unit;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(supports(enum_unit))]
+#[allow(dead_code)]
+struct Units {
+    data: Data<Unit, Ignored>,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(opts), and_then = "never")]
+#[allow(dead_code)]
+struct Checked {
+    #[darling(default)]
+    req: Option<String>,
+}
+
+fn never(_: Checked) -> Result<Checked> {
+    Err(Error::custom("rejected"))
+}
+
+/// The line and columns of the text each of the errors points at, with its message.
+fn locations(err: Error) -> Vec<(String, usize, usize, usize)> {
+    err.flatten()
+        .into_iter()
+        .map(|err| {
+            let message = err.to_string();
+            let span = syn::Error::from(err).span();
+            (
+                message,
+                span.start().line,
+                span.start().column,
+                span.end().column,
+            )
+        })
+        .collect()
+}
+
+fn parse(source: &str) -> syn::DeriveInput {
+    syn::parse_str(source).unwrap()
+}
+
+#[test]
+fn unknown_field_points_at_item() {
+    let err = Opts::from_derive_input(&parse("#[opts(req = \"x\", bogus = 1)]\nstruct Foo {}"))
+        .unwrap_err();
+    let (message, line, start, end) = locations(err).remove(0);
+    assert!(message.starts_with("Unknown field: `bogus`"));
+    assert_eq!((line, start, end), (1, 18, 27));
+}
+
+#[test]
+fn missing_field_points_at_ident() {
+    let err = Opts::from_derive_input(&parse("#[opts()]\nstruct Foo {}")).unwrap_err();
+    assert_eq!(
+        locations(err),
+        vec![("Missing field `req`".to_string(), 2, 7, 10)]
+    );
+}
+
+#[test]
+fn nested_missing_field_points_at_list() {
+    let err = Opts::from_derive_input(&parse("#[opts(req = \"x\", inner())]\nstruct Foo {}"))
+        .unwrap_err();
+    assert_eq!(
+        locations(err),
+        vec![("Missing field `a` at inner".to_string(), 1, 18, 25)]
+    );
+}
+
+#[test]
+fn unsupported_shape_points_at_ident() {
+    let err = Opts::from_derive_input(&parse("#[opts(req = \"x\")]\nstruct Foo;")).unwrap_err();
+    let (_, line, start, end) = locations(err).remove(0);
+    assert_eq!((line, start, end), (2, 7, 10));
+}
+
+#[test]
+fn unsupported_variant_points_at_variant() {
+    let err = Units::from_derive_input(&parse("enum Foo {\n    A,\n    B(u8),\n}")).unwrap_err();
+    let (_, line, start, end) = locations(err).remove(0);
+    assert_eq!((line, start, end), (3, 4, 5));
+}
+
+#[test]
+fn and_then_error_points_at_ident() {
+    let err = Checked::from_derive_input(&parse("struct Foo;")).unwrap_err();
+    assert_eq!(locations(err), vec![("rejected".to_string(), 1, 7, 10)]);
+}
@@ -0,0 +1,48 @@
+//! `util::Ignored` can stand in for any receiver the caller doesn't care about.
+
+use darling::{ast, util::Ignored, FromAttributes, FromDeriveInput, FromField};
+use syn::parse_quote;
+
+#[derive(Debug, FromField)]
+#[darling(attributes(demo))]
+struct DemoField {
+    ident: Option<syn::Ident>,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(demo))]
+struct Demo {
+    data: ast::Data<Ignored, DemoField>,
+}
+
+#[test]
+fn ignores_variants() {
+    let di = parse_quote! {
+        enum Foo {
+            Bar(u8),
+            Baz { qux: String },
+        }
+    };
+
+    let parsed = Demo::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.data.take_enum().unwrap(), vec![Ignored, Ignored]);
+}
+
+#[test]
+fn reads_fields() {
+    let di = parse_quote! {
+        struct Foo {
+            bar: u8,
+        }
+    };
+
+    let parsed = Demo::from_derive_input(&di).unwrap();
+    let fields = parsed.data.take_struct().unwrap();
+    assert_eq!(fields.fields[0].ident, Some(parse_quote!(bar)));
+}
+
+#[test]
+fn ignores_attributes() {
+    let attrs: Vec<syn::Attribute> = vec![parse_quote!(#[demo(anything = "at all")])];
+    assert_eq!(Ignored::from_attributes(&attrs).unwrap(), Ignored);
+}
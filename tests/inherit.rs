@@ -0,0 +1,109 @@
+//! Field and variant receivers can take settings from their parsed container.
+
+use darling::ast::{self, Inherit};
+use darling::{FromDeriveInput, FromField, FromVariant};
+use syn::parse_quote;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, darling::FromMeta)]
+struct Settings {
+    #[darling(default)]
+    prefix: Option<String>,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(inherit), map = "Self::propagate")]
+struct Container {
+    #[darling(default)]
+    settings: Settings,
+    data: ast::Data<Variant, Field>,
+}
+
+impl Container {
+    fn propagate(mut self) -> Self {
+        let settings = self.settings.clone();
+        self.data.inherit(&settings);
+        self
+    }
+}
+
+#[derive(Debug, FromField)]
+#[darling(attributes(inherit))]
+struct Field {
+    ident: Option<syn::Ident>,
+    #[darling(default)]
+    prefix: Option<String>,
+}
+
+impl Inherit<Settings> for Field {
+    fn inherit(&mut self, parent: &Settings) {
+        if self.prefix.is_none() {
+            self.prefix = parent.prefix.clone();
+        }
+    }
+}
+
+#[derive(Debug, FromVariant)]
+#[darling(attributes(inherit))]
+struct Variant {
+    #[darling(default)]
+    prefix: Option<String>,
+}
+
+impl Inherit<Settings> for Variant {
+    fn inherit(&mut self, parent: &Settings) {
+        if self.prefix.is_none() {
+            self.prefix = parent.prefix.clone();
+        }
+    }
+}
+
+#[test]
+fn fields_inherit_container_settings() {
+    let di = parse_quote! {
+        #[inherit(settings(prefix = "get_"))]
+        struct Foo {
+            bar: u8,
+            #[inherit(prefix = "is_")]
+            baz: bool,
+        }
+    };
+
+    let parsed = Container::from_derive_input(&di).unwrap();
+    let fields = parsed.data.take_struct().unwrap();
+    let prefixes: Vec<_> = fields
+        .iter()
+        .map(|f| (f.ident.as_ref().unwrap().to_string(), f.prefix.clone()))
+        .collect();
+    assert_eq!(
+        prefixes,
+        vec![
+            ("bar".to_string(), Some("get_".to_string())),
+            ("baz".to_string(), Some("is_".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn variants_inherit_container_settings() {
+    let di = parse_quote! {
+        #[inherit(settings(prefix = "new_"))]
+        enum Foo {
+            Bar,
+            #[inherit(prefix = "make_")]
+            Baz,
+        }
+    };
+
+    let parsed = Container::from_derive_input(&di).unwrap();
+    let prefixes: Vec<_> = parsed
+        .data
+        .take_enum()
+        .unwrap()
+        .into_iter()
+        .map(|v| v.prefix)
+        .collect();
+    assert_eq!(
+        prefixes,
+        vec![Some("new_".to_string()), Some("make_".to_string())]
+    );
+}
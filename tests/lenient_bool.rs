@@ -0,0 +1,29 @@
+//! `util::lenient_bool` reads the spellings of booleans common in config files.
+
+use darling::FromMeta;
+use syn::parse_quote;
+
+#[derive(Debug, FromMeta)]
+struct Feature {
+    #[darling(with = "darling::util::lenient_bool")]
+    enabled: bool,
+    #[darling(with = "darling::util::lenient_bool", default)]
+    verbose: Option<bool>,
+}
+
+#[test]
+fn reads_config_spellings() {
+    let feature =
+        Feature::from_list(&[parse_quote!(enabled = "on"), parse_quote!(verbose = "no")]).unwrap();
+    assert!(feature.enabled);
+    assert_eq!(feature.verbose, Some(false));
+
+    let feature = Feature::from_list(&[parse_quote!(enabled)]).unwrap();
+    assert!(feature.enabled);
+    assert_eq!(feature.verbose, None);
+}
+
+#[test]
+fn plain_bools_stay_strict() {
+    assert!(bool::from_meta(&parse_quote!(enabled = "yes")).is_err());
+}
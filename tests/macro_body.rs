@@ -0,0 +1,52 @@
+//! The body of a function-like macro can be parsed straight from its tokens.
+
+use darling::FromMeta;
+use quote::quote;
+
+#[derive(Debug, Default, FromMeta, PartialEq, Eq)]
+#[darling(default)]
+struct Retry {
+    max: u8,
+}
+
+#[derive(Debug, FromMeta, PartialEq, Eq)]
+struct MacroArgs {
+    key: String,
+    #[darling(default)]
+    retry: Retry,
+}
+
+#[test]
+fn parse_body() {
+    let args = MacroArgs::from_macro_body(quote! {
+        key = "value",
+        retry(max = 3),
+    })
+    .unwrap();
+    assert_eq!(
+        args,
+        MacroArgs {
+            key: "value".to_string(),
+            retry: Retry { max: 3 },
+        }
+    );
+}
+
+#[test]
+fn same_as_attribute_args() {
+    let tokens = quote!(key = "value", retry(max = 1));
+    assert_eq!(
+        MacroArgs::from_macro_body(tokens.clone()).unwrap(),
+        MacroArgs::from_attribute_args(tokens).unwrap()
+    );
+}
+
+#[test]
+fn malformed_body() {
+    let err = MacroArgs::from_macro_body(quote!(key = "value" retry)).unwrap_err();
+    assert!(
+        err.to_string().starts_with("Unable to parse macro input"),
+        "{}",
+        err
+    );
+}
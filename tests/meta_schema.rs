@@ -0,0 +1,128 @@
+//! Describing accepted options with `#[derive(MetaSchema)]`.
+
+use darling::meta_schema::{FieldSchema, SchemaBody, VariantSchema};
+use darling::{FromMeta, MetaSchema};
+
+fn three() -> u8 {
+    3
+}
+
+#[derive(FromMeta, MetaSchema)]
+#[darling(default)]
+#[allow(dead_code)]
+struct Defaulted {
+    name: String,
+}
+
+impl Default for Defaulted {
+    fn default() -> Self {
+        Defaulted {
+            name: "x".to_string(),
+        }
+    }
+}
+
+#[derive(FromMeta, MetaSchema)]
+#[allow(dead_code)]
+struct Settings {
+    #[darling(rename = "label")]
+    name: String,
+    #[darling(default = "three")]
+    retries: u8,
+    fast: darling::util::Flag,
+    #[darling(multiple)]
+    tag: Vec<String>,
+    #[darling(deprecated = "use `label`")]
+    title: Option<String>,
+    #[darling(skip)]
+    internal: bool,
+}
+
+#[derive(FromMeta, MetaSchema)]
+#[allow(dead_code)]
+enum Mode {
+    Fast,
+    Custom(u8),
+    Tuned { level: u8 },
+}
+
+#[derive(FromMeta, MetaSchema)]
+#[allow(dead_code)]
+struct Wrapper<T>(T);
+
+#[test]
+fn describes_fields() {
+    let schema = Settings::meta_schema();
+    assert_eq!(schema.ident, "Settings");
+    assert!(!schema.allow_unknown_fields);
+
+    assert_eq!(
+        schema.field("label"),
+        Some(&FieldSchema {
+            name: "label".into(),
+            aliases: &[],
+            ty: "String",
+            required: true,
+            default: None,
+            multiple: false,
+            deprecated: None,
+        })
+    );
+    assert_eq!(schema.field("retries").unwrap().default, Some("three()"));
+    // `Flag` fills in absent values, so it isn't required.
+    assert!(!schema.field("fast").unwrap().required);
+    assert!(schema.field("tag").unwrap().multiple);
+    assert_eq!(
+        schema.field("title").unwrap().deprecated,
+        Some("use `label`")
+    );
+    assert!(schema.field("internal").is_none());
+}
+
+#[test]
+fn describes_container_default() {
+    let schema = Defaulted::meta_schema();
+    let field = schema.field("name").unwrap();
+    assert!(!field.required);
+    assert_eq!(field.default, Some("Default::default().name"));
+}
+
+#[test]
+fn describes_variants() {
+    let schema = Mode::meta_schema();
+    assert_eq!(
+        schema.variant("fast"),
+        Some(&VariantSchema {
+            name: "fast".into(),
+            body: SchemaBody::Unit,
+            deprecated: None,
+        })
+    );
+    assert_eq!(
+        schema.variant("custom").unwrap().body,
+        SchemaBody::Newtype("u8")
+    );
+    assert!(schema
+        .variant("tuned")
+        .unwrap()
+        .body
+        .field("level")
+        .is_some());
+}
+
+#[test]
+fn describes_generic_newtypes() {
+    assert_eq!(Wrapper::<u8>::meta_schema().body, SchemaBody::Newtype("T"));
+}
+
+#[test]
+fn exports_json() {
+    assert_eq!(
+        Defaulted::meta_schema().to_json(),
+        concat!(
+            r#"{"ident":"Defaulted","allow_unknown_fields":false,"body":{"kind":"fields","fields":["#,
+            r#"{"name":"name","aliases":[],"type":"String","required":false,"default":"Default::default().name","#,
+            r#""multiple":false,"deprecated":null}]}}"#
+        )
+    );
+}
@@ -0,0 +1,82 @@
+//! `#[darling(name_value)]` lets a field receive the value of `#[my_attr = value]`.
+
+use darling::{FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(deprecated))]
+struct Deprecation {
+    #[darling(name_value)]
+    note: Option<String>,
+    since: Option<String>,
+}
+
+#[test]
+fn reads_name_value_form() {
+    let di = parse_quote! {
+        #[deprecated = "use `Other`"]
+        struct Example;
+    };
+
+    let parsed = Deprecation::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.note, Some("use `Other`".to_string()));
+    assert_eq!(parsed.since, None);
+}
+
+#[test]
+fn list_form_still_works() {
+    let di = parse_quote! {
+        #[deprecated(since = "1.0", note = "gone")]
+        struct Example;
+    };
+
+    let parsed = Deprecation::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.note, Some("gone".to_string()));
+    assert_eq!(parsed.since, Some("1.0".to_string()));
+}
+
+#[allow(dead_code)]
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(deprecated))]
+struct NoNameValue {
+    note: Option<String>,
+}
+
+#[test]
+fn name_value_form_is_an_error_without_field() {
+    let di = parse_quote! {
+        #[deprecated = "use `Other`"]
+        struct Example;
+    };
+
+    let err = NoNameValue::from_derive_input(&di).unwrap_err();
+    assert!(err.to_string().contains("#[deprecated(...)]"));
+}
+
+#[derive(Debug, PartialEq, FromMeta)]
+struct Limit {
+    #[darling(name_value)]
+    max: u32,
+    #[darling(default)]
+    strict: bool,
+}
+
+#[derive(Debug, FromMeta)]
+struct Options {
+    limit: Limit,
+}
+
+#[test]
+fn nested_value_form() {
+    let short = Options::from_list(&[parse_quote!(limit = 10)]).unwrap();
+    assert_eq!(
+        short.limit,
+        Limit {
+            max: 10,
+            strict: false
+        }
+    );
+
+    let long = Options::from_list(&[parse_quote!(limit(max = 10, strict))]).unwrap();
+    assert!(long.limit.strict);
+}
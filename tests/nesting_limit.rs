@@ -0,0 +1,97 @@
+//! Recursive `FromMeta` types should fail cleanly on deeply nested input.
+
+use darling::{FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, Default, FromMeta)]
+#[darling(default, max_depth = 3)]
+struct Node {
+    label: Option<String>,
+    child: Option<Box<Node>>,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(tree))]
+struct Tree {
+    root: Node,
+}
+
+#[test]
+fn nesting_within_limit() {
+    let di = parse_quote! {
+        #[tree(root(child(child(label = "leaf"))))]
+        pub struct Foo;
+    };
+
+    let tree = Tree::from_derive_input(&di).unwrap();
+    let leaf = tree.root.child.unwrap().child.unwrap();
+    assert_eq!(leaf.label, Some("leaf".to_string()));
+}
+
+#[test]
+fn nesting_beyond_limit_reports_path() {
+    let di = parse_quote! {
+        #[tree(root(child(child(child(label = "too deep")))))]
+        pub struct Foo;
+    };
+
+    let err = Tree::from_derive_input(&di).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Nesting limit exceeded: Expected no more than 3 levels at root/child/child/child"
+    );
+}
+
+#[derive(Debug, Default, FromMeta)]
+#[darling(default, max_depth = 2)]
+struct Leaf {
+    label: Option<String>,
+    child: Option<Box<Leaf>>,
+}
+
+#[derive(Debug, Default, FromMeta)]
+#[darling(default, max_depth = 8)]
+struct Branch {
+    child: Option<Box<Branch>>,
+    leaf: Option<Leaf>,
+}
+
+#[test]
+fn inner_limit_counts_from_its_own_list() {
+    let meta: syn::Meta = parse_quote!(branch(child(child(child(leaf(child(label = "x")))))));
+    let branch = Branch::from_meta(&meta).unwrap();
+    let leaf = branch
+        .child
+        .unwrap()
+        .child
+        .unwrap()
+        .child
+        .unwrap()
+        .leaf
+        .unwrap();
+    assert_eq!(leaf.child.unwrap().label, Some("x".to_string()));
+
+    let meta: syn::Meta = parse_quote!(branch(child(leaf(child(child(label = "x"))))));
+    let err = Branch::from_meta(&meta).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Nesting limit exceeded: Expected no more than 2 levels at child/leaf/child/child"
+    );
+}
+
+#[test]
+fn default_limit_stops_unbounded_recursion() {
+    #[derive(Debug, Default, FromMeta)]
+    #[darling(default)]
+    struct Unbounded {
+        child: Option<Box<Unbounded>>,
+    }
+
+    let mut tokens = quote::quote!(child);
+    for _ in 0..darling::util::DEFAULT_MAX_DEPTH {
+        tokens = quote::quote!(child(#tokens));
+    }
+
+    let meta: syn::Meta = syn::parse2(quote::quote!(outer(#tokens))).unwrap();
+    Unbounded::from_meta(&meta).unwrap_err();
+}
@@ -0,0 +1,102 @@
+//! `#[darling(prefix = "...")]` reads a nested struct from keys which share a prefix.
+
+use darling::{FromDeriveInput, FromMeta, ToMeta};
+use syn::parse_quote;
+
+#[derive(Debug, Default, PartialEq, FromMeta, ToMeta)]
+#[darling(default)]
+struct Retry {
+    max: u8,
+    delay: Option<u32>,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(client))]
+struct Client {
+    #[darling(prefix = "retry_")]
+    retry: Retry,
+    retry_budget: Option<u8>,
+    #[darling(prefix = "cache_", default)]
+    cache: Option<Cache>,
+}
+
+#[derive(Debug, PartialEq, FromMeta)]
+struct Cache {
+    size: u32,
+}
+
+#[test]
+fn reads_prefixed_keys() {
+    let di = parse_quote! {
+        #[client(retry_max = 3, retry_budget = 10, retry_delay = 250)]
+        struct Example;
+    };
+
+    let client = Client::from_derive_input(&di).unwrap();
+    assert_eq!(
+        client.retry,
+        Retry {
+            max: 3,
+            delay: Some(250)
+        }
+    );
+    assert_eq!(client.retry_budget, Some(10));
+    assert_eq!(client.cache, None);
+}
+
+#[test]
+fn absent_group_uses_nested_defaults() {
+    let di = parse_quote! {
+        #[client(cache_size = 8)]
+        struct Example;
+    };
+
+    let client = Client::from_derive_input(&di).unwrap();
+    assert_eq!(client.retry, Retry::default());
+    assert_eq!(client.cache, Some(Cache { size: 8 }));
+}
+
+#[test]
+fn unknown_prefixed_key() {
+    let di = parse_quote! {
+        #[client(retry_maxx = 3)]
+        struct Example;
+    };
+
+    let err = Client::from_derive_input(&di).unwrap_err();
+    // Errors from the nested type name its keys without the prefix, and are located at the field.
+    assert!(err.to_string().starts_with("Unknown field: `maxx`"));
+    assert!(err.to_string().ends_with(" at retry"));
+}
+
+#[test]
+fn group_name_is_not_a_key() {
+    let di = parse_quote! {
+        #[client(retry(max = 3))]
+        struct Example;
+    };
+
+    let err = Client::from_derive_input(&di).unwrap_err();
+    assert!(err.to_string().starts_with("Unknown field: `retry`"));
+}
+
+#[derive(Debug, ToMeta)]
+struct Settings {
+    #[darling(prefix = "retry_")]
+    retry: Retry,
+}
+
+#[test]
+fn writes_prefixed_keys() {
+    let settings = Settings {
+        retry: Retry {
+            max: 2,
+            delay: None,
+        },
+    };
+
+    assert_eq!(
+        settings.to_meta_string("settings"),
+        "settings(retry_max = 2)"
+    );
+}
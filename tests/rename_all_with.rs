@@ -0,0 +1,175 @@
+//! `#[darling(rename_all_with = "...")]` computes field names with a custom function.
+
+use darling::util::RenameRule;
+use darling::{FromDeriveInput, FromMeta, MetaSchema, ToMeta};
+use syn::parse_quote;
+
+/// Writes field names in Ada case, such as `Max_Retries`.
+fn ada_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("_")
+}
+
+#[derive(Debug, PartialEq, FromMeta, ToMeta, MetaSchema)]
+#[darling(rename_all_with = "ada_case")]
+struct Retry {
+    max_retries: u8,
+    #[darling(default)]
+    base_delay: Option<u32>,
+    #[darling(rename = "jitter")]
+    with_jitter: Option<bool>,
+}
+
+#[test]
+fn reads_renamed_fields() {
+    let retry = Retry::from_list(&[
+        parse_quote!(Max_Retries = 3),
+        parse_quote!(Base_Delay = 100),
+        parse_quote!(jitter = true),
+    ])
+    .unwrap();
+    assert_eq!(
+        retry,
+        Retry {
+            max_retries: 3,
+            base_delay: Some(100),
+            with_jitter: Some(true),
+        }
+    );
+}
+
+#[test]
+fn original_names_are_unknown() {
+    let err = Retry::from_list(&[parse_quote!(max_retries = 3)]).unwrap_err();
+    let errors: Vec<String> = err.flatten().into_iter().map(|e| e.to_string()).collect();
+    assert!(
+        errors[0].starts_with("Unknown field: `max_retries`"),
+        "{}",
+        errors[0]
+    );
+    assert_eq!(errors[1], "Missing field `Max_Retries`");
+}
+
+#[test]
+fn writes_renamed_fields() {
+    let retry = Retry {
+        max_retries: 2,
+        base_delay: None,
+        with_jitter: None,
+    };
+    assert_eq!(retry.to_meta_string("retry"), "retry(Max_Retries = 2)");
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(client), rename_all_with = "ada_case")]
+struct Client {
+    ident: syn::Ident,
+    request_timeout: Option<u32>,
+}
+
+#[test]
+fn derive_input() {
+    let client = Client::from_derive_input(&parse_quote! {
+        #[client(Request_Timeout = 5)]
+        struct Example;
+    })
+    .unwrap();
+    assert_eq!(client.ident, "Example");
+    assert_eq!(client.request_timeout, Some(5));
+}
+
+#[test]
+fn public_rename_rule() {
+    assert_eq!(
+        RenameRule::ScreamingSnakeCase.apply_to_field("max_retries"),
+        "MAX_RETRIES"
+    );
+    assert_eq!(
+        RenameRule::KebabCase.apply_to_variant("BaseDelay"),
+        "base-delay"
+    );
+}
+
+#[cfg(feature = "suggestions")]
+#[test]
+fn suggests_renamed_fields() {
+    let err = Retry::from_list(&[parse_quote!(Max_Retrie = 3)]).unwrap_err();
+    assert!(
+        err.to_string().contains("Did you mean `Max_Retries`?"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn schema_lists_renamed_fields() {
+    let schema = Retry::meta_schema();
+    assert!(schema.field("Max_Retries").unwrap().required);
+    assert!(schema.field("max_retries").is_none());
+    assert!(schema.field("jitter").is_some());
+}
+
+#[test]
+fn docs_name_the_function() {
+    assert!(Retry::ATTRIBUTE_DOCS.starts_with("* `max_retries`: `u8`\n"));
+    assert!(Retry::ATTRIBUTE_DOCS
+        .ends_with("\nThe names above are passed through `ada_case` before they are matched.\n"));
+}
+
+/// Writes variant names in upper case.
+fn upper(name: &str) -> String {
+    name.to_uppercase()
+}
+
+#[derive(Debug, PartialEq, FromMeta, ToMeta, MetaSchema)]
+#[darling(rename_all_with = "upper")]
+enum Mode {
+    Fast,
+    Tuned(u8),
+    #[darling(rename = "custom")]
+    Custom,
+}
+
+#[test]
+fn enum_variants_are_renamed() {
+    assert_eq!(Mode::from_string("FAST").unwrap(), Mode::Fast);
+    assert_eq!(Mode::from_string("custom").unwrap(), Mode::Custom);
+    assert_eq!(
+        Mode::from_list(&[parse_quote!(TUNED = 3)]).unwrap(),
+        Mode::Tuned(3)
+    );
+    // The function receives the variant ident, not the snake case enums default to.
+    assert!(Mode::from_string("fast").is_err());
+    assert_eq!(Mode::Fast.to_meta_string("mode"), "mode = \"FAST\"");
+    assert!(Mode::meta_schema().variant("TUNED").is_some());
+}
+
+#[test]
+fn unknown_variant_lists_renamed_names() {
+    let err = Mode::from_string("SLOW").unwrap_err();
+    assert!(err.to_string().contains("`FAST`"), "{}", err);
+}
+
+#[test]
+fn conflicts_with_rename_all() {
+    let expanded = darling::expand_str(
+        r#"
+        #[derive(FromMeta)]
+        #[darling(rename_all = "snake_case", rename_all_with = "upper")]
+        enum Mode {
+            Fast,
+        }
+        "#,
+    )
+    .unwrap();
+
+    assert!(expanded.contains("Options `rename_all` and `rename_all_with` are mutually exclusive"));
+}
@@ -0,0 +1,87 @@
+//! Names registered with `renamed` produce an error that points callers to the new name.
+
+use darling::{error::ErrorKind, FromDeriveInput};
+use syn::parse_quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(
+    attributes(demo),
+    renamed(old = "with_fn", new = "with", since = "0.2"),
+    renamed(old = "skip_it", new = "skip")
+)]
+struct Demo {
+    #[darling(default)]
+    with: Option<String>,
+    #[darling(default)]
+    skip: bool,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(
+    attributes(demo),
+    allow_unknown_fields,
+    renamed(old = "with_fn", new = "with")
+)]
+struct Lenient {
+    #[allow(dead_code)]
+    #[darling(default)]
+    with: Option<String>,
+}
+
+#[test]
+fn renamed_with_version() {
+    let di = parse_quote! {
+        #[demo(with_fn = "path")]
+        pub struct Foo;
+    };
+
+    let err = Demo::from_derive_input(&di).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Field `with_fn` was renamed to `with` in version 0.2"
+    );
+
+    match *err.kind() {
+        ErrorKind::RenamedField(ref renamed) => {
+            assert_eq!(renamed.old(), "with_fn");
+            assert_eq!(renamed.new_name(), "with");
+            assert_eq!(renamed.since(), Some("0.2"));
+        }
+        ref other => panic!("Unexpected error kind: {:?}", other),
+    }
+}
+
+#[test]
+fn renamed_without_version() {
+    let di = parse_quote! {
+        #[demo(skip_it)]
+        pub struct Foo;
+    };
+
+    let err = Demo::from_derive_input(&di).unwrap_err();
+    assert_eq!(err.to_string(), "Field `skip_it` was renamed to `skip`");
+}
+
+#[test]
+fn renamed_reported_when_unknown_fields_allowed() {
+    let di = parse_quote! {
+        #[demo(with_fn = "path", other)]
+        pub struct Foo;
+    };
+
+    let err = Lenient::from_derive_input(&di).unwrap_err();
+    assert_eq!(err.len(), 1);
+    assert!(err.to_string().starts_with("Field `with_fn` was renamed"));
+}
+
+#[test]
+fn new_names_still_parse() {
+    let di = parse_quote! {
+        #[demo(with = "path", skip)]
+        pub struct Foo;
+    };
+
+    let parsed = Demo::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.with, Some("path".to_string()));
+    assert!(parsed.skip);
+}
@@ -0,0 +1,86 @@
+//! Recording where parsed options came from with `darling::report`.
+
+use darling::report::{record, ReportEntry};
+use darling::{FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, FromMeta)]
+#[allow(dead_code)]
+#[darling(allow_unknown_fields, report)]
+struct Inner {
+    name: String,
+    #[darling(default)]
+    level: u8,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[allow(dead_code)]
+#[darling(attributes(opts), report)]
+struct Opts {
+    inner: Inner,
+    #[darling(default)]
+    retries: u8,
+    #[darling(multiple)]
+    tag: Vec<String>,
+    #[darling(default)]
+    fast: darling::util::Flag,
+}
+
+#[test]
+fn records_sources_defaults_and_ignored_keys() {
+    let di = parse_quote! {
+        #[opts(inner(name = "x", extra = 1), tag = "a")]
+        #[opts(tag = "b")]
+        struct Foo;
+    };
+
+    let (opts, report) = record(|| Opts::from_derive_input(&di));
+    let opts = opts.unwrap();
+    assert_eq!(opts.tag, vec!["a", "b"]);
+
+    assert!(report.supplied("inner").is_some());
+    assert!(report.supplied("inner.name").is_some());
+    assert!(report.defaulted("inner.level"));
+    assert!(report.defaulted("retries"));
+    assert!(report.defaulted("fast"));
+    assert!(!report.defaulted("inner.name"));
+    assert_eq!(report.ignored().collect::<Vec<_>>(), vec!["inner.extra"]);
+
+    let tags = report
+        .iter()
+        .filter(|entry| match **entry {
+            ReportEntry::Supplied { ref path, .. } => path == "tag",
+            _ => false,
+        })
+        .count();
+    assert_eq!(tags, 2);
+    assert_eq!(report.attributes("opts").count(), 2);
+}
+
+#[derive(Debug, FromMeta)]
+#[allow(dead_code)]
+struct Unreported {
+    name: String,
+    #[darling(default)]
+    level: u8,
+}
+
+#[test]
+fn containers_without_report_record_nothing() {
+    let item = parse_quote!(unreported(name = "x"));
+    let (value, report) = record(|| Unreported::from_meta(&item));
+    value.unwrap();
+    assert!(report.is_empty());
+}
+
+#[test]
+fn nothing_is_recorded_without_record() {
+    let di = parse_quote! {
+        #[opts(inner(name = "x"))]
+        struct Foo;
+    };
+    Opts::from_derive_input(&di).unwrap();
+
+    let ((), report) = record(|| {});
+    assert!(report.is_empty());
+}
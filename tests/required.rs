@@ -0,0 +1,98 @@
+//! `#[darling(required(...))]` customizes the error for an absent field.
+
+use darling::error::ErrorKind;
+use darling::{FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(export), default)]
+struct Export {
+    #[darling(required(message = "An export needs a format", help = "add `format = \"json\"`"))]
+    format: String,
+    #[darling(required(help = "add `path = \"out\"`"))]
+    path: Option<String>,
+    #[darling(required)]
+    level: Option<u8>,
+    name: String,
+}
+
+impl Default for Export {
+    fn default() -> Self {
+        Export {
+            format: "csv".into(),
+            path: None,
+            level: None,
+            name: "export".into(),
+        }
+    }
+}
+
+#[test]
+fn custom_messages() {
+    let di = parse_quote! {
+        #[export(level = 1)]
+        struct Example;
+    };
+
+    let err = Export::from_derive_input(&di).unwrap_err();
+    let errors: Vec<String> = err.flatten().into_iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        errors,
+        vec![
+            "Missing field `format`\nnote: An export needs a format\nhelp: add `format = \"json\"`",
+            "Missing field `path`\nhelp: add `path = \"out\"`",
+        ]
+    );
+}
+
+#[test]
+fn kind_is_missing_field() {
+    let di = parse_quote! {
+        #[export(format = "json", level = 1)]
+        struct Example;
+    };
+
+    let err = Export::from_derive_input(&di).unwrap_err();
+    match *err.kind() {
+        ErrorKind::MissingField(ref name) => assert_eq!(name, "path"),
+        ref other => panic!("unexpected error kind {:?}", other),
+    }
+}
+
+#[test]
+fn bare_word_makes_option_required() {
+    let di = parse_quote! {
+        #[export(format = "json", path = "out")]
+        struct Example;
+    };
+
+    let err = Export::from_derive_input(&di).unwrap_err();
+    assert_eq!(err.to_string(), "Missing field `level`");
+}
+
+#[test]
+fn present_fields_parse() {
+    let di = parse_quote! {
+        #[export(format = "json", path = "out", level = 2)]
+        struct Example;
+    };
+
+    let export = Export::from_derive_input(&di).unwrap();
+    assert_eq!(export.format, "json");
+    assert_eq!(export.path.as_ref().map(String::as_str), Some("out"));
+    assert_eq!(export.level, Some(2));
+    assert_eq!(export.name, "export");
+}
+
+#[derive(Debug, FromMeta)]
+struct Plain {
+    #[darling(required(message = "Set a name"))]
+    #[allow(dead_code)]
+    name: String,
+}
+
+#[test]
+fn from_meta() {
+    let err = Plain::from_list(&[]).unwrap_err();
+    assert_eq!(err.to_string(), "Missing field `name`\nnote: Set a name");
+}
@@ -44,3 +44,84 @@ fn verify_skipped_field_not_required() {
         }
     );
 }
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(skip_test), forward_warnings)]
+#[allow(dead_code)]
+struct Policies {
+    warnings: Vec<darling::error::Warning>,
+    ipsum: String,
+    #[darling(skip)]
+    computed: u8,
+    #[darling(skip, skipped_input = "warn")]
+    warned: u8,
+    #[darling(skip, skipped_input = "ignore")]
+    ignored: u8,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(skip_test), allow_unknown_fields, forward_warnings)]
+#[allow(dead_code)]
+struct Lenient {
+    warnings: Vec<darling::error::Warning>,
+    #[darling(skip)]
+    computed: u8,
+}
+
+#[test]
+fn skipped_field_in_input_is_explained() {
+    let di = parse_quote! {
+        #[skip_test(ipsum = "Hello", computed = 3)]
+        struct Baz;
+    };
+
+    let err = Policies::from_derive_input(&di).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`computed` is ignored because it is computed internally"
+    );
+}
+
+#[test]
+fn skipped_input_policies() {
+    let di = parse_quote! {
+        #[skip_test(ipsum = "Hello", warned = 3, ignored = 4)]
+        struct Baz;
+    };
+
+    let parsed = Policies::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.warned, 0);
+    assert_eq!(parsed.ignored, 0);
+
+    assert_eq!(parsed.warnings.len(), 1);
+    assert_eq!(
+        parsed.warnings[0].message(),
+        "`warned` is ignored because it is computed internally"
+    );
+}
+
+#[test]
+fn allow_unknown_fields_warns() {
+    let di = parse_quote! {
+        #[skip_test(computed = 3)]
+        struct Baz;
+    };
+
+    let parsed = Lenient::from_derive_input(&di).unwrap();
+    assert_eq!(parsed.warnings.len(), 1);
+}
+
+#[cfg(feature = "suggestions")]
+#[test]
+fn skipped_fields_are_not_suggested() {
+    let di = parse_quote! {
+        #[skip_test(ipsum = "Hello", computd = 3)]
+        struct Baz;
+    };
+
+    let err = Policies::from_derive_input(&di).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unknown field: `computd`. Expected one of `ipsum`"
+    );
+}
@@ -0,0 +1,89 @@
+//! `#[darling(span = "...")]` chooses whether the code generated to parse each field carries
+//! the span of the field type or the call-site span.
+
+use darling::{FromDeriveInput, FromMeta};
+use proc_macro2::{TokenStream, TokenTree};
+use syn::parse_quote;
+
+#[derive(Debug, Default, FromMeta)]
+#[darling(span = "call_site", default)]
+struct Limits {
+    max: Option<u16>,
+    strict: bool,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(opts), span = "input")]
+struct Opts {
+    limits: Limits,
+}
+
+#[test]
+fn parses_with_either_strategy() {
+    let di = parse_quote! {
+        #[opts(limits(max = 5, strict))]
+        struct Foo;
+    };
+
+    let opts = Opts::from_derive_input(&di).unwrap();
+    assert_eq!(opts.limits.max, Some(5));
+    assert!(opts.limits.strict);
+}
+
+/// The line and column of each ident named `name` in `tokens`.
+fn locations_of(tokens: TokenStream, name: &str) -> Vec<(usize, usize)> {
+    let mut locations = Vec::new();
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ref ident) if ident == name => {
+                let start = ident.span().start();
+                locations.push((start.line, start.column));
+            }
+            TokenTree::Group(group) => locations.extend(locations_of(group.stream(), name)),
+            _ => {}
+        }
+    }
+    locations
+}
+
+/// The locations of the calls which parse the fields of the `FromMeta` impl for `source`.
+fn parse_calls(source: &str) -> Vec<(usize, usize)> {
+    let input: syn::DeriveInput = syn::parse_str(source).unwrap();
+    let mut locations = locations_of(
+        darling_core::derive::from_meta(&input),
+        "from_meta_with_warnings",
+    );
+    // The first is the definition of the method in the generated impl.
+    locations.remove(0);
+    locations
+}
+
+#[test]
+fn input_spans_point_at_field_types() {
+    let calls = parse_calls(
+        "#[darling(span = \"input\")]
+struct Limits {
+    max: u16,
+    strict: bool,
+}",
+    );
+    assert_eq!(calls, vec![(3, 9), (4, 12)]);
+}
+
+#[test]
+fn input_is_the_default() {
+    assert_eq!(
+        parse_calls("struct Limits {\n    max: u16,\n}"),
+        vec![(2, 9)]
+    );
+}
+
+#[test]
+fn call_site_spans_point_at_the_derive() {
+    let input = "#[darling(span = \"call_site\")]
+struct Limits {
+    max: u16,
+}";
+    let call_site = proc_macro2::Span::call_site().start();
+    assert_eq!(parse_calls(input), vec![(call_site.line, call_site.column)]);
+}
@@ -0,0 +1,33 @@
+//! Float fields opt into infinite and not-a-number values with `util::special_float`.
+
+use darling::FromMeta;
+use syn::parse_quote;
+
+#[derive(Debug, FromMeta)]
+struct Bounds {
+    #[darling(with = "darling::util::special_float")]
+    min: f64,
+    #[darling(with = "darling::util::special_float", default)]
+    max: Option<f64>,
+    step: f64,
+}
+
+#[test]
+fn reads_special_values() {
+    let bounds = Bounds::from_list(&[
+        parse_quote!(min = "-inf"),
+        parse_quote!(max(inf)),
+        parse_quote!(step = 0.5),
+    ])
+    .unwrap();
+    assert_eq!(bounds.min, std::f64::NEG_INFINITY);
+    assert_eq!(bounds.max, Some(std::f64::INFINITY));
+    assert!((bounds.step - 0.5).abs() < std::f64::EPSILON);
+}
+
+#[test]
+fn plain_float_fields_stay_finite() {
+    let err =
+        Bounds::from_list(&[parse_quote!(min = 0.0), parse_quote!(step = "nan")]).unwrap_err();
+    assert!(err.to_string().contains("`nan` is not a finite number"));
+}
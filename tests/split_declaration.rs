@@ -42,6 +42,23 @@ fn duplicates_across_split_attrs_error() {
     assert_eq!(pr.to_string(), Error::duplicate_field("foo").to_string());
 }
 
+#[test]
+fn duplicates_point_back_at_first_occurrence() {
+    let di = parse_quote! {
+        #[split(foo = "Hello", bar)]
+        #[split(foo = "World")]
+        pub struct Foo;
+    };
+
+    let err = Lorem::from_derive_input(&di).unwrap_err();
+    assert!(err.first_span().is_some());
+
+    // The first occurrence is only a note, so the duplicate is still reported once.
+    let tokens = err.write_errors().to_string();
+    assert!(tokens.contains("Duplicate field `foo`"));
+    assert_eq!(tokens.matches("compile_error").count(), 1);
+}
+
 #[test]
 fn multiple_errors_accrue_to_instance() {
     let di = parse_quote! {
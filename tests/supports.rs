@@ -88,3 +88,26 @@ fn struct_named() {
     StructContainer::from_derive_input(&source::newtype_enum()).unwrap_err();
     StructContainer::from_derive_input(&source::empty_enum()).unwrap_err();
 }
+
+#[test]
+fn unsupported_shape_names_expected_shapes() {
+    let err = StructContainer::from_derive_input(&source::tuple_struct()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unsupported shape `struct_tuple`. Expected struct_named."
+    );
+
+    let err = StructContainer::from_derive_input(&source::newtype_enum()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unsupported shape `enum`. Expected struct_named."
+    );
+}
+
+#[test]
+fn unsupported_variant_names_expected_shapes() {
+    let err = Container::from_derive_input(&source::named_field_enum()).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Unsupported shape `named`. Expected newtype or unit."));
+}
@@ -0,0 +1,43 @@
+//! `darling::Error` converts to and from `syn::Error`, so code built on `syn` parsing can use `?`.
+
+use darling::FromMeta;
+use syn::parse_quote;
+
+/// Reads a string holding a comma-separated list of types.
+fn type_list(meta: &syn::Meta) -> darling::Result<Vec<syn::Type>> {
+    let source = String::from_meta(meta)?;
+    let types = syn::parse::Parser::parse_str(
+        syn::punctuated::Punctuated::<syn::Type, syn::Token![,]>::parse_terminated,
+        &source,
+    )?;
+    Ok(types.into_iter().collect())
+}
+
+#[derive(Debug, FromMeta)]
+struct Bounds {
+    #[darling(with = "type_list")]
+    types: Vec<syn::Type>,
+}
+
+#[test]
+fn syn_errors_convert_with_question_mark() {
+    let bounds = Bounds::from_list(&[parse_quote!(types = "u8, Vec<String>")]).unwrap();
+    assert_eq!(bounds.types.len(), 2);
+
+    let err = Bounds::from_list(&[parse_quote!(types = "u8, 3")]).unwrap_err();
+    assert!(err.to_string().ends_with("at types"), "{}", err);
+}
+
+/// A validation written against `syn::Result` which reports every darling error.
+fn check(items: &[syn::NestedMeta]) -> syn::Result<Bounds> {
+    Ok(Bounds::from_list(items)?)
+}
+
+#[test]
+fn darling_errors_convert_to_syn() {
+    let err = check(&[parse_quote!(typos = 1)]).unwrap_err();
+    let messages: Vec<String> = err.into_iter().map(|e| e.to_string()).collect();
+    assert_eq!(messages.len(), 2);
+    assert!(messages[0].starts_with("Unknown field: `typos`"));
+    assert_eq!(messages[1], "Missing field `types`");
+}
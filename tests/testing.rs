@@ -0,0 +1,45 @@
+//! Snapshot tests of diagnostics with the `testing` feature.
+#![cfg(all(feature = "testing", feature = "suggestions"))]
+
+use darling::testing::{assert_snapshot, render_errors, CompileFailCase};
+use darling::FromMeta;
+
+#[derive(Debug, FromMeta)]
+#[allow(dead_code)]
+struct Settings {
+    rename: String,
+    #[darling(default)]
+    skip: bool,
+}
+
+#[test]
+fn settings_diagnostics() {
+    let rendered = render_errors::<Settings, _>(&[
+        r#"my_attr(rename = "x")"#,
+        "my_attr(rename = 1)",
+        r#"my_attr(renam = "x", skip = "perhaps")"#,
+    ]);
+
+    assert_snapshot(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots/settings.txt"),
+        &rendered,
+    );
+}
+
+#[test]
+fn compile_fail_cases() {
+    let case = CompileFailCase::new("settings_typo", r#"my_attr(renam = "x")"#)
+        .expect_error("Unknown field: `renam`")
+        .expect_error("missing field `rename`");
+    case.check::<Settings>();
+
+    let dir = std::env::temp_dir().join(format!("darling-ui-{}", std::process::id()));
+    let path = case
+        .write_fixture(&dir, "#[derive(Settings)]\n#[{attr}]\nstruct Example;\n")
+        .unwrap();
+    let fixture = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(path.ends_with("settings_typo.rs"));
+    assert!(fixture.contains(r#"#[my_attr(renam = "x")]"#));
+}
@@ -0,0 +1,99 @@
+//! Writing parsed options back out with `#[derive(ToMeta)]`.
+
+use darling::util::Flag;
+use darling::{FromMeta, ToMeta};
+use quote::ToTokens;
+use syn::parse_quote;
+
+#[derive(Debug, PartialEq, FromMeta, ToMeta)]
+struct Settings {
+    #[darling(rename = "label")]
+    name: String,
+    #[darling(default)]
+    retries: Option<u8>,
+    #[darling(default)]
+    fast: Flag,
+    #[darling(multiple)]
+    tag: Vec<String>,
+    mode: Mode,
+    #[darling(skip)]
+    internal: bool,
+}
+
+#[derive(Debug, PartialEq, FromMeta, ToMeta)]
+enum Mode {
+    Quick,
+    Custom(i32),
+    Tuned {
+        level: u8,
+    },
+    #[darling(skip)]
+    #[allow(dead_code)]
+    Hidden,
+}
+
+#[derive(Debug, PartialEq, FromMeta, ToMeta)]
+struct Wrapper<T>(T);
+
+fn render<T: ToMeta>(value: &T) -> String {
+    value
+        .to_meta(&parse_quote!(opts))
+        .unwrap()
+        .into_token_stream()
+        .to_string()
+}
+
+fn round_trip<T: ToMeta + FromMeta + PartialEq + std::fmt::Debug>(value: T) {
+    let meta = value.to_meta(&parse_quote!(opts)).unwrap();
+    assert_eq!(T::from_meta(&meta).unwrap(), value);
+}
+
+fn settings(mode: Mode) -> Settings {
+    Settings {
+        name: "x".to_string(),
+        retries: None,
+        fast: Flag::present(),
+        tag: vec!["a".to_string(), "b".to_string()],
+        mode,
+        internal: false,
+    }
+}
+
+#[test]
+fn writes_struct() {
+    assert_eq!(
+        render(&settings(Mode::Quick)),
+        r#"opts (label = "x" , fast , tag = "a" , tag = "b" , mode = "quick")"#
+    );
+}
+
+#[test]
+fn writes_variants() {
+    assert_eq!(render(&Mode::Custom(-1)), r#"opts (custom = "-1")"#);
+    assert_eq!(
+        render(&Mode::Tuned { level: 2 }),
+        "opts (tuned (level = 2))"
+    );
+    assert!(Mode::Hidden.to_meta(&parse_quote!(opts)).is_none());
+}
+
+#[test]
+fn round_trips() {
+    round_trip(settings(Mode::Quick));
+    round_trip(settings(Mode::Custom(-4)));
+    round_trip(Settings {
+        retries: Some(3),
+        fast: Flag::default(),
+        tag: vec![],
+        ..settings(Mode::Tuned { level: 9 })
+    });
+    round_trip(Wrapper(5u16));
+}
+
+#[test]
+fn renders_attribute_syntax() {
+    assert_eq!(
+        settings(Mode::Tuned { level: 2 }).to_meta_string("my_attr"),
+        r#"my_attr(label = "x", fast, tag = "a", tag = "b", mode(tuned(level = 2)))"#
+    );
+}
@@ -0,0 +1,68 @@
+//! Tuple structs and tuple variants with several fields read them from positional items.
+
+use darling::{FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, PartialEq, FromMeta)]
+struct Range(u8, #[darling(default)] Option<u8>);
+
+#[derive(Debug, PartialEq, FromMeta)]
+enum Bound {
+    Between(u8, u8),
+    Above(u8),
+}
+
+#[derive(Debug, FromMeta)]
+struct Limits {
+    range: Range,
+    bound: Option<Bound>,
+}
+
+#[test]
+fn positional_items() {
+    let limits = Limits::from_list(&[
+        parse_quote!(range(1, 5)),
+        parse_quote!(bound(between(2, 3))),
+    ])
+    .unwrap();
+    assert_eq!(limits.range, Range(1, Some(5)));
+    assert_eq!(limits.bound, Some(Bound::Between(2, 3)));
+
+    let limits = Limits::from_list(&[parse_quote!(range(1))]).unwrap();
+    assert_eq!(limits.range, Range(1, None));
+}
+
+#[test]
+fn positional_errors() {
+    let err = Range::from_list(&[]).unwrap_err();
+    assert_eq!(err.to_string(), "Missing field `0`");
+
+    let err = Range::from_meta(&parse_quote!(range(1, 2, 3))).unwrap_err();
+    assert_eq!(err.to_string(), "Too many items: Expected no more than 2");
+
+    let err = Range::from_meta(&parse_quote!(range(1, "x"))).unwrap_err();
+    assert!(err.to_string().ends_with("at 1"), "{}", err);
+}
+
+#[derive(Debug, Default, FromDeriveInput)]
+#[darling(attributes(pair), default)]
+struct Pair(String, #[darling(skip)] bool, u8);
+
+#[test]
+fn derive_input_with_container_default() {
+    let pair = Pair::from_derive_input(&parse_quote! {
+        #[pair("left", 2)]
+        struct Example;
+    })
+    .unwrap();
+    assert_eq!(pair.0, "left");
+    assert!(!pair.1);
+    assert_eq!(pair.2, 2);
+
+    let pair = Pair::from_derive_input(&parse_quote! {
+        #[pair("left")]
+        struct Example;
+    })
+    .unwrap();
+    assert_eq!(pair.2, 0);
+}
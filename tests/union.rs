@@ -0,0 +1,103 @@
+//! `supports(union)` lets a derive input be a union, whose fields are read into `Data::Union`.
+
+use darling::{ast, FromDeriveInput, FromField};
+use syn::parse_quote;
+
+#[derive(Debug, FromField)]
+#[darling(attributes(ffi))]
+struct ReprField {
+    ident: Option<syn::Ident>,
+    #[darling(default)]
+    tag: bool,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(ffi), supports(struct_named, union))]
+struct Repr {
+    data: ast::Data<(), ReprField>,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(ffi))]
+struct Unsupported {
+    #[allow(dead_code)]
+    data: ast::Data<(), ReprField>,
+}
+
+#[test]
+fn reads_union_fields() {
+    let repr = Repr::from_derive_input(&parse_quote! {
+        union Value {
+            #[ffi(tag)]
+            int: i32,
+            float: f32,
+        }
+    })
+    .unwrap();
+
+    assert!(repr.data.is_union());
+    let fields = repr.data.take_union().unwrap();
+    assert!(fields.is_struct());
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields.fields[0].ident.as_ref().unwrap(), "int");
+    assert!(fields.fields[0].tag);
+    assert!(!fields.fields[1].tag);
+}
+
+#[test]
+fn structs_are_still_structs() {
+    let repr = Repr::from_derive_input(&parse_quote! {
+        struct Value {
+            int: i32,
+        }
+    })
+    .unwrap();
+
+    assert!(repr.data.is_struct());
+}
+
+#[test]
+fn unions_need_opt_in() {
+    let union = parse_quote! {
+        union Value {
+            int: i32,
+        }
+    };
+
+    let err = Unsupported::from_derive_input(&union).unwrap_err();
+    assert_eq!(err.to_string(), "Unions are not supported");
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(ffi), supports(any))]
+struct AnyShape {
+    #[allow(dead_code)]
+    ident: syn::Ident,
+}
+
+#[test]
+fn any_excludes_unions() {
+    let err = AnyShape::from_derive_input(&parse_quote! {
+        union Value {
+            int: i32,
+        }
+    })
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Unsupported shape `union`. Expected any.");
+}
+
+#[test]
+fn manual_conversion_needs_opt_in() {
+    let union: syn::DeriveInput = parse_quote! {
+        union Value {
+            int: i32,
+        }
+    };
+
+    let err = ast::Data::<(), ReprField>::try_from(&union.data).unwrap_err();
+    assert_eq!(err.to_string(), "Unions are not supported");
+    ast::Data::<(), ReprField>::try_empty_from(&union.data).unwrap_err();
+
+    let data = ast::Data::<(), ReprField>::try_from_with_unions(&union.data).unwrap();
+    assert_eq!(data.take_union().unwrap().len(), 1);
+}
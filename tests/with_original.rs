@@ -0,0 +1,49 @@
+//! `util::WithOriginal` keeps the syntax node a receiver was parsed from.
+
+use darling::{ast, util::WithOriginal, FromAttributes, FromDeriveInput, FromField};
+use syn::parse_quote;
+
+#[derive(Debug, FromField)]
+#[darling(attributes(demo))]
+struct DemoField {
+    #[darling(default)]
+    rename: Option<String>,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(demo), supports(struct_named))]
+struct Demo {
+    data: ast::Data<(), WithOriginal<DemoField, syn::Field>>,
+}
+
+#[derive(Debug, FromAttributes)]
+#[darling(attributes(demo))]
+struct DemoAttrs {
+    #[darling(default)]
+    rename: Option<String>,
+}
+
+#[test]
+fn fields_keep_original() {
+    let di = parse_quote! {
+        struct Foo {
+            #[demo(rename = "baz")]
+            #[doc = "kept"]
+            bar: u8,
+        }
+    };
+
+    let parsed = Demo::from_derive_input(&di).unwrap();
+    let field = &parsed.data.take_struct().unwrap().fields[0];
+    assert_eq!(field.parsed.rename, Some("baz".to_string()));
+    assert_eq!(field.original.ident, Some(parse_quote!(bar)));
+    assert_eq!(field.original.attrs.len(), 2);
+}
+
+#[test]
+fn attributes_keep_original() {
+    let attrs: Vec<syn::Attribute> = vec![parse_quote!(#[demo(rename = "baz")])];
+    let parsed = WithOriginal::<DemoAttrs, Vec<syn::Attribute>>::from_attributes(&attrs).unwrap();
+    assert_eq!(parsed.parsed.rename, Some("baz".to_string()));
+    assert_eq!(parsed.original, attrs);
+}
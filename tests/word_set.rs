@@ -0,0 +1,68 @@
+//! `util::WordSet` collects bare words into a set of user-defined flags.
+
+use darling::{util::WordSet, FromDeriveInput, FromMeta};
+use syn::parse_quote;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+enum Permission {
+    Read,
+    Write,
+    Create,
+}
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(opts))]
+struct Opts {
+    flags: WordSet<Permission>,
+}
+
+#[test]
+fn collects_words() {
+    let di = parse_quote! {
+        #[opts(flags(read, create))]
+        pub struct Foo;
+    };
+
+    let opts = Opts::from_derive_input(&di).unwrap();
+    assert!(opts.flags.contains(&Permission::Read));
+    assert!(!opts.flags.contains(&Permission::Write));
+    assert_eq!(&*opts.flags, &[Permission::Read, Permission::Create]);
+}
+
+#[test]
+fn absent_is_empty() {
+    let di = parse_quote! {
+        #[opts()]
+        pub struct Foo;
+    };
+
+    let opts = Opts::from_derive_input(&di).unwrap();
+    assert!(opts.flags.is_empty());
+}
+
+// The expected names are only embedded in generated code when suggestions are enabled.
+#[cfg(feature = "suggestions")]
+#[test]
+fn unknown_word_lists_flags() {
+    let di = parse_quote! {
+        #[opts(flags(read, exec))]
+        pub struct Foo;
+    };
+
+    let err = Opts::from_derive_input(&di).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unknown literal value `exec`. Expected one of `read`, `write`, `create` at flags"
+    );
+}
+
+#[test]
+fn duplicate_word() {
+    let di = parse_quote! {
+        #[opts(flags(read, write, read))]
+        pub struct Foo;
+    };
+
+    let err = Opts::from_derive_input(&di).unwrap_err();
+    assert_eq!(err.to_string(), "Duplicate field `read` at flags");
+}